@@ -4,6 +4,9 @@ var filesToCache = [
   './index.html',
   './splot.js',
   './splot_bg.wasm',
+  './parser_worker.js',
+  './parser_worker_bg.wasm',
+  './parser_worker_loader.js',
 ];
 
 /* Start the service worker and cache all of the app's content */
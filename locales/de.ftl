@@ -0,0 +1,477 @@
+# German
+
+window-about = Über
+window-usage = Benutzung
+window-help = Hilfe
+window-binary-layout = Binärformat
+window-ubx-messages = UBX-Nachrichten
+window-can-signals = CAN-Signale
+window-derived-channels = Abgeleitete Kanäle
+window-import-capture = Aufzeichnung importieren
+drop-csv = CSV-Datei zum Importieren ablegen
+window-export-samples = Messwerte exportieren
+window-compare-captures = Aufzeichnungen vergleichen
+window-debug-bundle = Debug-Paket
+window-captures = Aufzeichnungen
+window-appearance-preset = Darstellungsvorlage
+window-startup-actions = Startaktionen
+window-init-commands = Initialisierungsbefehle
+window-automation = Automatisierung
+window-alarms = Alarme
+window-conditional-capture = Bedingte Aufzeichnung
+window-connection-profiles = Verbindungsprofile
+window-appearance = Darstellung
+menu-channels = Kanäle
+menu-create-debug-bundle = Debug-Paket erstellen
+menu-quit = Beenden
+menu-browse-captures = Aufzeichnungen durchsuchen
+dummy-connection = Testverbindung
+label-profile = Profil:
+label-port = Port:
+label-baudrate = Baudrate:
+custom = Benutzerdefiniert
+label-data-bits = Datenbits:
+label-flow-control = Flusskontrolle:
+label-parity = Parität:
+label-stop-bits = Stoppbits:
+label-timeout = Zeitlimit:
+auto-reconnect = Automatisch neu verbinden
+disconnect = Trennen
+edit-reconnect = Bearbeiten & neu verbinden
+cancel = Abbrechen
+reconnect = Neu verbinden
+connect = Verbinden
+label-pages = Seiten:
+tab-connection = Verbindung
+none = Keine
+toggle-connection = Verbindung
+reset = Zurücksetzen
+clear = Leeren
+archive = Archiv
+pause = Pause
+record = ⏺ Aufnehmen
+label-record-file = Aufnahmedatei:
+label-time-unit = Zeiteinheit:
+unwrap-time = Zeit entfalten
+host-time = Hostzeit
+label-value-separator = Werttrenner:
+layout = Format
+messages = Nachrichten
+signals = Signale
+label-format = Format:
+label-accent-color = Akzentfarbe:
+label-rounding = Rundung:
+label-density = Dichte:
+label-language = Sprache:
+received-samples = Empfangene Messwerte:
+page-time-value = Zeit - Wert
+page-xy = X - Y
+page-trajectory = 3D
+page-serial-monitor = Serieller Monitor
+page-raw-traffic = Rohdaten
+page-spectrogram = Spektrogramm
+page-dashboard = Dashboard
+page-diagnostics = Diagnose
+page-archive = Archiv
+copy-image = 📋 Bild kopieren
+window-data-servers = Datenserver
+unit-kilobytes-per-sec = kB/s
+unit-samples-per-sec = Messwerte/s
+window-acquisition = Erfassungsdiagnose
+window-device-script = Geräteskript
+device-script = Skript
+about-description = Ein plattformübergreifender serieller Plotter und Monitor
+label-created-by = Erstellt von:
+repository = Repository
+label-powered-by = Basiert auf:
+label-example = Beispiel:
+usage-values = Splot verarbeitet die Daten einer seriellen Verbindung und sucht nach Werten, die durch den angegebenen Trenner getrennt und mit einem Zeilenumbruch abgeschlossen sind.\nJeder Wert wird nach seinem Index eingefügt, daher sollte die Anzahl der Werte pro Zeile gleich bleiben.
+usage-names = Werte können auch einen Namen haben, der in der Werteliste erscheint. Dazu wird dem Wert "<name>=" vorangestellt
+usage-time = Ein besonderer benannter Wert ist der mit "time=" oder "t=".\nEr wird als Zeit für die Darstellung verwendet.\nEr muss monoton steigen und sollte daher von einem Timer kommen.\nDie Zeiteinheit der vom Gerät empfangenen Zeitwerte kann in der Anwendung eingestellt werden,\noder pro Wert mit einer Einheit als Suffix, z. B. "t_ms=", "t_us=" oder "time_s=".\nOhne einen solchen Wert verwendet die Anwendung die Empfangszeit der Daten
+help-web-port = - Problem: Im Web wird der Port nicht angezeigt.\n    Lösung: Den Browser neu starten. Das Gerät muss eventuell vor dem Start des Browsers angeschlossen werden.
+startup-actions-help = Aktionen beim Start von splot, damit es ohne Eingriff Daten erfasst.\nDer Port wird über seine USB-ID als "VID:PID" in Hex (z. B. "1a86:7523") oder über seinen Namen gefunden.\nIst er noch nicht verfügbar, wartet splot, bis er angeschlossen wird.\nDie Aktionen werden mit den Verbindungsprofilen gespeichert und beim Laden des Profils ausgeführt.
+start-recording = Aufnahme starten
+demo-mode = Demomodus
+web-serial-unsupported = ⚠ Web Serial API wird auf dieser ⚠\n Plattform nicht unterstützt
+port-usb-info = Hersteller: { $manufacturer }\nProdukt: { $product }\nSeriennummer: { $serial }
+hover-timeout = Länger dauernde Schreibvorgänge werden abgebrochen, Lesevorgänge ohne Daten in dieser Zeit neu gestartet
+hover-auto-reconnect = Bei Verbindungsverlust mit demselben Port und denselben Einstellungen neu verbinden
+status-lost-connection = ⚠ Getrennt
+hover-reconnecting = Verbindung zu `{ $port }` verloren, Verbindungsversuch { $attempt }
+hover-lost-connection = Verbindung zu `{ $port }` verloren
+status-connected = ● Verbunden: { $port }
+status-error = ⚠ Fehler
+status-disconnected = ○ Getrennt
+hover-disconnect = Den Port schließen
+hover-edit-reconnect = Die Verbindungseinstellungen entsperren, um mit geänderten Einstellungen neu zu verbinden
+hover-page = Öffnet die Seite als Tab, mit ihren Einstellungen daneben. Tabs ziehen, um sie anzuordnen, oder an einen Rand, um den Bereich zu teilen
+hover-connection = Die Verbindungseinstellungen anzeigen
+hover-copy-image = Die angezeigten Seiten als Bild in die Zwischenablage kopieren
+hover-archive = Die geleerten Messwerte auf der Archivseite behalten
+hover-pause = Das Lesen anhalten. Währenddessen kann der Zeit-Wert-Plot über alle Messwerte verschoben und gezoomt werden
+hover-recording = Aufnahme nach `{ $file }`, { $bytes } Bytes geschrieben
+hover-record = Die empfangenen Daten nach `{ $file }` aufnehmen. Rechtsklick ändert die Datei
+hover-unwrap-time = Empfangene Zeitwerte beim Überlauf des Gerätezählers entfalten und die Zeit bei Rücksprüngen fortsetzen
+hover-host-time-synced = Empfangene Zeitwerte werden auf die Hostuhr abgebildet.\nVersatz: { $offset } s, Drift: { $drift } ppm
+hover-host-time = Empfangene Zeitwerte auf die Hostuhr abbilden, damit sie zum seriellen Monitor passen
+hover-nmea = GGA-, RMC- und VTG-Sätze von GNSS-Modulen
+hover-can = Frames einer SocketCAN-Schnittstelle, z. B. can0
+binary-layout-help = Jedes Paket beginnt mit den Magic Bytes, gefolgt von den Feldern in der angegebenen Reihenfolge ohne Auffüllung.\nEin Feld namens "time" oder "t" wird als Zeit des Pakets verwendet.
+label-magic-bytes = Magic Bytes (hex):
+label-endianness = Bytereihenfolge:
+add-field = Feld hinzufügen
+packet-size = Paketgröße: { $bytes } Bytes
+ubx-messages-help = Frames mit ungültiger Prüfsumme werden als Parserfehler gezählt und übersprungen.\nPositionen sind in Grad, Höhen und Genauigkeiten in m, Geschwindigkeiten in m/s und Zeiten in s.
+can-signals-help = CAN-Schnittstellen werden unter Linux mit den seriellen Ports aufgelistet. Jedes Signal wird ein Kanal,\ndekodiert aus den Datenbytes der Frames mit seiner ID als roh * Skalierung + Versatz.
+column-name = Name
+column-can-id = CAN-ID
+column-byte = Byte
+column-type = Typ
+column-endianness = Bytereihenfolge
+column-scale = Skalierung
+column-offset = Versatz
+hover-can-signal-overflow = Der Wert passt nicht in die 8 Datenbytes
+add-signal = Signal hinzufügen
+derived-channels-help = Abgeleitete Kanäle werden aus Ausdrücken über die empfangenen Kanäle berechnet, sobald neue Messwerte eintreffen.\nKanäle werden über ihren Namen oder ihren Index als "c<index>" angesprochen, z. B. "c0 - c1" oder "sqrt(x*x + y*y)".\nProdukte und Verhältnisse zweier Kanäle können direkt gewählt werden. Die Integration über die Zeit macht z. B. aus Leistung Energie.\nDie FFT-Spitze verfolgt die dominante Frequenz eines Kanals oder ihre Amplitude über die letzten Messwerte.\nTief- und Hochpass filtern einen Kanal in Echtzeit mit einem Butterworth-Filter erster oder zweiter Ordnung.
+derived-operators = Operatoren: + - * / ^, Funktionen: { $functions }
+column-kind = Art
+column-expression = Ausdruck
+column-integrate = Integrieren
+n-samples = { $count } Messwerte
+unit-samples = Messwerte
+filter-first-order = 1.
+filter-second-order = 2.
+hover-cutoff = Die Grenzfrequenz
+hover-first-order = Erste Ordnung, -20 dB pro Dekade
+hover-second-order = Zweite Ordnung, -40 dB pro Dekade
+add-derived-channel = Abgeleiteten Kanal hinzufügen
+automation-help = Regeln führen eine Aktion aus, wenn eine Bedingung über die Kanäle positiv wird, wenn eine empfangene Zeile einen Text enthält,\noder regelmäßig während der Verbindung. Bedingungen sind Ausdrücke wie bei abgeleiteten Kanälen, z. B. löst "c0 - 3.3" aus,\nwenn Kanal 0 über 3.3 steigt. Gesendeter Text wird mit dem Zeilenende des seriellen Monitors abgeschlossen.\nBenachrichtigungen werden nur angezeigt, während das Fenster nicht im Fokus ist.
+column-when = Wenn
+column-action = Aktion
+every = alle
+add-rule = Regel hinzufügen
+flash-taskbar = Das Taskleistensymbol bei Benachrichtigungen blinken lassen
+automation-script = Skript
+automation-script-help = Ein Rhai-Skript für Logik, die nicht in die Regeln passt. Es definiert die benötigten Hooks, on_line(line),\non_samples(values) mit den letzten Werten nach Kanalname, und on_timer(). Sie rufen send(text), marker(label),\nnotify(text), start_recording(), stop_recording() und derive(name, expression) auf, das einen abgeleiteten Kanal erstellt.\nDie Anweisungen auf oberster Ebene laufen einmal beim Anwenden des Skripts, this ist eine Map, die Werte zwischen den Aufrufen behält.
+hover-apply-automation-script = Kompiliert das Skript erneut, die in this gehaltenen Werte werden verworfen
+alarms-help = Ein Alarm wird ausgelöst, wenn ein Kanal seine Grenzen für eine Anzahl aufeinanderfolgender Messwerte verletzt, und vom\nnächsten Messwert innerhalb der Grenzen aufgehoben. Kanäle werden über ihren Namen oder ihren Index als "c<index>" angesprochen.\nAusgelöste Alarme lassen den Kanal auf der Zeit-Wert-Seite blinken. Benachrichtigungen werden nur angezeigt, während das Fenster nicht im Fokus ist.
+column-channel = Kanal
+column-condition = Bedingung
+column-low = Unten
+column-high = Oben
+column-for = Für
+column-notify = Benachrichtigen
+hover-unknown-channel = Kein Kanal mit diesem Namen
+add-alarm = Alarm hinzufügen
+alarm-events = Ereignisse: { $count }
+label-file = Datei:
+load = Laden
+export = Exportieren
+import = Importieren
+label-values-newer = Werte neuer als:
+fade-trail = Spur ausblenden
+crosshair = Fadenkreuz
+label-channels = Kanäle:
+label-start-condition = Startbedingung
+label-stop-condition = Stoppbedingung
+hint-none = keine
+label-duration = Dauer
+hover-duration = Null, um nur bei der Stoppbedingung anzuhalten
+label-pre-trigger = Vorlauf
+rearm = Erneut scharf schalten
+hover-rearm = Nach einer Aufzeichnung erneut auf die Startbedingung warten
+label-capture-file = Datei
+disarm = Entschärfen
+arm = Scharf schalten
+status-capturing = ⏺ Zeichnet auf
+status-armed = Scharf
+status-disarmed = Entschärft
+n-captures = Aufzeichnungen: { $count }
+capture-file = Datei: `{ $file }`
+profiles-help = Profile bündeln den Port, die Verbindungs- und Parsereinstellungen und die Darstellung der Kanäle.\nSie werden ohne Verbindung über die Profilauswahl neben dem Port geladen.
+update = Aktualisieren
+hover-update-profile = Das Profil durch die aktuellen Einstellungen ersetzen
+label-name = Name:
+save-current = Aktuelle speichern
+hover-save-profile = Die aktuellen Einstellungen als neues Profil speichern
+init-commands-help = Befehle, die direkt nach dem Verbinden gesendet werden, z. B. damit das Gerät Daten sendet.\nJeder Befehl wird nach seiner Verzögerung gesendet, gezählt ab der Verbindung oder dem vorherigen Befehl.
+label-line-ending = Zeilenende:
+column-delay = Verzögerung
+column-command = Befehl
+add-command = Befehl hinzufügen
+history-spill = Gesamten Verlauf auf der Festplatte behalten
+hover-history-spill = Messwerte, die nicht in den Speicher passen, werden in temporäre Dateien ausgelagert, damit die ganze Aufzeichnung zurückgescrollt werden kann
+label-scroll-back = Zurückscrollen:
+live = Live
+history-spilled = { $size } MB auf die Festplatte ausgelagert
+session-diff-help = Vergleicht zwei CSV-Aufzeichnungen im Importformat, z. B. aufgenommen mit verschiedenen Firmware-Versionen.\nKanäle werden nach Namen gepaart, die zweite Aufzeichnung wird zu den Zeiten der ersten interpoliert\nund die Differenz "zweite - erste" wird dargestellt.
+label-first = Erste:
+label-second = Zweite:
+label-align-by = Ausrichten nach:
+label-channel = Kanal:
+label-rising-above = Steigend über:
+compare = Vergleichen
+comparing-failed = Vergleich fehlgeschlagen: { $error }
+web-compare-unavailable = Das Vergleichen von Dateien ist im Web nicht verfügbar.
+session-diff-offset = Die zweite Aufzeichnung ist um { $offset } s verschoben
+column-mean = Mittelwert
+column-rms = Effektivwert
+column-max-diff = Max. |Diff.|
+import-help = Importiert einen analogen CSV-Export eines Logikanalysators (Saleae Logic, sigrok / PulseView) und ersetzt die aktuellen Messwerte damit.\nDie erste Spalte muss die Zeit oder der Index des Messwerts sein, die Kopfzeile benennt die Kanäle. Einheiten in Klammern wie "Time [ms]" werden erkannt.\nCSV-Dateien können auch auf das Fenster gezogen werden.
+web-import-unavailable = Das Importieren von Dateien ist im Web nicht verfügbar.
+export-help = Exportiert die Messwerte der ausgewählten Kanäle. CSV-Dateien haben das Format des Imports, NumPy- und MATLAB-Dateien haben pro Zeile die Zeit und einen Wert je Kanal, fehlende Werte sind NaN.
+select-all = Alle
+select-visible = Sichtbare
+exported-channels = { $count } Kanäle nach `{ $file }` exportiert
+export-failed = Export fehlgeschlagen: { $error }
+web-export-unavailable = Das Exportieren von Dateien ist im Web nicht verfügbar.
+data-servers-help = Stellt die empfangenen Messwerte als JSON auf localhost bereit, für Skripte und andere Werkzeuge.
+label-allowed-origins = Erlaubte Ursprünge:
+hover-allowed-origins = Webseiten, die die Daten abfragen dürfen, durch Kommas getrennt. Anfragen anderer Webseiten werden abgelehnt
+enabled = Aktiviert
+http-api-serving = Bereitgestellt auf http://{ $addr }
+http-api-channels = Die Kanäle mit ihrem letzten Messwert
+http-api-samples-since = Die Messwerte neuer als die Zeit in Sekunden
+http-api-samples-channel = Die Messwerte eines Kanals, nach Index oder Name
+websocket-broadcasting = Sendet auf ws://{ $addr }, { $clients } Clients verbunden
+websocket-message = Jeder verarbeitete Messwert wird als Nachricht gesendet:
+device-script-help = Das Skript wird vom Port "dummy (script)" der Testverbindung abgespielt, ein Befehl pro Zeile:\nwait <ms>, send <text>, line <text> (gefolgt von einem Zeilenvorschub), on <anfrage> => <antwort>, repeat.\nTexte verstehen die Escapes \\n, \\r, \\t, \\\\ und \\xNN, Zeilen mit # am Anfang sind Kommentare.
+apply = Übernehmen
+hover-apply-script = Verbindet neu, damit die Testverbindung das Skript abspielt
+label-reads = Lesevorgänge:
+label-empty-reads = Leere Lesevorgänge:
+label-failed-reads = Fehlgeschlagene Lesevorgänge:
+label-read-latency = Leselatenz:
+label-poll-interval = Abfrageintervall:
+latest-and-max = { $latest }, max. { $max }
+label-parse-errors = Parserfehler:
+hover-parse-errors = Zeilen ohne Wert, sie werden verworfen
+label-partial-reads = Unvollständige Lesevorgänge:
+hover-partial-reads = Lesevorgänge, die innerhalb einer Zeile endeten
+label-unparsed-bytes = Unverarbeitete Bytes:
+label-fullest-channel-buffer = Vollster Kanalpuffer:
+label-monitor-buffer = Monitorpuffer:
+debug-bundle-help = Erstellt ein Zip-Archiv mit dem Log, den Einstellungen, der Parserdiagnose und den zuletzt empfangenen Bytes.\nEs kann einem Fehlerbericht angehängt werden.
+redact-port-names = Portnamen schwärzen
+create = Erstellen
+created-file = `{ $file }` erstellt
+debug-bundle-failed = Das Erstellen des Debug-Pakets ist fehlgeschlagen: { $error }
+captures-help = Aufnahmen, Exporte und Debug-Pakete werden bei automatischer Benennung im Aufzeichnungsverzeichnis erstellt.\nCSV-Exporte werden beim Öffnen importiert, Rohaufnahmen mit den aktuellen Formateinstellungen abgespielt.
+label-directory = Verzeichnis:
+label-session-name = Sitzungsname:
+hover-session-name = Platzhalter: { $placeholders }. Datum und Uhrzeit sind in UTC
+auto-session-names = Aufnahmen, Exporte und Debug-Pakete automatisch benennen
+refresh = ⟲ Aktualisieren
+n-files = { $count } Dateien
+open = Öffnen
+opening-failed = Öffnen fehlgeschlagen: { $error }
+appearance-preset-help = Exportiert die Namen, Farben, Einheiten und Achsen der Kanäle als Vorlage, damit alle mit derselben Firmware sie teilen.\nEine importierte Vorlage wird auf neue Kanäle angewendet, zugeordnet nach Name oder sonst nach Position.
+exported-appearance = Darstellung von { $count } Kanälen exportiert
+imported-appearance = Darstellung von { $count } Kanälen importiert
+import-failed = Import fehlgeschlagen: { $error }
+hover-clear-preset = Die importierte Vorlage nicht mehr auf neue Kanäle anwenden
+web-preset-unavailable = Das Exportieren und Importieren von Vorlagen ist im Web nicht verfügbar.
+remembered-appearances = Gemerkte Darstellungen: { $count }
+hover-remembered-appearances = Die Darstellung von Kanälen wird wiederhergestellt, wenn ein Kanal mit demselben Namen erscheint
+forget = Vergessen
+tracking-cursor = Mitlaufender Cursor
+hover-tracking-cursor = Die Werte aller sichtbaren Kanäle zur Zeit unter dem Mauszeiger anzeigen
+hover-crosshair = Die Koordinaten des Mauszeigers an den Achsen anzeigen
+now = Jetzt
+hover-time-zero = Die Zeiten relativ zum letzten Messwert anzeigen und exportieren.\nEin Rechtsklick auf den Plot setzt ihn auf einen anderen Zeitpunkt
+time-zero-at = bei { $time }
+log-y-axis = Logarithmische Y-Achse
+hover-log-y-axis = Werte, die nicht positiv sind, werden nicht angezeigt
+label-y-bounds = Y-Grenzen:
+label-downsampling = Reduktion:
+unit-points = Punkte
+hover-point-budget = Die maximale Anzahl gezeichneter Punkte pro Kanal
+label-render-budget = Zeichenbudget:
+label-values = Werte:
+unit-decimals = Nachkommastellen
+hover-value-format = Das Format der Werte in den Beschriftungen, Achsen und Anzeigen
+break-gaps = Linien an Lücken unterbrechen
+hover-break-gaps = Messwerte, die weiter als der Schwellwert auseinanderliegen, nicht verbinden
+shade-gaps = Lücken schattieren
+stacked-subplots = Gestapelte Teilplots
+hover-stacked-subplots = Jeder Kanal in einem eigenen Plot, mit gemeinsamer Zeitachse
+snapshot = 📷 Momentaufnahme
+hover-snapshot = Die sichtbaren Kurven als Schatten einfrieren, um sie mit der neuen Antwort zu vergleichen
+label-sort-channels = Kanäle sortieren:
+label-unit = Einheit:
+hover-left-axis = Linke Y-Achse
+hover-right-axis = Rechte Y-Achse
+hold = Halten
+hover-hold = Den letzten Wert bis zum Ende des Plots halten
+fill = Füllen
+hover-fill = Die Fläche zwischen der Linie und null füllen
+hover-invalid-values = Die Anzahl der NaN- und unendlichen Werte, die Linie wird bei NaN unterbrochen
+trigger = Trigger
+average-name = { $name } (Mittelwert)
+set-time-zero = t=0 hier setzen
+reset-time-zero = t=0 zurücksetzen
+hover-alarm = Alarm: { $channels }
+moving-average = Gleitender Mittelwert
+plausible-range = Plausibler Bereich
+range-to = bis
+out-of-range = Außerhalb des Bereichs: { $count }
+reference-traces = Referenzkurven
+reference-traces-help = Gespeicherte CSV-Aufzeichnungen, eingefroren hinter den Live-Daten relativ zu t=0 gezeichnet.
+loading-failed = Laden fehlgeschlagen: { $error }
+reference-traces-archive = Archivierte Aufzeichnungen können auf der Archivseite als Referenz verwendet werden.
+opacity = Deckkraft
+label-offset = Versatz:
+hover-reference-offset = Die Referenz auf der Zeitachse verschieben
+guide-lines = Hilfslinien
+guide-lines-help = Benannte waagerechte Linien bei einem festen Wert, wie die Grenzen des Geräts.
+add-guide-line = Hilfslinie hinzufügen
+cursors = Cursor
+show-cursors = Cursor anzeigen
+place-in-view = In der Ansicht platzieren
+column-mode = Modus
+column-level = Pegel
+column-edge = Flanke
+column-pre-trigger = Vorlauf
+trigger-free-running = Freilaufend
+trigger-armed = Scharf, wartet auf Trigger
+trigger-stopped = Angehalten
+trigger-triggered = Ausgelöst
+add-pair = Paar hinzufügen
+column-logarithmic = Logarithmisch
+hover-fade-trail = Ältere Messwerte transparenter zeichnen
+column-y-bounds = Y-Grenzen
+column-render-budget = Zeichenbudget
+hover-drag-channel = Einen Kanal auf eine Achse ziehen, um ihn auszuwählen
+axis-name = { $axis }-Achse
+reset-view = Ansicht zurücksetzen
+select-three-channels = Drei Kanäle auswählen
+column-range = Bereich
+column-low-limit = Untere Grenze
+column-high-limit = Obere Grenze
+remove = 🗑 Entfernen
+add-widget = Widget hinzufügen
+dashboard-empty = Widgets in den Dashboard-Einstellungen hinzufügen
+column-fft-size = FFT-Größe
+column-dynamic-range = Dynamikbereich
+no-samples = Keine Messwerte
+spectrogram-not-enough-samples = Nicht genug Messwerte, die FFT braucht { $needed } Messwerte, empfangen { $received }
+unknown-sample-rate = Die Abtastrate kann nicht bestimmt werden
+column-total-bytes = Bytes gesamt
+column-total-samples = Messwerte gesamt
+archive-empty = Keine archivierten Aufzeichnungen, "Archiv" aktivieren, um die Messwerte beim Leeren zu behalten
+archived-capture = { $count } Messwerte, { $duration }
+use-as-reference = Als Referenz verwenden
+hover-use-as-reference = Die ausgewählte Aufzeichnung hinter die Live-Daten des Zeit-Wert-Plots zeichnen
+hover-export-archived = Die ausgewählte Aufzeichnung als CSV exportieren
+exported-capture = `{ $capture }` nach `{ $file }` exportiert
+label-timestamp = Zeitstempel:
+hover-hex = Die empfangenen Rohbytes als Hexdump anzeigen
+ansi-colors = ANSI-Farben
+hover-ansi-colors = ANSI-Farb-Escapesequenzen darstellen
+table = Tabelle
+hover-table = Die Zeilen am Werttrenner in Spalten aufteilen
+echo = Echo
+send = Senden
+hint-send = Zu sendender Text
+column-received = Empfangen
+column-time = Zeit
+column-index = Spalte { $index }
+received-bytes = Empfangen: { $count } Bytes
+shown-bytes = Angezeigt: { $count } Bytes
+hover-shown-bytes = Die zuletzt empfangenen Bytes werden behalten
+hover-line-terminators = Die Zeilenenden in den angezeigten Bytes
+other-control-bytes = Andere Steuerbytes: { $count }
+non-ascii-bytes = Nicht-ASCII-Bytes: { $count }
+highlight-line-endings = CR / LF hervorheben
+no-data = Keine Daten
+hover-render-budget = Die maximale Anzahl gezeichneter Punkte pro Frame, Linien über ihrem Anteil werden ausgedünnt
+decimated = { $factor }× ausgedünnt
+minimum = Minimum
+maximum = Maximum
+conditional-capture-help = Im scharfen Zustand wird eine CSV-Aufzeichnung geschrieben, sobald die Startbedingung positiv wird. Sie enthält die Messwerte des\nVorlaufs, die noch im Speicher sind, und endet nach der Dauer oder wenn die Stoppbedingung positiv wird.\nKanäle werden wie bei abgeleiteten Kanälen angesprochen, z. B. löst "c0 - 3.3" aus, wenn Kanal 0 über 3.3 steigt.\nDie Zeiten sind relativ zum Trigger. Die Datei wird nach der Sitzung benannt, wenn Sitzungsnamen aktiviert sind.
+archive-run = Lauf { $n }
+status-list-captures-failed = Auflisten der Aufnahmen fehlgeschlagen: { $error }
+status-imported-from = { $samples } Messwerte aus `{ $path }` importiert
+status-replayed-from = { $samples } Messwerte aus `{ $path }` wiedergegeben
+alarm-raised = { $condition } für { $samples } Messwerte, { $value }
+alarm-cleared = aufgehoben
+alarm-above = über { $high }
+alarm-below = unter { $low }
+alarm-outside = außerhalb von { $low } bis { $high }
+notification-alarm = Alarm bei { $channel }: { $message }
+status-imported-channels = { $samples } Messwerte in { $channels } Kanälen importiert
+status-import-failed = Import fehlgeschlagen: { $error }
+snapshot-name = Momentaufnahme { $n }
+status-copied-image = Bild mit { $width }x{ $height } kopiert
+cursor-name = Cursor { $n }
+tab-page-controls = { $page }: Einstellungen
+line-ending-none = Keine
+line-ending-cr = CR
+line-ending-lf = LF
+line-ending-cr-lf = CRLF
+smoothing-mode-off = Aus
+smoothing-mode-alongside = Zusätzlich
+smoothing-mode-instead = Stattdessen
+filter-action-discard = Verwerfen
+filter-action-clamp = Begrenzen
+filter-action-flag = Markieren
+rule-hook-condition = Bedingung
+rule-hook-line = Zeile
+rule-hook-timer = Timer
+rule-action-send = Senden
+rule-action-marker = Markierung
+rule-action-notify = Benachrichtigen
+rule-action-start-recording = Aufnahme starten
+rule-action-stop-recording = Aufnahme stoppen
+downsampling-off = Aus
+downsampling-lttb = LTTB
+downsampling-min-max = Min / Max
+derived-kind-expression = Ausdruck
+derived-kind-product = Produkt
+derived-kind-ratio = Verhältnis
+derived-kind-peak-frequency = FFT-Spitzenfrequenz
+derived-kind-peak-amplitude = FFT-Spitzenamplitude
+derived-kind-low-pass = Tiefpass
+derived-kind-high-pass = Hochpass
+widget-kind-numeric = Numerisch
+widget-kind-bar = Balken
+widget-kind-gauge = Zeigerinstrument
+export-format-csv = CSV
+export-format-npy = NumPy (.npy)
+export-format-npz = NumPy (.npz)
+export-format-mat = MATLAB (.mat)
+monitor-timestamp-none = Keiner
+monitor-timestamp-relative = Relativ
+monitor-timestamp-wall-clock = Uhrzeit (UTC)
+data-format-ascii = ASCII
+data-format-binary = Binär
+data-format-ubx = UBX
+data-format-nmea = NMEA
+data-format-can = CAN
+plot-axis-left = Links
+plot-axis-right = Rechts
+y-bounds-mode-free = Frei
+y-bounds-mode-autoscale = Automatisch
+y-bounds-mode-autoscale-margin = Automatisch mit Rand
+y-bounds-mode-fixed = Fest
+channel-order-arrival = Eingang
+channel-order-name = Name
+channel-order-activity = Aktivität
+channel-order-magnitude = Betrag
+notation-plain = Normal
+notation-scientific = Wissenschaftlich
+notation-si-prefix = SI-Präfix
+diff-alignment-start = Anfang
+diff-alignment-trigger = Trigger
+alarm-condition-above = Über
+alarm-condition-below = Unter
+alarm-condition-out-of-range = Außerhalb des Bereichs
+trigger-mode-off = Aus
+trigger-mode-normal = Normal
+trigger-mode-single = Einzeln
+trigger-edge-rising = Steigend
+trigger-edge-falling = Fallend
+trigger-edge-both = Beide
+density-compact = Kompakt
+density-normal = Normal
+density-comfortable = Großzügig
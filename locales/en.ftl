@@ -0,0 +1,478 @@
+# The English base catalog, every message has a key here.
+# Lines are `key = message`, other languages fall back to these messages.
+
+window-about = About
+window-usage = Usage
+window-help = Help
+window-binary-layout = Binary Layout
+window-ubx-messages = UBX Messages
+window-can-signals = CAN Signals
+window-derived-channels = Derived Channels
+window-import-capture = Import Capture
+drop-csv = Drop a CSV file to import it
+window-export-samples = Export Samples
+window-compare-captures = Compare Captures
+window-debug-bundle = Debug Bundle
+window-captures = Captures
+window-appearance-preset = Appearance Preset
+window-startup-actions = Startup Actions
+window-init-commands = Init Commands
+window-automation = Automation
+window-alarms = Alarms
+window-conditional-capture = Conditional Capture
+window-connection-profiles = Connection Profiles
+window-appearance = Appearance
+menu-channels = Channels
+menu-create-debug-bundle = Create Debug Bundle
+menu-quit = Quit
+menu-browse-captures = Browse Captures
+dummy-connection = Dummy connection
+label-profile = Profile:
+label-port = Port:
+label-baudrate = Baudrate:
+custom = Custom
+label-data-bits = Data Bits:
+label-flow-control = Flow Control:
+label-parity = Parity:
+label-stop-bits = Stop Bits:
+label-timeout = Timeout:
+auto-reconnect = Auto reconnect
+disconnect = Disconnect
+edit-reconnect = Edit & Reconnect
+cancel = Cancel
+reconnect = Reconnect
+connect = Connect
+label-pages = Pages:
+tab-connection = Connection
+none = None
+toggle-connection = Connection
+reset = Reset
+clear = Clear
+archive = Archive
+pause = Pause
+record = ⏺ Record
+label-record-file = Record File:
+label-time-unit = Time Unit:
+unwrap-time = Unwrap Time
+host-time = Host Time
+label-value-separator = Value Separator:
+layout = Layout
+messages = Messages
+signals = Signals
+label-format = Format:
+label-accent-color = Accent color:
+label-rounding = Rounding:
+label-density = Density:
+label-language = Language:
+received-samples = Received Samples:
+page-time-value = Time - Value
+page-xy = X - Y
+page-trajectory = 3D
+page-serial-monitor = Serial Monitor
+page-raw-traffic = Raw Traffic
+page-spectrogram = Spectrogram
+page-dashboard = Dashboard
+page-diagnostics = Diagnostics
+page-archive = Archive
+copy-image = 📋 Copy image
+window-data-servers = Data Servers
+unit-kilobytes-per-sec = kB/s
+unit-samples-per-sec = samples/s
+window-acquisition = Acquisition Diagnostics
+window-device-script = Device Script
+device-script = Script
+about-description = A multi-platform serial plotter and monitor
+label-created-by = Created by:
+repository = Repository
+label-powered-by = Powered by:
+label-example = Example:
+usage-values = Splot parses data coming from a serial connection and looks for values separated by the specified separator and terminated by a newline character.\nEach value is inserted by its index, so it is important to keep the number of values per line constant.
+usage-names = Values can also have a name, which will appear in the values list. To specify a name, prefix the variable with "<name>="
+usage-time = A special named value is the one with "time=" or "t=".\nThis indicates that this value should used as the time for plotting.\nIt must be monotonically increasing, so probably should come from a timer.\nThe time unit for the time values received by the device can be set in the application,\nor per value with a unit suffix, e.g. "t_ms=", "t_us=" or "time_s=".\nIf no such variable is specified, the application takes the time when receiving the data
+help-web-port = - Issue: When using the app on the web, the port does not show up.\n    Fix: Restart the browser. The device might need to be plugged in before the browser is launched.
+startup-actions-help = Actions executed when splot is started, so that it comes up capturing data without any interaction.\nThe port is matched by its USB id as "VID:PID" in hex (e.g. "1a86:7523") or by its name.\nIf it is not available yet, splot waits until it is plugged in.\nThe actions are saved with connection profiles and executed when the profile is loaded.
+start-recording = Start recording
+demo-mode = Demo Mode
+web-serial-unsupported = ⚠ Web Serial API not supported ⚠\n on this platform 
+port-usb-info = Manufacturer: { $manufacturer }\nProduct: { $product }\nSerial number: { $serial }
+hover-timeout = Writes that take longer are aborted, reads that receive nothing for longer are restarted
+hover-auto-reconnect = Reconnect with the same port and settings when the connection is lost
+status-lost-connection = ⚠ Disconnected
+hover-reconnecting = Lost the connection to `{ $port }`, reconnection attempt { $attempt }
+hover-lost-connection = Lost the connection to `{ $port }`
+status-connected = ● Connected: { $port }
+status-error = ⚠ Error
+status-disconnected = ○ Disconnected
+hover-disconnect = Close the port
+hover-edit-reconnect = Unlock the connection settings to reconnect with changed settings
+hover-page = Opens the page as tab, with its settings next to it. Drag the tabs to rearrange them, or to an edge to split the area
+hover-connection = Show the connection controls
+hover-copy-image = Copy the displayed pages to the clipboard as image
+hover-archive = Keep the cleared samples on the archive page
+hover-pause = Stop reading. While paused, the Time - Value plot can be dragged and zoomed through all samples
+hover-recording = Recording to `{ $file }`, { $bytes } bytes written
+hover-record = Record the received data to `{ $file }`. Right-click to change the file
+hover-unwrap-time = Unwrap received time values when the device counter rolls over, and continue the time when it jumps backwards
+hover-host-time-synced = Received time values are mapped onto the host clock.\nOffset: { $offset } s, drift: { $drift } ppm
+hover-host-time = Map received time values onto the host clock, so that they line up with the serial monitor
+hover-nmea = GGA, RMC and VTG sentences of GNSS modules
+hover-can = Frames of a SocketCAN interface, e.g. can0
+binary-layout-help = Each packet starts with the magic bytes, followed by the fields in the given order without padding.\nA field named "time" or "t" is used as the time of the packet.
+label-magic-bytes = Magic Bytes (hex):
+label-endianness = Endianness:
+add-field = Add Field
+packet-size = Packet size: { $bytes } bytes
+ubx-messages-help = Frames with an invalid checksum are counted as parse errors and skipped.\nPositions are in degrees, heights and accuracies in m, velocities in m/s and times in s.
+can-signals-help = CAN interfaces are listed with the serial ports on Linux. Each signal becomes a channel,\ndecoded from the data bytes of the frames with its id as raw * scale + offset.
+column-name = Name
+column-can-id = CAN ID
+column-byte = Byte
+column-type = Type
+column-endianness = Endianness
+column-scale = Scale
+column-offset = Offset
+hover-can-signal-overflow = The value does not fit into the 8 data bytes
+add-signal = Add Signal
+derived-channels-help = Derived channels are computed from expressions over the received channels as new samples arrive.\nChannels are referenced by their name or by their index as "c<index>", e.g. "c0 - c1" or "sqrt(x*x + y*y)".\nProducts and ratios of two channels can be picked directly. Integrating over time turns e.g. power into energy.\nThe FFT peak tracks the dominant frequency of a channel, or its amplitude, over the latest samples.\nLow-pass and high-pass filter a channel in real time with a Butterworth filter of first or second order.
+derived-operators = Operators: + - * / ^, Functions: { $functions }
+column-kind = Kind
+column-expression = Expression
+column-integrate = Integrate
+n-samples = { $count } samples
+unit-samples = samples
+filter-first-order = 1st
+filter-second-order = 2nd
+hover-cutoff = The cutoff frequency
+hover-first-order = First order, -20 dB per decade
+hover-second-order = Second order, -40 dB per decade
+add-derived-channel = Add Derived Channel
+automation-help = Rules run an action when a condition over the channels becomes positive, when a received line contains a text,\nor periodically while connected. Conditions are expressions like in derived channels, e.g. "c0 - 3.3" fires\nwhen channel 0 rises above 3.3. Sent text is terminated with the line ending of the serial monitor.\nNotifications are only shown while the window is unfocused.
+column-when = When
+column-action = Action
+every = every
+add-rule = Add Rule
+flash-taskbar = Flash the taskbar icon on notifications
+automation-script = Script
+automation-script-help = A Rhai script for logic that doesn't fit into the rules. It defines the hooks it needs, on_line(line),\non_samples(values) with the latest values by channel name, and on_timer(). They call send(text), marker(label),\nnotify(text), start_recording(), stop_recording() and derive(name, expression), which creates a derived channel.\nThe top-level statements run once when the script is applied, this is a map that keeps values between the calls.
+hover-apply-automation-script = Compiles the script again, the values kept in this are discarded
+alarms-help = An alarm is raised when a channel violates its limits for a number of consecutive samples, and cleared by the\nnext sample within the limits. Channels are referenced by their name or by their index as "c<index>".\nRaised alarms flash the channel on the Time-Value page. Notifications are only shown while the window is unfocused.
+column-channel = Channel
+column-condition = Condition
+column-low = Low
+column-high = High
+column-for = For
+column-notify = Notify
+hover-unknown-channel = No channel with this name
+add-alarm = Add Alarm
+alarm-events = Events: { $count }
+label-file = File:
+load = Load
+export = Export
+import = Import
+label-values-newer = Values newer:
+fade-trail = Fade trail
+crosshair = Crosshair
+label-channels = Channels:
+label-start-condition = Start condition
+label-stop-condition = Stop condition
+hint-none = none
+label-duration = Duration
+hover-duration = Zero to stop only on the stop condition
+label-pre-trigger = Pre-trigger
+rearm = Re-arm
+hover-rearm = Wait for the start condition again after a capture
+label-capture-file = File
+disarm = Disarm
+arm = Arm
+status-capturing = ⏺ Capturing
+status-armed = Armed
+status-disarmed = Disarmed
+n-captures = Captures: { $count }
+capture-file = File: `{ $file }`
+profiles-help = Profiles bundle the port, the connection and parser settings and the appearance of the channels.\nLoad them from the profile selection next to the port, while not connected.
+update = Update
+hover-update-profile = Replace the profile with the current settings
+label-name = Name:
+save-current = Save Current
+hover-save-profile = Save the current settings as new profile
+init-commands-help = Commands transmitted right after connecting, e.g. to make the device start sending data.\nEach command is sent after its delay, counted from the connection or the previous command.
+label-line-ending = Line ending:
+column-delay = Delay
+column-command = Command
+add-command = Add Command
+history-spill = Keep whole history on disk
+hover-history-spill = Samples that don't fit into memory are spilled to temporary files, so that the whole capture can be scrolled back through
+label-scroll-back = Scroll back:
+live = Live
+history-spilled = { $size } MB spilled to disk
+session-diff-help = Compares two CSV captures in the import format, e.g. recorded with different firmware builds.\nChannels are paired by name, the second capture is interpolated at the times of the first one\nand the difference "second - first" is plotted.
+label-first = First:
+label-second = Second:
+label-align-by = Align by:
+label-channel = Channel:
+label-rising-above = Rising above:
+compare = Compare
+comparing-failed = Comparing failed: { $error }
+web-compare-unavailable = Comparing files is not available on the web.
+session-diff-offset = The second capture is shifted by { $offset } s
+column-mean = Mean
+column-rms = RMS
+column-max-diff = Max |diff|
+import-help = Imports an analog CSV export of a logic analyzer (Saleae Logic, sigrok / PulseView) and replaces the current samples with it.\nThe first column must be the time or the sample index, the header row names the channels. Units in brackets like "Time [ms]" are recognized.\nCSV files can also be dropped onto the window.
+web-import-unavailable = Importing files is not available on the web.
+export-help = Exports the samples of the selected channels. CSV files are in the format of the import, NumPy and MATLAB files have the time and a value for each channel per row, missing values are NaN.
+select-all = All
+select-visible = Visible
+exported-channels = Exported { $count } channels to `{ $file }`
+export-failed = Export failed: { $error }
+web-export-unavailable = Exporting files is not available on the web.
+data-servers-help = Serves the received samples as JSON on localhost, for scripts and other tools.
+label-allowed-origins = Allowed origins:
+hover-allowed-origins = Websites that may request the data, separated by commas. Requests of other websites are rejected
+enabled = Enabled
+http-api-serving = Serving on http://{ $addr }
+http-api-channels = The channels with their latest sample
+http-api-samples-since = The samples newer than the time in seconds
+http-api-samples-channel = The samples of a channel, by index or name
+websocket-broadcasting = Broadcasting on ws://{ $addr }, { $clients } clients connected
+websocket-message = Every parsed sample is sent as message:
+device-script-help = The script is played by the "dummy (script)" port of the dummy connection, one command per line:\nwait <ms>, send <text>, line <text> (followed by a line feed), on <request> => <response>, repeat.\nTexts understand the escapes \\n, \\r, \\t, \\\\ and \\xNN, lines starting with # are comments.
+apply = Apply
+hover-apply-script = Reconnects, so that the dummy connection plays the script
+label-reads = Reads:
+label-empty-reads = Empty reads:
+label-failed-reads = Failed reads:
+label-read-latency = Read latency:
+label-poll-interval = Poll interval:
+latest-and-max = { $latest }, max { $max }
+label-parse-errors = Parse errors:
+hover-parse-errors = Lines without a value, they are dropped
+label-partial-reads = Partial reads:
+hover-partial-reads = Reads that ended within a line
+label-unparsed-bytes = Unparsed bytes:
+label-fullest-channel-buffer = Fullest channel buffer:
+label-monitor-buffer = Monitor buffer:
+debug-bundle-help = Creates a zip archive with the log, the settings, parser diagnostics and the latest received bytes.\nAttach it when reporting an issue.
+redact-port-names = Redact port names
+create = Create
+created-file = Created `{ $file }`
+debug-bundle-failed = Creating the debug bundle failed: { $error }
+captures-help = Recordings, exports and debug bundles are created in the capture directory when named automatically.\nCSV exports are imported when opened, raw recordings are replayed with the current format settings.
+label-directory = Directory:
+label-session-name = Session Name:
+hover-session-name = Placeholders: { $placeholders }. The date and time are in UTC
+auto-session-names = Name recordings, exports and debug bundles automatically
+refresh = ⟲ Refresh
+n-files = { $count } files
+open = Open
+opening-failed = Opening failed: { $error }
+appearance-preset-help = Exports the names, colors, units and axes of the channels as preset, so that everyone plotting the same firmware shares them.\nAn imported preset is applied to new channels, matched by name or otherwise by their position.
+exported-appearance = Exported the appearance of { $count } channels
+imported-appearance = Imported the appearance of { $count } channels
+import-failed = Import failed: { $error }
+hover-clear-preset = Stop applying the imported preset to new channels
+web-preset-unavailable = Exporting and importing presets is not available on the web.
+remembered-appearances = Remembered appearances: { $count }
+hover-remembered-appearances = The appearance of channels is restored when a channel with the same name appears
+forget = Forget
+tracking-cursor = Tracking cursor
+hover-tracking-cursor = Show the values of all visible channels at the hovered time
+hover-crosshair = Show the coordinates of the pointer at the axes
+now = Now
+hover-time-zero = Display and export the times relative to the latest sample.\nRight click the plot to set it at another moment
+time-zero-at = at { $time }
+log-y-axis = Logarithmic Y axis
+hover-log-y-axis = Values that are not positive are not displayed
+label-y-bounds = Y bounds:
+label-downsampling = Downsampling:
+unit-points = points
+hover-point-budget = The maximum number of points rendered per channel
+label-render-budget = Render budget:
+label-values = Values:
+unit-decimals = decimals
+hover-value-format = The format of the values in the labels, axes and readouts
+break-gaps = Break lines at gaps
+hover-break-gaps = Don't connect samples that are further apart than the threshold
+shade-gaps = Shade gaps
+stacked-subplots = Stacked subplots
+hover-stacked-subplots = Each channel in its own plot, with a shared time axis
+snapshot = 📷 Snapshot
+hover-snapshot = Freeze the visible traces as ghosts, to compare them against the new response
+label-sort-channels = Sort channels:
+label-unit = Unit:
+hover-left-axis = Left Y axis
+hover-right-axis = Right Y axis
+hold = Hold
+hover-hold = Hold the last value up to the end of the plot
+fill = Fill
+hover-fill = Fill the area between the line and zero
+hover-invalid-values = The number of NaN and infinite values, the line is broken at NaN
+trigger = Trigger
+average-name = { $name } (average)
+set-time-zero = Set t=0 here
+reset-time-zero = Reset t=0
+hover-alarm = Alarm: { $channels }
+moving-average = Moving average
+plausible-range = Plausible range
+range-to = to
+out-of-range = Out of range: { $count }
+reference-traces = Reference traces
+reference-traces-help = Saved CSV captures, drawn frozen behind the live data relative to t=0.
+loading-failed = Loading failed: { $error }
+reference-traces-archive = Archived captures can be used as reference on the archive page.
+opacity = Opacity
+label-offset = Offset:
+hover-reference-offset = Shift the reference on the time axis
+guide-lines = Guide lines
+guide-lines-help = Named horizontal lines at a fixed value, like the limits of the device.
+add-guide-line = Add guide line
+cursors = Cursors
+show-cursors = Show cursors
+place-in-view = Place in view
+column-mode = Mode
+column-level = Level
+column-edge = Edge
+column-pre-trigger = Pre-Trigger
+trigger-free-running = Free running
+trigger-armed = Armed, waiting for trigger
+trigger-stopped = Stopped
+trigger-triggered = Triggered
+add-pair = Add Pair
+column-logarithmic = Logarithmic
+hover-fade-trail = Draw older samples more transparent
+column-y-bounds = Y bounds
+column-render-budget = Render budget
+hover-drag-channel = Drag a channel onto an axis to select it
+axis-name = { $axis }-Axis
+reset-view = Reset View
+select-three-channels = Select three channels
+column-range = Range
+column-low-limit = Low limit
+column-high-limit = High limit
+remove = 🗑 Remove
+add-widget = Add Widget
+dashboard-empty = Add widgets in the dashboard settings
+column-fft-size = FFT Size
+column-dynamic-range = Dynamic Range
+no-samples = No samples
+spectrogram-not-enough-samples = Not enough samples, the FFT needs { $needed } samples, received { $received }
+unknown-sample-rate = Can't determine the sample rate
+column-total-bytes = Total bytes
+column-total-samples = Total samples
+archive-empty = No archived captures, enable "Archive" to keep the samples when clearing
+archived-capture = { $count } samples, { $duration }
+use-as-reference = Use as reference
+hover-use-as-reference = Draw the selected capture behind the live data of the Time-Value plot
+hover-export-archived = Export the selected capture as CSV
+exported-capture = Exported `{ $capture }` to `{ $file }`
+label-timestamp = Timestamp:
+hover-hex = Show the raw received bytes as hex dump
+ansi-colors = ANSI colors
+hover-ansi-colors = Render ANSI color escape sequences
+table = Table
+hover-table = Split the lines into columns by the value separator
+echo = Echo
+send = Send
+hint-send = Text to send
+column-received = Received
+column-time = Time
+column-index = Column { $index }
+received-bytes = Received: { $count } bytes
+shown-bytes = Shown: { $count } bytes
+hover-shown-bytes = The latest received bytes are kept
+hover-line-terminators = The line terminators in the shown bytes
+other-control-bytes = Other control bytes: { $count }
+non-ascii-bytes = Non-ASCII bytes: { $count }
+highlight-line-endings = Highlight CR / LF
+no-data = No data
+hover-render-budget = The maximum number of points rendered per frame, lines above their share are decimated
+decimated = { $factor }× decimated
+minimum = Minimum
+maximum = Maximum
+conditional-capture-help = While armed, a CSV capture is written when the start condition becomes positive. It includes the samples of the\npre-trigger window that are still in memory and stops after the duration or when the stop condition becomes positive.\nChannels are referenced like in derived channels, e.g. "c0 - 3.3" triggers when channel 0 rises above 3.3.\nThe times are relative to the trigger. The file is named after the session when session names are enabled.
+archive-run = Run { $n }
+status-list-captures-failed = Listing captures failed: { $error }
+status-imported-from = Imported { $samples } samples from `{ $path }`
+status-replayed-from = Replayed { $samples } samples from `{ $path }`
+alarm-raised = { $condition } for { $samples } samples, { $value }
+alarm-cleared = cleared
+alarm-above = above { $high }
+alarm-below = below { $low }
+alarm-outside = outside { $low } to { $high }
+notification-alarm = Alarm on { $channel }: { $message }
+status-imported-channels = Imported { $samples } samples in { $channels } channels
+status-import-failed = Import failed: { $error }
+snapshot-name = Snapshot { $n }
+status-copied-image = Copied { $width }x{ $height } image
+cursor-name = Cursor { $n }
+tab-page-controls = { $page } Settings
+line-ending-none = None
+line-ending-cr = CR
+line-ending-lf = LF
+line-ending-cr-lf = CRLF
+smoothing-mode-off = Off
+smoothing-mode-alongside = Alongside
+smoothing-mode-instead = Instead
+filter-action-discard = Discard
+filter-action-clamp = Clamp
+filter-action-flag = Flag
+rule-hook-condition = Condition
+rule-hook-line = Line
+rule-hook-timer = Timer
+rule-action-send = Send
+rule-action-marker = Marker
+rule-action-notify = Notify
+rule-action-start-recording = Start Recording
+rule-action-stop-recording = Stop Recording
+downsampling-off = Off
+downsampling-lttb = LTTB
+downsampling-min-max = Min / Max
+derived-kind-expression = Expression
+derived-kind-product = Product
+derived-kind-ratio = Ratio
+derived-kind-peak-frequency = FFT Peak Frequency
+derived-kind-peak-amplitude = FFT Peak Amplitude
+derived-kind-low-pass = Low-Pass
+derived-kind-high-pass = High-Pass
+widget-kind-numeric = Numeric
+widget-kind-bar = Bar
+widget-kind-gauge = Gauge
+export-format-csv = CSV
+export-format-npy = NumPy (.npy)
+export-format-npz = NumPy (.npz)
+export-format-mat = MATLAB (.mat)
+monitor-timestamp-none = None
+monitor-timestamp-relative = Relative
+monitor-timestamp-wall-clock = Wall clock (UTC)
+data-format-ascii = ASCII
+data-format-binary = Binary
+data-format-ubx = UBX
+data-format-nmea = NMEA
+data-format-can = CAN
+plot-axis-left = Left
+plot-axis-right = Right
+y-bounds-mode-free = Free
+y-bounds-mode-autoscale = Autoscale
+y-bounds-mode-autoscale-margin = Autoscale with margin
+y-bounds-mode-fixed = Fixed
+channel-order-arrival = Arrival
+channel-order-name = Name
+channel-order-activity = Activity
+channel-order-magnitude = Magnitude
+notation-plain = Plain
+notation-scientific = Scientific
+notation-si-prefix = SI prefix
+diff-alignment-start = Start
+diff-alignment-trigger = Trigger
+alarm-condition-above = Above
+alarm-condition-below = Below
+alarm-condition-out-of-range = Out of Range
+trigger-mode-off = Off
+trigger-mode-normal = Normal
+trigger-mode-single = Single
+trigger-edge-rising = Rising
+trigger-edge-falling = Falling
+trigger-edge-both = Both
+density-compact = Compact
+density-normal = Normal
+density-comfortable = Comfortable
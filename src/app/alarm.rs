@@ -0,0 +1,193 @@
+use super::locale::{tr, tr_args};
+
+/// The maximum number of alarm events that are kept, the oldest ones are dropped
+pub const MAX_ALARM_EVENTS: usize = 1000;
+
+/// When the value of a channel violates an alarm rule
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum AlarmCondition {
+    /// Above the high limit
+    #[default]
+    Above,
+    /// Below the low limit
+    Below,
+    /// Below the low or above the high limit
+    OutOfRange,
+}
+
+impl std::fmt::Display for AlarmCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlarmCondition::Above => write!(f, "Above"),
+            AlarmCondition::Below => write!(f, "Below"),
+            AlarmCondition::OutOfRange => write!(f, "Out of Range"),
+        }
+    }
+}
+
+impl AlarmCondition {
+    pub const ALL: [Self; 3] = [Self::Above, Self::Below, Self::OutOfRange];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            AlarmCondition::Above => tr("alarm-condition-above"),
+            AlarmCondition::Below => tr("alarm-condition-below"),
+            AlarmCondition::OutOfRange => tr("alarm-condition-out-of-range"),
+        }
+    }
+
+    pub fn uses_low(self) -> bool {
+        matches!(self, AlarmCondition::Below | AlarmCondition::OutOfRange)
+    }
+
+    pub fn uses_high(self) -> bool {
+        matches!(self, AlarmCondition::Above | AlarmCondition::OutOfRange)
+    }
+}
+
+/// Raises an alarm when the values of a channel violate the limits for a number of consecutive samples
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AlarmRule {
+    pub enabled: bool,
+    /// The name of the channel, or its index as `c<index>`
+    pub channel: String,
+    pub condition: AlarmCondition,
+    pub low: f64,
+    pub high: f64,
+    /// The number of consecutive samples that need to violate the limits
+    pub samples: usize,
+    /// If a desktop notification is shown when the alarm is raised
+    pub notify: bool,
+    #[serde(skip)]
+    consecutive: usize,
+    /// The index of the channel while the alarm is raised
+    #[serde(skip)]
+    raised_on: Option<usize>,
+}
+
+impl Default for AlarmRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            channel: String::from("c0"),
+            condition: AlarmCondition::default(),
+            low: 0.0,
+            high: 1.0,
+            samples: 1,
+            notify: false,
+            consecutive: 0,
+            raised_on: None,
+        }
+    }
+}
+
+/// A raised or cleared alarm
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlarmEvent {
+    /// The time of the sample in nanoseconds
+    pub time: i64,
+    pub channel: String,
+    pub message: String,
+    pub raised: bool,
+}
+
+impl AlarmRule {
+    /// Needs to be called when the rule was changed
+    pub fn reset(&mut self) {
+        self.consecutive = 0;
+        self.raised_on = None;
+    }
+
+    /// The index of the channel, while the alarm is raised
+    pub fn raised_on(&self) -> Option<usize> {
+        self.raised_on
+    }
+
+    pub fn describe(&self) -> String {
+        match self.condition {
+            AlarmCondition::Above => tr_args("alarm-above", &[("high", &self.high)]),
+            AlarmCondition::Below => tr_args("alarm-below", &[("low", &self.low)]),
+            AlarmCondition::OutOfRange => {
+                tr_args("alarm-outside", &[("low", &self.low), ("high", &self.high)])
+            }
+        }
+    }
+
+    /// Checks the next value of the channel with the index.
+    ///
+    /// Returns `Some(true)` when the alarm is raised, and `Some(false)` when it is cleared by a value within the limits.
+    /// NaN values are within the limits.
+    pub fn check(&mut self, channel: usize, value: f64) -> Option<bool> {
+        let violated = match self.condition {
+            AlarmCondition::Above => value > self.high,
+            AlarmCondition::Below => value < self.low,
+            AlarmCondition::OutOfRange => value < self.low || value > self.high,
+        };
+
+        if violated {
+            self.consecutive += 1;
+        } else {
+            self.consecutive = 0;
+        }
+
+        match self.raised_on {
+            None if self.consecutive >= self.samples.max(1) => {
+                self.raised_on = Some(channel);
+                Some(true)
+            }
+            Some(_) if !violated => {
+                self.raised_on = None;
+                Some(false)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alarm_rules() {
+        let mut alarm = AlarmRule {
+            condition: AlarmCondition::OutOfRange,
+            low: -1.0,
+            samples: 3,
+            ..Default::default()
+        };
+        let results = [0.0, 2.0, -2.0, f64::NAN, 2.0, 3.0, 4.0, 5.0, 0.5]
+            .into_iter()
+            .map(|v| alarm.check(4, v))
+            .collect::<Vec<Option<bool>>>();
+
+        assert_eq!(
+            results,
+            [
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(true),
+                None,
+                Some(false)
+            ]
+        );
+        assert_eq!(alarm.raised_on(), None);
+    }
+}
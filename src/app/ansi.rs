@@ -0,0 +1,152 @@
+use egui::text::{LayoutJob, TextFormat};
+use egui::Color32;
+
+/// The 16 standard and bright colors
+const BASIC_COLORS: [Color32; 16] = [
+    Color32::from_rgb(0, 0, 0),
+    Color32::from_rgb(205, 49, 49),
+    Color32::from_rgb(13, 188, 121),
+    Color32::from_rgb(229, 229, 16),
+    Color32::from_rgb(36, 114, 200),
+    Color32::from_rgb(188, 63, 188),
+    Color32::from_rgb(17, 168, 205),
+    Color32::from_rgb(229, 229, 229),
+    Color32::from_rgb(102, 102, 102),
+    Color32::from_rgb(241, 76, 76),
+    Color32::from_rgb(35, 209, 139),
+    Color32::from_rgb(245, 245, 67),
+    Color32::from_rgb(59, 142, 234),
+    Color32::from_rgb(214, 112, 214),
+    Color32::from_rgb(41, 184, 219),
+    Color32::from_rgb(255, 255, 255),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AnsiColor {
+    /// An index into the 256 color palette
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl AnsiColor {
+    fn to_color32(self, bold: bool) -> Color32 {
+        match self {
+            // Bold standard colors are displayed in their bright variant, like most terminals do
+            AnsiColor::Indexed(i @ 0..=7) if bold => BASIC_COLORS[i as usize + 8],
+            AnsiColor::Indexed(i @ 0..=15) => BASIC_COLORS[i as usize],
+            AnsiColor::Indexed(i @ 16..=231) => {
+                let i = i - 16;
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+
+                Color32::from_rgb(level(i / 36), level((i / 6) % 6), level(i % 6))
+            }
+            AnsiColor::Indexed(i) => {
+                let gray = 8 + (i - 232) * 10;
+                Color32::from_gray(gray)
+            }
+            AnsiColor::Rgb(r, g, b) => Color32::from_rgb(r, g, b),
+        }
+    }
+}
+
+/// The text style selected by SGR (Select Graphic Rendition) escape sequences
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnsiStyle {
+    fg: Option<AnsiColor>,
+    bg: Option<AnsiColor>,
+    bold: bool,
+    italics: bool,
+    underline: bool,
+}
+
+impl AnsiStyle {
+    /// Applies the `;` separated parameters of a SGR sequence
+    fn apply_sgr(&mut self, params: &str) {
+        // An empty parameter is the same as 0
+        let mut codes = params.split(';').map(|c| c.parse::<u8>().unwrap_or(0));
+
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                3 => self.italics = true,
+                4 => self.underline = true,
+                22 => self.bold = false,
+                23 => self.italics = false,
+                24 => self.underline = false,
+                30..=37 => self.fg = Some(AnsiColor::Indexed(code - 30)),
+                38 => self.fg = extended_color(&mut codes),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(AnsiColor::Indexed(code - 40)),
+                48 => self.bg = extended_color(&mut codes),
+                49 => self.bg = None,
+                90..=97 => self.fg = Some(AnsiColor::Indexed(code - 90 + 8)),
+                100..=107 => self.bg = Some(AnsiColor::Indexed(code - 100 + 8)),
+                _ => {}
+            }
+        }
+    }
+
+    fn text_format(&self, default_format: &TextFormat) -> TextFormat {
+        let mut format = default_format.clone();
+
+        if let Some(fg) = self.fg {
+            format.color = fg.to_color32(self.bold);
+        }
+        if let Some(bg) = self.bg {
+            format.background = bg.to_color32(false);
+        }
+        format.italics = self.italics;
+        if self.underline {
+            format.underline = egui::Stroke::new(1.0, format.color);
+        }
+
+        format
+    }
+}
+
+/// Parses the color of the extended `38;5;<index>` and `38;2;<r>;<g>;<b>` forms
+fn extended_color(codes: &mut impl Iterator<Item = u8>) -> Option<AnsiColor> {
+    match codes.next()? {
+        5 => Some(AnsiColor::Indexed(codes.next()?)),
+        2 => Some(AnsiColor::Rgb(codes.next()?, codes.next()?, codes.next()?)),
+        _ => None,
+    }
+}
+
+/// Appends the text to the layout job, styled by the SGR escape sequences it contains.
+///
+/// Other escape sequences are removed. The style carries over to the next call,
+/// so that it persists across lines like in a terminal.
+pub fn append_ansi(
+    job: &mut LayoutJob,
+    text: &str,
+    default_format: &TextFormat,
+    style: &mut AnsiStyle,
+) {
+    let append = |job: &mut LayoutJob, text: &str, style: &AnsiStyle| {
+        if !text.is_empty() {
+            job.append(text, 0.0, style.text_format(default_format));
+        }
+    };
+    let mut rest = text;
+
+    while let Some(esc) = rest.find('\x1b') {
+        append(job, &rest[..esc], style);
+        rest = &rest[esc + 1..];
+
+        if let Some(csi) = rest.strip_prefix('[') {
+            // The parameters are terminated by a final byte in the range 0x40..=0x7e
+            let Some(end) = csi.find(|c: char| ('\x40'..='\x7e').contains(&c)) else {
+                return;
+            };
+
+            if csi[end..].starts_with('m') {
+                style.apply_sgr(&csi[..end]);
+            }
+            rest = &csi[end + 1..];
+        }
+    }
+
+    append(job, rest, style);
+}
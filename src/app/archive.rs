@@ -0,0 +1,32 @@
+use super::import::ImportedCapture;
+use super::nanos_to_secs;
+
+/// The maximum number of archived captures, the oldest ones are dropped
+pub const MAX_ARCHIVED: usize = 16;
+
+/// Samples that were moved out of the live view when it was cleared
+#[derive(Debug, Clone)]
+pub struct ArchivedCapture {
+    pub label: String,
+    pub capture: ImportedCapture,
+    /// The time displayed as t=0 when it was archived
+    pub time_zero: i64,
+}
+
+impl ArchivedCapture {
+    /// The time between the first and the last sample
+    pub fn duration_secs(&self) -> f64 {
+        let samples = self.capture.samples_vec.iter();
+        let first = samples
+            .clone()
+            .filter_map(|s| s.first())
+            .map(|s| s.time)
+            .min();
+        let last = samples.filter_map(|s| s.last()).map(|s| s.time).max();
+
+        match (first, last) {
+            (Some(first), Some(last)) => nanos_to_secs(last - first),
+            _ => 0.0,
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use super::derived::resolve_channel;
+use super::expression::Expr;
+use super::locale::tr;
+use instant::{Duration, Instant};
+
+/// What triggers an automation rule
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum RuleHook {
+    /// The condition expression becomes positive
+    #[default]
+    Condition,
+    /// A received line contains the text
+    Line,
+    /// Periodically while connected
+    Timer,
+}
+
+impl std::fmt::Display for RuleHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleHook::Condition => write!(f, "Condition"),
+            RuleHook::Line => write!(f, "Line"),
+            RuleHook::Timer => write!(f, "Timer"),
+        }
+    }
+}
+
+impl RuleHook {
+    pub const ALL: [Self; 3] = [Self::Condition, Self::Line, Self::Timer];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            RuleHook::Condition => tr("rule-hook-condition"),
+            RuleHook::Line => tr("rule-hook-line"),
+            RuleHook::Timer => tr("rule-hook-timer"),
+        }
+    }
+}
+
+/// What an automation rule does when it fires
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum RuleAction {
+    /// Sends the text to the device
+    #[default]
+    Send,
+    /// Adds a marker to the plot
+    Marker,
+    /// Shows an OS notification with the text, when the window is unfocused
+    Notify,
+    StartRecording,
+    StopRecording,
+}
+
+impl std::fmt::Display for RuleAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleAction::Send => write!(f, "Send"),
+            RuleAction::Marker => write!(f, "Marker"),
+            RuleAction::Notify => write!(f, "Notify"),
+            RuleAction::StartRecording => write!(f, "Start Recording"),
+            RuleAction::StopRecording => write!(f, "Stop Recording"),
+        }
+    }
+}
+
+impl RuleAction {
+    pub const ALL: [Self; 5] = [
+        Self::Send,
+        Self::Marker,
+        Self::Notify,
+        Self::StartRecording,
+        Self::StopRecording,
+    ];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            RuleAction::Send => tr("rule-action-send"),
+            RuleAction::Marker => tr("rule-action-marker"),
+            RuleAction::Notify => tr("rule-action-notify"),
+            RuleAction::StartRecording => tr("rule-action-start-recording"),
+            RuleAction::StopRecording => tr("rule-action-stop-recording"),
+        }
+    }
+
+    /// If the action uses the argument
+    pub fn has_argument(self) -> bool {
+        matches!(
+            self,
+            RuleAction::Send | RuleAction::Marker | RuleAction::Notify
+        )
+    }
+}
+
+/// Runs an action when the received data matches, or periodically.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AutomationRule {
+    pub enabled: bool,
+    pub hook: RuleHook,
+    /// The condition expression, or the text a line needs to contain.
+    ///
+    /// Channels are referenced like in derived channels, e.g. `c0 - 3.3` fires when channel 0 rises above 3.3.
+    pub pattern: String,
+    /// The interval of the timer
+    pub interval_ms: u64,
+    pub action: RuleAction,
+    /// The text that is sent, the label of the marker or the notification text
+    pub argument: String,
+    #[serde(skip)]
+    compiled: Option<Result<Expr, String>>,
+    /// If the condition was true at the last evaluation
+    #[serde(skip)]
+    condition_was_true: bool,
+    #[serde(skip)]
+    last_fired: Option<Instant>,
+}
+
+impl Default for AutomationRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            hook: RuleHook::default(),
+            pattern: String::from("c0"),
+            interval_ms: 1000,
+            action: RuleAction::default(),
+            argument: String::new(),
+            compiled: None,
+            condition_was_true: false,
+            last_fired: None,
+        }
+    }
+}
+
+impl AutomationRule {
+    /// Needs to be called when the hook or the pattern was changed
+    pub fn invalidate(&mut self) {
+        self.compiled = None;
+        self.condition_was_true = false;
+        self.last_fired = None;
+    }
+
+    /// The condition expression error, if it fails to compile
+    pub fn error(&mut self) -> Option<&str> {
+        if self.hook != RuleHook::Condition {
+            return None;
+        }
+
+        self.compiled().err()
+    }
+
+    fn compiled(&mut self) -> Result<&Expr, &str> {
+        let pattern = &self.pattern;
+
+        self.compiled
+            .get_or_insert_with(|| Expr::parse(pattern).map_err(|e| e.to_string()))
+            .as_ref()
+            .map_err(|e| e.as_str())
+    }
+
+    /// Evaluates the condition with the latest channel values, indexed by the channel index.
+    ///
+    /// Returns true when the condition became positive, so that the rule fires once per crossing.
+    pub fn check_condition(&mut self, values: &[Option<f64>], names: &[&str]) -> bool {
+        if !self.enabled || self.hook != RuleHook::Condition {
+            return false;
+        }
+        let Ok(expr) = self.compiled() else {
+            return false;
+        };
+
+        let Some(value) = expr.eval(&|var| values.get(resolve_channel(var, names)?).copied()?)
+        else {
+            return false;
+        };
+
+        let is_true = value > 0.0;
+        let fired = is_true && !self.condition_was_true;
+        self.condition_was_true = is_true;
+
+        fired
+    }
+
+    /// Returns true when the received line contains the text
+    pub fn check_line(&self, line: &str) -> bool {
+        self.enabled
+            && self.hook == RuleHook::Line
+            && !self.pattern.is_empty()
+            && line.contains(&self.pattern)
+    }
+
+    /// Returns true when the interval has elapsed since the rule last fired
+    pub fn check_timer(&mut self) -> bool {
+        if !self.enabled || self.hook != RuleHook::Timer {
+            return false;
+        }
+
+        let now = Instant::now();
+        let last_fired = *self.last_fired.get_or_insert(now);
+
+        // A zero interval would fire every frame
+        if now.duration_since(last_fired) < Duration::from_millis(self.interval_ms.max(10)) {
+            return false;
+        }
+
+        self.last_fired = Some(now);
+        true
+    }
+}
@@ -0,0 +1,160 @@
+use std::path::{Path, PathBuf};
+
+use instant::Duration;
+
+/// The placeholders that are replaced in session name templates
+pub const PLACEHOLDERS: [&str; 4] = ["{date}", "{time}", "{port}", "{profile}"];
+
+/// Expands the placeholders of the template, with the date and time in UTC.
+///
+/// The port is reduced to its last path component, characters that are not allowed in file names are replaced.
+pub fn session_name(template: &str, since_epoch: Duration, port: &str, profile: &str) -> String {
+    let secs = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let secs_of_day = secs % 86400;
+
+    let port = port.rsplit(['/', '\\']).next().unwrap_or_default();
+
+    let name = template
+        .replace("{date}", &format!("{year:04}-{month:02}-{day:02}"))
+        .replace(
+            "{time}",
+            &format!(
+                "{:02}-{:02}-{:02}",
+                secs_of_day / 3600,
+                (secs_of_day / 60) % 60,
+                secs_of_day % 60
+            ),
+        )
+        .replace("{port}", if port.is_empty() { "none" } else { port })
+        .replace(
+            "{profile}",
+            if profile.is_empty() { "none" } else { profile },
+        );
+
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// Formats the time since the epoch in UTC as `yyyy-mm-dd hh:mm`
+pub fn format_date_time(since_epoch: Duration) -> String {
+    let secs = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let secs_of_day = secs % 86400;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs_of_day / 3600,
+        (secs_of_day / 60) % 60
+    )
+}
+
+/// The year, month and day of the days since the epoch, in the proleptic gregorian calendar
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// A file in the capture directory
+#[derive(Debug, Clone)]
+pub struct CaptureFile {
+    pub path: PathBuf,
+    pub name: String,
+    pub size: u64,
+    /// The modification time since the epoch
+    pub modified: Option<Duration>,
+}
+
+impl CaptureFile {
+    /// CSV exports are imported, other files are replayed as raw recordings
+    pub fn is_csv(&self) -> bool {
+        self.path
+            .extension()
+            .map_or(false, |e| e.eq_ignore_ascii_case("csv"))
+    }
+
+    /// Debug bundles can't be opened
+    pub fn can_open(&self) -> bool {
+        !self
+            .path
+            .extension()
+            .map_or(false, |e| e.eq_ignore_ascii_case("zip"))
+    }
+}
+
+/// Lists the files in the capture directory, the newest first. A missing directory has no captures.
+pub fn list_captures(dir: impl AsRef<Path>) -> anyhow::Result<Vec<CaptureFile>> {
+    let dir = dir.as_ref();
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        anyhow::anyhow!(
+            "failed to read capture directory `{}`, Err: {e}",
+            dir.display()
+        )
+    })?;
+
+    let mut captures = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+
+            Some(CaptureFile {
+                path: entry.path(),
+                name: entry.file_name().to_string_lossy().into_owned(),
+                size: metadata.len(),
+                modified: metadata
+                    .modified()
+                    .ok()
+                    .and_then(|m| m.duration_since(std::time::UNIX_EPOCH).ok()),
+            })
+        })
+        .collect::<Vec<CaptureFile>>();
+
+    captures.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(captures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_names() {
+        // 2024-02-29 13:05:09 UTC
+        let since_epoch = Duration::from_secs(1_709_211_909);
+
+        assert_eq!(
+            session_name("{date}_{time}_{port}", since_epoch, "/dev/ttyUSB0", ""),
+            "2024-02-29_13-05-09_ttyUSB0"
+        );
+        assert_eq!(
+            session_name("{profile}: {port}", since_epoch, "", "a/b"),
+            "a_b_ none"
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+/// Kept for the lifetime of the app, on X11 the clipboard content is served by its owner
+static CLIPBOARD: Lazy<Mutex<Option<arboard::Clipboard>>> = Lazy::new(|| Mutex::new(None));
+
+/// Puts the image on the system clipboard
+pub fn copy_image(image: &egui::ColorImage) -> anyhow::Result<()> {
+    let mut clipboard = CLIPBOARD
+        .lock()
+        .map_err(|_| anyhow::anyhow!("clipboard lock is poisoned"))?;
+
+    if clipboard.is_none() {
+        *clipboard = Some(
+            arboard::Clipboard::new()
+                .map_err(|e| anyhow::anyhow!("failed to open clipboard, Err: {e}"))?,
+        );
+    }
+
+    clipboard
+        .as_mut()
+        .unwrap()
+        .set_image(arboard::ImageData {
+            width: image.width(),
+            height: image.height(),
+            bytes: image.as_raw().into(),
+        })
+        .map_err(|e| anyhow::anyhow!("failed to copy image to clipboard, Err: {e}"))
+}
@@ -0,0 +1,72 @@
+use super::locale::tr;
+
+/// The line ending appended to transmitted commands
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum LineEnding {
+    None,
+    Cr,
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl std::fmt::Display for LineEnding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineEnding::None => write!(f, "None"),
+            LineEnding::Cr => write!(f, "CR"),
+            LineEnding::Lf => write!(f, "LF"),
+            LineEnding::CrLf => write!(f, "CRLF"),
+        }
+    }
+}
+
+impl LineEnding {
+    pub const ALL: [Self; 4] = [Self::None, Self::Cr, Self::Lf, Self::CrLf];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            LineEnding::None => tr("line-ending-none"),
+            LineEnding::Cr => tr("line-ending-cr"),
+            LineEnding::Lf => tr("line-ending-lf"),
+            LineEnding::CrLf => tr("line-ending-cr-lf"),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::None => "",
+            LineEnding::Cr => "\r",
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    /// The bytes of the text with the line ending appended
+    pub fn terminate(self, text: &str) -> Vec<u8> {
+        let mut data = Vec::with_capacity(text.len() + 2);
+        data.extend_from_slice(text.as_bytes());
+        data.extend_from_slice(self.as_str().as_bytes());
+        data
+    }
+}
+
+/// A command that is transmitted right after connecting
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct InitCommand {
+    pub command: String,
+    /// The delay before the command is transmitted, after connecting or the previous command
+    pub delay_ms: u64,
+}
@@ -0,0 +1,344 @@
+use std::io::Write;
+
+use super::derived::resolve_channel;
+use super::export::{self, ExportChannel};
+use super::expression::Expr;
+use super::{secs_to_nanos, Sample};
+use crate::fixedsizebuffer::FixedSizeBuffer;
+
+/// Writes the samples to a CSV capture only around an event: the capture starts when the start condition fires,
+/// with the samples of the pre-trigger window that are still in memory, and stops after the duration or when the stop condition fires.
+///
+/// The times in the file are relative to the trigger.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ConditionalCapture {
+    /// Starts the capture when it becomes positive, channels are referenced like in derived channels
+    pub start_condition: String,
+    /// Stops the capture when it becomes positive, unused when empty
+    pub stop_condition: String,
+    /// Stops the capture after the time in seconds, unused when zero
+    pub duration: f64,
+    /// The time before the trigger in seconds that is included in the capture
+    pub pre_trigger: f64,
+    /// Arms again after a capture, instead of taking a single one
+    pub rearm: bool,
+    /// The file of the capture, numbered when there are several. Unused when session names are enabled
+    pub path: String,
+    #[serde(skip)]
+    armed: bool,
+    #[serde(skip)]
+    start: Condition,
+    #[serde(skip)]
+    stop: Condition,
+    #[serde(skip)]
+    file: Option<CaptureFile>,
+    /// The number of captures since the capture was armed
+    #[serde(skip)]
+    n_captures: usize,
+    /// The file of the last finished capture
+    #[serde(skip)]
+    last_path: Option<String>,
+}
+
+impl Default for ConditionalCapture {
+    fn default() -> Self {
+        Self {
+            start_condition: String::from("c0 - 1"),
+            stop_condition: String::new(),
+            duration: 10.0,
+            pre_trigger: 1.0,
+            rearm: false,
+            path: String::from("splot_capture.csv"),
+            armed: false,
+            start: Condition::default(),
+            stop: Condition::default(),
+            file: None,
+            n_captures: 0,
+            last_path: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CaptureFile {
+    writer: std::io::BufWriter<std::fs::File>,
+    path: String,
+    /// The time of the trigger in nanoseconds
+    trigger_time: i64,
+    /// The number of channels in the header, channels that appear later are not captured
+    n_channels: usize,
+}
+
+/// A compiled condition expression that fires when it becomes positive
+#[derive(Debug, Default)]
+struct Condition {
+    /// The source and the compiled expression, compiled again when the source changes
+    compiled: Option<(String, Result<Expr, String>)>,
+    was_true: bool,
+}
+
+impl Condition {
+    fn compiled(&mut self, source: &str) -> Result<&Expr, &str> {
+        if self.compiled.as_ref().map_or(true, |(s, _)| s != source) {
+            self.compiled = Some((
+                source.to_string(),
+                Expr::parse(source).map_err(|e| e.to_string()),
+            ));
+            self.was_true = false;
+        }
+
+        match self.compiled.as_ref() {
+            Some((_, res)) => res.as_ref().map_err(|e| e.as_str()),
+            None => Err(""),
+        }
+    }
+
+    /// Returns true when the condition became positive
+    fn check(&mut self, source: &str, values: &[Option<f64>], names: &[&str]) -> bool {
+        let is_true = self.compiled(source).ok().map_or(false, |expr| {
+            expr.eval(&|var| values.get(resolve_channel(var, names)?).copied()?)
+                .map_or(false, |value| value > 0.0)
+        });
+        let fired = is_true && !self.was_true;
+        self.was_true = is_true;
+
+        fired
+    }
+}
+
+impl ConditionalCapture {
+    pub fn is_armed(&self) -> bool {
+        self.armed
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.file.is_some()
+    }
+
+    /// Waits for the start condition
+    pub fn arm(&mut self) {
+        self.armed = true;
+        self.n_captures = 0;
+        self.start.was_true = false;
+    }
+
+    /// Stops waiting, and finishes a running capture
+    pub fn disarm(&mut self) -> anyhow::Result<()> {
+        self.armed = false;
+        self.finish()
+    }
+
+    /// The file of the running or the last finished capture
+    pub fn path(&self) -> Option<&str> {
+        self.file
+            .as_ref()
+            .map(|f| f.path.as_str())
+            .or(self.last_path.as_deref())
+    }
+
+    /// The number of captures since the capture was armed
+    pub fn n_captures(&self) -> usize {
+        self.n_captures
+    }
+
+    /// The errors of the start and the stop condition, if they fail to compile
+    pub fn errors(&mut self) -> (Option<String>, Option<String>) {
+        let start = self
+            .start
+            .compiled(&self.start_condition)
+            .err()
+            .map(str::to_string);
+        let stop = if self.stop_condition.is_empty() {
+            None
+        } else {
+            self.stop
+                .compiled(&self.stop_condition)
+                .err()
+                .map(str::to_string)
+        };
+
+        (start, stop)
+    }
+
+    /// Processes the new samples of each channel, after they were added to the channels.
+    ///
+    /// `session_path` is only called when a capture starts, a path of None uses the configured path.
+    pub fn process(
+        &mut self,
+        channels: &[FixedSizeBuffer<Sample>],
+        names: &[&str],
+        units: &[&str],
+        n_new: &[usize],
+        session_path: impl FnOnce() -> anyhow::Result<Option<String>>,
+    ) -> anyhow::Result<()> {
+        if !self.armed && self.file.is_none() {
+            return Ok(());
+        }
+
+        let values = channels
+            .iter()
+            .map(|s| s.last().map(|s| s.value))
+            .collect::<Vec<Option<f64>>>();
+        let Some(latest) = channels
+            .iter()
+            .filter_map(|s| s.last())
+            .map(|s| s.time)
+            .max()
+        else {
+            return Ok(());
+        };
+
+        let Some(file) = self.file.as_mut() else {
+            if self.start.check(&self.start_condition, &values, names) {
+                self.start_file(channels, names, units, latest, session_path)?;
+                // Only transitions after the trigger stop the capture
+                if !self.stop_condition.is_empty() {
+                    self.stop.check(&self.stop_condition, &values, names);
+                }
+            }
+            return Ok(());
+        };
+
+        let text = export::write_csv_rows(
+            channels[..file.n_channels]
+                .iter()
+                .zip(n_new)
+                .map(|(samples, &n)| ExportChannel {
+                    name: "",
+                    unit: "",
+                    samples: (samples.len().saturating_sub(n)..samples.len())
+                        .filter_map(|k| samples.get(k)),
+                })
+                .collect(),
+            file.trigger_time,
+        );
+        file.writer.write_all(text.as_bytes())?;
+
+        let elapsed = latest - file.trigger_time;
+        let stop_fired = !self.stop_condition.is_empty()
+            && self.stop.check(&self.stop_condition, &values, names);
+        if stop_fired || (self.duration > 0.0 && elapsed >= secs_to_nanos(self.duration)) {
+            self.finish()?;
+            if !self.rearm {
+                self.armed = false;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start_file(
+        &mut self,
+        channels: &[FixedSizeBuffer<Sample>],
+        names: &[&str],
+        units: &[&str],
+        trigger_time: i64,
+        session_path: impl FnOnce() -> anyhow::Result<Option<String>>,
+    ) -> anyhow::Result<()> {
+        let path = match session_path()? {
+            Some(path) => path,
+            None => numbered_path(&self.path, self.n_captures),
+        };
+        let file = std::fs::File::create(&path).map_err(|e| {
+            anyhow::anyhow!("failed to create conditional capture file `{path}`, Err: {e}")
+        })?;
+        let mut writer = std::io::BufWriter::new(file);
+
+        // The pre-trigger window, as far as it is still in memory
+        let from = trigger_time - secs_to_nanos(self.pre_trigger.max(0.0));
+        let export_channels = channels
+            .iter()
+            .zip(names.iter().zip(units))
+            .map(|(samples, (name, unit))| ExportChannel {
+                name,
+                unit,
+                samples: samples.iter().filter(move |s| s.time >= from),
+            })
+            .collect::<Vec<_>>();
+        writer.write_all(export::write_csv_header(&export_channels).as_bytes())?;
+        writer.write_all(export::write_csv_rows(export_channels, trigger_time).as_bytes())?;
+
+        log::info!("started conditional capture to `{path}`");
+
+        self.file = Some(CaptureFile {
+            writer,
+            path,
+            trigger_time,
+            n_channels: channels.len(),
+        });
+        self.n_captures += 1;
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> anyhow::Result<()> {
+        let Some(mut file) = self.file.take() else {
+            return Ok(());
+        };
+
+        log::info!("finished conditional capture to `{}`", file.path);
+        self.last_path = Some(file.path);
+        file.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// The path with the number before the extension, unchanged for the first capture
+fn numbered_path(path: &str, n: usize) -> String {
+    if n == 0 {
+        return path.to_string();
+    }
+
+    let path = std::path::Path::new(path);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}_{n}.{}", ext.to_string_lossy()),
+        None => format!("{stem}_{n}"),
+    };
+
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conditional_capture() {
+        let path = std::env::temp_dir().join("splot_conditional_capture_test.csv");
+        let mut capture = ConditionalCapture {
+            start_condition: String::from("v - 1"),
+            duration: 2.0,
+            pre_trigger: 2.0,
+            path: path.to_string_lossy().into_owned(),
+            ..Default::default()
+        };
+        capture.arm();
+
+        let mut channels = vec![FixedSizeBuffer::new(100)];
+        for (i, value) in [0.0, 0.0, 0.0, 0.0, 0.0, 2.0, 3.0, 4.0, 5.0]
+            .into_iter()
+            .enumerate()
+        {
+            channels[0].add(Sample {
+                time: i as i64 * 1_000_000_000,
+                value,
+                name: None,
+            });
+            capture
+                .process(&channels, &["v"], &["V"], &[1], || Ok(None))
+                .unwrap();
+        }
+
+        assert!(!capture.is_armed());
+        assert!(!capture.is_capturing());
+        assert_eq!(capture.n_captures(), 1);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "Time [s],v [V]\n-2,0\n-1,0\n0,2\n1,3\n2,4\n"
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+use super::locale::tr_args;
+use super::{nanos_to_secs, secs_to_nanos};
+
+/// The distance in points in which a cursor can be grabbed
+const GRAB_DISTANCE: f32 = 6.0;
+
+/// Two draggable measurement cursors on the Time-Value plot, like on a scope.
+#[derive(Debug, Clone, Default)]
+pub struct MeasurementCursors {
+    pub enabled: bool,
+    /// The cursor times in nanoseconds. Placed into the visible window when `None`.
+    positions: Option<[i64; 2]>,
+    hovered: Option<usize>,
+    dragged: Option<usize>,
+}
+
+impl MeasurementCursors {
+    pub fn positions(&self) -> Option<[i64; 2]> {
+        self.positions.filter(|_| self.enabled)
+    }
+
+    /// Places the cursors into the visible window again on the next frame
+    pub fn reset(&mut self) {
+        self.positions = None;
+        self.hovered = None;
+        self.dragged = None;
+    }
+
+    /// Whether the cursors currently take the pointer, so the plot should not be dragged
+    pub fn is_grabbing(&self) -> bool {
+        self.enabled && (self.hovered.is_some() || self.dragged.is_some())
+    }
+
+    /// Handles dragging the cursors and draws them, given the visible time window in nanoseconds.
+    ///
+    /// The plot displays the times relative to `time_zero`.
+    pub fn interact(
+        &mut self,
+        plot_ui: &mut egui_plot::PlotUi,
+        start: i64,
+        end: i64,
+        time_zero: i64,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut positions = self.positions.unwrap_or_else(|| {
+            let third = (end - start) / 3;
+            [start + third, end - third]
+        });
+
+        let response = plot_ui.response().clone();
+        self.hovered = response.hover_pos().and_then(|pointer| {
+            positions.iter().position(|&t| {
+                let x = plot_ui
+                    .screen_from_plot(egui_plot::PlotPoint::new(nanos_to_secs(t - time_zero), 0.0))
+                    .x;
+                (x - pointer.x).abs() < GRAB_DISTANCE
+            })
+        });
+
+        if response.drag_started() {
+            self.dragged = self.hovered;
+        }
+        if let (Some(i), Some(pointer)) = (self.dragged, plot_ui.pointer_coordinate()) {
+            positions[i] = secs_to_nanos(pointer.x) + time_zero;
+        }
+        if response.drag_stopped() {
+            self.dragged = None;
+        }
+        self.positions = Some(positions);
+
+        if self.is_grabbing() {
+            plot_ui
+                .ctx()
+                .set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+        }
+
+        for (i, &t) in positions.iter().enumerate() {
+            plot_ui.vline(
+                egui_plot::VLine::new(nanos_to_secs(t - time_zero))
+                    .color(egui::Color32::LIGHT_GREEN)
+                    .width(if self.hovered == Some(i) || self.dragged == Some(i) {
+                        2.0
+                    } else {
+                        1.0
+                    })
+                    .name(tr_args("cursor-name", &[("n", &(i + 1))])),
+            );
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use super::locale::tr;
+
+/// How a widget of the dashboard displays the latest value of its channel
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum WidgetKind {
+    /// A large numeric readout
+    #[default]
+    Numeric,
+    Bar,
+    /// A radial gauge
+    Gauge,
+}
+
+impl std::fmt::Display for WidgetKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WidgetKind::Numeric => write!(f, "Numeric"),
+            WidgetKind::Bar => write!(f, "Bar"),
+            WidgetKind::Gauge => write!(f, "Gauge"),
+        }
+    }
+}
+
+impl WidgetKind {
+    pub const ALL: [WidgetKind; 3] = [WidgetKind::Numeric, WidgetKind::Bar, WidgetKind::Gauge];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            WidgetKind::Numeric => tr("widget-kind-numeric"),
+            WidgetKind::Bar => tr("widget-kind-bar"),
+            WidgetKind::Gauge => tr("widget-kind-gauge"),
+        }
+    }
+}
+
+/// A widget of the dashboard page, showing the latest value of a channel
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DashboardWidget {
+    /// The name of the channel, so that the dashboard survives restarts
+    pub channel: String,
+    pub kind: WidgetKind,
+    /// The range of the bar and the gauge
+    pub min: f64,
+    pub max: f64,
+    /// Values outside of these limits are highlighted
+    pub low_limit: Option<f64>,
+    pub high_limit: Option<f64>,
+}
+
+impl Default for DashboardWidget {
+    fn default() -> Self {
+        Self {
+            channel: String::new(),
+            kind: WidgetKind::default(),
+            min: 0.0,
+            max: 100.0,
+            low_limit: None,
+            high_limit: None,
+        }
+    }
+}
+
+impl DashboardWidget {
+    /// The position of the value in the range from 0.0 to 1.0, clamped
+    pub fn fraction(&self, value: f64) -> f32 {
+        let range = self.max - self.min;
+        if range <= 0.0 || !value.is_finite() {
+            return 0.0;
+        }
+
+        ((value - self.min) / range).clamp(0.0, 1.0) as f32
+    }
+
+    /// If the value is outside of the limits
+    pub fn is_out_of_limits(&self, value: f64) -> bool {
+        self.low_limit.map_or(false, |low| value < low)
+            || self.high_limit.map_or(false, |high| value > high)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dashboard_widget_limits() {
+        let widget = DashboardWidget {
+            min: -10.0,
+            max: 10.0,
+            high_limit: Some(5.0),
+            ..Default::default()
+        };
+
+        assert_eq!(widget.fraction(0.0), 0.5);
+        assert_eq!(widget.fraction(20.0), 1.0);
+        assert_eq!(widget.fraction(f64::NAN), 0.0);
+        assert!(!widget.is_out_of_limits(-20.0));
+        assert!(widget.is_out_of_limits(5.5));
+    }
+}
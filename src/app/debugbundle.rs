@@ -0,0 +1,33 @@
+use std::io::Write;
+
+use super::ziparchive;
+
+/// A bundle of files that helps to diagnose issues, written as zip archive.
+#[derive(Debug, Default)]
+pub struct DebugBundle {
+    files: Vec<(String, Vec<u8>)>,
+}
+
+impl DebugBundle {
+    pub fn add_file(&mut self, name: &str, data: impl Into<Vec<u8>>) {
+        self.files.push((name.to_string(), data.into()));
+    }
+
+    /// Writes the files into a zip archive at the given path. The files are stored without compression.
+    pub fn write(&self, path: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let file = std::fs::File::create(path).map_err(|e| {
+            anyhow::anyhow!(
+                "failed to create debug bundle `{}`, Err: {e}",
+                path.display()
+            )
+        })?;
+        let mut writer = std::io::BufWriter::new(file);
+
+        writer.write_all(&ziparchive::write_stored(&self.files))?;
+        writer.flush()?;
+
+        log::debug!("wrote debug bundle to `{}`", path.display());
+        Ok(())
+    }
+}
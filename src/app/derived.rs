@@ -0,0 +1,365 @@
+use super::expression::{BinOp, Expr};
+use super::fft;
+use super::iir::IirFilter;
+use super::locale::tr;
+use super::{nanos_to_secs, Sample};
+use crate::fixedsizebuffer::FixedSizeBuffer;
+
+/// How the value of a derived channel is computed
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum DerivedKind {
+    /// A free-form expression
+    #[default]
+    Expression,
+    /// The product of two channels, e.g. voltage × current = power
+    Product,
+    /// The ratio of two channels
+    Ratio,
+    /// The dominant frequency of a channel, e.g. the RPM estimated from vibration
+    PeakFrequency,
+    /// The amplitude of the dominant frequency of a channel
+    PeakAmplitude,
+    /// A channel filtered with a Butterworth low-pass
+    LowPass,
+    /// A channel filtered with a Butterworth high-pass
+    HighPass,
+}
+
+impl std::fmt::Display for DerivedKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DerivedKind::Expression => write!(f, "Expression"),
+            DerivedKind::Product => write!(f, "Product"),
+            DerivedKind::Ratio => write!(f, "Ratio"),
+            DerivedKind::PeakFrequency => write!(f, "FFT Peak Frequency"),
+            DerivedKind::PeakAmplitude => write!(f, "FFT Peak Amplitude"),
+            DerivedKind::LowPass => write!(f, "Low-Pass"),
+            DerivedKind::HighPass => write!(f, "High-Pass"),
+        }
+    }
+}
+
+impl DerivedKind {
+    pub const ALL: [Self; 7] = [
+        Self::Expression,
+        Self::Product,
+        Self::Ratio,
+        Self::PeakFrequency,
+        Self::PeakAmplitude,
+        Self::LowPass,
+        Self::HighPass,
+    ];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            DerivedKind::Expression => tr("derived-kind-expression"),
+            DerivedKind::Product => tr("derived-kind-product"),
+            DerivedKind::Ratio => tr("derived-kind-ratio"),
+            DerivedKind::PeakFrequency => tr("derived-kind-peak-frequency"),
+            DerivedKind::PeakAmplitude => tr("derived-kind-peak-amplitude"),
+            DerivedKind::LowPass => tr("derived-kind-low-pass"),
+            DerivedKind::HighPass => tr("derived-kind-high-pass"),
+        }
+    }
+
+    /// If the channel is computed from the FFT of the first selected channel
+    pub fn is_fft_peak(self) -> bool {
+        matches!(self, Self::PeakFrequency | Self::PeakAmplitude)
+    }
+
+    /// If the channel is the first selected channel filtered in real time
+    pub fn is_filter(self) -> bool {
+        matches!(self, Self::LowPass | Self::HighPass)
+    }
+}
+
+/// A committed edit of the derived channels, only the affected channel is recreated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DerivedEdit {
+    /// The name of the channel at the index was changed, its samples are kept
+    Renamed(usize),
+    /// The computation of the channel at the index was changed, its samples are cleared
+    Changed(usize),
+    /// A channel was appended
+    Added,
+    /// The channel at the index was removed
+    Removed(usize),
+}
+
+/// A virtual channel computed from the values of the received channels.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DerivedChannel {
+    pub name: String,
+    pub kind: DerivedKind,
+    /// The expression. Channels are referenced by their name or by `c<index>`, e.g. `c0 - c1`
+    pub expression: String,
+    /// The indices of the two channels of a product or ratio. The FFT peak uses the first one
+    pub channels: [usize; 2],
+    /// The number of latest samples the FFT peak is computed from
+    pub fft_size: usize,
+    /// The cutoff frequency of the low-pass and high-pass in Hz
+    pub cutoff: f64,
+    /// The order of the low-pass and high-pass, 1 or 2
+    pub filter_order: u8,
+    /// Integrates the values over time in seconds, e.g. power into energy
+    pub integrate: bool,
+    #[serde(skip)]
+    filter: IirFilter,
+    #[serde(skip)]
+    compiled: Option<Result<Expr, String>>,
+    #[serde(skip)]
+    integral: f64,
+    /// The time and value of the last integrated sample
+    #[serde(skip)]
+    integral_last: Option<(i64, f64)>,
+}
+
+impl Default for DerivedChannel {
+    fn default() -> Self {
+        Self::new(String::from("derived"), String::from("c0"))
+    }
+}
+
+impl DerivedChannel {
+    pub fn new(name: String, expression: String) -> Self {
+        Self {
+            name,
+            kind: DerivedKind::default(),
+            expression,
+            channels: [0, 1],
+            fft_size: 256,
+            cutoff: 1.0,
+            filter_order: 2,
+            integrate: false,
+            filter: IirFilter::default(),
+            compiled: None,
+            integral: 0.0,
+            integral_last: None,
+        }
+    }
+
+    /// Needs to be called when the expression was changed. Also restarts the integration and the filter.
+    pub fn invalidate(&mut self) {
+        self.compiled = None;
+        self.filter.reset();
+        self.integral = 0.0;
+        self.integral_last = None;
+    }
+
+    /// The expression error, if it fails to compile
+    pub fn error(&mut self) -> Option<&str> {
+        self.compiled().err()
+    }
+
+    fn compiled(&mut self) -> Result<&Expr, &str> {
+        let (kind, expression, [a, b]) = (self.kind, &self.expression, self.channels);
+
+        self.compiled
+            .get_or_insert_with(|| {
+                let op = match kind {
+                    DerivedKind::Expression => {
+                        return Expr::parse(expression).map_err(|e| e.to_string())
+                    }
+                    DerivedKind::Product => BinOp::Mul,
+                    DerivedKind::Ratio => BinOp::Div,
+                    DerivedKind::PeakFrequency
+                    | DerivedKind::PeakAmplitude
+                    | DerivedKind::LowPass
+                    | DerivedKind::HighPass => return Ok(Expr::Var(format!("c{a}"))),
+                };
+
+                Ok(Expr::BinOp(
+                    op,
+                    Box::new(Expr::Var(format!("c{a}"))),
+                    Box::new(Expr::Var(format!("c{b}"))),
+                ))
+            })
+            .as_ref()
+            .map_err(|e| e.as_str())
+    }
+
+    /// Evaluates the expression for the newly received samples.
+    ///
+    /// `new_samples` and `history` are indexed by the channel index, `names` holds the channel names.
+    /// For each newly received value of the referenced channels a sample is produced,
+    /// channels without a new value contribute their latest value.
+    pub fn evaluate(
+        &mut self,
+        new_samples: &[Vec<Sample>],
+        history: &[FixedSizeBuffer<Sample>],
+        names: &[&str],
+    ) -> Vec<Sample> {
+        let mut samples = if self.kind.is_fft_peak() {
+            self.evaluate_fft_peak(new_samples, history)
+        } else if self.kind.is_filter() {
+            self.evaluate_filter(new_samples)
+        } else {
+            self.evaluate_expr(new_samples, history, names)
+        };
+
+        if self.integrate {
+            self.integrate_samples(&mut samples);
+        }
+
+        samples
+    }
+
+    fn evaluate_expr(
+        &mut self,
+        new_samples: &[Vec<Sample>],
+        history: &[FixedSizeBuffer<Sample>],
+        names: &[&str],
+    ) -> Vec<Sample> {
+        let Ok(expr) = self.compiled() else {
+            return vec![];
+        };
+
+        let mut vars = vec![];
+        expr.variables(&mut vars);
+
+        let Some(indices) = vars
+            .iter()
+            .map(|var| resolve_channel(var, names))
+            .collect::<Option<Vec<usize>>>()
+        else {
+            return vec![];
+        };
+
+        let n = indices
+            .iter()
+            .map(|&i| new_samples.get(i).map(|s| s.len()).unwrap_or(0))
+            .max()
+            .unwrap_or(0);
+
+        (0..n)
+            .filter_map(|k| {
+                let mut time = None;
+
+                let value_at = |i: usize| {
+                    new_samples
+                        .get(i)
+                        .and_then(|s| s.get(k).or_else(|| s.last()))
+                        .or_else(|| history.get(i).and_then(|h| h.last()))
+                };
+
+                for &i in indices.iter() {
+                    if let Some(sample) = new_samples.get(i).and_then(|s| s.get(k)) {
+                        time = Some(sample.time);
+                        break;
+                    }
+                }
+
+                let value = expr.eval(&|var| {
+                    let i = resolve_channel(var, names)?;
+                    value_at(i).map(|s| s.value)
+                })?;
+
+                Some(Sample {
+                    time: time?,
+                    value,
+                    name: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Computes the FFT peak over the latest samples of the source channel, once for every read with new samples
+    fn evaluate_fft_peak(
+        &self,
+        new_samples: &[Vec<Sample>],
+        history: &[FixedSizeBuffer<Sample>],
+    ) -> Vec<Sample> {
+        let i = self.channels[0];
+        let (Some(new), Some(history)) = (new_samples.get(i), history.get(i)) else {
+            return vec![];
+        };
+        let Some(last) = new.last() else {
+            return vec![];
+        };
+
+        let samples = history
+            .iter()
+            .skip(history.len().saturating_sub(self.fft_size))
+            .chain(new.iter())
+            .collect::<Vec<&Sample>>();
+        if samples.len() < self.fft_size || !self.fft_size.is_power_of_two() {
+            return vec![];
+        }
+
+        let (times, values): (Vec<f64>, Vec<f64>) = samples[samples.len() - self.fft_size..]
+            .iter()
+            .map(|s| (s.time_secs(), s.value))
+            .unzip();
+
+        let Some((frequency, amplitude)) =
+            fft::sample_rate(&times).and_then(|sample_rate| fft::peak(&values, sample_rate))
+        else {
+            return vec![];
+        };
+
+        vec![Sample {
+            time: last.time,
+            value: if self.kind == DerivedKind::PeakFrequency {
+                frequency
+            } else {
+                amplitude
+            },
+            name: None,
+        }]
+    }
+
+    /// Filters every new sample of the source channel
+    fn evaluate_filter(&mut self, new_samples: &[Vec<Sample>]) -> Vec<Sample> {
+        let Some(new) = new_samples.get(self.channels[0]) else {
+            return vec![];
+        };
+        let high_pass = self.kind == DerivedKind::HighPass;
+
+        new.iter()
+            .map(|s| Sample {
+                time: s.time,
+                value: self
+                    .filter
+                    .step(high_pass, self.filter_order, self.cutoff, s.time, s.value),
+                name: None,
+            })
+            .collect()
+    }
+
+    /// Replaces the values with their running integral, using the trapezoidal rule
+    fn integrate_samples(&mut self, samples: &mut [Sample]) {
+        for sample in samples.iter_mut() {
+            if let Some((last_time, last_value)) = self.integral_last {
+                self.integral +=
+                    (last_value + sample.value) * 0.5 * nanos_to_secs(sample.time - last_time);
+            }
+
+            self.integral_last = Some((sample.time, sample.value));
+            sample.value = self.integral;
+        }
+    }
+}
+
+/// Resolves a variable to a channel index, either by name or by `c<index>`
+pub fn resolve_channel(var: &str, names: &[&str]) -> Option<usize> {
+    if let Some(i) = names.iter().position(|n| *n == var) {
+        return Some(i);
+    }
+
+    var.strip_prefix('c')
+        .and_then(|i| i.parse::<usize>().ok())
+        .filter(|&i| i < names.len())
+}
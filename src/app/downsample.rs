@@ -0,0 +1,263 @@
+use super::locale::tr;
+
+/// How the points of a line are reduced, when there are more than the point budget
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum Downsampling {
+    /// All points are rendered
+    Off,
+    /// Largest-Triangle-Three-Buckets, keeps the visual shape of the line
+    #[default]
+    Lttb,
+    /// Keeps the minimum and maximum of each bucket, so that no peak is lost
+    MinMax,
+}
+
+impl std::fmt::Display for Downsampling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Downsampling::Off => write!(f, "Off"),
+            Downsampling::Lttb => write!(f, "LTTB"),
+            Downsampling::MinMax => write!(f, "Min / Max"),
+        }
+    }
+}
+
+impl Downsampling {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Lttb, Self::MinMax];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            Downsampling::Off => tr("downsampling-off"),
+            Downsampling::Lttb => tr("downsampling-lttb"),
+            Downsampling::MinMax => tr("downsampling-min-max"),
+        }
+    }
+
+    /// Reduces the points, which need to be sorted by x, to at most `budget` points
+    pub fn apply(self, points: Vec<[f64; 2]>, budget: usize) -> Vec<[f64; 2]> {
+        if points.len() <= budget {
+            return points;
+        }
+
+        match self {
+            Downsampling::Off => points,
+            Downsampling::Lttb => lttb(&points, budget),
+            Downsampling::MinMax => min_max(&points, budget),
+        }
+    }
+}
+
+/// The minimum share of the frame budget of a line, so that many lines still show their shape
+const MIN_LINE_POINTS: usize = 100;
+
+/// Limits the number of points a plot renders per frame, so that it stays responsive
+/// with all channels at full buffer size.
+///
+/// The budget is divided between the lines, lines above their share are decimated.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RenderBudget {
+    /// The maximum number of points rendered per frame
+    pub max_points: usize,
+    /// The ratio of the points in view to the rendered points, in the last frame
+    #[serde(skip)]
+    decimation: f64,
+}
+
+impl Default for RenderBudget {
+    fn default() -> Self {
+        Self {
+            max_points: 100_000,
+            decimation: 1.0,
+        }
+    }
+}
+
+impl RenderBudget {
+    pub const MIN_POINTS: usize = 1_000;
+    pub const MAX_POINTS: usize = 2_000_000;
+
+    /// The downsampling and the point budget of each of the lines.
+    ///
+    /// Above their share of the frame budget lines are decimated, with min / max when downsampling is off.
+    pub fn line_budget(
+        &self,
+        downsampling: Downsampling,
+        point_budget: usize,
+        n_lines: usize,
+    ) -> (Downsampling, usize) {
+        let share = (self.max_points / n_lines.max(1)).max(MIN_LINE_POINTS);
+
+        match downsampling {
+            Downsampling::Off => (Downsampling::MinMax, share),
+            _ => (downsampling, point_budget.min(share)),
+        }
+    }
+
+    /// Records the number of points in view and the number of rendered points of a frame
+    pub fn finish_frame(&mut self, n_points: usize, n_rendered: usize) {
+        self.decimation = if n_rendered > 0 {
+            (n_points as f64 / n_rendered as f64).max(1.0)
+        } else {
+            1.0
+        };
+    }
+
+    /// The ratio of the points in view to the rendered points, in the last frame
+    pub fn decimation(&self) -> f64 {
+        self.decimation
+    }
+}
+
+/// Keeps every n-th point of a path, and its last point, so that at most `budget` points remain.
+///
+/// Unlike the downsampling, the points don't need to be sorted by x.
+pub fn decimate_path(points: Vec<[f64; 2]>, budget: usize) -> Vec<[f64; 2]> {
+    if points.len() <= budget || budget < 2 {
+        return points;
+    }
+
+    let step = (points.len() - 1 + budget - 2) / (budget - 1);
+    let last = points[points.len() - 1];
+    let mut decimated = points.into_iter().step_by(step).collect::<Vec<_>>();
+    if decimated.last() != Some(&last) {
+        decimated.push(last);
+    }
+
+    decimated
+}
+
+fn lttb(points: &[[f64; 2]], threshold: usize) -> Vec<[f64; 2]> {
+    let n = points.len();
+    if threshold < 3 || threshold >= n {
+        return points.to_vec();
+    }
+
+    // The first and last point are always kept, the others are divided into buckets
+    let bucket_size = (n - 2) as f64 / (threshold - 2) as f64;
+    let bucket_start = |i: usize| ((i as f64 * bucket_size) as usize + 1).min(n - 1);
+
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(points[0]);
+    let mut a = points[0];
+
+    for i in 0..threshold - 2 {
+        let (start, end) = (bucket_start(i), bucket_start(i + 1));
+        let next = &points[end..bucket_start(i + 2).max(end + 1).min(n)];
+
+        let avg = next
+            .iter()
+            .fold([0.0, 0.0], |acc, p| [acc[0] + p[0], acc[1] + p[1]])
+            .map(|v| v / next.len() as f64);
+
+        // Keep the point that forms the largest triangle with the last kept point and the average of the next bucket
+        let Some(&selected) = points[start..end.max(start + 1)]
+            .iter()
+            .max_by(|p, q| triangle_area(a, **p, avg).total_cmp(&triangle_area(a, **q, avg)))
+        else {
+            continue;
+        };
+
+        sampled.push(selected);
+        a = selected;
+    }
+
+    sampled.push(points[n - 1]);
+    sampled
+}
+
+fn triangle_area(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    ((a[0] - c[0]) * (b[1] - a[1]) - (a[0] - b[0]) * (c[1] - a[1])).abs()
+}
+
+fn min_max(points: &[[f64; 2]], budget: usize) -> Vec<[f64; 2]> {
+    // Each bucket contributes up to two points
+    let n_buckets = (budget / 2).max(1);
+    let bucket_size = (points.len() + n_buckets - 1) / n_buckets;
+
+    points
+        .chunks(bucket_size)
+        .flat_map(|bucket| {
+            let min = bucket
+                .iter()
+                .enumerate()
+                .min_by(|p, q| p.1[1].total_cmp(&q.1[1]));
+            let max = bucket
+                .iter()
+                .enumerate()
+                .max_by(|p, q| p.1[1].total_cmp(&q.1[1]));
+
+            match (min, max) {
+                (Some(min), Some(max)) if min.0 == max.0 => vec![*min.1],
+                // Keep them in the order of x
+                (Some(min), Some(max)) if min.0 < max.0 => vec![*min.1, *max.1],
+                (Some(min), Some(max)) => vec![*max.1, *min.1],
+                _ => vec![],
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downsampling_keeps_budget_and_ends() {
+        let points = (0..10_000)
+            .map(|i| [i as f64, if i == 5000 { 100.0 } else { (i % 7) as f64 }])
+            .collect::<Vec<[f64; 2]>>();
+
+        for downsampling in [Downsampling::Lttb, Downsampling::MinMax] {
+            let sampled = downsampling.apply(points.clone(), 500);
+
+            assert!(sampled.len() <= 500, "{downsampling}: {}", sampled.len());
+            assert_eq!(sampled.first(), points.first(), "{downsampling}");
+            assert!(
+                sampled.windows(2).all(|w| w[0][0] < w[1][0]),
+                "{downsampling}"
+            );
+            // The single peak survives
+            assert!(sampled.contains(&[5000.0, 100.0]), "{downsampling}");
+        }
+
+        assert_eq!(Downsampling::Off.apply(points.clone(), 500), points);
+    }
+
+    #[test]
+    fn render_budget() {
+        let mut budget = RenderBudget {
+            max_points: 10_000,
+            ..Default::default()
+        };
+        // Lines above their share are decimated, even when downsampling is off
+        assert_eq!(
+            budget.line_budget(Downsampling::Off, 4000, 4),
+            (Downsampling::MinMax, 2500)
+        );
+        assert_eq!(
+            budget.line_budget(Downsampling::Lttb, 4000, 2),
+            (Downsampling::Lttb, 4000)
+        );
+        budget.finish_frame(40_000, 10_000);
+        assert_eq!(budget.decimation(), 4.0);
+
+        let path = (0..1001).map(|i| [i as f64, 0.0]).collect::<Vec<_>>();
+        let decimated = decimate_path(path.clone(), 100);
+        assert!(decimated.len() <= 100, "{}", decimated.len());
+        assert_eq!(decimated.first(), path.first());
+        assert_eq!(decimated.last(), path.last());
+    }
+}
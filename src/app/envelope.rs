@@ -0,0 +1,270 @@
+use std::collections::VecDeque;
+
+use super::Sample;
+use crate::fixedsizebuffer::FixedSizeBuffer;
+
+/// The number of buckets of a level that are summarized by one bucket of the next level,
+/// and the number of samples summarized by a bucket of the lowest level
+const BRANCHING: u64 = 16;
+/// The number of levels, the highest one summarizes 16^6 (~16.7 million) samples per bucket
+const N_LEVELS: u32 = 6;
+
+/// The minimum and maximum of a run of consecutive samples
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    /// The index of the bucket within its level, counted from the first sample ever added
+    index: u64,
+    /// The time of the first sample
+    start: i64,
+    /// The time of the last sample
+    end: i64,
+    /// The time and the value of the minimum and the maximum, None when no value was finite
+    min: Option<(i64, f64)>,
+    max: Option<(i64, f64)>,
+}
+
+impl Bucket {
+    fn new(index: u64, sample: &Sample) -> Self {
+        let extremum = Some((sample.time, sample.value)).filter(|(_, v)| v.is_finite());
+
+        Self {
+            index,
+            start: sample.time,
+            end: sample.time,
+            min: extremum,
+            max: extremum,
+        }
+    }
+
+    fn add(&mut self, sample: &Sample) {
+        self.end = sample.time;
+        if !sample.value.is_finite() {
+            return;
+        }
+
+        if self.min.map_or(true, |(_, min)| sample.value < min) {
+            self.min = Some((sample.time, sample.value));
+        }
+        if self.max.map_or(true, |(_, max)| sample.value > max) {
+            self.max = Some((sample.time, sample.value));
+        }
+    }
+}
+
+/// A hierarchical min / max summary of the samples of a channel, so that zoomed out views draw the envelope
+/// from a few buckets instead of iterating every sample each frame.
+///
+/// It is updated incrementally with the samples that were added to the buffer since the last update.
+#[derive(Debug, Clone, Default)]
+pub struct MinMaxSummary {
+    /// The id of the summarized buffer
+    buffer_id: u64,
+    /// The number of samples of the buffer that were summarized
+    n_added: u64,
+    /// The number of samples that were removed from the front of the buffer
+    n_removed: u64,
+    /// The buckets of each level, level `l` summarizes 16^(l+1) samples per bucket
+    levels: Vec<VecDeque<Bucket>>,
+}
+
+impl MinMaxSummary {
+    /// If the summary is up to date with the buffer
+    pub fn is_current(&self, samples: &FixedSizeBuffer<Sample>) -> bool {
+        self.buffer_id == samples.id() && self.n_added == samples.n_added()
+    }
+
+    /// Summarizes the samples that were added to the buffer since the last update,
+    /// and drops the buckets of the removed ones
+    pub fn update(&mut self, samples: &FixedSizeBuffer<Sample>) {
+        if self.buffer_id != samples.id() || self.n_added > samples.n_added() {
+            *self = Self {
+                buffer_id: samples.id(),
+                ..Self::default()
+            };
+        }
+
+        let n_removed = samples.n_added() - samples.len() as u64;
+        // Only the samples that are still in the buffer can be summarized
+        let first_new = self.n_added.max(n_removed);
+        if first_new > self.n_added {
+            self.levels.clear();
+        }
+
+        for seq in first_new..samples.n_added() {
+            if let Some(sample) = samples.get((seq - n_removed) as usize) {
+                self.add(seq, sample);
+            }
+        }
+        self.n_added = samples.n_added();
+        self.n_removed = n_removed;
+
+        for (l, buckets) in self.levels.iter_mut().enumerate() {
+            let size = bucket_size(l);
+            while buckets
+                .front()
+                .map_or(false, |b| (b.index + 1) * size <= n_removed)
+            {
+                buckets.pop_front();
+            }
+        }
+    }
+
+    fn add(&mut self, seq: u64, sample: &Sample) {
+        if self.levels.is_empty() {
+            self.levels = vec![VecDeque::new(); N_LEVELS as usize];
+        }
+
+        for (l, buckets) in self.levels.iter_mut().enumerate() {
+            let index = seq / bucket_size(l);
+            match buckets.back_mut() {
+                Some(bucket) if bucket.index == index => bucket.add(sample),
+                _ => buckets.push_back(Bucket::new(index, sample)),
+            }
+        }
+    }
+
+    /// The min / max envelope of the samples between the times, as pairs of time and value in the order of time,
+    /// from the finest level with at most `max_buckets` buckets in the range.
+    ///
+    /// Buckets without finite values are NaN, where the line is broken.
+    pub fn envelope(
+        &self,
+        samples: &FixedSizeBuffer<Sample>,
+        start: i64,
+        end: i64,
+        max_buckets: usize,
+    ) -> Option<Vec<(i64, f64)>> {
+        if !self.is_current(samples) {
+            return None;
+        }
+
+        let (l, from, to) = self.levels.iter().enumerate().find_map(|(l, buckets)| {
+            let from = buckets.partition_point(|b| b.end < start);
+            let to = buckets.partition_point(|b| b.start <= end).max(from);
+            (to - from <= max_buckets).then_some((l, from, to))
+        })?;
+        let size = bucket_size(l);
+
+        let mut points = vec![];
+        for (k, bucket) in self.levels[l].range(from..to).enumerate() {
+            if bucket.index * size >= self.n_removed {
+                push_extrema(&mut points, bucket);
+            } else if k == 0 {
+                // The bucket still contains removed samples, its rest is covered by the finer levels
+                self.push_front(
+                    &mut points,
+                    samples,
+                    l,
+                    (bucket.index + 1) * size,
+                    start,
+                    end,
+                );
+            }
+        }
+
+        Some(points)
+    }
+
+    /// Pushes the extrema of the samples from the first one in the buffer up to `end_seq`,
+    /// from the levels below `level` and the samples themselves
+    fn push_front(
+        &self,
+        points: &mut Vec<(i64, f64)>,
+        samples: &FixedSizeBuffer<Sample>,
+        level: usize,
+        end_seq: u64,
+        start: i64,
+        end: i64,
+    ) {
+        if level == 0 {
+            let n = end_seq.saturating_sub(self.n_removed) as usize;
+            let mut bucket: Option<Bucket> = None;
+            for sample in (0..n).filter_map(|k| samples.get(k)) {
+                match bucket.as_mut() {
+                    Some(bucket) => bucket.add(sample),
+                    None => bucket = Some(Bucket::new(0, sample)),
+                }
+            }
+            if let Some(bucket) = bucket.filter(|b| b.end >= start && b.start <= end) {
+                push_extrema(points, &bucket);
+            }
+            return;
+        }
+
+        let size = bucket_size(level - 1);
+        // The first bucket of the level below without removed samples
+        let first_full = (self.n_removed + size - 1) / size;
+        self.push_front(points, samples, level - 1, first_full * size, start, end);
+
+        for bucket in self.levels[level - 1]
+            .iter()
+            .skip_while(|b| b.index < first_full)
+            .take_while(|b| b.index * size < end_seq)
+            .filter(|b| b.end >= start && b.start <= end)
+        {
+            push_extrema(points, bucket);
+        }
+    }
+}
+
+fn push_extrema(points: &mut Vec<(i64, f64)>, bucket: &Bucket) {
+    match (bucket.min, bucket.max) {
+        (Some(min), Some(max)) if min.0 == max.0 => points.push(min),
+        (Some(min), Some(max)) if min.0 < max.0 => points.extend([min, max]),
+        (Some(min), Some(max)) => points.extend([max, min]),
+        _ => points.push((bucket.start, f64::NAN)),
+    }
+}
+
+/// The number of samples a bucket of the level summarizes
+fn bucket_size(level: usize) -> u64 {
+    BRANCHING.pow(level as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_max_envelope() {
+        let mut samples = FixedSizeBuffer::new(10_000);
+        let mut summary = MinMaxSummary::default();
+        // Updated in chunks, while the oldest samples are removed
+        for chunk in 0..25 {
+            for k in chunk * 1000..(chunk + 1) * 1000 {
+                let value = match k {
+                    20_000 => 100.0,
+                    24_321 => -50.0,
+                    _ => (k % 13) as f64,
+                };
+                samples.add(Sample {
+                    time: k as i64,
+                    value,
+                    name: None,
+                });
+            }
+            summary.update(&samples);
+        }
+
+        let envelope = summary
+            .envelope(&samples, 0, i64::MAX, 100)
+            .expect("the summary is current");
+        assert!(envelope.len() < 300, "{}", envelope.len());
+        assert!(envelope.windows(2).all(|w| w[0].0 <= w[1].0));
+        // Nothing from before the first sample in the buffer
+        assert!(envelope.iter().all(|&(time, _)| time >= 15_000));
+        assert!(envelope.contains(&(20_000, 100.0)));
+        assert!(envelope.contains(&(24_321, -50.0)));
+        assert!(envelope.iter().any(|&(time, _)| time < 15_016));
+
+        let part = summary.envelope(&samples, 22_000, 23_000, 100).unwrap();
+        assert!(part.iter().all(|&(_, value)| (0.0..=12.0).contains(&value)));
+
+        samples.add(Sample {
+            time: 25_000,
+            value: 0.0,
+            name: None,
+        });
+        assert!(summary.envelope(&samples, 0, i64::MAX, 100).is_none());
+    }
+}
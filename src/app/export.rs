@@ -0,0 +1,380 @@
+use super::locale::tr;
+use super::{nanos_to_secs, ziparchive, Sample};
+
+/// The file formats the samples can be exported as
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum ExportFormat {
+    /// The format of the import
+    #[default]
+    Csv,
+    /// A single NumPy array, with the time in the first column
+    Npy,
+    /// NumPy arrays of the time and each channel
+    Npz,
+    /// MATLAB Level 5 MAT-file with variables of the time and each channel
+    Mat,
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Csv => write!(f, "CSV"),
+            ExportFormat::Npy => write!(f, "NumPy (.npy)"),
+            ExportFormat::Npz => write!(f, "NumPy (.npz)"),
+            ExportFormat::Mat => write!(f, "MATLAB (.mat)"),
+        }
+    }
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::Csv,
+        ExportFormat::Npy,
+        ExportFormat::Npz,
+        ExportFormat::Mat,
+    ];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => tr("export-format-csv"),
+            ExportFormat::Npy => tr("export-format-npy"),
+            ExportFormat::Npz => tr("export-format-npz"),
+            ExportFormat::Mat => tr("export-format-mat"),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Npy => "npy",
+            ExportFormat::Npz => "npz",
+            ExportFormat::Mat => "mat",
+        }
+    }
+
+    /// Writes the channels in the format, the times relative to `time_zero`
+    pub fn write<'a, I: Iterator<Item = &'a Sample>>(
+        self,
+        channels: Vec<ExportChannel<'a, I>>,
+        time_zero: i64,
+    ) -> Vec<u8> {
+        match self {
+            ExportFormat::Csv => write_csv_capture(channels, time_zero).into_bytes(),
+            ExportFormat::Npy => write_npy(channels, time_zero),
+            ExportFormat::Npz => write_npz(channels, time_zero),
+            ExportFormat::Mat => write_mat(channels, time_zero),
+        }
+    }
+}
+
+/// A channel that is exported
+pub struct ExportChannel<'a, I: Iterator<Item = &'a Sample>> {
+    pub name: &'a str,
+    pub unit: &'a str,
+    /// The samples, sorted by time
+    pub samples: I,
+}
+
+/// Writes the channels as CSV capture in the format of the import, so that exports can be imported and compared again.
+///
+/// Samples of different channels with the same time share a row, the fields of channels without a sample at that time are empty.
+/// The times are written relative to `time_zero`.
+pub fn write_csv_capture<'a, I: Iterator<Item = &'a Sample>>(
+    channels: Vec<ExportChannel<'a, I>>,
+    time_zero: i64,
+) -> String {
+    let mut text = write_csv_header(&channels);
+    text.push_str(&write_csv_rows(channels, time_zero));
+    text
+}
+
+/// The header line of the CSV capture
+pub fn write_csv_header<'a, I: Iterator<Item = &'a Sample>>(
+    channels: &[ExportChannel<'a, I>],
+) -> String {
+    let mut text = String::from("Time [s]");
+    for channel in channels.iter() {
+        text.push(',');
+        text.push_str(&channel.name.replace(',', " "));
+        if !channel.unit.is_empty() {
+            text.push_str(&format!(" [{}]", channel.unit));
+        }
+    }
+    text.push('\n');
+    text
+}
+
+/// The rows of the CSV capture without the header, so that captures can be written in parts
+pub fn write_csv_rows<'a, I: Iterator<Item = &'a Sample>>(
+    channels: Vec<ExportChannel<'a, I>>,
+    time_zero: i64,
+) -> String {
+    let mut text = String::new();
+    for (time, row) in sample_rows(channels) {
+        text.push_str(&nanos_to_secs(time - time_zero).to_string());
+        for value in row {
+            text.push(',');
+            if let Some(value) = value {
+                text.push_str(&value.to_string());
+            }
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Groups the samples of the channels into rows by time, sorted by time.
+///
+/// Samples of different channels with the same time share a row, channels without a sample at that time are None.
+fn sample_rows<'a, I: Iterator<Item = &'a Sample>>(
+    channels: Vec<ExportChannel<'a, I>>,
+) -> Vec<(i64, Vec<Option<f64>>)> {
+    let n_channels = channels.len();
+    let mut samples = channels
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, channel)| channel.samples.map(move |s| (s.time, i, s.value)))
+        .collect::<Vec<(i64, usize, f64)>>();
+    // Stable, so that samples of a channel with the same time keep their order
+    samples.sort_by_key(|&(time, i, _)| (time, i));
+
+    let mut rows: Vec<(i64, Vec<Option<f64>>)> = vec![];
+
+    for (time, i, value) in samples {
+        match rows.last_mut() {
+            Some((row_time, row)) if *row_time == time && row[i].is_none() => row[i] = Some(value),
+            _ => {
+                let mut row = vec![None; n_channels];
+                row[i] = Some(value);
+                rows.push((time, row));
+            }
+        }
+    }
+
+    rows
+}
+
+/// The time column and a column for each channel, missing samples are NaN
+fn sample_columns<'a, I: Iterator<Item = &'a Sample>>(
+    channels: Vec<ExportChannel<'a, I>>,
+    time_zero: i64,
+) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n_channels = channels.len();
+    let rows = sample_rows(channels);
+
+    let times = rows
+        .iter()
+        .map(|(time, _)| nanos_to_secs(time - time_zero))
+        .collect();
+    let columns = (0..n_channels)
+        .map(|i| {
+            rows.iter()
+                .map(|(_, row)| row[i].unwrap_or(f64::NAN))
+                .collect()
+        })
+        .collect();
+
+    (times, columns)
+}
+
+/// Names of the time and the channels that are valid identifiers in Python and MATLAB, and unique
+pub fn variable_names(channel_names: &[&str]) -> Vec<String> {
+    let mut names = vec![String::from("time")];
+
+    for name in channel_names {
+        let mut variable = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .take(56)
+            .collect::<String>();
+        if !variable.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            variable.insert_str(0, "ch_");
+        }
+
+        let mut unique = variable.clone();
+        let mut suffix = 2;
+        while names.contains(&unique) {
+            unique = format!("{variable}_{suffix}");
+            suffix += 1;
+        }
+        names.push(unique);
+    }
+
+    names
+}
+
+/// A 2D array with a row for each time, the time in the first column and the channels in the following ones
+fn write_npy<'a, I: Iterator<Item = &'a Sample>>(
+    channels: Vec<ExportChannel<'a, I>>,
+    time_zero: i64,
+) -> Vec<u8> {
+    let n_columns = channels.len() + 1;
+    let (times, columns) = sample_columns(channels, time_zero);
+
+    let values = times.iter().enumerate().flat_map(|(row, &time)| {
+        std::iter::once(time).chain(columns.iter().map(move |column| column[row]))
+    });
+
+    npy_array(&[times.len(), n_columns], values)
+}
+
+/// An archive of a 1D array for the time and each channel
+fn write_npz<'a, I: Iterator<Item = &'a Sample>>(
+    channels: Vec<ExportChannel<'a, I>>,
+    time_zero: i64,
+) -> Vec<u8> {
+    let names = variable_names(&channels.iter().map(|c| c.name).collect::<Vec<&str>>());
+    let (times, columns) = sample_columns(channels, time_zero);
+
+    let files = std::iter::once(times)
+        .chain(columns)
+        .zip(names)
+        .map(|(values, name)| {
+            (
+                format!("{name}.npy"),
+                npy_array(&[values.len()], values.into_iter()),
+            )
+        })
+        .collect::<Vec<(String, Vec<u8>)>>();
+
+    ziparchive::write_stored(&files)
+}
+
+/// A MAT-file with a column vector for the time and each channel
+fn write_mat<'a, I: Iterator<Item = &'a Sample>>(
+    channels: Vec<ExportChannel<'a, I>>,
+    time_zero: i64,
+) -> Vec<u8> {
+    let names = variable_names(&channels.iter().map(|c| c.name).collect::<Vec<&str>>());
+    let (times, columns) = sample_columns(channels, time_zero);
+
+    let mut out = format!(
+        "MATLAB 5.0 MAT-file, Created by: splot {}",
+        env!("CARGO_PKG_VERSION")
+    )
+    .into_bytes();
+    out.resize(116, b' ');
+    out.extend([0; 8]); // no subsystem data
+    out.extend(0x0100_u16.to_le_bytes());
+    out.extend(b"IM");
+
+    for (values, name) in std::iter::once(times).chain(columns).zip(names) {
+        let mut matrix = vec![];
+        // Array flags with the double class, dimensions and name
+        mat_element(&mut matrix, 6, &[6, 0, 0, 0, 0, 0, 0, 0]);
+        let dimensions = [values.len() as i32, 1];
+        mat_element(
+            &mut matrix,
+            5,
+            &dimensions
+                .iter()
+                .flat_map(|d| d.to_le_bytes())
+                .collect::<Vec<u8>>(),
+        );
+        mat_element(&mut matrix, 1, name.as_bytes());
+        mat_element(
+            &mut matrix,
+            9,
+            &values
+                .iter()
+                .flat_map(|v| v.to_le_bytes())
+                .collect::<Vec<u8>>(),
+        );
+
+        mat_element(&mut out, 14, &matrix);
+    }
+
+    out
+}
+
+/// Appends a data element with its tag, padded to 8 bytes
+fn mat_element(out: &mut Vec<u8>, data_type: u32, data: &[u8]) {
+    out.extend(data_type.to_le_bytes());
+    out.extend((data.len() as u32).to_le_bytes());
+    out.extend(data);
+    out.resize(out.len() + (8 - data.len() % 8) % 8, 0);
+}
+
+/// A little endian float64 array in the NumPy format, in C order
+fn npy_array(shape: &[usize], values: impl Iterator<Item = f64>) -> Vec<u8> {
+    let shape = match shape {
+        [n] => format!("({n},)"),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!("{{'descr': '<f8', 'fortran_order': False, 'shape': {shape}, }}");
+    // Magic, version and header length come before the header, which ends with a newline
+    let unpadded = 10 + header.len() + 1;
+    header.extend(std::iter::repeat(' ').take((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    let mut out = b"\x93NUMPY\x01\x00".to_vec();
+    out.extend((header.len() as u16).to_le_bytes());
+    out.extend(header.as_bytes());
+    out.extend(values.flat_map(f64::to_le_bytes));
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numpy_and_matlab_exports() {
+        let sample = |time: i64, value: f64| Sample {
+            time,
+            value,
+            name: None,
+        };
+        let a = [sample(0, 1.0), sample(1_000_000_000, 2.0)];
+        let channels = || {
+            vec![ExportChannel {
+                name: "a",
+                unit: "",
+                samples: a.iter(),
+            }]
+        };
+
+        let npy = ExportFormat::Npy.write(channels(), 0);
+        let header_len = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+        assert_eq!(&npy[..6], b"\x93NUMPY");
+        assert_eq!((10 + header_len) % 64, 0);
+        assert!(String::from_utf8_lossy(&npy[10..10 + header_len]).contains("'shape': (2, 2)"));
+        // Time and value of the second row
+        assert_eq!(npy.len(), 10 + header_len + 4 * 8);
+        assert_eq!(
+            npy[10 + header_len + 16..10 + header_len + 24],
+            1.0f64.to_le_bytes()
+        );
+
+        let mat = ExportFormat::Mat.write(channels(), 0);
+        assert_eq!(&mat[126..128], b"IM");
+        // Two variables with flags, dimensions, name and two values
+        assert_eq!(mat.len(), 128 + 2 * (8 + 16 + 16 + 16 + 24));
+
+        assert_eq!(
+            variable_names(&["time", "1 a", "a-b"]),
+            ["time", "time_2", "ch_1_a", "a_b"]
+        );
+    }
+}
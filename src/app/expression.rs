@@ -0,0 +1,404 @@
+/// A parsed math expression over channel values.
+///
+/// Supports numbers, variables, `+ - * / ^`, parentheses and the functions listed in `FUNCTIONS`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Num(f64),
+    Var(String),
+    Neg(Box<Expr>),
+    BinOp(BinOp, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+/// The available functions and their number of arguments
+pub const FUNCTIONS: &[(&str, usize)] = &[
+    ("sqrt", 1),
+    ("abs", 1),
+    ("sin", 1),
+    ("cos", 1),
+    ("tan", 1),
+    ("asin", 1),
+    ("acos", 1),
+    ("atan", 1),
+    ("exp", 1),
+    ("ln", 1),
+    ("log10", 1),
+    ("floor", 1),
+    ("ceil", 1),
+    ("round", 1),
+    ("min", 2),
+    ("max", 2),
+    ("pow", 2),
+    ("atan2", 2),
+];
+
+impl Expr {
+    pub fn parse(src: &str) -> anyhow::Result<Self> {
+        let tokens = tokenize(src)?;
+        let mut parser = ExprParser { tokens, pos: 0 };
+
+        let expr = parser.expr()?;
+        if let Some(token) = parser.peek() {
+            return Err(anyhow::anyhow!("unexpected token `{token}`"));
+        }
+
+        Ok(expr)
+    }
+
+    /// Evaluates the expression. Returns `None` if a variable can't be resolved.
+    pub fn eval(&self, lookup: &impl Fn(&str) -> Option<f64>) -> Option<f64> {
+        let value = match self {
+            Expr::Num(v) => *v,
+            Expr::Var(name) => lookup(name)?,
+            Expr::Neg(e) => -e.eval(lookup)?,
+            Expr::BinOp(op, a, b) => {
+                let (a, b) = (a.eval(lookup)?, b.eval(lookup)?);
+
+                match op {
+                    BinOp::Add => a + b,
+                    BinOp::Sub => a - b,
+                    BinOp::Mul => a * b,
+                    BinOp::Div => a / b,
+                    BinOp::Pow => a.powf(b),
+                }
+            }
+            Expr::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|a| a.eval(lookup))
+                    .collect::<Option<Vec<f64>>>()?;
+
+                match (name.as_str(), args.as_slice()) {
+                    ("sqrt", [a]) => a.sqrt(),
+                    ("abs", [a]) => a.abs(),
+                    ("sin", [a]) => a.sin(),
+                    ("cos", [a]) => a.cos(),
+                    ("tan", [a]) => a.tan(),
+                    ("asin", [a]) => a.asin(),
+                    ("acos", [a]) => a.acos(),
+                    ("atan", [a]) => a.atan(),
+                    ("exp", [a]) => a.exp(),
+                    ("ln", [a]) => a.ln(),
+                    ("log10", [a]) => a.log10(),
+                    ("floor", [a]) => a.floor(),
+                    ("ceil", [a]) => a.ceil(),
+                    ("round", [a]) => a.round(),
+                    ("min", [a, b]) => a.min(*b),
+                    ("max", [a, b]) => a.max(*b),
+                    ("pow", [a, b]) => a.powf(*b),
+                    ("atan2", [a, b]) => a.atan2(*b),
+                    _ => return None,
+                }
+            }
+        };
+
+        Some(value)
+    }
+
+    /// Collects the names of all variables used in the expression
+    pub fn variables<'a>(&'a self, vars: &mut Vec<&'a str>) {
+        match self {
+            Expr::Num(_) => {}
+            Expr::Var(name) => {
+                if !vars.contains(&name.as_str()) {
+                    vars.push(name);
+                }
+            }
+            Expr::Neg(e) => e.variables(vars),
+            Expr::BinOp(_, a, b) => {
+                a.variables(vars);
+                b.variables(vars);
+            }
+            Expr::Call(_, args) => {
+                for a in args {
+                    a.variables(vars);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(char),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Num(v) => write!(f, "{v}"),
+            Token::Ident(name) => write!(f, "{name}"),
+            Token::Op(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+fn tokenize(src: &str) -> anyhow::Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = src.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                let exponent_sign =
+                    (c == '-' || c == '+') && src[..i].ends_with(['e', 'E']) && i > start;
+                if c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || exponent_sign {
+                    end = i + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let num = &src[start..end];
+            tokens.push(Token::Num(
+                num.parse()
+                    .map_err(|_| anyhow::anyhow!("invalid number `{num}`"))?,
+            ));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' {
+                    end = i + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Ident(src[start..end].to_string()));
+        } else if "+-*/^(),".contains(c) {
+            tokens.push(Token::Op(c));
+            chars.next();
+        } else {
+            return Err(anyhow::anyhow!("unexpected character `{c}`"));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser with the usual operator precedence
+struct ExprParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl ExprParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_op(&mut self, op: char) -> bool {
+        if self.peek() == Some(&Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_op(&mut self, op: char) -> anyhow::Result<()> {
+        if self.eat_op(op) {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("expected `{op}`"))
+        }
+    }
+
+    /// expr = term { ("+" | "-") term }
+    fn expr(&mut self) -> anyhow::Result<Expr> {
+        let mut lhs = self.term()?;
+
+        loop {
+            let op = if self.eat_op('+') {
+                BinOp::Add
+            } else if self.eat_op('-') {
+                BinOp::Sub
+            } else {
+                return Ok(lhs);
+            };
+
+            lhs = Expr::BinOp(op, Box::new(lhs), Box::new(self.term()?));
+        }
+    }
+
+    /// term = unary { ("*" | "/") unary }
+    fn term(&mut self) -> anyhow::Result<Expr> {
+        let mut lhs = self.unary()?;
+
+        loop {
+            let op = if self.eat_op('*') {
+                BinOp::Mul
+            } else if self.eat_op('/') {
+                BinOp::Div
+            } else {
+                return Ok(lhs);
+            };
+
+            lhs = Expr::BinOp(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+    }
+
+    /// unary = "-" unary | power
+    fn unary(&mut self) -> anyhow::Result<Expr> {
+        if self.eat_op('-') {
+            Ok(Expr::Neg(Box::new(self.unary()?)))
+        } else {
+            self.power()
+        }
+    }
+
+    /// power = atom [ "^" unary ]
+    fn power(&mut self) -> anyhow::Result<Expr> {
+        let base = self.atom()?;
+
+        if self.eat_op('^') {
+            Ok(Expr::BinOp(
+                BinOp::Pow,
+                Box::new(base),
+                Box::new(self.unary()?),
+            ))
+        } else {
+            Ok(base)
+        }
+    }
+
+    /// atom = number | ident | ident "(" args ")" | "(" expr ")"
+    fn atom(&mut self) -> anyhow::Result<Expr> {
+        match self.next() {
+            Some(Token::Num(v)) => Ok(Expr::Num(v)),
+            Some(Token::Ident(name)) => {
+                if !self.eat_op('(') {
+                    return Ok(Expr::Var(name));
+                }
+
+                let Some(&(_, n_args)) = FUNCTIONS.iter().find(|(f, _)| *f == name) else {
+                    return Err(anyhow::anyhow!("unknown function `{name}`"));
+                };
+
+                let mut args = vec![];
+                if !self.eat_op(')') {
+                    loop {
+                        args.push(self.expr()?);
+                        if self.eat_op(')') {
+                            break;
+                        }
+                        self.expect_op(',')?;
+                    }
+                }
+
+                if args.len() != n_args {
+                    return Err(anyhow::anyhow!(
+                        "function `{name}` expects {n_args} argument(s), got {}",
+                        args.len()
+                    ));
+                }
+
+                Ok(Expr::Call(name, args))
+            }
+            Some(Token::Op('(')) => {
+                let expr = self.expr()?;
+                self.expect_op(')')?;
+                Ok(expr)
+            }
+            Some(token) => Err(anyhow::anyhow!("unexpected token `{token}`")),
+            None => Err(anyhow::anyhow!("unexpected end of expression")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(src: &str) -> Option<f64> {
+        let lookup = |var: &str| match var {
+            "c0" => Some(2.0),
+            "volts" => Some(3.0),
+            _ => None,
+        };
+        Expr::parse(src).unwrap().eval(&lookup)
+    }
+
+    #[test]
+    fn expression_precedence() {
+        assert_eq!(eval("1 + 2 * 3"), Some(7.0));
+        assert_eq!(eval("(1 + 2) * 3"), Some(9.0));
+        assert_eq!(eval("8 - 2 - 1"), Some(5.0));
+        assert_eq!(eval("8 / 2 / 2"), Some(2.0));
+        assert_eq!(eval("2 * 3 ^ 2"), Some(18.0));
+        assert_eq!(eval("2 ^ 3 ^ 2"), Some(512.0));
+        assert_eq!(eval("c0 * volts + 1e1"), Some(16.0));
+    }
+
+    #[test]
+    fn expression_unary_minus() {
+        assert_eq!(eval("-c0"), Some(-2.0));
+        assert_eq!(eval("--c0"), Some(2.0));
+        assert_eq!(eval("-2 ^ 2"), Some(-4.0));
+        assert_eq!(eval("2 ^ -1"), Some(0.5));
+        assert_eq!(eval("3 * -volts"), Some(-9.0));
+        assert_eq!(eval("1 - -1"), Some(2.0));
+    }
+
+    #[test]
+    fn expression_functions() {
+        assert_eq!(eval("sqrt(16)"), Some(4.0));
+        assert_eq!(eval("abs(-c0)"), Some(2.0));
+        assert_eq!(eval("max(c0, volts)"), Some(3.0));
+        assert_eq!(eval("min(c0, volts) + pow(2, 3)"), Some(10.0));
+        assert_eq!(
+            eval("round(sqrt(c0 * c0 + volts * volts) * 100)"),
+            Some(361.0)
+        );
+    }
+
+    #[test]
+    fn expression_unknown_channels() {
+        assert_eq!(eval("c1"), None);
+        assert_eq!(eval("c0 + amps"), None);
+        assert_eq!(eval("max(c0, amps)"), None);
+
+        let expr = Expr::parse("c0 * amps + c0").unwrap();
+        let mut vars = vec![];
+        expr.variables(&mut vars);
+        assert_eq!(vars, ["c0", "amps"]);
+    }
+
+    #[test]
+    fn expression_malformed() {
+        for src in [
+            "",
+            "1 +",
+            "(1 + 2",
+            "1 + 2)",
+            "1 2",
+            "c0 $ 2",
+            "foo(1)",
+            "sqrt(1, 2)",
+            "max(1)",
+            "1..2",
+        ] {
+            assert!(Expr::parse(src).is_err(), "`{src}` was parsed");
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::f64::consts::PI;
+
+/// The selectable FFT sizes
+pub const FFT_SIZES: [usize; 7] = [64, 128, 256, 512, 1024, 2048, 4096];
+
+/// In-place radix-2 FFT. The length of `re` and `im` must be the same power of two.
+pub fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+    debug_assert!(n.is_power_of_two() && im.len() == n);
+
+    // Bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f64;
+        let (w_im, w_re) = angle.sin_cos();
+
+        for start in (0..n).step_by(len) {
+            let (mut cur_re, mut cur_im) = (1.0, 0.0);
+
+            for k in 0..len / 2 {
+                let a = start + k;
+                let b = a + len / 2;
+
+                let t_re = re[b] * cur_re - im[b] * cur_im;
+                let t_im = re[b] * cur_im + im[b] * cur_re;
+
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+
+                (cur_re, cur_im) = (cur_re * w_re - cur_im * w_im, cur_re * w_im + cur_im * w_re);
+            }
+        }
+
+        len <<= 1;
+    }
+}
+
+/// The amplitude spectrum of the values, with a Hann window applied and the mean removed.
+/// NaN and infinite values are replaced by the mean.
+///
+/// The length of `values` must be a power of two. Returns `values.len() / 2` bins,
+/// bin `k` corresponds to the frequency `k * sample_rate / values.len()`.
+pub fn amplitude_spectrum(values: &[f64]) -> Vec<f64> {
+    let n = values.len();
+    let finite = || values.iter().filter(|v| v.is_finite());
+    let mean = finite().sum::<f64>() / finite().count().max(1) as f64;
+
+    let mut re = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            if v.is_finite() {
+                (v - mean) * hann(i, n)
+            } else {
+                0.0
+            }
+        })
+        .collect::<Vec<f64>>();
+    let mut im = vec![0.0; n];
+
+    fft(&mut re, &mut im);
+
+    // Scale so that a full-scale sine has its amplitude, compensating the window gain of 0.5
+    let scale = 4.0 / n as f64;
+
+    re.iter()
+        .zip(im.iter())
+        .take(n / 2)
+        .map(|(re, im)| (re * re + im * im).sqrt() * scale)
+        .collect()
+}
+
+fn hann(i: usize, n: usize) -> f64 {
+    0.5 - 0.5 * (2.0 * PI * i as f64 / (n - 1).max(1) as f64).cos()
+}
+
+/// The frequency and amplitude of the strongest component of the values, ignoring the DC bin.
+///
+/// The length of `values` must be a power of two. The frequency is refined
+/// with a parabolic interpolation between the neighbouring bins.
+pub fn peak(values: &[f64], sample_rate: f64) -> Option<(f64, f64)> {
+    let spectrum = amplitude_spectrum(values);
+    let (k, &amplitude) = spectrum
+        .iter()
+        .enumerate()
+        .skip(1)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+    let offset = match (spectrum.get(k - 1), spectrum.get(k + 1)) {
+        (Some(&left), Some(&right)) => {
+            let denom = left - 2.0 * amplitude + right;
+            if denom.abs() > f64::EPSILON {
+                (0.5 * (left - right) / denom).clamp(-0.5, 0.5)
+            } else {
+                0.0
+            }
+        }
+        _ => 0.0,
+    };
+
+    Some((
+        (k as f64 + offset) * sample_rate / values.len() as f64,
+        amplitude,
+    ))
+}
+
+/// Estimates the sample rate of the evenly spaced times
+pub fn sample_rate(times: &[f64]) -> Option<f64> {
+    let (first, last) = (times.first()?, times.last()?);
+    let span = last - first;
+
+    if times.len() < 2 || span <= 0.0 {
+        return None;
+    }
+
+    Some((times.len() - 1) as f64 / span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fft_peak_of_sine() {
+        let sample_rate = 1000.0;
+        let values = (0..1024)
+            .map(|i| 2.0 * (2.0 * std::f64::consts::PI * 50.0 * i as f64 / sample_rate).sin())
+            .collect::<Vec<f64>>();
+
+        let (frequency, amplitude) = peak(&values, sample_rate).unwrap();
+
+        assert!((frequency - 50.0).abs() < 0.5, "{frequency}");
+        assert!((amplitude - 2.0).abs() < 0.3, "{amplitude}");
+    }
+}
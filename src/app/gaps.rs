@@ -0,0 +1,68 @@
+/// Splits the points of a line, which need to be sorted by x, at points with an invalid y value
+/// and where the distance to the next point is larger than the gap threshold. The invalid points are dropped.
+///
+/// There is always at least one segment, so that the line can still be extended.
+pub fn split_lines(points: Vec<[f64; 2]>, gap_threshold: Option<f64>) -> Vec<Vec<[f64; 2]>> {
+    let mut segments = vec![vec![]];
+
+    for point in points {
+        let current = segments.last_mut().unwrap();
+        if !point[1].is_finite() {
+            if !current.is_empty() {
+                segments.push(vec![]);
+            }
+        } else if current.last().map_or(false, |last: &[f64; 2]| {
+            gap_threshold.map_or(false, |threshold| point[0] - last[0] > threshold)
+        }) {
+            segments.push(vec![point]);
+        } else {
+            current.push(point);
+        }
+    }
+
+    segments
+}
+
+/// The x ranges between the segments that are larger than the gap threshold
+pub fn gap_ranges(segments: &[Vec<[f64; 2]>], threshold: f64) -> Vec<(f64, f64)> {
+    segments
+        .iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .windows(2)
+        .filter_map(|w| Some((w[0].last()?[0], w[1].first()?[0])))
+        .filter(|(start, end)| end - start > threshold)
+        .collect()
+}
+
+/// Divides the point budget between the segments by their number of points, each keeps at least its ends
+pub fn segment_budget(budget: usize, segment_len: usize, total_len: usize) -> usize {
+    if total_len == 0 {
+        return budget;
+    }
+
+    ((budget as f64 * segment_len as f64 / total_len as f64) as usize).max(3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_gaps() {
+        let points = vec![[0.0, 1.0], [0.5, 2.0], [2.0, 3.0], [2.2, 4.0], [5.0, 5.0]];
+
+        let segments = split_lines(points, Some(1.0));
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[1], [[2.0, 3.0], [2.2, 4.0]]);
+        assert_eq!(gap_ranges(&segments, 1.0), [(0.5, 2.0), (2.2, 5.0)]);
+
+        assert_eq!(split_lines(vec![], Some(1.0)), [Vec::<[f64; 2]>::new()]);
+
+        // Invalid values break the line without a gap
+        let points = vec![[0.0, 1.0], [0.1, f64::NAN], [0.2, 2.0], [0.3, 3.0]];
+        let segments = split_lines(points, None);
+        assert_eq!(segments, [vec![[0.0, 1.0]], vec![[0.2, 2.0], [0.3, 3.0]]]);
+        assert!(gap_ranges(&segments, 1.0).is_empty());
+    }
+}
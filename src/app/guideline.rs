@@ -0,0 +1,25 @@
+use super::PlotAxis;
+
+/// A named horizontal line at a fixed value in the Time-Value plot, like a limit of the device
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GuideLine {
+    pub label: String,
+    pub value: f64,
+    /// The axis the value is on
+    pub axis: PlotAxis,
+    pub color: egui::Color32,
+    pub visible: bool,
+}
+
+impl Default for GuideLine {
+    fn default() -> Self {
+        Self {
+            label: String::from("limit"),
+            value: 0.0,
+            axis: PlotAxis::default(),
+            color: egui::Color32::GOLD,
+            visible: true,
+        }
+    }
+}
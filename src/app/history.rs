@@ -0,0 +1,215 @@
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+use super::Sample;
+
+/// The size of a spilled sample, the time and the value
+const RECORD_SIZE: u64 = 16;
+
+/// Samples evicted from the in-memory buffers, spilled into temporary files so that
+/// the whole capture can be scrolled back through.
+///
+/// Each received channel has its own file of fixed size records. The samples of a channel are sorted by time,
+/// so a time range can be looked up with a binary search.
+#[derive(Debug)]
+pub struct SpilledHistory {
+    dir: PathBuf,
+    channels: Vec<SpilledChannel>,
+}
+
+#[derive(Debug)]
+struct SpilledChannel {
+    writer: BufWriter<File>,
+    reader: File,
+    len: u64,
+    first_time: i64,
+    /// The plot requests the same range every frame
+    cached: Option<CachedRange>,
+}
+
+/// The last range query and its result
+#[derive(Debug)]
+struct CachedRange {
+    query: (i64, i64, usize),
+    samples: Vec<(i64, f64)>,
+}
+
+impl SpilledHistory {
+    /// Creates a temporary directory for the history files
+    pub fn new() -> anyhow::Result<Self> {
+        let dir = std::env::temp_dir().join(format!("splot-history-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).map_err(|e| {
+            anyhow::anyhow!(
+                "failed to create history directory `{}`, Err: {e}",
+                dir.display()
+            )
+        })?;
+
+        log::debug!("spilling history to `{}`", dir.display());
+
+        Ok(Self {
+            dir,
+            channels: vec![],
+        })
+    }
+
+    /// The time of the oldest spilled sample
+    pub fn first_time(&self) -> Option<i64> {
+        self.channels.iter().map(|c| c.first_time).min()
+    }
+
+    /// The size of the spilled samples in bytes
+    pub fn bytes(&self) -> u64 {
+        self.channels.iter().map(|c| c.len * RECORD_SIZE).sum()
+    }
+
+    pub fn spill(&mut self, channel: usize, sample: &Sample) -> anyhow::Result<()> {
+        while self.channels.len() <= channel {
+            let path = self
+                .dir
+                .join(format!("channel_{}.bin", self.channels.len()));
+            let writer = File::create(&path)?;
+            let reader = File::open(&path)?;
+
+            self.channels.push(SpilledChannel {
+                writer: BufWriter::new(writer),
+                reader,
+                len: 0,
+                first_time: sample.time,
+                cached: None,
+            });
+        }
+
+        let c = &mut self.channels[channel];
+        c.writer.write_all(&sample.time.to_le_bytes())?;
+        c.writer.write_all(&sample.value.to_le_bytes())?;
+        c.len += 1;
+        // Only samples inside the cached range change its result
+        if matches!(&c.cached, Some(r) if sample.time <= r.query.1) {
+            c.cached = None;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the samples of the channel in the time range.
+    ///
+    /// When there are more than `max_points`, evenly spaced samples are read.
+    pub fn read_range(
+        &mut self,
+        channel: usize,
+        start: i64,
+        end: i64,
+        max_points: usize,
+    ) -> anyhow::Result<&[(i64, f64)]> {
+        let Some(c) = self.channels.get_mut(channel) else {
+            return Ok(&[]);
+        };
+
+        let query = (start, end, max_points);
+        if c.cached.as_ref().map(|r| r.query) != Some(query) {
+            let samples = c.read_range(start, end, max_points)?;
+            c.cached = Some(CachedRange { query, samples });
+        }
+
+        Ok(c.cached
+            .as_ref()
+            .map(|r| r.samples.as_slice())
+            .unwrap_or(&[]))
+    }
+}
+
+impl Drop for SpilledHistory {
+    fn drop(&mut self) {
+        self.channels.clear();
+
+        if let Err(e) = std::fs::remove_dir_all(&self.dir) {
+            log::warn!(
+                "failed to remove history directory `{}`, Err: {e}",
+                self.dir.display()
+            );
+        }
+    }
+}
+
+impl SpilledChannel {
+    fn read_record(&mut self, index: u64) -> anyhow::Result<(i64, f64)> {
+        let mut buf = [0; RECORD_SIZE as usize];
+        self.reader.seek(SeekFrom::Start(index * RECORD_SIZE))?;
+        self.reader.read_exact(&mut buf)?;
+
+        let (time, value) = buf.split_at(8);
+        Ok((
+            i64::from_le_bytes(time.try_into()?),
+            f64::from_le_bytes(value.try_into()?),
+        ))
+    }
+
+    /// The index of the first record with a time not less than `time`
+    fn lower_bound(&mut self, time: i64) -> anyhow::Result<u64> {
+        let (mut lo, mut hi) = (0, self.len);
+
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.read_record(mid)?.0 < time {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        Ok(lo)
+    }
+
+    fn read_range(
+        &mut self,
+        start: i64,
+        end: i64,
+        max_points: usize,
+    ) -> anyhow::Result<Vec<(i64, f64)>> {
+        self.writer.flush()?;
+
+        let first = self.lower_bound(start)?;
+        let last = self.lower_bound(end.saturating_add(1))?;
+        let n = last.saturating_sub(first);
+        let step = (n / max_points.max(1) as u64).max(1);
+
+        (first..last)
+            .step_by(step as usize)
+            .map(|i| self.read_record(i))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spilled_history_range() {
+        let mut history = SpilledHistory::new().unwrap();
+
+        for i in 0..1000 {
+            let sample = Sample {
+                time: i * 1_000_000,
+                value: i as f64,
+                name: None,
+            };
+            history.spill(0, &sample).unwrap();
+        }
+
+        let all = history
+            .read_range(0, 100_000_000, 199_000_000, 1000)
+            .unwrap();
+        assert_eq!(all.len(), 100);
+        assert_eq!(all.first(), Some(&(100_000_000, 100.0)));
+        assert_eq!(all.last(), Some(&(199_000_000, 199.0)));
+
+        let strided = history.read_range(0, 0, i64::MAX, 100).unwrap();
+        assert_eq!(strided.len(), 100);
+        assert_eq!(strided[1], (10_000_000, 10.0));
+
+        assert!(history.read_range(1, 0, i64::MAX, 100).unwrap().is_empty());
+    }
+}
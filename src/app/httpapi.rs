@@ -0,0 +1,412 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+
+use instant::Duration;
+
+/// The maximum size of a request head, larger requests are rejected
+const MAX_REQUEST_SIZE: usize = 8192;
+/// The time a request waits for the app to answer it
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+/// The names the server on localhost is addressed by
+const LOCAL_HOSTS: [&str; 2] = ["127.0.0.1", "localhost"];
+
+/// A request to the HTTP API, answered by the app because it owns the samples
+#[derive(Debug)]
+pub struct ApiRequest {
+    pub path: String,
+    /// The decoded query parameters
+    pub query: Vec<(String, String)>,
+    reply: mpsc::Sender<(u16, String)>,
+}
+
+impl ApiRequest {
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Sends the JSON body with the status code to the client
+    pub fn respond(self, status: u16, body: String) {
+        // The client might have timed out already
+        let _ = self.reply.send((status, body));
+    }
+}
+
+/// A HTTP server on localhost that forwards the GET requests to the app.
+///
+/// Requests of websites are rejected, unless their origin is allowed. The server is stopped when it is dropped.
+#[derive(Debug)]
+pub struct HttpApi {
+    addr: SocketAddr,
+    requests: mpsc::Receiver<ApiRequest>,
+    stop: Arc<AtomicBool>,
+}
+
+impl HttpApi {
+    /// Binds to the port on localhost. Every request triggers a repaint, so that the app answers it without delay.
+    ///
+    /// The allowed origins are websites like `http://localhost:8000` that may request the data.
+    pub fn start(
+        port: u16,
+        allowed_origins: Vec<String>,
+        ctx: egui::Context,
+    ) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .map_err(|e| anyhow::anyhow!("failed to bind to port {port}, Err: {e}"))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+
+        let (sender, requests) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread_stop = Arc::clone(&stop);
+        std::thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let sender = sender.clone();
+                        let allowed_origins = allowed_origins.clone();
+                        let ctx = ctx.clone();
+                        std::thread::spawn(move || {
+                            let res = handle_connection(
+                                stream,
+                                addr.port(),
+                                &allowed_origins,
+                                &sender,
+                                &ctx,
+                            );
+                            if let Err(e) = res {
+                                log::debug!("handling HTTP API connection failed, Err: {e}");
+                            }
+                        });
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        std::thread::sleep(Duration::from_millis(50));
+                    }
+                    Err(e) => {
+                        log::error!("accepting HTTP API connection failed, Err: {e}");
+                        std::thread::sleep(Duration::from_millis(500));
+                    }
+                }
+            }
+            log::debug!("stopped HTTP API on {addr}");
+        });
+
+        log::debug!("started HTTP API on {addr}");
+        Ok(Self {
+            addr,
+            requests,
+            stop,
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The requests that are waiting for an answer
+    pub fn pending_requests(&self) -> Vec<ApiRequest> {
+        self.requests.try_iter().collect()
+    }
+}
+
+impl Drop for HttpApi {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    port: u16,
+    allowed_origins: &[String],
+    sender: &mpsc::Sender<ApiRequest>,
+    ctx: &egui::Context,
+) -> anyhow::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+
+    let Some(head) = read_request_head(&mut stream)? else {
+        return write_response(&mut stream, 431, &error_json("request too large"), None);
+    };
+    let origin = match check_origin(&head, port, allowed_origins) {
+        Ok(origin) => origin,
+        Err(e) => return write_response(&mut stream, 403, &error_json(e), None),
+    };
+    let mut request_line = head.lines().next().unwrap_or_default().split(' ');
+    let (method, target) = (request_line.next(), request_line.next());
+
+    let Some(target) = target else {
+        return write_response(&mut stream, 400, &error_json("malformed request"), origin);
+    };
+    if method != Some("GET") {
+        return write_response(
+            &mut stream,
+            405,
+            &error_json("only GET is supported"),
+            origin,
+        );
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let (key, value) = p.split_once('=').unwrap_or((p, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect();
+
+    let (reply, response) = mpsc::channel();
+    sender.send(ApiRequest {
+        path: path.to_string(),
+        query,
+        reply,
+    })?;
+    ctx.request_repaint();
+
+    match response.recv_timeout(RESPONSE_TIMEOUT) {
+        Ok((status, body)) => write_response(&mut stream, status, &body, origin),
+        Err(_) => write_response(
+            &mut stream,
+            503,
+            &error_json("splot did not respond"),
+            origin,
+        ),
+    }
+}
+
+/// The value of the header in the request head
+pub fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .find(|(n, _)| n.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+}
+
+/// Checks that the request is addressed to the server on localhost, which protects against DNS rebinding,
+/// and that a request of a website comes from an allowed origin or the server itself.
+///
+/// Returns the origin of an allowed website, which may read the response.
+pub fn check_origin<'a>(
+    head: &'a str,
+    port: u16,
+    allowed_origins: &[String],
+) -> Result<Option<&'a str>, &'static str> {
+    let is_local = |host: &str| {
+        let (name, host_port) = host.rsplit_once(':').unwrap_or((host, "80"));
+        LOCAL_HOSTS.iter().any(|h| h.eq_ignore_ascii_case(name)) && host_port == port.to_string()
+    };
+
+    if header(head, "host").map_or(false, |host| !is_local(host)) {
+        return Err("host not allowed");
+    }
+
+    // Tools that are not browsers don't send an origin
+    let Some(origin) = header(head, "origin") else {
+        return Ok(None);
+    };
+    let origin = origin.trim_end_matches('/');
+    if allowed_origins.iter().any(|o| o == origin)
+        || origin.strip_prefix("http://").map_or(false, is_local)
+    {
+        Ok(Some(origin))
+    } else {
+        Err("origin not allowed")
+    }
+}
+
+/// The origins separated by commas or whitespace, without trailing slashes
+pub fn parse_origins(origins: &str) -> Vec<String> {
+    origins
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|o| o.trim_end_matches('/'))
+        .filter(|o| !o.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Reads the request line and the headers. None when they are too large.
+pub fn read_request_head(stream: &mut TcpStream) -> anyhow::Result<Option<String>> {
+    let mut head = vec![];
+    let mut buf = [0; 1024];
+
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf)?;
+        if n == 0 {
+            return Err(anyhow::anyhow!(
+                "connection closed before the end of the request"
+            ));
+        }
+        head.extend_from_slice(&buf[..n]);
+        if head.len() > MAX_REQUEST_SIZE {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(String::from_utf8_lossy(&head).into_owned()))
+}
+
+/// Writes the response, the allowed origin of a website may read it
+fn write_response(
+    stream: &mut TcpStream,
+    status: u16,
+    body: &str,
+    origin: Option<&str>,
+) -> anyhow::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        431 => "Request Header Fields Too Large",
+        _ => "Service Unavailable",
+    };
+    let allow_origin = origin
+        .map(|origin| format!("Access-Control-Allow-Origin: {origin}\r\nVary: Origin\r\n"))
+        .unwrap_or_default();
+
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+        {allow_origin}Connection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Decodes `%XX` escapes and `+` as space
+pub fn percent_decode(s: &str) -> String {
+    let mut bytes = vec![];
+    let mut iter = s.bytes();
+
+    while let Some(b) = iter.next() {
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [iter.next(), iter.next()];
+                match hex
+                    .iter()
+                    .flatten()
+                    .map(|&h| (h as char).to_digit(16))
+                    .collect::<Option<Vec<u32>>>()
+                {
+                    Some(digits) if digits.len() == 2 => {
+                        bytes.push((digits[0] * 16 + digits[1]) as u8)
+                    }
+                    _ => {
+                        bytes.push(b'%');
+                        bytes.extend(hex.iter().flatten());
+                    }
+                }
+            }
+            b => bytes.push(b),
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// The string as JSON string literal
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// The number as JSON number, JSON has no NaN and infinity so they are null
+pub fn json_number(v: f64) -> String {
+    if v.is_finite() {
+        v.to_string()
+    } else {
+        String::from("null")
+    }
+}
+
+pub fn error_json(message: &str) -> String {
+    format!("{{\"error\":{}}}", json_string(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn http_api_roundtrip() {
+        let api = HttpApi::start(0, vec![], egui::Context::default()).unwrap();
+        let addr = api.addr();
+
+        let client = std::thread::spawn(move || {
+            let mut stream = std::net::TcpStream::connect(addr).unwrap();
+            stream
+                .write_all(b"GET /samples?channel=a%20b&since=1.5 HTTP/1.1\r\n\r\n")
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+
+        let request = loop {
+            if let Some(request) = api.pending_requests().pop() {
+                break request;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(request.path, "/samples");
+        assert_eq!(request.param("channel"), Some("a b"));
+        assert_eq!(request.param("since"), Some("1.5"));
+        request.respond(200, json_string("\"ok\"\n"));
+
+        let response = client.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\n\"\\\"ok\\\"\\n\""));
+    }
+
+    #[test]
+    fn http_api_origins() {
+        let allowed = parse_origins("http://localhost:8000/, https://example.org");
+        assert_eq!(allowed, ["http://localhost:8000", "https://example.org"]);
+        let check = |head: &'static str| check_origin(head, 7878, &allowed);
+
+        assert_eq!(
+            check("GET / HTTP/1.1\r\nHost: 127.0.0.1:7878\r\n"),
+            Ok(None)
+        );
+        assert_eq!(
+            check("GET / HTTP/1.1\r\nHost: localhost:7878\r\nOrigin: http://localhost:8000\r\n"),
+            Ok(Some("http://localhost:8000"))
+        );
+        assert_eq!(
+            check("GET / HTTP/1.1\r\nHost: localhost:7878\r\nOrigin: http://localhost:7878\r\n"),
+            Ok(Some("http://localhost:7878"))
+        );
+        // A website that was not allowed
+        assert!(check(
+            "GET / HTTP/1.1\r\nHost: 127.0.0.1:7878\r\nOrigin: https://evil.example\r\n"
+        )
+        .is_err());
+        // DNS rebinding
+        assert!(check("GET / HTTP/1.1\r\nHost: evil.example:7878\r\n").is_err());
+        assert!(check("GET / HTTP/1.1\r\nHost: localhost:8000\r\n").is_err());
+    }
+}
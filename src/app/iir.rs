@@ -0,0 +1,102 @@
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+use super::nanos_to_secs;
+
+/// The state of a first or second order Butterworth filter.
+///
+/// The coefficients are computed for the interval of each sample, so that a varying sample rate is followed.
+#[derive(Debug, Clone, Default)]
+pub struct IirFilter {
+    /// The last two inputs and outputs
+    x: [f64; 2],
+    y: [f64; 2],
+    last_time: Option<i64>,
+}
+
+impl IirFilter {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Filters the value received at the time in nanoseconds.
+    ///
+    /// The first value initializes the filter as if it had settled. NaN and infinite values are passed through
+    /// without changing the state.
+    pub fn step(&mut self, high_pass: bool, order: u8, cutoff: f64, time: i64, x: f64) -> f64 {
+        if !x.is_finite() {
+            return x;
+        }
+
+        let Some(last_time) = self.last_time.filter(|&t| t < time) else {
+            if self.last_time.is_none() {
+                let y = if high_pass { 0.0 } else { x };
+                self.x = [x, x];
+                self.y = [y, y];
+                self.last_time = Some(time);
+            }
+            return self.y[0];
+        };
+        self.last_time = Some(time);
+
+        // Prewarped with the bilinear transform, the cutoff must stay below the Nyquist frequency
+        let sample_rate = 1.0 / nanos_to_secs(time - last_time);
+        let k = (PI * cutoff.clamp(0.0, 0.49 * sample_rate) / sample_rate).tan();
+
+        let ([b0, b1, b2], [a1, a2]) = if order < 2 {
+            let norm = 1.0 / (1.0 + k);
+            let a1 = (k - 1.0) * norm;
+            if high_pass {
+                ([norm, -norm, 0.0], [a1, 0.0])
+            } else {
+                ([k * norm, k * norm, 0.0], [a1, 0.0])
+            }
+        } else {
+            let q = FRAC_1_SQRT_2;
+            let norm = 1.0 / (1.0 + k / q + k * k);
+            let a = [2.0 * (k * k - 1.0) * norm, (1.0 - k / q + k * k) * norm];
+            if high_pass {
+                ([norm, -2.0 * norm, norm], a)
+            } else {
+                let b0 = k * k * norm;
+                ([b0, 2.0 * b0, b0], a)
+            }
+        };
+
+        let y = b0 * x + b1 * self.x[0] + b2 * self.x[1] - a1 * self.y[0] - a2 * self.y[1];
+        self.x = [x, self.x[0]];
+        self.y = [y, self.y[0]];
+
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iir_filters() {
+        // 1 kHz sampling, 10 Hz cutoff
+        let amplitude_after = |high_pass: bool, order: u8, frequency: f64| {
+            let mut filter = IirFilter::default();
+
+            (0..2000)
+                .map(|k| {
+                    let t = k as f64 / 1000.0;
+                    let x = 1.0 + (2.0 * std::f64::consts::PI * frequency * t).sin();
+                    filter.step(high_pass, order, 10.0, (t * 1e9) as i64, x)
+                })
+                .skip(1000)
+                .fold(0.0_f64, |max, y| {
+                    max.max((y - if high_pass { 0.0 } else { 1.0 }).abs())
+                })
+        };
+
+        // A decade above the cutoff
+        assert!(amplitude_after(false, 1, 100.0) < 0.15);
+        assert!(amplitude_after(false, 2, 100.0) < 0.015);
+        // The offset is removed, the fast signal passes
+        assert!((amplitude_after(true, 2, 100.0) - 1.0).abs() < 0.02);
+        assert!(amplitude_after(true, 2, 1.0) < 0.02);
+    }
+}
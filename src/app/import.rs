@@ -0,0 +1,194 @@
+use super::{parse_value, Sample, TimeUnit};
+
+/// A capture imported from a CSV export of another tool
+#[derive(Debug, Clone, Default)]
+pub struct ImportedCapture {
+    pub names: Vec<String>,
+    pub units: Vec<String>,
+    /// Outer vec is one for each channel, inner vec is the "history"
+    pub samples_vec: Vec<Vec<Sample>>,
+}
+
+impl ImportedCapture {
+    pub fn n_samples(&self) -> u64 {
+        self.samples_vec.iter().map(|s| s.len() as u64).sum()
+    }
+}
+
+/// The names of a first column that holds the index of the samples instead of their time
+const INDEX_COLUMNS: [&str; 6] = ["", "#", "n", "idx", "index", "sample"];
+
+/// If the line is a comment, a `#` directly followed by a separator starts a header instead
+fn is_comment(line: &str) -> bool {
+    match line.strip_prefix('#') {
+        Some(rest) => !rest.trim_start_matches(' ').starts_with([',', ';', '\t']),
+        None => line.starts_with(';'),
+    }
+}
+
+/// Parses analog CSV exports as written by logic analyzer software (Saleae Logic, sigrok / PulseView),
+/// as well as the exports of splot.
+///
+/// The first column is the time, the header names the channels. A first column named like `index` holds
+/// the sample index instead, which becomes the time in seconds, empty indices are counted.
+/// Units in brackets are recognized, e.g. `Time [ms]` or `Channel 0 [V]`.
+/// Lines starting with `;` or `#` are treated as comments, except a header whose first column is named `#`.
+pub fn parse_csv_capture(text: &str) -> anyhow::Result<ImportedCapture> {
+    let mut lines = text
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !is_comment(l));
+
+    let header = lines
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("CSV capture is empty"))?;
+    let separator = [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|&sep| header.matches(sep).count())
+        .unwrap_or(',');
+
+    let mut columns = header
+        .split(separator)
+        .map(|c| split_unit(c.trim().trim_matches('"')));
+
+    let (time_name, time_unit_str) = columns
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("CSV capture has no time column"))?;
+    let indexed = INDEX_COLUMNS.contains(&time_name.to_lowercase().as_str());
+    let time_unit = match time_unit_str.as_str() {
+        _ if indexed => TimeUnit::S,
+        "" => TimeUnit::S,
+        unit => TimeUnit::from_suffix(unit)
+            .ok_or_else(|| anyhow::anyhow!("unsupported time unit `{unit}`"))?,
+    };
+
+    let (names, units): (Vec<String>, Vec<String>) = columns.unzip();
+    if names.is_empty() {
+        return Err(anyhow::anyhow!("CSV capture has no value columns"));
+    }
+
+    let mut samples_vec = vec![vec![]; names.len()];
+
+    for (line_i, line) in lines.enumerate() {
+        let mut fields = line.split(separator).map(|f| f.trim().trim_matches('"'));
+
+        let time = match fields.next().and_then(parse_value) {
+            Some(time) => time,
+            None if indexed => line_i as f64,
+            None => {
+                return Err(anyhow::anyhow!(
+                    "invalid time value in data line {}",
+                    line_i + 1
+                ))
+            }
+        };
+        let time = time_unit.convert_to_nanos(time);
+
+        for (samples, field) in samples_vec.iter_mut().zip(fields) {
+            // Empty fields are common in exports with channels of different sample rates
+            if let Some(value) = parse_value(field) {
+                samples.push(Sample {
+                    time,
+                    value,
+                    name: None,
+                });
+            }
+        }
+    }
+
+    // The name of the first sample names the channel
+    for (samples, name) in samples_vec.iter_mut().zip(names.iter()) {
+        if let Some(first) = samples.first_mut() {
+            first.name = Some(name.clone());
+        }
+    }
+
+    Ok(ImportedCapture {
+        names,
+        units,
+        samples_vec,
+    })
+}
+
+/// Splits a column header like `Channel 0 [V]` into its name and unit
+fn split_unit(column: &str) -> (String, String) {
+    if let Some((name, rest)) = column.split_once('[') {
+        if let Some((unit, _)) = rest.split_once(']') {
+            return (name.trim().to_string(), unit.trim().to_string());
+        }
+    }
+
+    if let Some((name, rest)) = column.split_once('(') {
+        if let Some((unit, _)) = rest.split_once(')') {
+            return (name.trim().to_string(), unit.trim().to_string());
+        }
+    }
+
+    (column.to_string(), String::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exported_capture_can_be_imported() {
+        let sample = |time: i64, value: f64| Sample {
+            time,
+            value,
+            name: None,
+        };
+        let a = [sample(0, 1.0), sample(1_000_000, 2.0)];
+        let b = [sample(1_000_000, 3.5)];
+
+        let text = crate::app::export::write_csv_capture(
+            vec![
+                crate::app::export::ExportChannel {
+                    name: "a",
+                    unit: "V",
+                    samples: a.iter(),
+                },
+                crate::app::export::ExportChannel {
+                    name: "b",
+                    unit: "",
+                    samples: b.iter(),
+                },
+            ],
+            0,
+        );
+        let capture = parse_csv_capture(&text).unwrap();
+
+        assert_eq!(capture.names, ["a", "b"]);
+        assert_eq!(capture.units, ["V", ""]);
+        assert_eq!(capture.samples_vec[0].len(), 2);
+        assert_eq!(capture.samples_vec[1][0].time, 1_000_000);
+        assert_eq!(capture.samples_vec[1][0].value, 3.5);
+    }
+
+    #[test]
+    fn indexed_capture_import() {
+        let capture = parse_csv_capture("index,x\n0,1.5\n1,2.5\n,3.5\n").unwrap();
+
+        assert_eq!(
+            capture.samples_vec[0]
+                .iter()
+                .map(|s| (s.time, s.value))
+                .collect::<Vec<_>>(),
+            [(0, 1.5), (1_000_000_000, 2.5), (2_000_000_000, 3.5)]
+        );
+    }
+
+    #[test]
+    fn hash_indexed_capture_import() {
+        let capture = parse_csv_capture("# exported samples\n#\tx\n0\t1.5\n1\t2.5\n").unwrap();
+
+        assert_eq!(capture.names, ["x"]);
+        assert_eq!(
+            capture.samples_vec[0]
+                .iter()
+                .map(|s| (s.time, s.value))
+                .collect::<Vec<_>>(),
+            [(0, 1.5), (1_000_000_000, 2.5)]
+        );
+    }
+}
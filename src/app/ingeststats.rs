@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+
+use instant::{Duration, Instant};
+
+use crate::fixedsizebuffer::FixedSizeBuffer;
+
+/// The interval the rates are computed over
+const INTERVAL: Duration = Duration::from_secs(1);
+/// The number of intervals kept, an hour
+const HISTORY_SIZE: usize = 3600;
+/// The sliding window of the throughput indicators
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(2);
+
+/// The names of the recorded metrics
+pub const METRICS: [&str; 3] = ["bytes/s", "lines/s", "parse errors/s"];
+
+/// The throughput of the connection over time, to analyze throughput issues
+#[derive(Debug, Clone)]
+pub struct IngestStats {
+    /// The counts of the metrics in the current interval
+    counts: [u64; 3],
+    interval_start: Instant,
+    /// The time in seconds since the start and the rate, for each metric
+    rates: [FixedSizeBuffer<[f64; 2]>; 3],
+}
+
+impl Default for IngestStats {
+    fn default() -> Self {
+        Self {
+            counts: [0; 3],
+            interval_start: Instant::now(),
+            rates: [(); 3].map(|_| FixedSizeBuffer::new(HISTORY_SIZE)),
+        }
+    }
+}
+
+impl IngestStats {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Counts the received bytes, lines and parse errors
+    pub fn record(&mut self, bytes: u64, lines: u64, errors: u64) {
+        self.counts[0] += bytes;
+        self.counts[1] += lines;
+        self.counts[2] += errors;
+    }
+
+    /// Adds the rates of the current interval, once it has elapsed
+    pub fn update(&mut self, start_time: Instant) {
+        let elapsed = self.interval_start.elapsed();
+        if elapsed < INTERVAL {
+            return;
+        }
+
+        let time = Instant::now().duration_since(start_time).as_secs_f64();
+        for (rates, count) in self.rates.iter_mut().zip(self.counts) {
+            rates.add([time, count as f64 / elapsed.as_secs_f64()]);
+        }
+
+        self.counts = [0; 3];
+        self.interval_start = Instant::now();
+    }
+
+    /// The rates of the metric with the index into `METRICS`
+    pub fn rates(&self, metric: usize) -> Vec<[f64; 2]> {
+        self.rates[metric].iter().copied().collect()
+    }
+
+    /// The latest rate of the metric with the index into `METRICS`
+    pub fn latest(&self, metric: usize) -> Option<f64> {
+        self.rates[metric].last().map(|r| r[1])
+    }
+}
+
+/// The received bytes and samples over a sliding window, for the indicators in the top bar
+#[derive(Debug, Clone, Default)]
+pub struct Throughput {
+    /// The time, the number of bytes and the number of samples of each channel of the reads in the window
+    reads: VecDeque<(Instant, u64, Vec<usize>)>,
+}
+
+impl Throughput {
+    pub fn clear(&mut self) {
+        self.reads.clear();
+    }
+
+    pub fn record(&mut self, bytes: u64, samples: Vec<usize>) {
+        let now = Instant::now();
+
+        while self.reads.front().map_or(false, |(time, ..)| {
+            now.duration_since(*time) > THROUGHPUT_WINDOW
+        }) {
+            self.reads.pop_front();
+        }
+        self.reads.push_back((now, bytes, samples));
+    }
+
+    fn in_window(&self) -> impl Iterator<Item = &(Instant, u64, Vec<usize>)> {
+        let now = Instant::now();
+
+        self.reads
+            .iter()
+            .filter(move |(time, ..)| now.duration_since(*time) <= THROUGHPUT_WINDOW)
+    }
+
+    pub fn bytes_per_sec(&self) -> f64 {
+        self.in_window().map(|(_, bytes, _)| *bytes).sum::<u64>() as f64
+            / THROUGHPUT_WINDOW.as_secs_f64()
+    }
+
+    /// The samples per second of the channel with the index
+    pub fn samples_per_sec(&self, channel: usize) -> f64 {
+        self.in_window()
+            .filter_map(|(.., samples)| samples.get(channel))
+            .sum::<usize>() as f64
+            / THROUGHPUT_WINDOW.as_secs_f64()
+    }
+}
+
+/// The timing and the error counts of the reads from the connection, to debug a choppy plot
+#[derive(Debug, Clone, Default)]
+pub struct AcquisitionStats {
+    pub reads: u64,
+    pub empty_reads: u64,
+    pub read_errors: u64,
+    /// Lines that could not be parsed and were dropped
+    pub parse_errors: u64,
+    /// Reads that ended within a line, the rest of the line waits for the next read
+    pub partial_reads: u64,
+    /// The duration from installing a read until it is ready
+    pub last_read_latency: Option<Duration>,
+    pub max_read_latency: Duration,
+    /// The interval between the polls of the read
+    pub last_poll_interval: Option<Duration>,
+    pub max_poll_interval: Duration,
+    read_started: Option<Instant>,
+    last_poll: Option<Instant>,
+}
+
+impl AcquisitionStats {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn read_started(&mut self) {
+        self.read_started = Some(Instant::now());
+    }
+
+    pub fn polled(&mut self) {
+        let now = Instant::now();
+
+        if let Some(last_poll) = self.last_poll {
+            let interval = now.duration_since(last_poll);
+            self.last_poll_interval = Some(interval);
+            self.max_poll_interval = self.max_poll_interval.max(interval);
+        }
+        self.last_poll = Some(now);
+    }
+
+    /// Records a finished read with the number of bytes, None when it failed
+    pub fn read_finished(&mut self, bytes: Option<usize>) {
+        self.reads += 1;
+        match bytes {
+            Some(0) => self.empty_reads += 1,
+            Some(_) => {}
+            None => self.read_errors += 1,
+        }
+
+        if let Some(read_started) = self.read_started.take() {
+            let latency = read_started.elapsed();
+            self.last_read_latency = Some(latency);
+            self.max_read_latency = self.max_read_latency.max(latency);
+        }
+    }
+
+    pub fn record_parse(&mut self, errors: u64, partial: bool) {
+        self.parse_errors += errors;
+        self.partial_reads += u64::from(partial);
+    }
+}
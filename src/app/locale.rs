@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use once_cell::sync::Lazy;
+
+/// The languages of the user interface.
+///
+/// A language is added with a catalog in `locales/` and a variant here, missing messages fall back to English.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // In the language itself, so that it can be found without understanding the current one
+        match self {
+            Language::English => write!(f, "English"),
+            Language::German => write!(f, "Deutsch"),
+        }
+    }
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../../locales/en.ftl"),
+            Language::German => include_str!("../../locales/de.ftl"),
+        }
+    }
+}
+
+static CATALOGS: Lazy<Vec<HashMap<&'static str, String>>> = Lazy::new(|| {
+    Language::ALL
+        .iter()
+        .map(|language| parse_catalog(language.source()))
+        .collect()
+});
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language as usize, Ordering::Relaxed);
+}
+
+/// The message of the key in the current language, falling back to English and then to the key itself
+pub fn tr(key: &'static str) -> &'static str {
+    let current = CURRENT.load(Ordering::Relaxed);
+
+    CATALOGS[current]
+        .get(key)
+        .or_else(|| CATALOGS[Language::English as usize].get(key))
+        .map(String::as_str)
+        .unwrap_or(key)
+}
+
+/// The message of the key with its `{ $name }` placeholders replaced by the arguments
+pub fn tr_args(key: &'static str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+    args.iter()
+        .fold(tr(key).to_string(), |message, (name, value)| {
+            message.replace(&format!("{{ ${name} }}"), &value.to_string())
+        })
+}
+
+/// Parses the `key = message` lines of a catalog, lines starting with `#` are comments.
+/// Line breaks in messages are written as `\n` and backslashes as `\\`.
+pub fn parse_catalog(source: &str) -> HashMap<&str, String> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, message)| (key.trim(), unescape(message.trim())))
+        .collect()
+}
+
+fn unescape(message: &str) -> String {
+    let mut unescaped = String::with_capacity(message.len());
+    let mut chars = message.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            (c, _) => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+/// The keys of the catalog that are missing in the English base catalog
+#[allow(unused)]
+pub fn unknown_keys(language: Language) -> Vec<&'static str> {
+    CATALOGS[language as usize]
+        .keys()
+        .filter(|key| !CATALOGS[Language::English as usize].contains_key(*key))
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_catalogs() {
+        let catalog = parse_catalog(
+            "# comment\n\nquit = Quit\nlabel-port =  Port: \nhelp = First line,\\nsecond \\\\n line\n",
+        );
+        assert_eq!(catalog.len(), 3);
+        assert_eq!(catalog["label-port"], "Port:");
+        assert_eq!(catalog["help"], "First line,\nsecond \\n line");
+
+        for language in Language::ALL {
+            assert!(unknown_keys(language).is_empty(), "{language}");
+        }
+        assert_eq!(tr("window-about"), "About");
+        assert_eq!(tr("missing-key"), "missing-key");
+        assert_eq!(
+            tr_args(
+                "missing { $what } for { $who }",
+                &[("what", &"key"), ("who", &2)]
+            ),
+            "missing key for 2"
+        );
+    }
+}
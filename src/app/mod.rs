@@ -1,23 +1,184 @@
+pub mod alarm;
+pub mod ansi;
+pub mod archive;
+pub mod automation;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod capture;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod clipboard;
+pub mod commands;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod conditionalcapture;
+pub mod cursors;
+pub mod dashboard;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod debugbundle;
+pub mod derived;
+pub mod downsample;
+pub mod envelope;
+#[cfg_attr(target_arch = "wasm32", allow(unused))]
+pub mod export;
+pub mod expression;
+pub mod fft;
+pub mod gaps;
+pub mod guideline;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod history;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod httpapi;
+pub mod iir;
+pub mod import;
+pub mod ingeststats;
+pub mod locale;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod notification;
+#[cfg(target_arch = "wasm32")]
+pub mod parseworker;
+pub mod preset;
+pub mod profile;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod recorder;
+pub mod reference;
+pub mod script;
+#[cfg_attr(target_arch = "wasm32", allow(unused))]
+pub mod sessiondiff;
+pub mod smoothing;
+pub mod theme;
+pub mod trajectory;
+pub mod trigger;
 pub mod ui;
+pub mod valuefilter;
+pub mod valueformat;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod websocket;
+#[cfg_attr(target_arch = "wasm32", allow(unused))]
+pub mod ziparchive;
+
+pub use crate::parser::{
+    binarylayout, can, interpolate_value_at, nanos_to_secs, parse_value, secs_to_nanos,
+    time_field_unit, ubx, Marker, ParseFormat, ParseResult, Parser, Sample, TextEvent, TimeUnit,
+};
 
+use egui_dock::{DockState, NodeIndex};
 use futures::lock::Mutex;
 use instant::{Duration, Instant};
-use std::collections::VecDeque;
-use std::io::{BufRead, Cursor};
+use std::collections::BTreeMap;
 use std::rc::Rc;
 
+use self::alarm::{AlarmEvent, AlarmRule};
+use self::archive::ArchivedCapture;
+use self::automation::{AutomationRule, RuleAction};
+use self::binarylayout::BinaryLayout;
+use self::can::CanSignal;
+use self::commands::{InitCommand, LineEnding};
+use self::cursors::MeasurementCursors;
+use self::dashboard::DashboardWidget;
+use self::derived::{DerivedChannel, DerivedEdit, DerivedKind};
+use self::downsample::{Downsampling, RenderBudget};
+use self::envelope::MinMaxSummary;
+use self::export::ExportFormat;
+use self::guideline::GuideLine;
+use self::import::ImportedCapture;
+use self::ingeststats::{AcquisitionStats, IngestStats, Throughput};
+use self::locale::{tr, tr_args, Language};
+use self::preset::{AppearancePreset, ChannelAppearance};
+use self::profile::ConnectionProfile;
+use self::reference::ReferenceTrace;
+use self::script::{AutomationScript, ScriptOutput};
+use self::sessiondiff::{DiffAlignment, SessionDiff};
+use self::smoothing::SmoothingMode;
+use self::theme::Theme;
+use self::trajectory::OrbitCamera;
+use self::trigger::Trigger;
+use self::ubx::UbxMessage;
+use self::valuefilter::ValueFilter;
+use self::valueformat::ValueFormat;
 use crate::fixedsizebuffer::FixedSizeBuffer;
 #[allow(unused)]
 use crate::serialconnection::new_serial_connection;
+use crate::serialconnection::script::DeviceScript;
 use crate::serialconnection::{
-    new_serial_connection_dummy, DataBits, FlowControl, Parity, SerialConnection, StopBits,
+    new_serial_connection_dummy, new_serial_connection_dummy_script, validate_baudrate, DataBits,
+    FlowControl, Parity, PortInfo, SerialConnection, StopBits,
 };
 
+/// The example script of the scripted dummy device
+const DEFAULT_DEVICE_SCRIPT: &str = "# Answers writes that contain PING
+on PING => PONG\\n
+line a=1, b=0
+wait 100
+line a=2, b=0.5
+wait 100
+line a=3, b=1
+wait 100
+repeat
+";
+
+/// A line of the serial monitor, together with the time it was received
 #[derive(Debug, Clone)]
-pub struct Sample {
-    time: f64,
-    value: f64,
-    name: Option<String>,
+pub struct MonitorLine {
+    /// The time in nanoseconds since the start
+    time: i64,
+    /// The wall clock time since the unix epoch
+    wall_time: Duration,
+    text: String,
+}
+
+impl MonitorLine {
+    fn now(start_time: Instant, text: String) -> Self {
+        Self {
+            time: Instant::now().duration_since(start_time).as_nanos() as i64,
+            wall_time: instant::SystemTime::now()
+                .duration_since(instant::SystemTime::UNIX_EPOCH)
+                .unwrap_or_default(),
+            text,
+        }
+    }
+}
+
+/// The timestamp displayed in front of the lines of the serial monitor
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum MonitorTimestamp {
+    #[default]
+    None,
+    /// The time since the start
+    Relative,
+    /// The wall clock time in UTC
+    WallClock,
+}
+
+impl std::fmt::Display for MonitorTimestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MonitorTimestamp::None => write!(f, "None"),
+            MonitorTimestamp::Relative => write!(f, "Relative"),
+            MonitorTimestamp::WallClock => write!(f, "Wall clock (UTC)"),
+        }
+    }
+}
+
+impl MonitorTimestamp {
+    pub const ALL: [Self; 3] = [Self::None, Self::Relative, Self::WallClock];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            MonitorTimestamp::None => tr("monitor-timestamp-none"),
+            MonitorTimestamp::Relative => tr("monitor-timestamp-relative"),
+            MonitorTimestamp::WallClock => tr("monitor-timestamp-wall-clock"),
+        }
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -28,167 +189,357 @@ const SAMPLES_BUF_SIZE: usize = 2048;
 const MONITOR_LINES_BUF_SIZE: usize = 512;
 #[cfg(target_arch = "wasm32")]
 const MONITOR_LINES_BUF_SIZE: usize = 128;
+const MARKERS_BUF_SIZE: usize = 256;
+const TEXT_EVENTS_BUF_SIZE: usize = 4096;
+/// How often the available ports are refreshed while not connected, to notice plugged in devices
+#[cfg(not(target_arch = "wasm32"))]
+const PORTS_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+const RECONNECT_MIN_DELAY: Duration = Duration::from_millis(500);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(10);
+/// The repaint interval while nothing is connected or pending
+const IDLE_REPAINT_INTERVAL: Duration = Duration::from_millis(500);
+#[cfg(not(target_arch = "wasm32"))]
+const MONITOR_BYTES_BUF_SIZE: usize = 16384;
+#[cfg(target_arch = "wasm32")]
+const MONITOR_BYTES_BUF_SIZE: usize = 4096;
+/// The duration over which the activity of the channels is measured, in seconds
+const ACTIVITY_WINDOW_SECS: f64 = 2.0;
 
-const READ_BUF_SIZE: usize = 32;
+/// The maximum number of bytes read at once
+const READ_BUF_SIZE: usize = 65536;
 
 impl From<Sample> for egui_plot::PlotPoint {
     fn from(sample: Sample) -> Self {
         egui_plot::PlotPoint {
-            x: sample.time,
+            x: sample.time_secs(),
             y: sample.value,
         }
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct ParseResult {
-    full_lines: Vec<String>,
-    /// Outer vec is one for each position, inner vec is the "history"
-    samples_vec: Vec<Vec<Sample>>,
-    n_new_samples: u64,
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum DataFormat {
+    /// Lines of separated values
+    #[default]
+    Ascii,
+    /// Fixed-layout binary packets
+    Binary,
+    /// UBX protocol of u-blox GNSS modules
+    Ubx,
+    /// NMEA 0183 sentences of GNSS modules
+    Nmea,
+    /// Frames of a SocketCAN interface
+    Can,
 }
 
-/// reads full lines and counts the number of read bytes
-fn read_full_lines(input_buf: &[u8]) -> std::io::Result<(Vec<String>, usize)> {
-    let mut lines = vec![];
-    let mut read_bytes = 0;
-
-    let mut line = String::new();
-    let mut input_cursor = Cursor::new(input_buf);
-    loop {
-        let b = match input_cursor.read_line(&mut line) {
-            // Continue if not valid UTF-8 (or some other error)
-            Err(_e) => continue,
-            // if 0, the last line terminates with EOF, so is not a full line
-            Ok(0) => break,
-            Ok(b) => b,
-        };
-
-        // detect unfinished lines
-        if !line.ends_with('\n') {
-            break;
+impl std::fmt::Display for DataFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataFormat::Ascii => write!(f, "ASCII"),
+            DataFormat::Binary => write!(f, "Binary"),
+            DataFormat::Ubx => write!(f, "UBX"),
+            DataFormat::Nmea => write!(f, "NMEA"),
+            DataFormat::Can => write!(f, "CAN"),
         }
-
-        lines.push(std::mem::take(&mut line));
-        read_bytes += b;
     }
+}
 
-    Ok((lines, read_bytes))
+impl DataFormat {
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            DataFormat::Ascii => tr("data-format-ascii"),
+            DataFormat::Binary => tr("data-format-binary"),
+            DataFormat::Ubx => tr("data-format-ubx"),
+            DataFormat::Nmea => tr("data-format-nmea"),
+            DataFormat::Can => tr("data-format-can"),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct Parser {
-    buf: Vec<u8>,
+/// The Y axis of the Time-Value plot a channel is drawn against
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum PlotAxis {
+    #[default]
+    Left,
+    Right,
 }
 
-impl Parser {
-    pub fn clear(&mut self) {
-        self.buf.clear();
+impl std::fmt::Display for PlotAxis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlotAxis::Left => write!(f, "Left"),
+            PlotAxis::Right => write!(f, "Right"),
+        }
     }
+}
 
-    pub fn parse_from_serial_data(
-        &mut self,
-        serial_data: &[u8],
-        time_unit: TimeUnit,
-        value_separator: char,
-        start_time: Instant,
-    ) -> anyhow::Result<ParseResult> {
-        self.buf.extend(serial_data);
-
-        let mut added_samples = 0;
-        let mut samples_vec: Vec<Vec<Sample>> = vec![];
-
-        let mut time = Instant::now().duration_since(start_time).as_secs_f64();
+impl PlotAxis {
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            PlotAxis::Left => tr("plot-axis-left"),
+            PlotAxis::Right => tr("plot-axis-right"),
+        }
+    }
+}
 
-        // Read out full lines
-        let (full_lines, bytes_read) = read_full_lines(&self.buf)?;
+/// Maps values of the right Y axis into the coordinates of the plot, which are the ones of the left axis
+#[derive(Debug, Clone, Copy)]
+pub struct AxisTransform {
+    scale: f64,
+    offset: f64,
+}
 
-        // Drain the buffer by the bytes length of the read full lines
-        self.buf.drain(..bytes_read);
+impl AxisTransform {
+    /// Fits the range of values into the range of the plot, leaving a small margin
+    pub fn fit(min: f64, max: f64, plot_min: f64, plot_max: f64) -> Self {
+        let (plot_min, plot_max) =
+            if plot_min.is_finite() && plot_max.is_finite() && plot_max > plot_min {
+                (plot_min, plot_max)
+            } else {
+                (0.0, 1.0)
+            };
 
-        // parse them
-        for line in full_lines.iter() {
-            let line = line.trim();
+        let scale = if max > min {
+            0.9 * (plot_max - plot_min) / (max - min)
+        } else {
+            1.0
+        };
+        let offset = (plot_min + plot_max) * 0.5 - scale * (min + max) * 0.5;
 
-            // Don't add empy lines
-            if line.is_empty() {
-                continue;
-            }
+        Self { scale, offset }
+    }
 
-            for (i, value_str) in line.split(value_separator).enumerate() {
-                let mut is_time = false;
+    pub fn apply(&self, value: f64) -> f64 {
+        value * self.scale + self.offset
+    }
 
-                let mut name_splits: VecDeque<&str> =
-                    value_str.split('=').map(|s| s.trim()).collect();
+    pub fn invert(&self, plot_value: f64) -> f64 {
+        (plot_value - self.offset) / self.scale
+    }
+}
 
-                let name = if name_splits.len() > 1 {
-                    let name = name_splits.pop_front();
+/// How the Y bounds of a plot are determined
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum YBoundsMode {
+    /// The bounds are kept, and can be zoomed and dragged freely
+    #[default]
+    Free,
+    /// Fits the visible values exactly
+    Autoscale,
+    /// Fits the visible values, leaving a margin above and below
+    AutoscaleMargin,
+    /// The bounds entered by the user
+    Fixed,
+}
 
-                    if let Some(name) = name {
-                        is_time = name == "time" || name == "t";
-                    }
+impl std::fmt::Display for YBoundsMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            YBoundsMode::Free => write!(f, "Free"),
+            YBoundsMode::Autoscale => write!(f, "Autoscale"),
+            YBoundsMode::AutoscaleMargin => write!(f, "Autoscale with margin"),
+            YBoundsMode::Fixed => write!(f, "Fixed"),
+        }
+    }
+}
 
-                    name
-                } else {
-                    None
-                };
+impl YBoundsMode {
+    pub const ALL: [Self; 4] = [
+        Self::Free,
+        Self::Autoscale,
+        Self::AutoscaleMargin,
+        Self::Fixed,
+    ];
 
-                let Some(value) = name_splits.pop_front().and_then(|s| {
-                    s.chars()
-                        .filter(|&c| c.is_ascii_digit() || c == '-' || c == '.')
-                        .collect::<String>()
-                        .parse()
-                        .ok()
-                }) else {
-                    continue;
-                };
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            YBoundsMode::Free => tr("y-bounds-mode-free"),
+            YBoundsMode::Autoscale => tr("y-bounds-mode-autoscale"),
+            YBoundsMode::AutoscaleMargin => tr("y-bounds-mode-autoscale-margin"),
+            YBoundsMode::Fixed => tr("y-bounds-mode-fixed"),
+        }
+    }
+}
 
-                if is_time {
-                    time = time_unit.convert_to_secs(value);
-                    continue;
-                }
+/// The Y bounds settings of a plot
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct YBounds {
+    pub mode: YBoundsMode,
+    /// The margin as fraction of the range of the values
+    pub margin: f64,
+    pub min: f64,
+    pub max: f64,
+}
 
-                added_samples += 1;
+impl Default for YBounds {
+    fn default() -> Self {
+        Self {
+            mode: YBoundsMode::default(),
+            margin: 0.1,
+            min: -1.0,
+            max: 1.0,
+        }
+    }
+}
 
-                if let Some(samples) = samples_vec.get_mut(i) {
-                    samples.push(Sample {
-                        time,
-                        value,
-                        name: name.map(|s| s.to_string()),
-                    })
-                } else {
-                    samples_vec.push(vec![Sample {
-                        time,
-                        value,
-                        name: name.map(|s| s.to_string()),
-                    }]);
-                }
+impl YBounds {
+    /// The Y range of the plot in axis coordinates, or `None` if the bounds should be kept.
+    ///
+    /// `values` is the range of the visible values in axis coordinates,
+    /// `to_axis` maps the fixed bounds into axis coordinates.
+    pub fn range(
+        &self,
+        values: Option<(f64, f64)>,
+        to_axis: impl Fn(f64) -> Option<f64>,
+    ) -> Option<(f64, f64)> {
+        let (min, max) = match self.mode {
+            YBoundsMode::Free => return None,
+            YBoundsMode::Autoscale => values?,
+            YBoundsMode::AutoscaleMargin => {
+                let (min, max) = values?;
+                let margin = (max - min) * self.margin;
+                (min - margin, max + margin)
             }
+            YBoundsMode::Fixed => {
+                let (min, max) = (to_axis(self.min)?, to_axis(self.max)?);
+                (min.min(max), min.max(max))
+            }
+        };
+
+        if !min.is_finite() || !max.is_finite() {
+            return None;
+        }
+        // A constant value would collapse the plot
+        if max <= min {
+            return Some((min - 0.5, max + 0.5));
         }
 
-        Ok(ParseResult {
-            full_lines,
-            samples_vec,
-            n_new_samples: added_samples,
-        })
+        Some((min, max))
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct SamplesAppearance {
     name: String,
+    /// The name the channel was created with, the key of its remembered appearance
+    source_name: String,
     visible: bool,
     color: egui::Rgba,
+    /// The unit of the values, e.g. "°C"
+    unit: String,
+    axis: PlotAxis,
+    /// Applied to the received samples of the channel
+    value_filter: ValueFilter,
+    /// Holds the last value up to the end of the plot, so that slow channels don't appear to end
+    hold_last: bool,
+    /// Fills the area between the line and zero
+    fill: bool,
+    /// The number of received NaN and infinite values
+    invalid_values: u64,
+    smoothing: SmoothingMode,
+    /// The window of the moving average, in number of samples
+    smoothing_window: usize,
 }
 
 impl SamplesAppearance {
     fn new(name: String) -> Self {
         Self {
+            source_name: name.clone(),
             name,
             visible: true,
             color: egui::Rgba::BLUE,
+            unit: String::new(),
+            axis: PlotAxis::default(),
+            value_filter: ValueFilter::default(),
+            hold_last: false,
+            fill: false,
+            invalid_values: 0,
+            smoothing: SmoothingMode::default(),
+            smoothing_window: 10,
         }
     }
+
+    /// The name in the plot legend, with the unit
+    fn legend_name(&self) -> String {
+        if self.unit.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} [{}]", self.name, self.unit)
+        }
+    }
+
+    /// Formats the value together with the unit of the samples
+    fn format_value(&self, value: f64, format: ValueFormat) -> String {
+        format.format_with_unit(value, &self.unit)
+    }
+}
+
+/// Suffixes the name with `#<n>` if a channel with the same name already exists, e.g. `temp#2`.
+///
+/// Keeps name-keyed features like presets and exports unambiguous.
+fn unique_channel_name(name: String, appearances: &[SamplesAppearance]) -> String {
+    let exists = |name: &str| appearances.iter().any(|a| a.name == name);
+
+    if !exists(&name) {
+        return name;
+    }
+
+    let unique = (2..)
+        .map(|n| format!("{name}#{n}"))
+        .find(|candidate| !exists(candidate))
+        .unwrap_or_default();
+    log::warn!("duplicate channel name `{name}`, renamed to `{unique}`");
+
+    unique
+}
+
+fn remember_appearances(
+    remembered: &mut BTreeMap<String, ChannelAppearance>,
+    appearances: &[SamplesAppearance],
+) {
+    for appearance in appearances {
+        remembered.insert(
+            appearance.source_name.clone(),
+            ChannelAppearance::from(appearance),
+        );
+    }
 }
 
 fn unique_color_in_list(i: usize, len: usize) -> egui::Rgba {
@@ -206,78 +557,261 @@ fn recolor_samples_appearances(appereances: &mut [SamplesAppearance]) {
 }
 
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
 )]
-pub enum TimeUnit {
-    Us,
-    Ms,
-    S,
+pub enum PlotPage {
+    TimeValue,
+    XY,
+    /// Three channels as a trajectory in space
+    Trajectory,
+    SerialMonitor,
+    /// The received bytes as hex dump, independent of the parser
+    RawTraffic,
+    Spectrogram,
+    /// The latest values as numbers, bars and gauges
+    Dashboard,
+    /// The ingest metrics of the connection
+    Diagnostics,
+    /// The captures archived when clearing
+    Archive,
 }
 
-impl Default for TimeUnit {
+#[allow(clippy::derivable_impls)]
+impl Default for PlotPage {
     fn default() -> Self {
-        Self::S
+        Self::TimeValue
+    }
+}
+
+impl PlotPage {
+    pub const ALL: [PlotPage; 9] = [
+        PlotPage::TimeValue,
+        PlotPage::XY,
+        PlotPage::Trajectory,
+        PlotPage::SerialMonitor,
+        PlotPage::RawTraffic,
+        PlotPage::Spectrogram,
+        PlotPage::Dashboard,
+        PlotPage::Diagnostics,
+        PlotPage::Archive,
+    ];
+
+    /// If the page has settings that are shown in their own tab
+    pub fn has_controls(self) -> bool {
+        !matches!(self, PlotPage::SerialMonitor | PlotPage::RawTraffic)
     }
 }
 
-impl std::fmt::Display for TimeUnit {
+impl std::fmt::Display for PlotPage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TimeUnit::Us => write!(f, "us"),
-            TimeUnit::Ms => write!(f, "ms"),
-            TimeUnit::S => write!(f, "s"),
+            PlotPage::TimeValue => write!(f, "{}", tr("page-time-value")),
+            PlotPage::XY => write!(f, "{}", tr("page-xy")),
+            PlotPage::Trajectory => write!(f, "{}", tr("page-trajectory")),
+            PlotPage::SerialMonitor => write!(f, "{}", tr("page-serial-monitor")),
+            PlotPage::RawTraffic => write!(f, "{}", tr("page-raw-traffic")),
+            PlotPage::Spectrogram => write!(f, "{}", tr("page-spectrogram")),
+            PlotPage::Dashboard => write!(f, "{}", tr("page-dashboard")),
+            PlotPage::Diagnostics => write!(f, "{}", tr("page-diagnostics")),
+            PlotPage::Archive => write!(f, "{}", tr("page-archive")),
         }
     }
 }
 
-impl TimeUnit {
-    #[allow(unused)]
-    fn convert_from_secs(self, secs: f64) -> f64 {
+/// A tab of the dock area, dragged to rearrange and split the layout
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum DockTab {
+    /// The port, its settings and the connect button
+    Connection,
+    Page(PlotPage),
+    /// The settings of a page, e.g. the channel list of the time-value page
+    Controls(PlotPage),
+}
+
+impl std::fmt::Display for DockTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DockTab::Connection => write!(f, "{}", tr("tab-connection")),
+            DockTab::Page(page) => write!(f, "{page}"),
+            DockTab::Controls(page) => {
+                write!(f, "{}", tr_args("tab-page-controls", &[("page", page)]))
+            }
+        }
+    }
+}
+
+/// The connection controls above the time-value page and its controls
+pub fn default_dock_state() -> DockState<DockTab> {
+    let mut dock_state = DockState::new(vec![DockTab::Connection]);
+    let tree = dock_state.main_surface_mut();
+    let [_, page] = tree.split_below(
+        NodeIndex::root(),
+        0.1,
+        vec![DockTab::Page(PlotPage::TimeValue)],
+    );
+    tree.split_left(page, 0.22, vec![DockTab::Controls(PlotPage::TimeValue)]);
+    dock_state
+}
+
+/// The order of the channels in the channel list
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum ChannelOrder {
+    /// The order in which the channels were first received
+    #[default]
+    Arrival,
+    Name,
+    /// The most samples per second first
+    Activity,
+    /// The largest absolute current value first
+    Magnitude,
+}
+
+impl std::fmt::Display for ChannelOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TimeUnit::Us => secs * 1_000_000.0,
-            TimeUnit::Ms => secs * 1000.0,
-            TimeUnit::S => secs,
+            ChannelOrder::Arrival => write!(f, "Arrival"),
+            ChannelOrder::Name => write!(f, "Name"),
+            ChannelOrder::Activity => write!(f, "Activity"),
+            ChannelOrder::Magnitude => write!(f, "Magnitude"),
         }
     }
+}
+
+impl ChannelOrder {
+    pub const ALL: [Self; 4] = [Self::Arrival, Self::Name, Self::Activity, Self::Magnitude];
 
-    fn convert_to_secs(self, val: f64) -> f64 {
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
         match self {
-            TimeUnit::Us => val / 1_000_000.0,
-            TimeUnit::Ms => val / 1000.0,
-            TimeUnit::S => val,
+            ChannelOrder::Arrival => tr("channel-order-arrival"),
+            ChannelOrder::Name => tr("channel-order-name"),
+            ChannelOrder::Activity => tr("channel-order-activity"),
+            ChannelOrder::Magnitude => tr("channel-order-magnitude"),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum PlotPage {
-    TimeValue,
-    XY,
-    SerialMonitor,
+/// Actions executed when the app is started or a profile is loaded,
+/// so that it comes up capturing data without any interaction.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct StartupActions {
+    /// Select the port with this USB id (`VID:PID` in hex, e.g. `1a86:7523`) or this port name
+    port: String,
+    /// Connect to the selected port
+    auto_connect: bool,
+    /// Start recording once connected
+    auto_record: bool,
 }
 
-impl Default for PlotPage {
-    fn default() -> Self {
-        Self::TimeValue
+impl StartupActions {
+    fn is_active(&self) -> bool {
+        !self.port.trim().is_empty()
+    }
+
+    fn matches_port(&self, port: &PortInfo) -> bool {
+        let port_name = port.name.as_str();
+        let usb_id = port.usb_id();
+        let startup_port = self.port.trim();
+
+        if let Some((vid, pid)) = startup_port.split_once(':').and_then(|(vid, pid)| {
+            Some((
+                u16::from_str_radix(vid.trim(), 16).ok()?,
+                u16::from_str_radix(pid.trim(), 16).ok()?,
+            ))
+        }) {
+            usb_id == Some((vid, pid))
+        } else {
+            port_name == startup_port
+        }
     }
 }
 
-impl std::fmt::Display for PlotPage {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            PlotPage::TimeValue => write!(f, "Time - Value"),
-            PlotPage::XY => write!(f, "X - Y"),
-            PlotPage::SerialMonitor => write!(f, "Serial Monitor"),
+/// The settings used to open a connection
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionSettings {
+    port_index: usize,
+    baudrate: u32,
+    timeout: Duration,
+    data_bits: DataBits,
+    flow_control: FlowControl,
+    parity: Parity,
+    stop_bits: StopBits,
+}
+
+/// The colors of the X-Y pairs, in the order they are added
+const XY_PAIR_COLORS: [egui::Color32; 6] = [
+    egui::Color32::DARK_RED,
+    egui::Color32::LIGHT_BLUE,
+    egui::Color32::DARK_GREEN,
+    egui::Color32::GOLD,
+    egui::Color32::LIGHT_RED,
+    egui::Color32::BROWN,
+];
+
+/// Two channels plotted against each other on the X-Y page
+#[derive(Debug, Clone, Copy)]
+pub struct XyPair {
+    pub x: usize,
+    pub y: usize,
+    pub color: egui::Color32,
+    pub visible: bool,
+}
+
+impl XyPair {
+    /// A pair of the first channel, colored by its index
+    fn new(index: usize) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            color: XY_PAIR_COLORS[index % XY_PAIR_COLORS.len()],
+            visible: true,
         }
     }
 }
 
+/// A connection that failed while reading, e.g. because the cable was unplugged
+#[derive(Debug, Clone)]
+pub struct LostConnection {
+    settings: ConnectionSettings,
+    /// The port is found again by its name, its index might change when it is plugged in again
+    port_name: String,
+    /// The number of reconnection attempts so far
+    attempts: u32,
+    last_attempt: Instant,
+    /// The available ports are refreshed for the current attempt
+    ports_requested: bool,
+}
+
+impl LostConnection {
+    /// The delay before the next attempt, doubling with every attempt
+    fn backoff(&self) -> Duration {
+        (RECONNECT_MIN_DELAY * 2_u32.saturating_pow(self.attempts)).min(RECONNECT_MAX_DELAY)
+    }
+}
+
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 pub struct SplotApp {
     /// The baudrate
     baudrate: u32,
+    /// If a custom baudrate is entered instead of a preset
+    #[serde(skip)]
+    baudrate_custom: bool,
     /// The connection timeout
     timeout: Duration,
     /// Data bits
@@ -291,27 +825,222 @@ pub struct SplotApp {
 
     /// The unit used for received time values
     time_unit: TimeUnit,
+    /// Unwrap received time values that roll over or jump backwards, instead of plotting the jump
+    unwrap_device_time: bool,
+    /// Map the received time values onto the host clock with an estimated offset and drift,
+    /// so that they line up with the host time of the serial monitor
+    sync_device_time: bool,
     /// The value separator
     value_separator: char,
+    /// The format of the received data
+    data_format: DataFormat,
+    /// The packet layout used when the data format is binary
+    binary_layout: BinaryLayout,
+    /// The messages decoded when the data format is UBX
+    ubx_messages: Vec<UbxMessage>,
+    /// The signals mapped to channels when the data format is CAN
+    can_signals: Vec<CanSignal>,
+    /// Virtual channels computed from expressions over the received channels
+    derived_channels: Vec<DerivedChannel>,
+    /// The path of the last imported capture
+    import_path: String,
+    /// The file samples are exported to
+    export_path: String,
+    export_format: ExportFormat,
+    /// If the channel is exported, indexed by the channel index
+    #[serde(skip)]
+    export_channels: Vec<bool>,
+    /// Move the samples into the archive when clearing, instead of discarding them
+    archive_on_clear: bool,
+    #[serde(skip)]
+    archived: Vec<ArchivedCapture>,
+    /// The total number of archived captures, to label them
+    #[serde(skip)]
+    archived_count: usize,
+    #[serde(skip)]
+    archive_selected: usize,
+    /// The result of the last export of an archived capture
+    #[serde(skip)]
+    archive_status: Option<Result<String, String>>,
+    /// Saved captures drawn behind the live data of the Time-Value plot
+    #[serde(skip)]
+    references: Vec<ReferenceTrace>,
+    /// The path of the last loaded reference
+    reference_path: String,
+    /// The number of snapshots taken, to number their labels
+    #[serde(skip)]
+    snapshot_count: usize,
+    /// The error of the last loaded reference
+    #[serde(skip)]
+    reference_error: Option<String>,
+    /// Named horizontal lines in the Time-Value plot
+    guide_lines: Vec<GuideLine>,
+    /// The two captures that are compared
+    session_diff_paths: [String; 2],
+    session_diff_alignment: DiffAlignment,
+    /// The channel and level of the trigger alignment
+    session_diff_trigger_channel: usize,
+    session_diff_trigger_level: f64,
+    /// The file the received data is recorded into
+    record_path: String,
+    /// The directory session files are created in, and captures are listed from
+    capture_dir: String,
+    /// The name of new session files, see `capture::PLACEHOLDERS`
+    session_name_template: String,
+    /// Name recordings, exports and debug bundles after the template, instead of using the given files
+    auto_session_names: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    captures: Vec<capture::CaptureFile>,
+    /// The result of the last listing or opening of a capture, displayed in the captures window
+    #[serde(skip)]
+    captures_status: Option<Result<String, String>>,
+    /// Applied to the channels when they are created
+    appearance_preset: AppearancePreset,
+    /// The file appearance presets are exported to and imported from
+    appearance_preset_path: String,
+    /// The appearance of every channel seen so far, keyed by the name the channel was created with.
+    /// Restored when a channel with that name appears again.
+    channel_appearances: BTreeMap<String, ChannelAppearance>,
+    connection_profiles: Vec<ConnectionProfile>,
+    /// The name of the last loaded or saved profile
+    active_profile: String,
+    /// The name the current settings are saved as
+    #[serde(skip)]
+    new_profile_name: String,
+    /// The file debug bundles are written to
+    debug_bundle_path: String,
+    /// Replaces port names in the debug bundle
+    debug_bundle_redact_ports: bool,
+    /// Spill samples evicted from the in-memory buffers to temporary files, to keep the whole capture
+    #[cfg(not(target_arch = "wasm32"))]
+    history_spill: bool,
+    startup_actions: StartupActions,
+    /// Transmitted right after connecting
+    init_commands: Vec<InitCommand>,
+    init_commands_line_ending: LineEnding,
+    /// Actions that run when the received data matches, or periodically
+    automation_rules: Vec<AutomationRule>,
+    /// The Rhai script that complements the rules
+    automation_script: AutomationScript,
+    /// Flash the taskbar icon when an automation rule notifies
+    notify_flash_taskbar: bool,
+    /// Limits of the channels that raise an alarm when they are violated
+    alarms: Vec<AlarmRule>,
+    /// The raised and cleared alarms, the newest last
+    #[serde(skip)]
+    alarm_events: Vec<AlarmEvent>,
+    /// The texts of the raised alarms that are shown as notification
+    #[serde(skip)]
+    alarm_notifications: Vec<String>,
+    /// The line ending appended to text sent from the serial monitor
+    send_line_ending: LineEnding,
+    /// Shows the sent text in the serial monitor
+    send_local_echo: bool,
+    /// Retry the lost connection with the same port and settings
+    auto_reconnect: bool,
+    serial_monitor_timestamp: MonitorTimestamp,
+    /// Shows the raw received bytes as hex dump instead of the lines
+    serial_monitor_hex: bool,
+    /// Highlights CR and LF on the raw traffic page
+    raw_traffic_highlight: bool,
+    /// Renders ANSI color escape sequences instead of showing them literally
+    serial_monitor_ansi: bool,
+    /// Shows the lines split into columns by the value separator
+    serial_monitor_table: bool,
+    theme: Theme,
+    /// The language of the user interface
+    language: Language,
     /// if the dummy connection should be used
     /// ( not available with demo feature, there the dummy connection is always used )
     #[cfg(not(feature = "demo"))]
     dummy_connection: bool,
+    /// The script of the scripted dummy device
+    device_script: String,
+    /// The path of the last loaded device script
+    device_script_path: String,
 
     #[serde(skip)]
     serial_connection: Rc<Mutex<Box<dyn SerialConnection>>>,
     #[serde(skip)]
     start_time: Instant,
+    /// The received channels, followed by the derived channels
     #[serde(skip)]
     samples_vec: Vec<FixedSizeBuffer<Sample>>,
+    /// The min / max summaries of the channels, for zoomed out views
+    #[serde(skip)]
+    samples_summaries: Vec<MinMaxSummary>,
+    /// The number of received channels in `samples_vec`
+    #[serde(skip)]
+    n_raw_samples: usize,
     #[serde(skip)]
     samples_received: u64,
     /// The parser has internal state
     #[serde(skip)]
     parser: Parser,
+    /// Reused for every parse
+    #[serde(skip)]
+    parse_result: ParseResult,
+    /// Parses off the main thread in the web build, when the worker could be started
+    #[cfg(target_arch = "wasm32")]
+    #[serde(skip)]
+    parse_worker: Option<parseworker::ParseWorker>,
     /// pause reading the serial connection
     #[serde(skip)]
     pause: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    recorder: Option<recorder::Recorder>,
+    /// Writes the samples around a trigger condition to a file
+    #[cfg(not(target_arch = "wasm32"))]
+    conditional_capture: conditionalcapture::ConditionalCapture,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    conditional_capture_error: Option<String>,
+    /// Serve the samples over HTTP on localhost
+    http_api_enabled: bool,
+    http_api_port: u16,
+    /// The websites that may request the data of the HTTP API and the WebSocket, separated by commas
+    data_server_origins: String,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    http_api: Option<httpapi::HttpApi>,
+    #[serde(skip)]
+    http_api_error: Option<String>,
+    /// Broadcast every parsed sample over a WebSocket on localhost
+    sample_broadcast_enabled: bool,
+    sample_broadcast_port: u16,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    sample_broadcast: Option<websocket::Broadcast>,
+    #[serde(skip)]
+    sample_broadcast_error: Option<String>,
+    #[serde(skip)]
+    show_data_servers_window: bool,
+    /// The spilled samples of the received channels, when the history is spilled
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    history: Option<history::SpilledHistory>,
+    /// When the available ports were last requested
+    #[serde(skip)]
+    ports_last_refresh: Instant,
+    /// The connection that was lost and is reconnected to, if enabled
+    #[serde(skip)]
+    lost_connection: Option<LostConnection>,
+    /// The startup actions are not yet completed
+    #[serde(skip)]
+    startup_pending: bool,
+    #[serde(skip)]
+    startup_last_attempt: Instant,
+    /// Start recording when the connection of the startup actions succeeds
+    #[serde(skip)]
+    startup_record_pending: bool,
+    /// The index of the next init command to transmit
+    #[serde(skip)]
+    init_commands_next: Option<usize>,
+    /// When the connection was established or the last init command was transmitted
+    #[serde(skip)]
+    init_commands_last: Instant,
 
     // Ui state
     #[serde(skip)]
@@ -321,38 +1050,207 @@ pub struct SplotApp {
     #[serde(skip)]
     show_help_window: bool,
     #[serde(skip)]
+    show_binary_layout_window: bool,
+    #[serde(skip)]
+    show_ubx_messages_window: bool,
+    #[serde(skip)]
+    show_can_signals_window: bool,
+    #[serde(skip)]
+    show_derived_channels_window: bool,
+    #[serde(skip)]
+    show_import_window: bool,
+    #[serde(skip)]
+    show_export_window: bool,
+    #[serde(skip)]
+    show_startup_window: bool,
+    #[serde(skip)]
+    show_init_commands_window: bool,
+    #[serde(skip)]
+    show_appearance_window: bool,
+    #[serde(skip)]
+    show_automation_window: bool,
+    #[serde(skip)]
+    show_alarms_window: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    show_conditional_capture_window: bool,
+    #[serde(skip)]
+    show_profiles_window: bool,
+    #[serde(skip)]
+    show_session_diff_window: bool,
+    /// The result of the last comparison of two captures
+    #[serde(skip)]
+    session_diff: Option<Result<SessionDiff, String>>,
+    #[serde(skip)]
+    show_debug_bundle_window: bool,
+    #[serde(skip)]
+    show_acquisition_window: bool,
+    #[serde(skip)]
+    show_device_script_window: bool,
+    #[serde(skip)]
+    show_captures_window: bool,
+    #[serde(skip)]
+    show_appearance_preset_window: bool,
+    /// The result of the last preset export or import, displayed in its window
+    #[serde(skip)]
+    appearance_preset_status: Option<Result<String, String>>,
+    /// The result of the last created debug bundle, displayed in its window
+    #[serde(skip)]
+    debug_bundle_status: Option<Result<String, String>>,
+    /// The area of the displayed pages, cropped out of screenshots
+    #[serde(skip)]
+    pages_rect: egui::Rect,
+    /// A screenshot was requested to copy the pages to the clipboard
+    #[serde(skip)]
+    screenshot_pending: bool,
+    #[serde(skip)]
+    copy_image_status: Option<Result<String, String>>,
+    /// The theme and dark mode the style was last updated with
+    #[serde(skip)]
+    theme_applied: Option<(Theme, bool)>,
+    /// The result of the last import, displayed in the import window
+    #[serde(skip)]
+    import_status: Option<Result<String, String>>,
+    /// The result of the last export, displayed in its window
+    #[serde(skip)]
+    export_status: Option<Result<String, String>>,
+    #[serde(skip)]
     selected_port_index: Option<usize>,
     #[serde(skip)]
-    serial_monitor_lines: FixedSizeBuffer<String>,
+    serial_monitor_lines: FixedSizeBuffer<MonitorLine>,
+    /// The concatenated monitor lines with the timestamp mode they were formatted with.
+    /// Reset when the lines change
     #[serde(skip)]
-    samples_appearance: Vec<SamplesAppearance>,
+    serial_monitor_text: Option<(MonitorTimestamp, String)>,
+    /// The latest raw received bytes
     #[serde(skip)]
-    plot_page: PlotPage,
-    /// Only display measurements this far back
+    serial_monitor_bytes: FixedSizeBuffer<u8>,
+    /// The total number of received bytes
     #[serde(skip)]
-    plot_tv_newer: f64,
+    bytes_received: u64,
     #[serde(skip)]
-    plot_tv_bounds: egui_plot::PlotBounds,
-
+    ingest_stats: IngestStats,
     #[serde(skip)]
-    plot_xy_samples_x: usize,
+    throughput: Throughput,
     #[serde(skip)]
-    plot_xy_samples_y: usize,
-    /// Only display measurements this far back
+    acquisition_stats: AcquisitionStats,
     #[serde(skip)]
-    plot_xy_newer: f64,
-
-    // Async state
+    markers: FixedSizeBuffer<Marker>,
+    /// The values of fields that are texts, shown in lanes under the Time-Value plot
     #[serde(skip)]
-    promise_available_ports: Option<poll_promise::Promise<Vec<String>>>,
+    text_events: FixedSizeBuffer<TextEvent>,
+    /// The names of the fields of the text events, in the order they were first received
     #[serde(skip)]
-    promise_try_connect: Option<poll_promise::Promise<anyhow::Result<()>>>,
+    event_lanes: Vec<String>,
+    /// The text of the send box in the serial monitor
     #[serde(skip)]
-    promise_read: Option<poll_promise::Promise<anyhow::Result<Vec<u8>>>>,
+    send_text: String,
     #[serde(skip)]
-    is_connected: bool,
+    samples_appearance: Vec<SamplesAppearance>,
+    /// The layout of the pages and the connection controls
+    dock_state: DockState<DockTab>,
+    /// Only display measurements this far back
     #[serde(skip)]
-    available_ports: Vec<String>,
+    plot_tv_newer: f64,
+    /// How far back from the latest sample the plot ends, in seconds
+    #[serde(skip)]
+    plot_tv_scroll_back: f64,
+    #[serde(skip)]
+    plot_tv_bounds: egui_plot::PlotBounds,
+    /// While paused, the view is released for panning and zooming once it was placed at the followed view
+    #[serde(skip)]
+    plot_tv_view_released: bool,
+    /// The transform of the right Y axis in the last frame, when channels are assigned to it
+    #[serde(skip)]
+    plot_tv_right_axis: Option<AxisTransform>,
+    /// The time that is displayed and exported as t=0, in nanoseconds since the connection start.
+    /// The stored samples are not changed.
+    #[serde(skip)]
+    time_zero: i64,
+    /// The time the context menu of the plot was opened at
+    #[serde(skip)]
+    plot_tv_menu_time: Option<i64>,
+    plot_tv_tracking_cursor: bool,
+    /// Shows the coordinates of the pointer at the plot edges
+    plot_crosshair: bool,
+    plot_tv_log_y: bool,
+    plot_tv_y_bounds: YBounds,
+    plot_tv_downsampling: Downsampling,
+    /// The maximum number of points rendered per line when downsampling
+    plot_tv_point_budget: usize,
+    plot_tv_render_budget: RenderBudget,
+    /// Breaks the lines where no samples arrived for longer than the gap threshold
+    plot_tv_break_gaps: bool,
+    /// The gap threshold in seconds
+    plot_tv_gap_threshold: f64,
+    plot_tv_shade_gaps: bool,
+    /// Each channel in its own plot, stacked with a shared time axis
+    plot_tv_stacked: bool,
+    /// How values are written in the plot labels, axes and readouts
+    value_format: ValueFormat,
+    channel_order: ChannelOrder,
+    #[serde(skip)]
+    trigger: Trigger,
+    #[serde(skip)]
+    cursors: MeasurementCursors,
+
+    #[serde(skip)]
+    plot_xy_pairs: Vec<XyPair>,
+    /// Only display measurements this far back
+    #[serde(skip)]
+    plot_xy_newer: f64,
+    plot_xy_log_x: bool,
+    plot_xy_log_y: bool,
+    /// Fade out the older parts of the trails, so that the direction of the motion is visible
+    plot_xy_fade: bool,
+    plot_xy_render_budget: RenderBudget,
+
+    /// The channels of the X, Y and Z axes of the 3D trajectory
+    #[serde(skip)]
+    plot_3d_samples: [usize; 3],
+    /// Only display measurements this far back
+    #[serde(skip)]
+    plot_3d_newer: f64,
+    plot_3d_camera: OrbitCamera,
+
+    dashboard_widgets: Vec<DashboardWidget>,
+    plot_xy_y_bounds: YBounds,
+
+    #[serde(skip)]
+    spectrogram_samples: usize,
+    /// The number of samples per FFT, a power of two
+    #[serde(skip)]
+    spectrogram_fft_size: usize,
+    /// The displayed range of amplitudes in dB, below the maximum amplitude
+    #[serde(skip)]
+    spectrogram_dynamic_range: f64,
+    #[serde(skip)]
+    spectrogram_texture: Option<egui::TextureHandle>,
+
+    // Async state
+    #[serde(skip)]
+    promise_available_ports: Option<poll_promise::Promise<Vec<PortInfo>>>,
+    #[serde(skip)]
+    promise_try_connect: Option<poll_promise::Promise<anyhow::Result<bool>>>,
+    #[serde(skip)]
+    promise_read: Option<poll_promise::Promise<anyhow::Result<Vec<u8>>>>,
+    #[serde(skip)]
+    promise_write: Option<poll_promise::Promise<anyhow::Result<()>>>,
+    #[serde(skip)]
+    promise_close: Option<poll_promise::Promise<anyhow::Result<()>>>,
+    #[serde(skip)]
+    is_connected: bool,
+    /// The error of the last connection attempt
+    #[serde(skip)]
+    connection_error: Option<String>,
+    /// The settings of the current connection, to restore them when editing is canceled
+    #[serde(skip)]
+    connected_settings: Option<ConnectionSettings>,
+    /// Unlocks the connection controls while connected, for reconnecting with changed settings
+    #[serde(skip)]
+    edit_connection: bool,
+    #[serde(skip)]
+    available_ports: Vec<PortInfo>,
 }
 
 impl Default for SplotApp {
@@ -362,6 +1260,7 @@ impl Default for SplotApp {
 
         Self {
             baudrate: 115200,
+            baudrate_custom: false,
             timeout: Duration::from_millis(5000),
             data_bits: DataBits::default(),
             flow_control: FlowControl::default(),
@@ -369,35 +1268,207 @@ impl Default for SplotApp {
             stop_bits: StopBits::default(),
 
             time_unit: TimeUnit::default(),
+            unwrap_device_time: true,
+            sync_device_time: false,
             value_separator: ',',
+            data_format: DataFormat::default(),
+            binary_layout: BinaryLayout::default(),
+            ubx_messages: vec![UbxMessage::NavPvt],
+            can_signals: vec![CanSignal::default()],
+            derived_channels: vec![],
+            import_path: String::new(),
+            export_path: String::from("splot_export.csv"),
+            export_format: ExportFormat::default(),
+            export_channels: vec![],
+            archive_on_clear: false,
+            archived: vec![],
+            archived_count: 0,
+            archive_selected: 0,
+            archive_status: None,
+            references: vec![],
+            reference_path: String::new(),
+            snapshot_count: 0,
+            reference_error: None,
+            guide_lines: vec![],
+            session_diff_paths: [String::new(), String::new()],
+            session_diff_alignment: DiffAlignment::default(),
+            session_diff_trigger_channel: 0,
+            session_diff_trigger_level: 0.0,
+            record_path: String::from("splot_capture.log"),
+            capture_dir: String::from("captures"),
+            session_name_template: String::from("{date}_{time}_{port}"),
+            auto_session_names: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            captures: vec![],
+            captures_status: None,
+            appearance_preset: AppearancePreset::default(),
+            appearance_preset_path: String::from("splot_appearance.ron"),
+            channel_appearances: BTreeMap::new(),
+            connection_profiles: vec![],
+            active_profile: String::new(),
+            new_profile_name: String::new(),
+            debug_bundle_path: String::from("splot_debug_bundle.zip"),
+            debug_bundle_redact_ports: true,
+            #[cfg(not(target_arch = "wasm32"))]
+            history_spill: false,
+            startup_actions: StartupActions::default(),
+            init_commands: vec![],
+            init_commands_line_ending: LineEnding::default(),
+            automation_rules: vec![],
+            automation_script: AutomationScript::default(),
+            alarms: vec![],
+            alarm_events: vec![],
+            alarm_notifications: vec![],
+            notify_flash_taskbar: true,
+            send_line_ending: LineEnding::default(),
+            send_local_echo: true,
+            auto_reconnect: true,
+            serial_monitor_timestamp: MonitorTimestamp::default(),
+            serial_monitor_hex: false,
+            raw_traffic_highlight: true,
+            serial_monitor_ansi: true,
+            serial_monitor_table: false,
+            theme: Theme::default(),
+            language: Language::default(),
             #[cfg(not(feature = "demo"))]
             dummy_connection: false,
+            device_script: String::from(DEFAULT_DEVICE_SCRIPT),
+            device_script_path: String::new(),
 
             serial_connection,
             start_time: now,
             samples_vec: vec![],
+            samples_summaries: vec![],
+            n_raw_samples: 0,
             samples_received: 0,
             parser: Parser::default(),
+            parse_result: ParseResult::default(),
+            #[cfg(target_arch = "wasm32")]
+            parse_worker: None,
             pause: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            recorder: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            conditional_capture: conditionalcapture::ConditionalCapture::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            conditional_capture_error: None,
+            http_api_enabled: false,
+            http_api_port: 7878,
+            data_server_origins: String::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            http_api: None,
+            http_api_error: None,
+            sample_broadcast_enabled: false,
+            sample_broadcast_port: 7879,
+            #[cfg(not(target_arch = "wasm32"))]
+            sample_broadcast: None,
+            sample_broadcast_error: None,
+            show_data_servers_window: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            history: None,
+            ports_last_refresh: now,
+            lost_connection: None,
+            startup_pending: false,
+            startup_last_attempt: now,
+            startup_record_pending: false,
+            init_commands_next: None,
+            init_commands_last: now,
 
             show_about_window: false,
             show_usage_window: false,
             show_help_window: false,
+            show_binary_layout_window: false,
+            show_ubx_messages_window: false,
+            show_can_signals_window: false,
+            show_derived_channels_window: false,
+            show_import_window: false,
+            show_export_window: false,
+            show_startup_window: false,
+            show_init_commands_window: false,
+            show_appearance_window: false,
+            show_automation_window: false,
+            show_alarms_window: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            show_conditional_capture_window: false,
+            show_profiles_window: false,
+            show_session_diff_window: false,
+            session_diff: None,
+            show_debug_bundle_window: false,
+            show_acquisition_window: false,
+            show_device_script_window: false,
+            show_captures_window: false,
+            show_appearance_preset_window: false,
+            appearance_preset_status: None,
+            debug_bundle_status: None,
+            pages_rect: egui::Rect::NOTHING,
+            screenshot_pending: false,
+            copy_image_status: None,
+            theme_applied: None,
+            import_status: None,
+            export_status: None,
             selected_port_index: None,
             serial_monitor_lines: FixedSizeBuffer::new(MONITOR_LINES_BUF_SIZE),
+            serial_monitor_text: None,
+            serial_monitor_bytes: FixedSizeBuffer::new(MONITOR_BYTES_BUF_SIZE),
+            bytes_received: 0,
+            ingest_stats: IngestStats::default(),
+            throughput: Throughput::default(),
+            acquisition_stats: AcquisitionStats::default(),
+            markers: FixedSizeBuffer::new(MARKERS_BUF_SIZE),
+            text_events: FixedSizeBuffer::new(TEXT_EVENTS_BUF_SIZE),
+            event_lanes: vec![],
+            send_text: String::new(),
             samples_appearance: vec![],
-            plot_page: PlotPage::default(),
+            dock_state: default_dock_state(),
             plot_tv_newer: 10.0,
+            plot_tv_scroll_back: 0.0,
             plot_tv_bounds: egui_plot::PlotBounds::NOTHING,
-
-            plot_xy_samples_x: 0,
-            plot_xy_samples_y: 0,
+            plot_tv_view_released: false,
+            plot_tv_right_axis: None,
+            plot_tv_tracking_cursor: false,
+            time_zero: 0,
+            plot_tv_menu_time: None,
+            plot_crosshair: false,
+            plot_tv_log_y: false,
+            plot_tv_downsampling: Downsampling::default(),
+            plot_tv_point_budget: 4000,
+            plot_tv_render_budget: RenderBudget::default(),
+            plot_tv_break_gaps: false,
+            plot_tv_gap_threshold: 1.0,
+            plot_tv_shade_gaps: false,
+            plot_tv_stacked: false,
+            value_format: ValueFormat::default(),
+            plot_tv_y_bounds: YBounds::default(),
+            channel_order: ChannelOrder::default(),
+            trigger: Trigger::default(),
+            cursors: MeasurementCursors::default(),
+
+            plot_xy_pairs: vec![XyPair::new(0)],
             plot_xy_newer: 10.0,
+            plot_xy_log_x: false,
+            plot_xy_log_y: false,
+            plot_xy_fade: true,
+            plot_xy_render_budget: RenderBudget::default(),
+            plot_3d_samples: [0, 1, 2],
+            plot_3d_newer: 10.0,
+            plot_3d_camera: OrbitCamera::default(),
+            dashboard_widgets: vec![],
+            plot_xy_y_bounds: YBounds::default(),
+
+            spectrogram_samples: 0,
+            spectrogram_fft_size: 256,
+            spectrogram_dynamic_range: 60.0,
+            spectrogram_texture: None,
 
             promise_available_ports: None,
             promise_try_connect: None,
             promise_read: None,
+            promise_write: None,
+            promise_close: None,
             is_connected: false,
+            connection_error: None,
+            connected_settings: None,
+            edit_connection: false,
             available_ports: vec![],
         }
     }
@@ -422,30 +1493,116 @@ impl SplotApp {
 
     /// Some things need to be set up at runtime
     pub fn setup(&mut self, ctx: &egui::Context) {
+        self.startup_pending = self.startup_actions.is_active()
+            || self
+                .connection_profiles
+                .iter()
+                .any(|p| p.startup_actions.is_active());
         self.reset_connection(ctx);
         egui_extras::install_image_loaders(ctx);
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.parse_worker = parseworker::ParseWorker::new(ctx)
+                .map_err(|e| log::warn!("parsing on the main thread, Err: {e}"))
+                .ok();
+        }
+    }
+
+    /// Moves the current samples into the archive, read-only for comparison and export
+    pub fn archive_samples(&mut self) {
+        if self.samples_vec.iter().all(|s| s.is_empty()) {
+            return;
+        }
+        self.archived_count += 1;
+
+        let capture = ImportedCapture {
+            names: self
+                .samples_appearance
+                .iter()
+                .map(|a| a.name.clone())
+                .collect(),
+            units: self
+                .samples_appearance
+                .iter()
+                .map(|a| a.unit.clone())
+                .collect(),
+            samples_vec: self
+                .samples_vec
+                .iter()
+                .map(|s| s.iter().cloned().collect())
+                .collect(),
+        };
+
+        if self.archived.len() >= archive::MAX_ARCHIVED {
+            self.archived.remove(0);
+        }
+        self.archived.push(ArchivedCapture {
+            label: tr_args("archive-run", &[("n", &self.archived_count)]),
+            capture,
+            time_zero: self.time_zero,
+        });
+        self.archive_selected = self.archived.len() - 1;
     }
 
     #[allow(unused)]
     pub fn clear_samples(&mut self, ctx: &egui::Context) {
         self.samples_received = 0;
+        self.n_raw_samples = 0;
         self.samples_vec.clear();
+        self.samples_summaries.clear();
+        self.parser.reset_channels();
+        #[cfg(target_arch = "wasm32")]
+        if let Some(parse_worker) = self.parse_worker.as_mut() {
+            parse_worker.reset_channels();
+        }
+        self.remember_channel_appearances();
         self.samples_appearance.clear();
+        self.export_channels.clear();
         self.serial_monitor_lines.clear();
+        self.serial_monitor_text.take();
+        self.serial_monitor_bytes.clear();
+        self.bytes_received = 0;
+        self.ingest_stats.clear();
+        self.throughput.clear();
+        self.acquisition_stats.clear();
+        self.markers.clear();
+        self.text_events.clear();
+        self.event_lanes.clear();
+        #[cfg(not(target_arch = "wasm32"))]
+        self.history.take();
+        self.plot_tv_scroll_back = 0.0;
+        self.time_zero = 0;
+        self.sync_derived_samples();
+        self.trigger.rearm();
+        self.cursors.reset();
+        for alarm in self.alarms.iter_mut() {
+            alarm.reset();
+        }
     }
 
     pub fn reset_connection(&mut self, ctx: &egui::Context) {
         self.clear_samples(ctx);
-        self.parser.clear();
+        self.clear_parser();
 
         self.selected_port_index.take();
         self.available_ports.clear();
-        self.plot_xy_samples_x = 0;
-        self.plot_xy_samples_y = 0;
+        self.plot_xy_pairs = vec![XyPair::new(0)];
+        self.plot_3d_samples = [0, 1, 2];
+        self.spectrogram_samples = 0;
 
         self.promise_available_ports.take();
         self.promise_try_connect.take();
         self.promise_read.take();
+        self.promise_write.take();
+        self.promise_close.take();
+
+        self.is_connected = false;
+        self.connection_error.take();
+        self.lost_connection.take();
+        self.init_commands_next.take();
+        self.connected_settings.take();
+        self.edit_connection = false;
 
         #[cfg(feature = "demo")]
         {
@@ -455,7 +1612,7 @@ impl SplotApp {
 
         #[cfg(not(feature = "demo"))]
         if self.dummy_connection {
-            self.serial_connection = Rc::new(Mutex::new(new_serial_connection_dummy()));
+            self.serial_connection = Rc::new(Mutex::new(self.new_dummy_connection()));
         } else {
             self.serial_connection = Rc::new(Mutex::new(new_serial_connection()));
         }
@@ -466,9 +1623,21 @@ impl SplotApp {
         self.read(ctx);
     }
 
+    /// The dummy connection, with the scripted device when the device script is valid
+    #[allow(unused)]
+    fn new_dummy_connection(&self) -> Box<dyn SerialConnection> {
+        match DeviceScript::parse(&self.device_script) {
+            Ok(script) if !self.device_script.trim().is_empty() => {
+                new_serial_connection_dummy_script(script)
+            }
+            _ => new_serial_connection_dummy(),
+        }
+    }
+
     /// Installs the available_ports promise and polls for its readiness
     fn available_ports(&mut self, ctx: &egui::Context) {
         let c = Rc::clone(&self.serial_connection);
+        self.ports_last_refresh = Instant::now();
 
         let _ = self.promise_available_ports.get_or_insert_with(move || {
             poll_promise::Promise::spawn_local(
@@ -479,11 +1648,23 @@ impl SplotApp {
         self.poll_available_ports(ctx);
     }
 
+    /// Checks if the baudrate is supported by the selected connection
+    pub fn validate_baudrate(&self) -> anyhow::Result<()> {
+        #[cfg(not(feature = "demo"))]
+        let dummy = self.dummy_connection;
+
+        #[cfg(feature = "demo")]
+        let dummy = true;
+
+        validate_baudrate(self.baudrate, dummy)
+    }
+
     /// Installs the try_connect promise and polls for its readiness
     pub fn try_connect(&mut self, ctx: &egui::Context) {
         let c = Rc::clone(&self.serial_connection);
 
         if let Some(selected_port_index) = self.selected_port_index {
+            let baudrate_valid = self.validate_baudrate();
             let baudrate = self.baudrate;
             let timeout = self.timeout;
             let data_bits = self.data_bits;
@@ -491,21 +1672,35 @@ impl SplotApp {
             let parity = self.parity;
             let stop_bits = self.stop_bits;
 
+            self.connected_settings = Some(ConnectionSettings {
+                port_index: selected_port_index,
+                baudrate,
+                timeout,
+                data_bits,
+                flow_control,
+                parity,
+                stop_bits,
+            });
+
             // try connect
             let _ = self.promise_try_connect.get_or_insert_with(|| {
                 poll_promise::Promise::spawn_local(async move {
-                    c.lock()
-                        .await
-                        .try_connect(
-                            selected_port_index,
-                            baudrate,
-                            timeout,
-                            data_bits,
-                            flow_control,
-                            parity,
-                            stop_bits,
-                        )
-                        .await
+                    baudrate_valid?;
+
+                    let mut c = c.lock().await;
+
+                    c.try_connect(
+                        selected_port_index,
+                        baudrate,
+                        timeout,
+                        data_bits,
+                        flow_control,
+                        parity,
+                        stop_bits,
+                    )
+                    .await?;
+
+                    Ok(c.is_connected())
                 })
             });
 
@@ -513,15 +1708,51 @@ impl SplotApp {
         }
     }
 
+    /// Installs the close promise and stops reconnecting to a lost connection
+    pub fn disconnect(&mut self, ctx: &egui::Context) {
+        self.is_connected = false;
+        self.connection_error.take();
+        self.lost_connection.take();
+        self.init_commands_next.take();
+        self.connected_settings.take();
+        self.edit_connection = false;
+
+        let c = Rc::clone(&self.serial_connection);
+
+        // Waits for a pending read to finish before closing
+        let _ = self.promise_close.get_or_insert_with(|| {
+            poll_promise::Promise::spawn_local(async move { c.lock().await.close().await })
+        });
+
+        self.poll_close(ctx);
+    }
+
+    /// The name of the connected port
+    pub fn connected_port_name(&self) -> Option<&str> {
+        self.connected_settings
+            .and_then(|s| self.available_ports.get(s.port_index))
+            .map(|p| p.name.as_str())
+    }
+
     /// Installs the read promise and polls for its readiness
     fn read(&mut self, ctx: &egui::Context) {
         let c = Rc::clone(&self.serial_connection);
 
+        if self.promise_read.is_none() {
+            self.acquisition_stats.read_started();
+        }
+
         // read from serial port
         let _ = self.promise_read.get_or_insert_with(move || {
             poll_promise::Promise::spawn_local(async move {
                 if c.lock().await.is_connected() {
-                    c.lock().await.read(READ_BUF_SIZE).await
+                    let res = c.lock().await.read(READ_BUF_SIZE).await;
+
+                    // The device is gone, closing the port stops further failing reads
+                    if res.is_err() {
+                        let _ = c.lock().await.close().await;
+                    }
+                    res
                 } else {
                     Ok(vec![])
                 }
@@ -531,136 +1762,1990 @@ impl SplotApp {
         self.poll_read(ctx);
     }
 
+    /// Installs the write promise and polls for its readiness.
+    /// Returns false when a write is still in progress.
+    pub fn write(&mut self, ctx: &egui::Context, data: Vec<u8>) -> bool {
+        if self.promise_write.is_some() {
+            return false;
+        }
+
+        let c = Rc::clone(&self.serial_connection);
+
+        self.promise_write = Some(poll_promise::Promise::spawn_local(async move {
+            c.lock().await.write(&data).await
+        }));
+
+        self.poll_write(ctx);
+        true
+    }
+
+    /// Sends the text of the send box with the selected line ending
+    pub fn send_text(&mut self, ctx: &egui::Context) {
+        let data = self.send_line_ending.terminate(&self.send_text);
+
+        if !self.write(ctx, data) {
+            log::warn!("can't send text, a write is still in progress");
+            return;
+        }
+
+        let text = std::mem::take(&mut self.send_text);
+        if self.send_local_echo {
+            self.serial_monitor_lines
+                .add(MonitorLine::now(self.start_time, format!("> {text}\n")));
+            self.serial_monitor_text.take();
+        }
+    }
+
+    /// Transmits the init commands one after another, each after its delay
+    fn send_init_commands(&mut self, ctx: &egui::Context) {
+        let Some(i) = self.init_commands_next else {
+            return;
+        };
+        let Some(init_command) = self.init_commands.get(i) else {
+            self.init_commands_next = None;
+            return;
+        };
+
+        if !self.is_connected
+            || self.promise_write.is_some()
+            || self.init_commands_last.elapsed() < Duration::from_millis(init_command.delay_ms)
+        {
+            return;
+        }
+
+        let data = self
+            .init_commands_line_ending
+            .terminate(&init_command.command);
+
+        log::debug!("sending init command `{}`", init_command.command);
+
+        if self.write(ctx, data) {
+            self.init_commands_next = Some(i + 1);
+            self.init_commands_last = Instant::now();
+        }
+    }
+
+    /// Evaluates the conditions of the automation rules with the latest values of the channels.
+    /// Returns the actions of the rules that fired.
+    fn check_automation_conditions(&mut self) -> Vec<(RuleAction, String)> {
+        let names = self
+            .samples_appearance
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<&str>>();
+        let values = self
+            .samples_vec
+            .iter()
+            .map(|s| s.last().map(|s| s.value))
+            .collect::<Vec<Option<f64>>>();
+
+        self.automation_rules
+            .iter_mut()
+            .filter_map(|rule| {
+                rule.check_condition(&values, &names)
+                    .then(|| (rule.action, rule.argument.clone()))
+            })
+            .collect()
+    }
+
+    /// Calls the `on_samples()` hook of the automation script with the latest values of the channels
+    fn check_script_samples(&mut self) -> Vec<ScriptOutput> {
+        let names = self
+            .samples_appearance
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<&str>>();
+        let values = self
+            .samples_vec
+            .iter()
+            .map(|s| s.last().map(|s| s.value))
+            .collect::<Vec<Option<f64>>>();
+
+        self.automation_script.on_samples(&names, &values)
+    }
+
+    fn run_script_outputs(&mut self, ctx: &egui::Context, outputs: Vec<ScriptOutput>) {
+        for output in outputs {
+            match output {
+                ScriptOutput::Action(action, argument) => {
+                    self.run_automation_action(ctx, action, &argument)
+                }
+                ScriptOutput::Derive(name, expression) => self.derive_from_script(name, expression),
+            }
+        }
+    }
+
+    /// Creates the derived channel requested by the automation script, or changes its expression
+    fn derive_from_script(&mut self, name: String, expression: String) {
+        match self.derived_channels.iter().position(|d| d.name == name) {
+            Some(i) => {
+                let derived = &mut self.derived_channels[i];
+                if derived.kind == DerivedKind::Expression && derived.expression == expression {
+                    return;
+                }
+
+                log::debug!("automation script changes derived channel `{name}` to `{expression}`");
+                derived.kind = DerivedKind::Expression;
+                derived.expression = expression;
+                self.apply_derived_edit(DerivedEdit::Changed(i));
+            }
+            None => {
+                log::debug!("automation script creates derived channel `{name}` as `{expression}`");
+                self.derived_channels
+                    .push(DerivedChannel::new(name, expression));
+                self.apply_derived_edit(DerivedEdit::Added);
+            }
+        }
+    }
+
+    fn run_automation_action(&mut self, ctx: &egui::Context, action: RuleAction, argument: &str) {
+        log::debug!("running automation action `{action}` with `{argument}`");
+
+        match action {
+            RuleAction::Send => {
+                let data = self.send_line_ending.terminate(argument);
+
+                if !self.write(ctx, data) {
+                    log::warn!("automation can't send `{argument}`, a write is still in progress");
+                }
+            }
+            RuleAction::Marker => {
+                self.markers.add(Marker {
+                    time: Instant::now().duration_since(self.start_time).as_nanos() as i64,
+                    label: argument.to_string(),
+                });
+            }
+            RuleAction::Notify => self.notify(ctx, argument),
+            #[cfg(not(target_arch = "wasm32"))]
+            RuleAction::StartRecording => {
+                if self.recorder.is_none() {
+                    self.start_recording();
+                }
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            RuleAction::StopRecording => self.stop_recording(),
+            #[cfg(target_arch = "wasm32")]
+            RuleAction::StartRecording | RuleAction::StopRecording => {
+                log::warn!("recording is not available on the web")
+            }
+        }
+    }
+
+    /// Notifies about a fired rule, only when the window is unfocused to not distract while watching it
+    fn notify(&self, ctx: &egui::Context, text: &str) {
+        if ctx.input(|i| i.viewport().focused.unwrap_or(true)) {
+            return;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(e) = notification::show("splot", text) {
+            log::error!("failed to show notification, Err: {e}");
+        }
+        #[cfg(target_arch = "wasm32")]
+        log::warn!("notifications are not available on the web, `{text}`");
+
+        if self.notify_flash_taskbar {
+            ctx.send_viewport_cmd(egui::ViewportCommand::RequestUserAttention(
+                egui::UserAttentionType::Informational,
+            ));
+        }
+    }
+
+    /// Marks the connection as lost, so that it can be reconnected to
+    fn connection_lost(&mut self) {
+        if !self.is_connected {
+            return;
+        }
+        self.is_connected = false;
+        self.init_commands_next.take();
+
+        let Some(settings) = self.connected_settings.take() else {
+            return;
+        };
+        let port_name = self
+            .available_ports
+            .get(settings.port_index)
+            .map(|p| p.name.clone())
+            .unwrap_or_default();
+
+        log::warn!("lost connection to port `{port_name}`");
+
+        self.lost_connection = Some(LostConnection {
+            settings,
+            port_name,
+            attempts: 0,
+            last_attempt: Instant::now(),
+            ports_requested: false,
+        });
+    }
+
+    /// Reconnects to the lost port, once it is available again
+    fn reconnect(&mut self, ctx: &egui::Context) {
+        let Some(lost) = self.lost_connection.as_mut() else {
+            return;
+        };
+        lost.ports_requested = false;
+
+        let Some(port_index) = self
+            .available_ports
+            .iter()
+            .position(|p| p.name == lost.port_name)
+        else {
+            log::debug!(
+                "port `{}` not available yet, attempt {}",
+                lost.port_name,
+                lost.attempts
+            );
+            return;
+        };
+
+        let settings = lost.settings;
+        self.selected_port_index = Some(port_index);
+        self.baudrate = settings.baudrate;
+        self.timeout = settings.timeout;
+        self.data_bits = settings.data_bits;
+        self.flow_control = settings.flow_control;
+        self.parity = settings.parity;
+        self.stop_bits = settings.stop_bits;
+
+        self.try_connect(ctx);
+    }
+
+    /// Restores the settings of the current connection and locks the connection controls again
+    pub fn cancel_edit_connection(&mut self) {
+        if let Some(settings) = self.connected_settings {
+            self.selected_port_index = Some(settings.port_index);
+            self.baudrate = settings.baudrate;
+            self.timeout = settings.timeout;
+            self.data_bits = settings.data_bits;
+            self.flow_control = settings.flow_control;
+            self.parity = settings.parity;
+            self.stop_bits = settings.stop_bits;
+        }
+
+        self.edit_connection = false;
+    }
+
     fn poll_available_ports(&mut self, ctx: &egui::Context) {
         let Some(promise_available_ports) = self.promise_available_ports.as_mut() else {
             return;
         };
 
         if let Some(available_ports) = promise_available_ports.ready() {
+            // Keep the selected port when its index changes, because a device was plugged in or out
+            if !self.is_connected {
+                let selected = self
+                    .selected_port_index
+                    .and_then(|i| self.available_ports.get(i));
+                self.selected_port_index = match selected {
+                    Some(selected) => available_ports.iter().position(|p| p.name == selected.name),
+                    None => self.selected_port_index,
+                };
+            }
             self.available_ports = available_ports.clone();
 
             self.promise_available_ports.take();
+
+            if self.startup_pending {
+                self.run_startup_actions(ctx);
+            }
+            if self
+                .lost_connection
+                .as_ref()
+                .map_or(false, |lost| lost.ports_requested)
+            {
+                self.reconnect(ctx);
+            }
+
             ctx.request_repaint();
         }
     }
 
-    fn poll_try_connect(&mut self, ctx: &egui::Context) {
-        let Some(promise_try_connect) = self.promise_try_connect.as_mut() else {
+    /// Selects the port of the startup actions and connects to it.
+    /// When it isn't available, the first profile whose startup port is available is loaded instead.
+    /// Stays pending when no port is (yet) available.
+    fn run_startup_actions(&mut self, ctx: &egui::Context) {
+        self.startup_last_attempt = Instant::now();
+
+        let port_available = |actions: &StartupActions| {
+            actions.is_active() && self.available_ports.iter().any(|p| actions.matches_port(p))
+        };
+        if !port_available(&self.startup_actions) {
+            if let Some(index) = self
+                .connection_profiles
+                .iter()
+                .position(|p| port_available(&p.startup_actions))
+            {
+                self.load_profile(index);
+            }
+        }
+
+        let Some(port_index) = self
+            .available_ports
+            .iter()
+            .position(|p| self.startup_actions.matches_port(p))
+        else {
+            log::debug!(
+                "startup port `{}` not available yet",
+                self.startup_actions.port
+            );
             return;
         };
 
-        if let Some(res) = promise_try_connect.ready() {
-            if let Err(e) = res {
-                log::error!("try_connect() failed, Err: {}", e);
-            } else {
-                self.start_time = Instant::now();
-            }
+        self.startup_pending = false;
+        self.selected_port_index = Some(port_index);
 
-            self.promise_try_connect.take();
+        if self.startup_actions.auto_connect {
+            self.startup_record_pending = self.startup_actions.auto_record;
+            self.try_connect(ctx);
+        }
+    }
 
-            ctx.request_repaint();
+    /// The path of a new file in the capture directory named after the session template,
+    /// when automatic session names are enabled. Creates the capture directory.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn session_path(&self, extension: &str) -> anyhow::Result<Option<String>> {
+        if !self.auto_session_names {
+            return Ok(None);
         }
+
+        std::fs::create_dir_all(&self.capture_dir).map_err(|e| {
+            anyhow::anyhow!(
+                "failed to create capture directory `{}`, Err: {e}",
+                self.capture_dir
+            )
+        })?;
+
+        let port = self
+            .connected_port_name()
+            .or_else(|| {
+                self.selected_port_index
+                    .and_then(|i| self.available_ports.get(i))
+                    .map(|p| p.name.as_str())
+            })
+            .unwrap_or_default();
+        let name = capture::session_name(
+            &self.session_name_template,
+            instant::SystemTime::now()
+                .duration_since(instant::SystemTime::UNIX_EPOCH)
+                .unwrap_or_default(),
+            port,
+            &self.active_profile,
+        );
+
+        let path = std::path::Path::new(&self.capture_dir).join(format!("{name}.{extension}"));
+        Ok(Some(path.to_string_lossy().into_owned()))
     }
 
-    fn poll_read(&mut self, ctx: &egui::Context) {
-        let Some(promise_read) = self.promise_read.as_mut() else {
-            return;
-        };
-
-        if let Some(data_res) = promise_read.ready() {
-            match data_res {
-                Ok(serial_data) => {
-                    match self.parser.parse_from_serial_data(
-                        serial_data,
-                        self.time_unit,
-                        self.value_separator,
-                        self.start_time,
-                    ) {
-                        Ok(res) => {
-                            if !res.full_lines.is_empty() {
-                                self.serial_monitor_lines.extend(res.full_lines);
-                            }
-
-                            if res.n_new_samples > 0 {
-                                for (i, new_samples) in res.samples_vec.into_iter().enumerate() {
-                                    if let Some(samples) = self.samples_vec.get_mut(i) {
-                                        samples.extend(new_samples);
-                                    } else {
-                                        // Grow samples vec
-
-                                        // Give it the name of the first sample if provided
-                                        let name = new_samples
-                                            .first()
-                                            .and_then(|sample| sample.name.clone());
-
-                                        let mut new_buf = FixedSizeBuffer::new(SAMPLES_BUF_SIZE);
-                                        new_buf.extend(new_samples);
-
-                                        self.samples_vec.push(new_buf);
-
-                                        self.samples_appearance.push(SamplesAppearance::new(
-                                            name.unwrap_or_else(|| format!("Samples {i:02}")),
-                                        ));
-
-                                        recolor_samples_appearances(&mut self.samples_appearance);
-                                    }
-                                }
-
-                                self.samples_received += res.n_new_samples;
-                            }
-                        }
-                        Err(e) => {
-                            log::debug!("failed to add samples from serial data, Err: `{e}`");
-                            self.parser.clear();
-                        }
-                    }
-                }
-                Err(e) => log::warn!("device read failed, Err: `{e}`"),
+    /// Lists the files in the capture directory
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn refresh_captures(&mut self) {
+        match capture::list_captures(&self.capture_dir) {
+            Ok(captures) => self.captures = captures,
+            Err(e) => {
+                log::error!("listing captures failed, Err: {e}");
+                self.captures.clear();
+                self.captures_status = Some(Err(tr_args(
+                    "status-list-captures-failed",
+                    &[("error", &e)],
+                )));
             }
+        }
+    }
 
-            self.promise_read.take();
+    /// Imports a CSV capture or replays a raw recording, replacing the current samples
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open_capture(
+        &mut self,
+        ctx: &egui::Context,
+        capture: &capture::CaptureFile,
+    ) -> anyhow::Result<String> {
+        let path = capture.path.display();
+
+        if capture.is_csv() {
+            let text = std::fs::read_to_string(&capture.path)
+                .map_err(|e| anyhow::anyhow!("failed to read `{path}`, Err: {e}"))?;
+            let imported = import::parse_csv_capture(&text)?;
+            let n_samples = imported.n_samples();
+            self.load_imported_capture(ctx, imported);
+
+            Ok(tr_args(
+                "status-imported-from",
+                &[("samples", &n_samples), ("path", &path)],
+            ))
+        } else {
+            let data = std::fs::read(&capture.path)
+                .map_err(|e| anyhow::anyhow!("failed to read `{path}`, Err: {e}"))?;
+            let n_samples = self.replay_recording(ctx, &data)?;
+
+            Ok(tr_args(
+                "status-replayed-from",
+                &[("samples", &n_samples), ("path", &path)],
+            ))
+        }
+    }
 
-            // Always install another read
-            self.read(ctx);
+    /// The data format with the current settings
+    fn parse_format(&self) -> ParseFormat {
+        match self.data_format {
+            DataFormat::Ascii => ParseFormat::Ascii {
+                time_unit: self.time_unit,
+                value_separator: self.value_separator,
+            },
+            DataFormat::Binary => ParseFormat::Binary {
+                layout: self.binary_layout.clone(),
+                time_unit: self.time_unit,
+            },
+            DataFormat::Ubx => ParseFormat::Ubx {
+                messages: self.ubx_messages.clone(),
+            },
+            DataFormat::Nmea => ParseFormat::Nmea,
+            DataFormat::Can => ParseFormat::Can {
+                signals: self.can_signals.clone(),
+            },
         }
     }
 
-    /// Needs to be called repeatedly to poll promises
-    pub fn async_tasks(&mut self, ctx: &egui::Context) {
-        self.poll_available_ports(ctx);
-        self.poll_try_connect(ctx);
+    /// Parses recorded raw data with the current format settings and replaces the current samples with it
+    pub fn replay_recording(&mut self, ctx: &egui::Context, data: &[u8]) -> anyhow::Result<u64> {
+        self.clear_samples(ctx);
+        self.parser.clear();
+        // The receive times of a replay are meaningless, the next read enables it again
+        self.parser.set_time_unwrap(self.unwrap_device_time);
+        self.parser.set_time_sync(false);
+
+        let now = Instant::now().duration_since(self.start_time).as_nanos() as i64;
+        let mut res = ParseResult::default();
+        self.parser
+            .parse(data, &self.parse_format(), now, &mut res)?;
+        self.parser.clear();
 
-        if !self.pause {
-            self.poll_read(ctx);
+        self.add_samples(&mut res.samples_vec);
+        self.apply_units(&res.units);
+        self.samples_received += res.n_new_samples;
+
+        Ok(res.n_new_samples)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start_recording(&mut self) {
+        match self.session_path("log") {
+            Ok(Some(path)) => self.record_path = path,
+            Ok(None) => {}
+            Err(e) => {
+                log::error!("starting recording failed, Err: {e}");
+                return;
+            }
         }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        poll_promise::tick_local();
+        match recorder::Recorder::start(&self.record_path) {
+            Ok(recorder) => self.recorder = Some(recorder),
+            Err(e) => log::error!("starting recording failed, Err: {e}"),
+        }
     }
-}
 
-impl eframe::App for SplotApp {
-    /// Called by the frame work to save state before shutdown.
-    fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, self);
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn stop_recording(&mut self) {
+        self.recorder.take();
     }
 
-    /// Called each time the UI needs repainting, which may be many times per second.
-    /// Put your widgets into a `SidePanel`, `TopPanel`, `CentralPanel`, `Window` or `Area`.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        self.async_tasks(ctx);
+    /// Spills a sample evicted from the in-memory buffer of a received channel, when enabled
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spill_sample(&mut self, channel: usize, sample: &Sample) {
+        if !self.history_spill {
+            return;
+        }
 
-        self.draw_ui(ctx);
+        let history = match self.history.as_mut() {
+            Some(history) => history,
+            None => match history::SpilledHistory::new() {
+                Ok(history) => self.history.insert(history),
+                Err(e) => {
+                    log::error!("spilling history failed, Err: {e}");
+                    self.history_spill = false;
+                    return;
+                }
+            },
+        };
 
-        // repaint periodically
-        ctx.request_repaint_after(instant::Duration::from_secs_f64(1.0 / 60.0));
+        if let Err(e) = history.spill(channel, sample) {
+            log::error!("spilling history failed, Err: {e}");
+            self.history_spill = false;
+            self.history.take();
+        }
     }
-}
 
-#[cfg(target_arch = "wasm32")]
-pub(crate) static WEB_SERIAL_API_SUPPORTED: once_cell::sync::Lazy<bool> =
-    once_cell::sync::Lazy::new(|| {
-        let serial_itf = web_sys::window().unwrap().navigator().serial();
+    /// Writes the log, the settings, parser diagnostics and the latest received bytes into a debug bundle
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn create_debug_bundle(&mut self) -> anyhow::Result<()> {
+        /// The number of the latest received bytes included in the bundle
+        const RAW_BYTES: usize = 4096;
 
-        !serial_itf.is_undefined()
-    });
+        if let Some(path) = self.session_path("zip")? {
+            self.debug_bundle_path = path;
+        }
+
+        let mut ports = self
+            .available_ports
+            .iter()
+            .map(|p| p.name.clone())
+            .collect::<Vec<String>>();
+        ports.push(self.startup_actions.port.trim().to_string());
+        let redact = |text: String| {
+            if !self.debug_bundle_redact_ports {
+                return text;
+            }
+            ports
+                .iter()
+                .filter(|p| !p.is_empty())
+                .fold(text, |text, port| text.replace(port.as_str(), "<port>"))
+        };
+
+        let mut bundle = debugbundle::DebugBundle::default();
+
+        let mut log = crate::logbuffer::log_lines().join("\n");
+        log.push('\n');
+        bundle.add_file("log.txt", redact(log));
+
+        let settings = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        bundle.add_file("settings.ron", redact(settings));
+
+        let mut diagnostics = format!(
+            "version: {}\ndata format: {}\nvalue separator: {:?}\ntime unit: {}\n\
+            bytes received: {}\nsamples received: {}\nbuffered unparsed bytes: {}\n",
+            env!("CARGO_PKG_VERSION"),
+            self.data_format,
+            self.value_separator,
+            self.time_unit,
+            self.bytes_received,
+            self.samples_received,
+            self.n_unparsed_bytes(),
+        );
+        for (i, (samples, appearance)) in self
+            .samples_vec
+            .iter()
+            .zip(&self.samples_appearance)
+            .enumerate()
+        {
+            diagnostics += &format!(
+                "channel {i} `{}`: {} samples, last value: {:?}, out of plausible range: {}, invalid values: {}\n",
+                appearance.name,
+                samples.len(),
+                samples.last().map(|s| s.value),
+                appearance.value_filter.rejected(),
+                appearance.invalid_values,
+            );
+        }
+        bundle.add_file("diagnostics.txt", diagnostics);
+
+        let n_bytes = self.serial_monitor_bytes.len();
+        let raw = self
+            .serial_monitor_bytes
+            .iter()
+            .skip(n_bytes.saturating_sub(RAW_BYTES))
+            .copied()
+            .collect::<Vec<u8>>();
+        bundle.add_file("raw.bin", raw);
+
+        bundle.write(&self.debug_bundle_path)
+    }
+
+    fn poll_try_connect(&mut self, ctx: &egui::Context) {
+        let Some(promise_try_connect) = self.promise_try_connect.as_mut() else {
+            return;
+        };
+
+        if let Some(res) = promise_try_connect.ready() {
+            match res {
+                Ok(is_connected) => {
+                    self.is_connected = *is_connected;
+                    self.connection_error.take();
+                    self.start_time = Instant::now();
+                }
+                Err(e) => {
+                    log::error!("try_connect() failed, Err: {}", e);
+                    self.is_connected = false;
+                    self.connection_error = Some(e.to_string());
+                }
+            }
+
+            if self.is_connected {
+                self.lost_connection.take();
+            }
+
+            if self.is_connected && !self.init_commands.is_empty() {
+                self.init_commands_next = Some(0);
+                self.init_commands_last = Instant::now();
+            }
+
+            if !self.is_connected {
+                self.connected_settings.take();
+            } else if self.startup_record_pending {
+                #[cfg(not(target_arch = "wasm32"))]
+                self.start_recording();
+            }
+            self.startup_record_pending = false;
+            self.edit_connection = false;
+
+            self.promise_try_connect.take();
+
+            ctx.request_repaint();
+        }
+    }
+
+    fn poll_close(&mut self, ctx: &egui::Context) {
+        let Some(promise_close) = self.promise_close.as_mut() else {
+            return;
+        };
+
+        if let Some(res) = promise_close.ready() {
+            if let Err(e) = res {
+                log::error!("close() failed, Err: {}", e);
+                self.connection_error = Some(e.to_string());
+            }
+
+            self.promise_close.take();
+
+            ctx.request_repaint();
+        }
+    }
+
+    fn poll_write(&mut self, ctx: &egui::Context) {
+        let Some(promise_write) = self.promise_write.as_mut() else {
+            return;
+        };
+
+        if let Some(res) = promise_write.ready() {
+            if let Err(e) = res {
+                log::error!("device write failed, Err: `{e}`");
+            }
+
+            self.promise_write.take();
+
+            ctx.request_repaint();
+        }
+    }
+
+    fn poll_read(&mut self, ctx: &egui::Context) {
+        let Some(promise_read) = self.promise_read.take() else {
+            return;
+        };
+        self.acquisition_stats.polled();
+
+        let data_res = match promise_read.try_take() {
+            Ok(data_res) => data_res,
+            Err(promise_read) => {
+                self.promise_read = Some(promise_read);
+                return;
+            }
+        };
+        self.acquisition_stats
+            .read_finished(data_res.as_ref().ok().map(|data| data.len()));
+
+        match data_res {
+            Ok(serial_data) => {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(recorder) = self.recorder.as_mut() {
+                    if let Err(e) = recorder.write(&serial_data) {
+                        log::error!("recording failed, Err: {e}");
+                        self.recorder.take();
+                    }
+                }
+
+                self.serial_monitor_bytes
+                    .extend(serial_data.iter().copied());
+                self.bytes_received += serial_data.len() as u64;
+
+                self.parse_serial_data(ctx, &serial_data);
+            }
+            Err(e) => {
+                log::warn!("device read failed, Err: `{e}`");
+                self.connection_lost();
+            }
+        }
+
+        // Always install another read
+        self.read(ctx);
+    }
+
+    /// Parses the read data and merges the results.
+    ///
+    /// In the web build the data is parsed by the worker, its results are merged when they are received.
+    fn parse_serial_data(&mut self, ctx: &egui::Context, serial_data: &[u8]) {
+        let now = Instant::now().duration_since(self.start_time).as_nanos() as i64;
+
+        #[cfg(target_arch = "wasm32")]
+        if let Some(parse_worker) = self.parse_worker.as_ref() {
+            parse_worker.parse(
+                serial_data,
+                self.parse_format(),
+                self.unwrap_device_time,
+                self.sync_device_time,
+                now,
+            );
+            return;
+        }
+
+        // Taken out while in use and put back afterwards, so that its buffers are reused
+        let mut res = std::mem::take(&mut self.parse_result);
+        self.parser.set_time_unwrap(self.unwrap_device_time);
+        self.parser.set_time_sync(self.sync_device_time);
+
+        let parse_res = self
+            .parser
+            .parse(serial_data, &self.parse_format(), now, &mut res);
+        let parser_pending = !self.parser.buf.is_empty();
+        if parse_res.is_err() {
+            self.parser.clear();
+        }
+
+        self.merge_parse_result(ctx, &mut res, parse_res, serial_data.len(), parser_pending);
+        self.parse_result = res;
+    }
+
+    /// Merges the results of the parser worker
+    #[cfg(target_arch = "wasm32")]
+    fn poll_parse_worker(&mut self, ctx: &egui::Context) {
+        let Some(parse_worker) = self.parse_worker.as_mut() else {
+            return;
+        };
+        if parse_worker.has_failed() {
+            log::warn!("parser worker failed, parsing on the main thread");
+            self.parse_worker.take();
+            return;
+        }
+
+        for mut response in parse_worker.take_responses() {
+            let parse_res = match response.error {
+                Some(e) => Err(anyhow::anyhow!(e)),
+                None => Ok(()),
+            };
+            self.merge_parse_result(
+                ctx,
+                &mut response.result,
+                parse_res,
+                response.n_bytes,
+                response.n_pending_bytes > 0,
+            );
+        }
+    }
+
+    /// Adds the parsed samples, lines and events, and runs what they trigger
+    fn merge_parse_result(
+        &mut self,
+        ctx: &egui::Context,
+        res: &mut ParseResult,
+        parse_res: anyhow::Result<()>,
+        n_bytes: usize,
+        parser_pending: bool,
+    ) {
+        self.ingest_stats.record(
+            n_bytes as u64,
+            res.full_lines.len() as u64,
+            res.n_errors + u64::from(parse_res.is_err()),
+        );
+        self.throughput.record(
+            n_bytes as u64,
+            res.samples_vec.iter().map(|s| s.len()).collect(),
+        );
+        self.acquisition_stats
+            .record_parse(res.n_errors, parser_pending);
+
+        if let Err(e) = parse_res {
+            log::debug!("failed to add samples from serial data, Err: `{e}`");
+            return;
+        }
+
+        let mut fired = vec![];
+        let mut script_outputs = vec![];
+        for line in res.full_lines.iter() {
+            fired.extend(
+                self.automation_rules
+                    .iter()
+                    .filter(|rule| rule.check_line(line))
+                    .map(|rule| (rule.action, rule.argument.clone())),
+            );
+            script_outputs.extend(self.automation_script.on_line(line));
+        }
+
+        if !res.full_lines.is_empty() {
+            self.serial_monitor_text.take();
+        }
+        let start_time = self.start_time;
+        self.serial_monitor_lines.extend(
+            res.full_lines
+                .drain(..)
+                .map(|line| MonitorLine::now(start_time, line)),
+        );
+
+        for marker in res.markers.drain(..) {
+            self.markers.add(marker);
+        }
+        for event in res.text_events.drain(..) {
+            if !self.event_lanes.contains(&event.channel) {
+                self.event_lanes.push(event.channel.clone());
+            }
+            self.text_events.add(event);
+        }
+
+        if res.n_new_samples > 0 {
+            self.add_samples(&mut res.samples_vec);
+            self.apply_units(&res.units);
+            self.samples_received += res.n_new_samples;
+            fired.extend(self.check_automation_conditions());
+            script_outputs.extend(self.check_script_samples());
+        }
+
+        for (action, argument) in fired {
+            self.run_automation_action(ctx, action, &argument);
+        }
+        self.run_script_outputs(ctx, script_outputs);
+        for text in std::mem::take(&mut self.alarm_notifications) {
+            self.notify(ctx, &text);
+        }
+    }
+
+    /// Clears the parser state, also of the parser worker
+    fn clear_parser(&mut self) {
+        self.parser.clear();
+        #[cfg(target_arch = "wasm32")]
+        if let Some(parse_worker) = self.parse_worker.as_mut() {
+            parse_worker.clear();
+        }
+    }
+
+    /// The received bytes that were not parsed yet
+    fn n_unparsed_bytes(&self) -> usize {
+        #[cfg(target_arch = "wasm32")]
+        if let Some(parse_worker) = self.parse_worker.as_ref() {
+            return parse_worker.n_pending_bytes();
+        }
+
+        self.parser.buf.len()
+    }
+
+    /// The offset in nanoseconds and the drift in ppm of the device time sync, when enabled
+    fn time_sync_estimate(&self) -> Option<(i64, f64)> {
+        #[cfg(target_arch = "wasm32")]
+        if let Some(parse_worker) = self.parse_worker.as_ref() {
+            return parse_worker.time_sync();
+        }
+
+        self.parser
+            .time_sync()
+            .map(|time_sync| (time_sync.offset(), time_sync.drift_ppm()))
+    }
+
+    /// Sets the units that were parsed from the values, on the channels that don't have one yet
+    fn apply_units(&mut self, units: &[Option<String>]) {
+        for (appearance, unit) in self.samples_appearance.iter_mut().zip(units) {
+            if let (Some(unit), true) = (unit, appearance.unit.is_empty()) {
+                appearance.unit = unit.clone();
+            }
+        }
+    }
+
+    /// Adds newly parsed samples, indexed by the channel index.
+    /// Grows the channels if needed and updates the derived channels.
+    ///
+    /// The samples are drained, so that the buffers can be reused.
+    fn add_samples(&mut self, samples_vec: &mut [Vec<Sample>]) {
+        // Grow samples vec first, so that the first samples of a new channel are filtered too.
+        // The derived channels always come after the received ones
+        let n_channels = samples_vec
+            .iter()
+            .rposition(|s| !s.is_empty())
+            .map_or(0, |i| i + 1);
+        let new_channels = samples_vec
+            .iter()
+            .enumerate()
+            .take(n_channels)
+            .skip(self.n_raw_samples);
+        for (i, new_samples) in new_channels {
+            // Give it the name of the first sample if provided
+            let name = new_samples.first().and_then(|sample| sample.name.clone());
+
+            let name = unique_channel_name(
+                name.unwrap_or_else(|| format!("Samples {i:02}")),
+                &self.samples_appearance,
+            );
+
+            // Recoloring changes the other channels too
+            self.remember_channel_appearances();
+
+            self.samples_vec
+                .insert(self.n_raw_samples, FixedSizeBuffer::new(SAMPLES_BUF_SIZE));
+            self.samples_appearance
+                .insert(self.n_raw_samples, SamplesAppearance::new(name));
+            self.n_raw_samples += 1;
+
+            recolor_samples_appearances(&mut self.samples_appearance);
+            self.apply_appearance_preset();
+            self.restore_channel_appearances();
+        }
+
+        for (new_samples, appearance) in samples_vec
+            .iter_mut()
+            .zip(self.samples_appearance[..self.n_raw_samples].iter_mut())
+        {
+            appearance.invalid_values +=
+                new_samples.iter().filter(|s| !s.value.is_finite()).count() as u64;
+            appearance.value_filter.apply(new_samples);
+        }
+
+        let names = self.samples_appearance[..self.n_raw_samples]
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<&str>>();
+        let derived_samples = self
+            .derived_channels
+            .iter_mut()
+            .map(|d| d.evaluate(samples_vec, &self.samples_vec[..self.n_raw_samples], &names))
+            .collect::<Vec<Vec<Sample>>>();
+
+        // The number of new samples of each channel, to check and broadcast them once they were added
+        let mut n_new = samples_vec.iter().map(|s| s.len()).collect::<Vec<usize>>();
+        let n_new_derived = derived_samples
+            .iter()
+            .map(|s| s.len())
+            .collect::<Vec<usize>>();
+
+        let trigger_n_new = samples_vec
+            .get(self.trigger.samples)
+            .map(|s| s.len())
+            .filter(|&n| n > 0);
+
+        for (i, new_samples) in samples_vec.iter_mut().enumerate().take(self.n_raw_samples) {
+            for sample in new_samples.drain(..) {
+                if let Some(_evicted) = self.samples_vec[i].add(sample) {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    self.spill_sample(i, &_evicted);
+                }
+            }
+        }
+
+        let trigger_n_new = trigger_n_new.or_else(|| {
+            derived_samples
+                .get(self.trigger.samples.checked_sub(self.n_raw_samples)?)
+                .map(|s| s.len())
+        });
+
+        for (j, new_samples) in derived_samples.into_iter().enumerate() {
+            if let Some(samples) = self.samples_vec.get_mut(self.n_raw_samples + j) {
+                samples.extend(new_samples);
+            }
+        }
+
+        // Channels that were inserted or recreated are summarized again, they have a new buffer
+        self.samples_summaries
+            .resize_with(self.samples_vec.len(), MinMaxSummary::default);
+        for (summary, samples) in self.samples_summaries.iter_mut().zip(&self.samples_vec) {
+            summary.update(samples);
+        }
+
+        if let (Some(n_new), Some(samples)) =
+            (trigger_n_new, self.samples_vec.get(self.trigger.samples))
+        {
+            self.trigger
+                .check(samples, n_new, secs_to_nanos(self.plot_tv_newer));
+        }
+
+        n_new.resize(self.n_raw_samples, 0);
+        n_new.extend(n_new_derived);
+        self.check_alarms(&n_new);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.process_conditional_capture(&n_new);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.broadcast_samples(&n_new);
+    }
+
+    /// Starts, continues or stops the conditional capture with the new samples of each channel
+    #[cfg(not(target_arch = "wasm32"))]
+    fn process_conditional_capture(&mut self, n_new: &[usize]) {
+        if !self.conditional_capture.is_armed() && !self.conditional_capture.is_capturing() {
+            return;
+        }
+
+        let names = self
+            .samples_appearance
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<&str>>();
+        let units = self
+            .samples_appearance
+            .iter()
+            .map(|a| a.unit.as_str())
+            .collect::<Vec<&str>>();
+
+        // Taken, so that the session path can borrow the app
+        let mut capture = std::mem::take(&mut self.conditional_capture);
+        let res = capture.process(&self.samples_vec, &names, &units, n_new, || {
+            self.session_path("csv")
+        });
+        if let Err(e) = res {
+            log::error!("conditional capture failed, Err: {e:?}");
+            self.conditional_capture_error = Some(e.to_string());
+            if let Err(e) = capture.disarm() {
+                log::error!("failed to finish conditional capture, Err: {e:?}");
+            }
+        }
+        self.conditional_capture = capture;
+    }
+
+    /// Checks the new samples of each channel against the alarm rules
+    fn check_alarms(&mut self, n_new: &[usize]) {
+        let names = self
+            .samples_appearance
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<&str>>();
+
+        for alarm in self.alarms.iter_mut().filter(|a| a.enabled) {
+            let Some(i) = derived::resolve_channel(&alarm.channel, &names) else {
+                continue;
+            };
+            let samples = &self.samples_vec[i];
+            let n = n_new.get(i).copied().unwrap_or(0);
+
+            for sample in
+                (samples.len().saturating_sub(n)..samples.len()).filter_map(|k| samples.get(k))
+            {
+                let Some(raised) = alarm.check(i, sample.value) else {
+                    continue;
+                };
+
+                let message = if raised {
+                    tr_args(
+                        "alarm-raised",
+                        &[
+                            ("condition", &alarm.describe()),
+                            ("samples", &alarm.samples.max(1)),
+                            ("value", &sample.value),
+                        ],
+                    )
+                } else {
+                    String::from(tr("alarm-cleared"))
+                };
+                log::warn!("alarm on `{}`: {message}", names[i]);
+
+                if raised && alarm.notify {
+                    self.alarm_notifications.push(tr_args(
+                        "notification-alarm",
+                        &[("channel", &names[i]), ("message", &message)],
+                    ));
+                }
+                self.alarm_events.push(AlarmEvent {
+                    time: sample.time,
+                    channel: names[i].to_string(),
+                    message,
+                    raised,
+                });
+            }
+        }
+
+        let excess = self
+            .alarm_events
+            .len()
+            .saturating_sub(alarm::MAX_ALARM_EVENTS);
+        self.alarm_events.drain(..excess);
+    }
+
+    /// Sends the latest samples of each channel to the clients of the sample broadcast, as JSON object per sample
+    #[cfg(not(target_arch = "wasm32"))]
+    fn broadcast_samples(&self, n_new: &[usize]) {
+        use httpapi::{json_number, json_string};
+
+        let Some(broadcast) = self.sample_broadcast.as_ref() else {
+            return;
+        };
+
+        for ((samples, appearance), &n) in self
+            .samples_vec
+            .iter()
+            .zip(self.samples_appearance.iter())
+            .zip(n_new)
+        {
+            let name = json_string(&appearance.name);
+            let unit = json_string(&appearance.unit);
+
+            for sample in
+                (samples.len().saturating_sub(n)..samples.len()).filter_map(|i| samples.get(i))
+            {
+                broadcast.send(format!(
+                    "{{\"channel\":{name},\"unit\":{unit},\"time\":{},\"value\":{}}}",
+                    json_number(nanos_to_secs(sample.time - self.time_zero)),
+                    json_number(sample.value)
+                ));
+            }
+        }
+    }
+
+    /// Loads the imported capture, the result is displayed in the import window
+    pub fn finish_import(&mut self, ctx: &egui::Context, res: anyhow::Result<ImportedCapture>) {
+        self.import_status = Some(match res {
+            Ok(capture) => {
+                let status = tr_args(
+                    "status-imported-channels",
+                    &[
+                        ("samples", &capture.n_samples()),
+                        ("channels", &capture.names.len()),
+                    ],
+                );
+                self.load_imported_capture(ctx, capture);
+                Ok(status)
+            }
+            Err(e) => {
+                log::error!("importing capture failed, Err: {e}");
+                // Otherwise a failed drop would go unnoticed
+                self.show_import_window = true;
+                Err(tr_args("status-import-failed", &[("error", &e)]))
+            }
+        });
+    }
+
+    /// Imports a CSV file that was dropped onto the window. Each import replaces the samples,
+    /// so only the last of several dropped files is kept.
+    fn import_dropped_files(&mut self, ctx: &egui::Context) {
+        let Some(file) = ctx.input(|i| i.raw.dropped_files.last().cloned()) else {
+            return;
+        };
+
+        let name = file
+            .path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| file.name.clone());
+        if !name.to_lowercase().ends_with(".csv") {
+            self.finish_import(ctx, Err(anyhow::anyhow!("`{name}` is not a CSV file")));
+            return;
+        }
+
+        let text = match (&file.bytes, &file.path) {
+            // Files dropped onto the web page are read by the browser
+            (Some(bytes), _) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+            #[cfg(not(target_arch = "wasm32"))]
+            (None, Some(path)) => std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("failed to read `{name}`, Err: {e}")),
+            _ => Err(anyhow::anyhow!("failed to read `{name}`")),
+        };
+
+        log::debug!("importing dropped file `{name}`");
+        self.finish_import(ctx, text.and_then(|text| import::parse_csv_capture(&text)));
+    }
+
+    /// Replaces the current samples with an imported capture
+    pub fn load_imported_capture(&mut self, ctx: &egui::Context, mut capture: ImportedCapture) {
+        self.clear_samples(ctx);
+
+        let n_samples = capture.n_samples();
+        self.add_samples(&mut capture.samples_vec);
+        self.samples_received += n_samples;
+
+        for (appearance, unit) in self.samples_appearance.iter_mut().zip(capture.units) {
+            appearance.unit = unit;
+        }
+    }
+
+    /// Selects the channels for export that are not selected yet, by default the visible ones
+    pub fn sync_export_channels(&mut self) {
+        let n = self.export_channels.len();
+        self.export_channels
+            .extend(self.samples_appearance.iter().skip(n).map(|a| a.visible));
+        self.export_channels.truncate(self.samples_appearance.len());
+    }
+
+    /// Writes the selected channels into the export file, returns the number of exported channels
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn export_samples(&mut self) -> anyhow::Result<usize> {
+        if let Some(path) = self.session_path(self.export_format.extension())? {
+            self.export_path = path;
+        }
+
+        let channels = self
+            .samples_vec
+            .iter()
+            .zip(self.samples_appearance.iter())
+            .zip(self.export_channels.iter())
+            .filter(|(_, &selected)| selected)
+            .map(|((samples, appearance), _)| export::ExportChannel {
+                name: &appearance.name,
+                unit: &appearance.unit,
+                samples: samples.iter(),
+            })
+            .collect::<Vec<_>>();
+
+        if channels.is_empty() {
+            return Err(anyhow::anyhow!("no channels are selected"));
+        }
+        let n_channels = channels.len();
+
+        std::fs::write(
+            &self.export_path,
+            self.export_format.write(channels, self.time_zero),
+        )
+        .map_err(|e| anyhow::anyhow!("failed to write `{}`, Err: {e}", self.export_path))?;
+
+        Ok(n_channels)
+    }
+
+    /// Loads the two captures and compares them
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn compare_captures(&self) -> anyhow::Result<SessionDiff> {
+        let load = |path: &str| {
+            std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("failed to read `{path}`, Err: {e}"))
+                .and_then(|text| import::parse_csv_capture(&text))
+        };
+
+        sessiondiff::diff_captures(
+            &load(&self.session_diff_paths[0])?,
+            &load(&self.session_diff_paths[1])?,
+            self.session_diff_alignment,
+            self.session_diff_trigger_channel,
+            self.session_diff_trigger_level,
+        )
+    }
+
+    /// Recreates the channels of all derived channels, e.g. when the samples are cleared.
+    /// Edits of a single derived channel are applied with `apply_derived_edit()`.
+    pub fn sync_derived_samples(&mut self) {
+        self.remember_channel_appearances();
+        self.samples_vec.truncate(self.n_raw_samples);
+        self.samples_appearance.truncate(self.n_raw_samples);
+
+        for derived in self.derived_channels.iter_mut() {
+            derived.invalidate();
+
+            self.samples_vec
+                .push(FixedSizeBuffer::new(SAMPLES_BUF_SIZE));
+            let name = unique_channel_name(derived.name.clone(), &self.samples_appearance);
+            self.samples_appearance.push(SamplesAppearance::new(name));
+        }
+
+        recolor_samples_appearances(&mut self.samples_appearance);
+        self.apply_appearance_preset();
+        self.restore_channel_appearances();
+    }
+
+    /// Applies a committed edit of the derived channels to their channel only.
+    /// The samples and appearances of the other channels are kept.
+    pub fn apply_derived_edit(&mut self, edit: DerivedEdit) {
+        match edit {
+            DerivedEdit::Renamed(i) => {
+                let (Some(derived), Some(appearance)) = (
+                    self.derived_channels.get(i),
+                    self.samples_appearance.get(self.n_raw_samples + i),
+                ) else {
+                    return;
+                };
+                if appearance.source_name == derived.name {
+                    return;
+                }
+
+                let name = unique_channel_name(derived.name.clone(), &self.samples_appearance);
+                let appearance = &mut self.samples_appearance[self.n_raw_samples + i];
+                appearance.name = name.clone();
+                appearance.source_name = name;
+            }
+            DerivedEdit::Changed(i) => {
+                if let Some(derived) = self.derived_channels.get_mut(i) {
+                    derived.invalidate();
+                }
+                // The new buffer is summarized again on the next update
+                if let Some(samples) = self.samples_vec.get_mut(self.n_raw_samples + i) {
+                    *samples = FixedSizeBuffer::new(SAMPLES_BUF_SIZE);
+                }
+            }
+            DerivedEdit::Added => {
+                let Some(derived) = self.derived_channels.last() else {
+                    return;
+                };
+
+                let name = unique_channel_name(derived.name.clone(), &self.samples_appearance);
+                let mut appearance = SamplesAppearance::new(name);
+                appearance.color = unique_color_in_list(
+                    self.samples_appearance.len(),
+                    self.samples_appearance.len() + 1,
+                );
+                if let Some(remembered) = self.channel_appearances.get(&appearance.source_name) {
+                    remembered.apply(&mut appearance);
+                }
+
+                self.samples_vec
+                    .push(FixedSizeBuffer::new(SAMPLES_BUF_SIZE));
+                self.samples_appearance.push(appearance);
+            }
+            DerivedEdit::Removed(i) => {
+                let k = self.n_raw_samples + i;
+                if k < self.samples_vec.len() {
+                    self.samples_vec.remove(k);
+                    self.samples_appearance.remove(k);
+                }
+                if k < self.samples_summaries.len() {
+                    self.samples_summaries.remove(k);
+                }
+            }
+        }
+    }
+
+    pub fn is_dock_tab_open(&self, tab: DockTab) -> bool {
+        self.dock_state.find_tab(&tab).is_some()
+    }
+
+    /// Focuses the tab, or opens it next to the focused tab
+    pub fn open_dock_tab(&mut self, tab: DockTab) {
+        match self.dock_state.find_tab(&tab) {
+            Some((surface, node, i)) => {
+                self.dock_state.set_active_tab((surface, node, i));
+                self.dock_state
+                    .set_focused_node_and_surface((surface, node));
+            }
+            None => self.dock_state.push_to_focused_leaf(tab),
+        }
+    }
+
+    /// Focuses or opens the page, and its controls left of it
+    pub fn open_page(&mut self, page: PlotPage) {
+        self.open_dock_tab(DockTab::Page(page));
+
+        let controls = DockTab::Controls(page);
+        if !page.has_controls() {
+            return;
+        }
+        if self.is_dock_tab_open(controls) {
+            self.open_dock_tab(controls);
+            // Keep the focus on the page
+            self.open_dock_tab(DockTab::Page(page));
+            return;
+        }
+
+        let Some((surface, node, _)) = self.dock_state.find_tab(&DockTab::Page(page)) else {
+            return;
+        };
+        if let Some(tree) = self
+            .dock_state
+            .get_surface_mut(surface)
+            .and_then(|s| s.node_tree_mut())
+        {
+            tree.split_left(node, 0.22, vec![controls]);
+        }
+    }
+
+    pub fn toggle_dock_tab(&mut self, tab: DockTab) {
+        match self.dock_state.find_tab(&tab) {
+            Some(location) => {
+                self.dock_state.remove_tab(location);
+            }
+            None => self.dock_state.push_to_focused_leaf(tab),
+        }
+    }
+
+    /// Applies the appearance preset to the channels it matches
+    pub fn apply_appearance_preset(&mut self) {
+        if self.appearance_preset.is_empty() {
+            return;
+        }
+
+        for (i, appearance) in self.samples_appearance.iter_mut().enumerate() {
+            if let Some(preset) = self.appearance_preset.find(i, &appearance.name) {
+                preset.apply(appearance);
+            }
+        }
+    }
+
+    /// Remembers the appearance of the current channels, so that it is restored in later sessions
+    pub fn remember_channel_appearances(&mut self) {
+        remember_appearances(&mut self.channel_appearances, &self.samples_appearance);
+    }
+
+    /// Restores the remembered appearance of the channels
+    pub fn restore_channel_appearances(&mut self) {
+        for appearance in self.samples_appearance.iter_mut() {
+            if let Some(remembered) = self.channel_appearances.get(&appearance.source_name) {
+                remembered.apply(appearance);
+            }
+        }
+    }
+
+    /// The current connection, parser and channel appearance settings as profile
+    pub fn current_profile(&self, name: String) -> ConnectionProfile {
+        let appearance = if self.samples_appearance.is_empty() {
+            self.appearance_preset.clone()
+        } else {
+            AppearancePreset {
+                channels: self
+                    .samples_appearance
+                    .iter()
+                    .map(ChannelAppearance::from)
+                    .collect(),
+            }
+        };
+
+        ConnectionProfile {
+            name,
+            port: self
+                .selected_port_index
+                .and_then(|i| self.available_ports.get(i))
+                .map(|p| p.name.clone())
+                .unwrap_or_default(),
+            baudrate: self.baudrate,
+            timeout: self.timeout,
+            data_bits: self.data_bits,
+            flow_control: self.flow_control,
+            parity: self.parity,
+            stop_bits: self.stop_bits,
+            time_unit: self.time_unit,
+            unwrap_device_time: self.unwrap_device_time,
+            sync_device_time: self.sync_device_time,
+            value_separator: self.value_separator,
+            data_format: self.data_format,
+            binary_layout: self.binary_layout.clone(),
+            ubx_messages: self.ubx_messages.clone(),
+            can_signals: self.can_signals.clone(),
+            appearance,
+            startup_actions: self.startup_actions.clone(),
+        }
+    }
+
+    /// Applies the settings of the profile, selecting its port when it is available.
+    /// Its startup actions are executed when not connected.
+    pub fn load_profile(&mut self, index: usize) {
+        let Some(profile) = self.connection_profiles.get(index).cloned() else {
+            return;
+        };
+        log::debug!("loading connection profile `{}`", profile.name);
+
+        if let Some(port_index) = self
+            .available_ports
+            .iter()
+            .position(|p| p.name == profile.port)
+        {
+            self.selected_port_index = Some(port_index);
+        }
+        self.baudrate = profile.baudrate;
+        self.timeout = profile.timeout;
+        self.data_bits = profile.data_bits;
+        self.flow_control = profile.flow_control;
+        self.parity = profile.parity;
+        self.stop_bits = profile.stop_bits;
+        self.time_unit = profile.time_unit;
+        self.unwrap_device_time = profile.unwrap_device_time;
+        self.sync_device_time = profile.sync_device_time;
+        self.value_separator = profile.value_separator;
+        self.data_format = profile.data_format;
+        self.binary_layout = profile.binary_layout;
+        self.ubx_messages = profile.ubx_messages;
+        self.can_signals = profile.can_signals;
+        self.appearance_preset = profile.appearance;
+        self.apply_appearance_preset();
+        self.startup_actions = profile.startup_actions;
+        self.startup_pending = !self.is_connected && self.startup_actions.is_active();
+        self.active_profile = profile.name;
+    }
+
+    /// The start and end time of the window that the Time-Value plot follows, in nanoseconds
+    fn displayed_window(&self) -> Option<(i64, i64)> {
+        let last = self.samples_vec.first().and_then(|b| b.last())?;
+
+        let window_len = secs_to_nanos(self.plot_tv_newer);
+        let trigger_window = if self.trigger.is_active() {
+            self.trigger.window(window_len)
+        } else {
+            None
+        };
+        let scroll_end = last.time - secs_to_nanos(self.plot_tv_scroll_back);
+
+        Some(trigger_window.unwrap_or((scroll_end - window_len, scroll_end)))
+    }
+
+    /// Freezes the visible channels in the displayed window as a ghost reference,
+    /// to compare the response of the device after changing it
+    pub fn take_snapshot(&mut self) {
+        let Some((start, end)) = self.displayed_window() else {
+            return;
+        };
+
+        let mut capture = ImportedCapture::default();
+        let mut colors = vec![];
+        for (samples, appearance) in self.samples_vec.iter().zip(&self.samples_appearance) {
+            if !appearance.visible {
+                continue;
+            }
+
+            capture.names.push(appearance.name.clone());
+            capture.units.push(appearance.unit.clone());
+            capture.samples_vec.push(
+                samples
+                    .iter()
+                    .filter(|s| s.time >= start && s.time <= end)
+                    .map(|s| Sample {
+                        time: s.time - start,
+                        ..s.clone()
+                    })
+                    .collect(),
+            );
+            colors.push(egui::Color32::from(appearance.color));
+        }
+
+        self.snapshot_count += 1;
+        self.references.push(ReferenceTrace::snapshot(
+            tr_args("snapshot-name", &[("n", &self.snapshot_count)]),
+            capture,
+            colors,
+        ));
+    }
+
+    /// The indices of the channels, sorted by the selected channel order
+    pub fn ordered_channels(&self) -> Vec<usize> {
+        let mut indices = (0..self.samples_vec.len()).collect::<Vec<usize>>();
+
+        match self.channel_order {
+            ChannelOrder::Arrival => {}
+            ChannelOrder::Name => {
+                indices.sort_by(|&a, &b| {
+                    self.samples_appearance[a]
+                        .name
+                        .cmp(&self.samples_appearance[b].name)
+                });
+            }
+            ChannelOrder::Activity => {
+                // The activity is measured relative to the newest sample of any channel
+                let newest = self
+                    .samples_vec
+                    .iter()
+                    .filter_map(|s| s.last().map(|s| s.time))
+                    .max()
+                    .unwrap_or(0);
+                let since = newest - secs_to_nanos(ACTIVITY_WINDOW_SECS);
+                let rates = self
+                    .samples_vec
+                    .iter()
+                    .map(|s| s.len() - s.partition_point(|s| s.time < since))
+                    .collect::<Vec<usize>>();
+
+                indices.sort_by(|&a, &b| rates[b].cmp(&rates[a]));
+            }
+            ChannelOrder::Magnitude => {
+                let magnitude = |i: usize| {
+                    self.samples_vec[i]
+                        .last()
+                        .map(|s| s.value.abs())
+                        .filter(|v| !v.is_nan())
+                        .unwrap_or(-1.0)
+                };
+
+                indices.sort_by(|&a, &b| magnitude(b).total_cmp(&magnitude(a)));
+            }
+        }
+
+        indices
+    }
+
+    /// Nothing is connected and no promises are pending, so the UI only needs to repaint on input
+    fn is_idle(&self) -> bool {
+        !self.is_connected
+            && self.promise_available_ports.is_none()
+            && self.promise_try_connect.is_none()
+            && self.promise_write.is_none()
+            && self.promise_close.is_none()
+    }
+
+    /// Starts or stops the HTTP API as configured and answers its pending requests
+    #[cfg(not(target_arch = "wasm32"))]
+    fn serve_http_api(&mut self, ctx: &egui::Context) {
+        if !self.http_api_enabled {
+            self.http_api.take();
+            return;
+        }
+
+        if self.http_api.is_none() {
+            match httpapi::HttpApi::start(
+                self.http_api_port,
+                httpapi::parse_origins(&self.data_server_origins),
+                ctx.clone(),
+            ) {
+                Ok(api) => {
+                    self.http_api = Some(api);
+                    self.http_api_error = None;
+                }
+                Err(e) => {
+                    log::error!("starting HTTP API failed, Err: {e}");
+                    self.http_api_enabled = false;
+                    self.http_api_error = Some(e.to_string());
+                    return;
+                }
+            }
+        }
+
+        let requests = self
+            .http_api
+            .as_ref()
+            .map(|api| api.pending_requests())
+            .unwrap_or_default();
+        for request in requests {
+            let (status, body) = self.http_api_response(&request);
+            request.respond(status, body);
+        }
+    }
+
+    /// Answers `/channels` with the channels and their latest sample,
+    /// and `/samples` with the samples newer than the `since` parameter, optionally of a single `channel` by index or name.
+    ///
+    /// Times are in seconds relative to the displayed t=0.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn http_api_response(&self, request: &httpapi::ApiRequest) -> (u16, String) {
+        use httpapi::{error_json, json_number, json_string};
+
+        let channel_json = |i: usize, samples_json: String| {
+            let appearance = &self.samples_appearance[i];
+            format!(
+                "{{\"index\":{i},\"name\":{},\"unit\":{},{samples_json}}}",
+                json_string(&appearance.name),
+                json_string(&appearance.unit),
+            )
+        };
+        let sample_json = |s: &Sample| {
+            format!(
+                "[{},{}]",
+                json_number(nanos_to_secs(s.time - self.time_zero)),
+                json_number(s.value)
+            )
+        };
+
+        match request.path.as_str() {
+            "/channels" => {
+                let channels = self
+                    .samples_vec
+                    .iter()
+                    .enumerate()
+                    .map(|(i, samples)| {
+                        channel_json(
+                            i,
+                            format!(
+                                "\"n_samples\":{},\"latest\":{}",
+                                samples.len(),
+                                samples.last().map_or(String::from("null"), sample_json)
+                            ),
+                        )
+                    })
+                    .collect::<Vec<String>>();
+
+                (200, format!("[{}]", channels.join(",")))
+            }
+            "/samples" => {
+                let since = match request.param("since").map(str::parse::<f64>) {
+                    Some(Ok(since)) => since,
+                    Some(Err(_)) => return (400, error_json("`since` must be a number")),
+                    None => f64::NEG_INFINITY,
+                };
+                let channel = match request.param("channel") {
+                    Some(channel) => match channel.parse::<usize>().ok().or_else(|| {
+                        self.samples_appearance
+                            .iter()
+                            .position(|a| a.name == channel)
+                    }) {
+                        Some(i) if i < self.samples_vec.len() => Some(i),
+                        _ => return (404, error_json("unknown channel")),
+                    },
+                    None => None,
+                };
+
+                let channels = self
+                    .samples_vec
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| channel.map_or(true, |c| c == *i))
+                    .map(|(i, samples)| {
+                        let samples = samples
+                            .iter()
+                            .filter(|s| nanos_to_secs(s.time - self.time_zero) > since)
+                            .map(sample_json)
+                            .collect::<Vec<String>>();
+                        channel_json(i, format!("\"samples\":[{}]", samples.join(",")))
+                    })
+                    .collect::<Vec<String>>();
+
+                (200, format!("{{\"channels\":[{}]}}", channels.join(",")))
+            }
+            _ => (
+                404,
+                error_json("unknown endpoint, use /channels or /samples"),
+            ),
+        }
+    }
+
+    /// Requests a screenshot, the displayed pages are copied to the clipboard when it arrives
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn copy_pages_image(&mut self, ctx: &egui::Context) {
+        self.screenshot_pending = true;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn poll_screenshot(&mut self, ctx: &egui::Context) {
+        if !self.screenshot_pending {
+            return;
+        }
+
+        let Some(image) = ctx.input(|i| {
+            i.events.iter().find_map(|e| match e {
+                egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        }) else {
+            return;
+        };
+        self.screenshot_pending = false;
+
+        let pixels_per_point = ctx.pixels_per_point();
+        let screen_rect = egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(image.width() as f32, image.height() as f32) / pixels_per_point,
+        );
+        let image = image.region(
+            &self.pages_rect.intersect(screen_rect),
+            Some(pixels_per_point),
+        );
+
+        self.copy_image_status = Some(
+            clipboard::copy_image(&image)
+                .map(|()| {
+                    tr_args(
+                        "status-copied-image",
+                        &[("width", &image.width()), ("height", &image.height())],
+                    )
+                })
+                .map_err(|e| {
+                    log::error!("copying image failed, Err: {e:?}");
+                    e.to_string()
+                }),
+        );
+    }
+
+    /// Needs to be called repeatedly to poll promises
+    pub fn async_tasks(&mut self, ctx: &egui::Context) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.poll_screenshot(ctx);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.serve_http_api(ctx);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if !self.sample_broadcast_enabled {
+            self.sample_broadcast.take();
+        } else if self.sample_broadcast.is_none() {
+            match websocket::Broadcast::start(
+                self.sample_broadcast_port,
+                httpapi::parse_origins(&self.data_server_origins),
+            ) {
+                Ok(broadcast) => {
+                    self.sample_broadcast = Some(broadcast);
+                    self.sample_broadcast_error = None;
+                }
+                Err(e) => {
+                    log::error!("starting sample broadcast failed, Err: {e}");
+                    self.sample_broadcast_enabled = false;
+                    self.sample_broadcast_error = Some(e.to_string());
+                }
+            }
+        }
+
+        // Retry finding the startup port, the device might not be plugged in yet
+        if self.startup_pending
+            && self.promise_available_ports.is_none()
+            && self.startup_last_attempt.elapsed() > Duration::from_secs(1)
+        {
+            self.startup_last_attempt = Instant::now();
+            self.available_ports(ctx);
+        }
+
+        // Poll for hotplugged devices
+        #[cfg(not(target_arch = "wasm32"))]
+        if !self.is_connected
+            && self.promise_available_ports.is_none()
+            && self.ports_last_refresh.elapsed() > PORTS_REFRESH_INTERVAL
+        {
+            self.available_ports(ctx);
+        }
+
+        // Retry the lost connection with backoff, refreshing the available ports for each attempt
+        if let Some(lost) = self.lost_connection.as_mut() {
+            if self.auto_reconnect
+                && !lost.ports_requested
+                && self.promise_available_ports.is_none()
+                && self.promise_try_connect.is_none()
+                && lost.last_attempt.elapsed() > lost.backoff()
+            {
+                lost.attempts += 1;
+                lost.last_attempt = Instant::now();
+                lost.ports_requested = true;
+                self.available_ports(ctx);
+            }
+        }
+
+        self.poll_available_ports(ctx);
+        self.poll_try_connect(ctx);
+        self.poll_close(ctx);
+        self.send_init_commands(ctx);
+
+        if self.is_connected {
+            let fired = self
+                .automation_rules
+                .iter_mut()
+                .filter_map(|rule| {
+                    rule.check_timer()
+                        .then(|| (rule.action, rule.argument.clone()))
+                })
+                .collect::<Vec<_>>();
+
+            for (action, argument) in fired {
+                self.run_automation_action(ctx, action, &argument);
+            }
+
+            let script_outputs = self.automation_script.on_timer();
+            self.run_script_outputs(ctx, script_outputs);
+        }
+
+        self.poll_write(ctx);
+
+        if !self.pause {
+            self.poll_read(ctx);
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.poll_parse_worker(ctx);
+        self.ingest_stats.update(self.start_time);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        poll_promise::tick_local();
+    }
+}
+
+impl eframe::App for SplotApp {
+    /// Called by the frame work to save state before shutdown.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.remember_channel_appearances();
+        eframe::set_value(storage, eframe::APP_KEY, self);
+    }
+
+    /// Called each time the UI needs repainting, which may be many times per second.
+    /// Put your widgets into a `SidePanel`, `TopPanel`, `CentralPanel`, `Window` or `Area`.
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.async_tasks(ctx);
+        self.import_dropped_files(ctx);
+        locale::set_language(self.language);
+
+        // The dark / light mode switch replaces the visuals, so the theme is applied again
+        let dark_mode = ctx.style().visuals.dark_mode;
+        if self.theme_applied != Some((self.theme, dark_mode)) {
+            let theme = self.theme;
+            ctx.style_mut(|style| theme.apply(style));
+            self.theme_applied = Some((theme, dark_mode));
+        }
+
+        self.draw_ui(ctx);
+
+        // repaint periodically, but rarely when idle, so that splot doesn't consume a core in the background
+        if self.is_idle() {
+            ctx.request_repaint_after(IDLE_REPAINT_INTERVAL);
+        } else {
+            ctx.request_repaint_after(instant::Duration::from_secs_f64(1.0 / 60.0));
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) static WEB_SERIAL_API_SUPPORTED: once_cell::sync::Lazy<bool> =
+    once_cell::sync::Lazy::new(|| {
+        let serial_itf = web_sys::window().unwrap().navigator().serial();
+
+        !serial_itf.is_undefined()
+    });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_channel_names_are_suffixed() {
+        let mut appearances: Vec<SamplesAppearance> = vec![];
+        for name in ["temp", "temp", "humidity", "temp"] {
+            let name = unique_channel_name(name.to_string(), &appearances);
+            appearances.push(SamplesAppearance::new(name));
+        }
+
+        let names = appearances
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["temp", "temp#2", "humidity", "temp#3"]);
+    }
+
+    #[test]
+    fn value_filter_applies_to_new_channels() {
+        use super::preset::ChannelAppearance;
+
+        let mut value_filter = ValueFilter::default();
+        value_filter.enabled = true;
+        value_filter.min = 0.0;
+        value_filter.max = 10.0;
+        // Remembered from before the channel was cleared
+        let mut app = super::SplotApp::default();
+        app.channel_appearances.insert(
+            String::from("Samples 00"),
+            ChannelAppearance {
+                name: String::from("Samples 00"),
+                value_filter,
+                ..Default::default()
+            },
+        );
+
+        let sample = |time, value| super::Sample {
+            time,
+            value,
+            name: None,
+        };
+        // The first sample of the new channel is out of range
+        app.add_samples(&mut [vec![sample(0, 50.0), sample(1, 5.0)]]);
+        app.add_samples(&mut [vec![sample(2, -3.0), sample(3, 7.0)]]);
+
+        assert_eq!(app.n_raw_samples, 1);
+        assert_eq!(
+            app.samples_vec[0]
+                .iter()
+                .map(|s| s.value)
+                .collect::<Vec<_>>(),
+            [5.0, 7.0]
+        );
+        assert_eq!(app.samples_appearance[0].value_filter.rejected(), 2);
+    }
+
+    #[test]
+    fn dock_layout_persisted() {
+        let mut dock_state = default_dock_state();
+        dock_state.push_to_focused_leaf(DockTab::Page(PlotPage::SerialMonitor));
+
+        let text = ron::to_string(&dock_state).unwrap();
+        let restored: egui_dock::DockState<DockTab> = ron::from_str(&text).unwrap();
+
+        let tabs = |dock_state: &egui_dock::DockState<DockTab>| {
+            dock_state
+                .iter_all_tabs()
+                .map(|(location, tab)| (location, *tab))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(tabs(&restored), tabs(&dock_state));
+        assert!(restored.find_tab(&DockTab::Connection).is_some());
+    }
+
+    #[test]
+    fn settings_of_the_sidebar_layout_load() {
+        // Saved before the page settings moved from sidebars into dock tabs
+        let app: super::SplotApp =
+            ron::from_str("(baudrate: 9600, sidebar_side: Right, sidebar_visible: false)").unwrap();
+
+        assert_eq!(app.baudrate, 9600);
+        assert!(app.is_dock_tab_open(DockTab::Page(PlotPage::TimeValue)));
+        assert!(app.is_dock_tab_open(DockTab::Controls(PlotPage::TimeValue)));
+    }
+}
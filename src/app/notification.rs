@@ -0,0 +1,75 @@
+use std::process::Command;
+
+/// Shows an OS notification, with the tools each platform ships with.
+///
+/// The command runs in the background, failures are only logged.
+pub fn show(title: &str, body: &str) -> anyhow::Result<()> {
+    let mut command = notification_command(title, body);
+    let mut child = command
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("failed to spawn notification command, Err: {e}"))?;
+
+    // Reap the process, so that it doesn't linger
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => {
+            log::warn!("notification command failed with {status}");
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("failed to wait for notification command, Err: {e}"),
+    });
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn notification_command(title: &str, body: &str) -> Command {
+    let mut command = Command::new("notify-send");
+    command.args(["--app-name=splot", title, body]);
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn notification_command(title: &str, body: &str) -> Command {
+    let mut command = Command::new("osascript");
+    command.args([
+        "-e",
+        &format!(
+            "display notification {} with title {}",
+            apple_script_string(body),
+            apple_script_string(title)
+        ),
+    ]);
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn apple_script_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(target_os = "windows")]
+fn notification_command(title: &str, body: &str) -> Command {
+    // The texts are passed through the environment, to avoid escaping them for PowerShell
+    let script = "\
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null;
+$xml = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02);
+$texts = $xml.GetElementsByTagName('text');
+$texts.Item(0).AppendChild($xml.CreateTextNode($env:SPLOT_NOTIFICATION_TITLE)) > $null;
+$texts.Item(1).AppendChild($xml.CreateTextNode($env:SPLOT_NOTIFICATION_BODY)) > $null;
+$toast = [Windows.UI.Notifications.ToastNotification]::new($xml);
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('splot').Show($toast);";
+
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .env("SPLOT_NOTIFICATION_TITLE", title)
+        .env("SPLOT_NOTIFICATION_BODY", body);
+    command
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn notification_command(title: &str, body: &str) -> Command {
+    let mut command = Command::new("notify-send");
+    command.args([title, body]);
+    command
+}
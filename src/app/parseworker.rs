@@ -0,0 +1,159 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::ParseFormat;
+use crate::parser::worker::{self, WorkerRequest, WorkerResponse};
+
+/// The loader of the worker, generated by trunk next to the app
+const WORKER_URL: &str = "./parser_worker_loader.js";
+
+/// Parses the read chunks in a Web Worker, so that heavy parsing doesn't block the rendering.
+///
+/// The results are received asynchronously and merged by the app.
+pub struct ParseWorker {
+    worker: web_sys::Worker,
+    responses: Rc<RefCell<VecDeque<WorkerResponse>>>,
+    /// Set when the worker failed to load or crashed, the app parses on the main thread then
+    failed: Rc<Cell<bool>>,
+    /// Increased when the parser is cleared, the results of chunks read before are dropped
+    generation: u64,
+    n_pending_bytes: usize,
+    time_sync: Option<(i64, f64)>,
+    _onmessage: Closure<dyn FnMut(web_sys::MessageEvent)>,
+    _onerror: Closure<dyn FnMut(web_sys::ErrorEvent)>,
+}
+
+impl ParseWorker {
+    pub fn new(ctx: &egui::Context) -> anyhow::Result<Self> {
+        let worker = web_sys::Worker::new(WORKER_URL)
+            .map_err(|e| anyhow::anyhow!("failed to start the parser worker, Err: {e:?}"))?;
+        let responses = Rc::new(RefCell::new(VecDeque::new()));
+        let failed = Rc::new(Cell::new(false));
+
+        let onmessage = {
+            let responses = responses.clone();
+            let ctx = ctx.clone();
+
+            Closure::<dyn FnMut(_)>::new(move |event: web_sys::MessageEvent| {
+                let bytes = js_sys::Uint8Array::new(&event.data()).to_vec();
+                match worker::decode::<WorkerResponse>(&bytes) {
+                    Ok(response) => responses.borrow_mut().push_back(response),
+                    Err(e) => log::error!("failed to decode parser worker response, Err: {e}"),
+                }
+                ctx.request_repaint();
+            })
+        };
+        let onerror = {
+            let failed = failed.clone();
+            let ctx = ctx.clone();
+
+            Closure::<dyn FnMut(_)>::new(move |event: web_sys::ErrorEvent| {
+                log::error!("parser worker failed, Err: {}", event.message());
+                failed.set(true);
+                ctx.request_repaint();
+            })
+        };
+        worker.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        worker.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+
+        Ok(Self {
+            worker,
+            responses,
+            failed,
+            generation: 0,
+            n_pending_bytes: 0,
+            time_sync: None,
+            _onmessage: onmessage,
+            _onerror: onerror,
+        })
+    }
+
+    pub fn has_failed(&self) -> bool {
+        self.failed.get()
+    }
+
+    /// The received bytes that the worker has not parsed yet
+    pub fn n_pending_bytes(&self) -> usize {
+        self.n_pending_bytes
+    }
+
+    /// The offset in nanoseconds and the drift in ppm of the device time sync, when enabled
+    pub fn time_sync(&self) -> Option<(i64, f64)> {
+        self.time_sync
+    }
+
+    /// Posts a read chunk to be parsed.
+    /// `now` is the receive time in nanoseconds since the start of the connection.
+    pub fn parse(
+        &self,
+        data: &[u8],
+        format: ParseFormat,
+        time_unwrap: bool,
+        time_sync: bool,
+        now: i64,
+    ) {
+        self.post(&WorkerRequest::Parse {
+            generation: self.generation,
+            data: data.to_vec(),
+            format,
+            time_unwrap,
+            time_sync,
+            now,
+        });
+    }
+
+    /// Clears the parser of the worker, the results of the chunks posted before are dropped
+    pub fn clear(&mut self) {
+        self.generation += 1;
+        self.n_pending_bytes = 0;
+        self.post(&WorkerRequest::Clear);
+    }
+
+    /// Lays out the channels again, the results of the chunks posted before are dropped
+    pub fn reset_channels(&mut self) {
+        self.generation += 1;
+        self.post(&WorkerRequest::ResetChannels);
+    }
+
+    /// Takes the received results of the chunks posted since the last clear, in the order they were posted
+    pub fn take_responses(&mut self) -> Vec<WorkerResponse> {
+        let responses = self
+            .responses
+            .borrow_mut()
+            .drain(..)
+            .filter(|response| response.generation == self.generation)
+            .collect::<Vec<_>>();
+        if let Some(last) = responses.last() {
+            self.n_pending_bytes = last.n_pending_bytes;
+            self.time_sync = last.time_sync;
+        }
+
+        responses
+    }
+
+    fn post(&self, request: &WorkerRequest) {
+        let bytes = match worker::encode(request) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                log::error!("failed to encode parser worker request, Err: {e}");
+                return;
+            }
+        };
+        let array = js_sys::Uint8Array::from(bytes.as_slice());
+        let transfer = js_sys::Array::of1(&array.buffer());
+
+        if let Err(e) = self.worker.post_message_with_transfer(&array, &transfer) {
+            log::error!("failed to post parser worker request, Err: {e:?}");
+        }
+    }
+}
+
+impl Drop for ParseWorker {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
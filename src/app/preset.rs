@@ -0,0 +1,106 @@
+use super::smoothing::SmoothingMode;
+use super::valuefilter::ValueFilter;
+use super::{PlotAxis, SamplesAppearance};
+
+/// The appearance of a channel in a preset
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ChannelAppearance {
+    pub name: String,
+    /// The color as sRGB hex string, e.g. `#ff8000ff`
+    pub color: String,
+    pub unit: String,
+    pub axis: PlotAxis,
+    pub visible: bool,
+    pub hold_last: bool,
+    pub fill: bool,
+    pub smoothing: SmoothingMode,
+    pub smoothing_window: usize,
+    /// The plausible range of the values
+    pub value_filter: ValueFilter,
+}
+
+impl Default for ChannelAppearance {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            color: String::from("#ffffffff"),
+            unit: String::new(),
+            axis: PlotAxis::default(),
+            visible: true,
+            hold_last: false,
+            fill: false,
+            smoothing: SmoothingMode::default(),
+            smoothing_window: 10,
+            value_filter: ValueFilter::default(),
+        }
+    }
+}
+
+impl From<&SamplesAppearance> for ChannelAppearance {
+    fn from(appearance: &SamplesAppearance) -> Self {
+        Self {
+            name: appearance.name.clone(),
+            color: egui::Color32::from(appearance.color).to_hex(),
+            unit: appearance.unit.clone(),
+            axis: appearance.axis,
+            visible: appearance.visible,
+            hold_last: appearance.hold_last,
+            fill: appearance.fill,
+            smoothing: appearance.smoothing,
+            smoothing_window: appearance.smoothing_window,
+            value_filter: appearance.value_filter.clone(),
+        }
+    }
+}
+
+impl ChannelAppearance {
+    pub fn apply(&self, appearance: &mut SamplesAppearance) {
+        appearance.name = self.name.clone();
+        if let Ok(color) = egui::Color32::from_hex(&self.color) {
+            appearance.color = color.into();
+        }
+        appearance.unit = self.unit.clone();
+        appearance.axis = self.axis;
+        appearance.visible = self.visible;
+        appearance.hold_last = self.hold_last;
+        appearance.fill = self.fill;
+        appearance.smoothing = self.smoothing;
+        appearance.smoothing_window = self.smoothing_window;
+        appearance.value_filter = self.value_filter.clone();
+    }
+}
+
+/// The appearance of a set of channels, so that everyone plotting the same firmware shares a common convention.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AppearancePreset {
+    pub channels: Vec<ChannelAppearance>,
+}
+
+impl AppearancePreset {
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// The appearance for a channel, matched by its name or otherwise by its index
+    pub fn find(&self, index: usize, name: &str) -> Option<&ChannelAppearance> {
+        self.channels
+            .iter()
+            .find(|c| c.name == name)
+            .or_else(|| self.channels.get(index))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(ron::from_str(&text)?)
+    }
+}
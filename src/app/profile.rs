@@ -0,0 +1,60 @@
+use instant::Duration;
+
+use super::binarylayout::BinaryLayout;
+use super::can::CanSignal;
+use super::preset::AppearancePreset;
+use super::ubx::UbxMessage;
+use super::{DataFormat, StartupActions, TimeUnit};
+use crate::serialconnection::{DataBits, FlowControl, Parity, StopBits};
+
+/// The connection, parser and channel appearance settings for a device,
+/// so that switching between devices is one click.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ConnectionProfile {
+    pub name: String,
+    /// The name of the port, selected when the profile is loaded and the port is available
+    pub port: String,
+    pub baudrate: u32,
+    pub timeout: Duration,
+    pub data_bits: DataBits,
+    pub flow_control: FlowControl,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+    pub time_unit: TimeUnit,
+    pub unwrap_device_time: bool,
+    pub sync_device_time: bool,
+    pub value_separator: char,
+    pub data_format: DataFormat,
+    pub binary_layout: BinaryLayout,
+    pub ubx_messages: Vec<UbxMessage>,
+    pub can_signals: Vec<CanSignal>,
+    pub appearance: AppearancePreset,
+    /// Executed when the profile is loaded, and at startup when its port is available
+    pub startup_actions: StartupActions,
+}
+
+impl Default for ConnectionProfile {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            port: String::new(),
+            baudrate: 115200,
+            timeout: Duration::from_millis(5000),
+            data_bits: DataBits::default(),
+            flow_control: FlowControl::default(),
+            parity: Parity::default(),
+            stop_bits: StopBits::default(),
+            time_unit: TimeUnit::default(),
+            unwrap_device_time: true,
+            sync_device_time: false,
+            value_separator: ',',
+            data_format: DataFormat::default(),
+            binary_layout: BinaryLayout::default(),
+            ubx_messages: vec![UbxMessage::NavPvt],
+            can_signals: vec![CanSignal::default()],
+            appearance: AppearancePreset::default(),
+            startup_actions: StartupActions::default(),
+        }
+    }
+}
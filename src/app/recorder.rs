@@ -0,0 +1,56 @@
+use std::io::Write;
+
+/// Records the raw received data into a file.
+#[derive(Debug)]
+pub struct Recorder {
+    writer: std::io::BufWriter<std::fs::File>,
+    path: std::path::PathBuf,
+    bytes_written: u64,
+}
+
+impl Recorder {
+    /// Starts recording into the file at the given path. An existing file is appended to.
+    pub fn start(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| {
+                anyhow::anyhow!("failed to open record file `{}`, Err: {e}", path.display())
+            })?;
+
+        log::debug!("started recording to `{}`", path.display());
+
+        Ok(Self {
+            writer: std::io::BufWriter::new(file),
+            path,
+            bytes_written: 0,
+        })
+    }
+
+    pub fn write(&mut self, data: &[u8]) -> anyhow::Result<()> {
+        self.writer.write_all(data)?;
+        self.bytes_written += data.len() as u64;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Err(e) = self.writer.flush() {
+            log::error!(
+                "failed to flush record file `{}`, Err: {e}",
+                self.path.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use super::archive::ArchivedCapture;
+use super::import::ImportedCapture;
+use super::nanos_to_secs;
+
+/// A saved capture that is drawn frozen behind the live data of the Time-Value plot,
+/// to compare the current behavior of the device against a known-good run.
+///
+/// The times of the capture are placed relative to the displayed t=0.
+#[derive(Debug, Clone)]
+pub struct ReferenceTrace {
+    pub label: String,
+    pub capture: ImportedCapture,
+    pub color: egui::Color32,
+    /// From transparent at 0 to opaque at 1
+    pub opacity: f32,
+    /// Shifts the reference on the time axis, in seconds
+    pub offset: f64,
+    pub visible: bool,
+    /// The colors of the channels, drawn in `color` when empty
+    pub channel_colors: Vec<egui::Color32>,
+    /// The times are relative to the start of the displayed window instead of t=0,
+    /// so that the trace stays in view while the plot scrolls
+    pub follows_view: bool,
+}
+
+impl ReferenceTrace {
+    pub fn new(label: String, capture: ImportedCapture) -> Self {
+        Self {
+            label,
+            capture,
+            color: egui::Color32::GRAY,
+            opacity: 0.5,
+            offset: 0.0,
+            visible: true,
+            channel_colors: vec![],
+            follows_view: false,
+        }
+    }
+
+    /// A snapshot of the displayed traces, with the times relative to the start of the window
+    /// and the colors of the channels
+    pub fn snapshot(
+        label: String,
+        capture: ImportedCapture,
+        channel_colors: Vec<egui::Color32>,
+    ) -> Self {
+        Self {
+            opacity: 0.35,
+            channel_colors,
+            follows_view: true,
+            ..Self::new(label, capture)
+        }
+    }
+
+    /// The archived capture, with its t=0 at the displayed t=0
+    pub fn from_archived(archived: &ArchivedCapture) -> Self {
+        Self {
+            offset: -nanos_to_secs(archived.time_zero),
+            ..Self::new(archived.label.clone(), archived.capture.clone())
+        }
+    }
+
+    pub fn line_color(&self, channel: usize) -> egui::Color32 {
+        self.channel_colors
+            .get(channel)
+            .copied()
+            .unwrap_or(self.color)
+            .gamma_multiply(self.opacity)
+    }
+
+    /// The points of the channel between the x values, in seconds relative to the displayed t=0
+    pub fn points(&self, channel: usize, x_start: f64, x_end: f64) -> Vec<[f64; 2]> {
+        let Some(samples) = self.capture.samples_vec.get(channel) else {
+            return vec![];
+        };
+        let offset = if self.follows_view {
+            self.offset + x_start
+        } else {
+            self.offset
+        };
+
+        samples
+            .iter()
+            .map(|s| [nanos_to_secs(s.time) + offset, s.value])
+            .filter(|[x, _]| *x >= x_start && *x <= x_end)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_trace_points() {
+        let capture =
+            crate::app::import::parse_csv_capture("Time [s],x\n0,1\n1,2\n2,3\n3,4\n").unwrap();
+        let mut reference = ReferenceTrace::new(String::from("good"), capture);
+        reference.offset = 10.0;
+
+        assert_eq!(reference.points(0, 11.0, 12.5), [[11.0, 2.0], [12.0, 3.0]]);
+        assert!(reference.points(1, 0.0, 20.0).is_empty());
+    }
+
+    #[test]
+    fn snapshot_follows_view() {
+        let capture = crate::app::import::parse_csv_capture("Time [s],x\n0,1\n1,2\n2,3\n").unwrap();
+        let snapshot = ReferenceTrace::snapshot(
+            String::from("Snapshot 1"),
+            capture,
+            vec![egui::Color32::RED],
+        );
+
+        // Placed at the start of the displayed window
+        assert_eq!(snapshot.points(0, 5.0, 6.5), [[5.0, 1.0], [6.0, 2.0]]);
+        assert_eq!(
+            snapshot.line_color(0),
+            egui::Color32::RED.gamma_multiply(snapshot.opacity)
+        );
+    }
+}
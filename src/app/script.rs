@@ -0,0 +1,321 @@
+use super::automation::RuleAction;
+use instant::{Duration, Instant};
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Limits the operations of a single hook call, so that an endless loop doesn't freeze the app
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// What a script requested, run by the app after the hook returned
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptOutput {
+    /// Runs the action like a fired automation rule
+    Action(RuleAction, String),
+    /// Creates the derived channel with the name and expression, or changes its expression
+    Derive(String, String),
+}
+
+/// An automation script in Rhai, for logic that doesn't fit into the rules.
+///
+/// The script defines the hooks it needs, `on_line(line)`, `on_samples(values)` and `on_timer()`.
+/// They call `send(text)`, `marker(label)`, `notify(text)`, `start_recording()`, `stop_recording()`
+/// and `derive(name, expression)`. `this` is a map that keeps values between the calls.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AutomationScript {
+    pub enabled: bool,
+    pub source: String,
+    /// The interval of the `on_timer()` hook
+    pub interval_ms: u64,
+    #[serde(skip)]
+    compiled: Option<Result<CompiledScript, String>>,
+    /// The error of the last hook call
+    #[serde(skip)]
+    runtime_error: Option<String>,
+    #[serde(skip)]
+    last_timer: Option<Instant>,
+}
+
+impl Default for AutomationScript {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source: String::new(),
+            interval_ms: 1000,
+            compiled: None,
+            runtime_error: None,
+            last_timer: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CompiledScript {
+    engine: Engine,
+    ast: AST,
+    /// Bound to `this` in the hooks
+    state: Dynamic,
+    /// Collects the outputs of the registered functions
+    outputs: Rc<RefCell<Vec<ScriptOutput>>>,
+}
+
+impl CompiledScript {
+    /// Compiles the script and runs its top-level statements once, e.g. to create derived channels
+    fn new(source: &str) -> Result<Self, String> {
+        let outputs = Rc::new(RefCell::new(vec![]));
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.on_print(|text| log::info!("script: {text}"));
+        engine.on_debug(|text, _, pos| log::debug!("script at {pos}: {text}"));
+
+        for (name, action) in [
+            ("send", RuleAction::Send),
+            ("marker", RuleAction::Marker),
+            ("notify", RuleAction::Notify),
+        ] {
+            let outputs = outputs.clone();
+            engine.register_fn(name, move |text: &str| {
+                outputs
+                    .borrow_mut()
+                    .push(ScriptOutput::Action(action, text.to_string()));
+            });
+        }
+        for (name, action) in [
+            ("start_recording", RuleAction::StartRecording),
+            ("stop_recording", RuleAction::StopRecording),
+        ] {
+            let outputs = outputs.clone();
+            engine.register_fn(name, move || {
+                outputs
+                    .borrow_mut()
+                    .push(ScriptOutput::Action(action, String::new()));
+            });
+        }
+        {
+            let outputs = outputs.clone();
+            engine.register_fn("derive", move |name: &str, expression: &str| {
+                outputs.borrow_mut().push(ScriptOutput::Derive(
+                    name.to_string(),
+                    expression.to_string(),
+                ));
+            });
+        }
+
+        let ast = engine.compile(source).map_err(|e| e.to_string())?;
+        engine
+            .run_ast_with_scope(&mut Scope::new(), &ast)
+            .map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            engine,
+            ast,
+            state: Dynamic::from_map(Map::new()),
+            outputs,
+        })
+    }
+
+    fn has_fn(&self, name: &str, n_params: usize) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|f| f.name == name && f.params.len() == n_params)
+    }
+}
+
+impl AutomationScript {
+    /// Needs to be called when the source was changed, the state in `this` is discarded
+    pub fn invalidate(&mut self) {
+        self.compiled = None;
+        self.runtime_error = None;
+        self.last_timer = None;
+    }
+
+    /// The compile error, or the error of the last hook call
+    pub fn error(&self) -> Option<&str> {
+        match &self.compiled {
+            Some(Err(e)) => Some(e),
+            _ => self.runtime_error.as_deref(),
+        }
+    }
+
+    /// Compiles the script if needed. Returns the outputs of its top-level statements.
+    pub fn compile(&mut self) -> Vec<ScriptOutput> {
+        if !self.enabled || self.compiled.is_some() {
+            return vec![];
+        }
+
+        let compiled = CompiledScript::new(&self.source);
+        if let Err(e) = &compiled {
+            log::warn!("failed to compile automation script, Err: {e}");
+        }
+        let outputs = compiled
+            .as_ref()
+            .map(|c| c.outputs.take())
+            .unwrap_or_default();
+        self.compiled = Some(compiled);
+
+        outputs
+    }
+
+    /// Calls `on_line(line)` with a received line
+    pub fn on_line(&mut self, line: &str) -> Vec<ScriptOutput> {
+        self.call("on_line", vec![Dynamic::from(line.to_string())])
+    }
+
+    /// Calls `on_samples(values)` with the latest values of the channels, keyed by the channel name
+    pub fn on_samples(&mut self, names: &[&str], values: &[Option<f64>]) -> Vec<ScriptOutput> {
+        let values = names
+            .iter()
+            .zip(values)
+            .filter_map(|(name, value)| Some(((*name).into(), Dynamic::from_float((*value)?))))
+            .collect::<Map>();
+
+        self.call("on_samples", vec![Dynamic::from_map(values)])
+    }
+
+    /// Calls `on_timer()` when the interval has elapsed since the last call
+    pub fn on_timer(&mut self) -> Vec<ScriptOutput> {
+        let now = Instant::now();
+        let last_timer = *self.last_timer.get_or_insert(now);
+
+        // A zero interval would fire every frame
+        if now.duration_since(last_timer) < Duration::from_millis(self.interval_ms.max(10)) {
+            return vec![];
+        }
+
+        self.last_timer = Some(now);
+        self.call("on_timer", vec![])
+    }
+
+    fn call(&mut self, name: &str, args: Vec<Dynamic>) -> Vec<ScriptOutput> {
+        let mut outputs = self.compile();
+        let Some(Ok(compiled)) = &mut self.compiled else {
+            return outputs;
+        };
+        if !self.enabled || !compiled.has_fn(name, args.len()) {
+            return outputs;
+        }
+
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut compiled.state);
+        let res = compiled.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            &compiled.ast,
+            name,
+            args,
+        );
+        match res {
+            Ok(_) => self.runtime_error = None,
+            Err(e) => {
+                log::warn!("automation script `{name}` failed, Err: {e}");
+                self.runtime_error = Some(format!("{name}: {e}"));
+            }
+        }
+
+        outputs.extend(compiled.outputs.take());
+        outputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(source: &str) -> AutomationScript {
+        AutomationScript {
+            enabled: true,
+            source: source.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn script_hooks() {
+        let mut script = script(
+            r#"
+    derive("power", "volts * amps");
+
+    fn on_line(line) {
+        if line.contains("ready") {
+            send("start");
+        }
+    }
+
+    fn on_samples(values) {
+        if values.volts > 3.0 {
+            marker("high " + values.volts);
+        }
+    }
+    "#,
+        );
+
+        assert_eq!(
+            script.compile(),
+            [ScriptOutput::Derive("power".into(), "volts * amps".into())]
+        );
+        assert_eq!(script.on_line("booting"), []);
+        assert_eq!(
+            script.on_line("device ready"),
+            [ScriptOutput::Action(RuleAction::Send, "start".into())]
+        );
+        assert_eq!(
+            script.on_samples(&["volts", "amps"], &[Some(3.5), None]),
+            [ScriptOutput::Action(RuleAction::Marker, "high 3.5".into())]
+        );
+        // A missing hook is not an error
+        assert_eq!(script.on_timer(), []);
+        assert_eq!(script.error(), None);
+    }
+
+    #[test]
+    fn script_keeps_state() {
+        let mut script = script(
+            r#"
+    fn on_line(line) {
+        if this.count == () {
+            this.count = 0;
+        }
+        this.count += 1;
+        if this.count == 3 {
+            notify("3 lines");
+        }
+    }
+    "#,
+        );
+
+        assert_eq!(script.on_line("a"), []);
+        assert_eq!(script.on_line("b"), []);
+        assert_eq!(
+            script.on_line("c"),
+            [ScriptOutput::Action(RuleAction::Notify, "3 lines".into())]
+        );
+
+        script.invalidate();
+        assert_eq!(script.on_line("d"), []);
+    }
+
+    #[test]
+    fn script_errors() {
+        let mut disabled = script("send(\"hi\");");
+        disabled.enabled = false;
+        assert_eq!(disabled.compile(), []);
+        assert_eq!(disabled.on_line("a"), []);
+
+        let mut syntax = script("fn on_line(line) {");
+        assert_eq!(syntax.on_line("a"), []);
+        assert!(syntax.error().is_some());
+
+        let mut runtime = script("fn on_line(line) { unknown_fn(line) }");
+        assert_eq!(runtime.on_line("a"), []);
+        assert!(runtime.error().unwrap().contains("unknown_fn"));
+
+        // Endless loops are stopped by the operation limit
+        let mut endless = script("fn on_line(line) { loop { send(line); } }");
+        let start = Instant::now();
+        endless.on_line("a");
+        assert!(endless.error().is_some());
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,188 @@
+use super::import::ImportedCapture;
+use super::locale::tr;
+use super::{nanos_to_secs, Sample};
+
+/// How the two captures are aligned in time
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum DiffAlignment {
+    /// The first samples of both captures are at the same time
+    #[default]
+    Start,
+    /// The first rising crossing of the trigger level on the trigger channel is at the same time
+    Trigger,
+}
+
+impl std::fmt::Display for DiffAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiffAlignment::Start => write!(f, "Start"),
+            DiffAlignment::Trigger => write!(f, "Trigger"),
+        }
+    }
+}
+
+impl DiffAlignment {
+    pub const ALL: [Self; 2] = [Self::Start, Self::Trigger];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            DiffAlignment::Start => tr("diff-alignment-start"),
+            DiffAlignment::Trigger => tr("diff-alignment-trigger"),
+        }
+    }
+}
+
+/// The difference of a channel of the second capture to the same channel of the first capture
+#[derive(Debug, Clone)]
+pub struct ChannelDiff {
+    pub name: String,
+    /// The time in seconds since the alignment point and the difference
+    pub points: Vec<[f64; 2]>,
+    pub mean: f64,
+    pub rms: f64,
+    pub max_abs: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionDiff {
+    /// The time the second capture was shifted by, in seconds
+    pub offset_secs: f64,
+    pub channels: Vec<ChannelDiff>,
+}
+
+/// Compares the channels of two captures.
+///
+/// Channels are paired by name, falling back to their position. The second capture is
+/// interpolated at the times of the first one, only the overlapping time range is compared.
+pub fn diff_captures(
+    a: &ImportedCapture,
+    b: &ImportedCapture,
+    alignment: DiffAlignment,
+    trigger_channel: usize,
+    trigger_level: f64,
+) -> anyhow::Result<SessionDiff> {
+    let (origin_a, origin_b) = match alignment {
+        DiffAlignment::Start => (start_time(a), start_time(b)),
+        DiffAlignment::Trigger => (
+            trigger_time(a, trigger_channel, trigger_level),
+            trigger_time(b, trigger_channel, trigger_level),
+        ),
+    };
+    let (Some(origin_a), Some(origin_b)) = (origin_a, origin_b) else {
+        return Err(anyhow::anyhow!(
+            "can't align the captures, the {alignment} alignment point is missing"
+        ));
+    };
+
+    let channels = a
+        .names
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| {
+            let j = b.names.iter().position(|n| n == name).unwrap_or(i);
+            let (samples_a, samples_b) = (a.samples_vec.get(i)?, b.samples_vec.get(j)?);
+
+            let points = samples_a
+                .iter()
+                .filter_map(|s| {
+                    let time = s.time - origin_a;
+                    let value_b = interpolate(samples_b, time + origin_b)?;
+
+                    Some([nanos_to_secs(time), value_b - s.value])
+                })
+                .collect::<Vec<[f64; 2]>>();
+
+            if points.is_empty() {
+                return None;
+            }
+
+            let n = points.len() as f64;
+
+            Some(ChannelDiff {
+                name: name.clone(),
+                mean: points.iter().map(|p| p[1]).sum::<f64>() / n,
+                rms: (points.iter().map(|p| p[1] * p[1]).sum::<f64>() / n).sqrt(),
+                max_abs: points.iter().map(|p| p[1].abs()).fold(0.0, f64::max),
+                points,
+            })
+        })
+        .collect::<Vec<ChannelDiff>>();
+
+    if channels.is_empty() {
+        return Err(anyhow::anyhow!("the captures have no overlapping channels"));
+    }
+
+    Ok(SessionDiff {
+        offset_secs: nanos_to_secs(origin_b - origin_a),
+        channels,
+    })
+}
+
+fn start_time(capture: &ImportedCapture) -> Option<i64> {
+    capture
+        .samples_vec
+        .iter()
+        .filter_map(|s| s.first().map(|s| s.time))
+        .min()
+}
+
+fn trigger_time(capture: &ImportedCapture, channel: usize, level: f64) -> Option<i64> {
+    capture
+        .samples_vec
+        .get(channel)?
+        .windows(2)
+        .find(|w| w[0].value < level && w[1].value >= level)
+        .map(|w| w[1].time)
+}
+
+/// Interpolates linearly between the samples, which need to be sorted by time
+fn interpolate(samples: &[Sample], time: i64) -> Option<f64> {
+    let i = samples.partition_point(|s| s.time < time);
+    let next = samples.get(i)?;
+
+    if next.time == time {
+        return Some(next.value);
+    }
+
+    let prev = samples.get(i.checked_sub(1)?)?;
+    let t = (time - prev.time) as f64 / (next.time - prev.time) as f64;
+
+    Some(prev.value + (next.value - prev.value) * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_diff_trigger_alignment() {
+        use crate::app::import::parse_csv_capture;
+        let a = parse_csv_capture("Time [s],x\n0,0\n1,0\n2,5\n3,5\n4,5\n").unwrap();
+        // The same signal, one second later and one higher
+        let b = parse_csv_capture("Time [s],x\n0,1\n1,1\n2,1\n3,6\n4,6\n5,6\n").unwrap();
+
+        let diff = diff_captures(&a, &b, DiffAlignment::Trigger, 0, 2.5).unwrap();
+
+        assert_eq!(diff.offset_secs, 1.0);
+        assert_eq!(diff.channels.len(), 1);
+        assert_eq!(
+            diff.channels[0].points,
+            vec![[-2.0, 1.0], [-1.0, 1.0], [0.0, 1.0], [1.0, 1.0], [2.0, 1.0]]
+        );
+        assert_eq!(diff.channels[0].max_abs, 1.0);
+
+        assert!(diff_captures(&a, &b, DiffAlignment::Trigger, 0, 10.0).is_err());
+    }
+}
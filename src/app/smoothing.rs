@@ -0,0 +1,81 @@
+use super::locale::tr;
+
+/// How the moving average of a channel is displayed. The samples themselves are not changed.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum SmoothingMode {
+    #[default]
+    Off,
+    /// The average is drawn over the faded raw trace
+    Alongside,
+    /// Only the average is drawn
+    Instead,
+}
+
+impl std::fmt::Display for SmoothingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SmoothingMode::Off => write!(f, "Off"),
+            SmoothingMode::Alongside => write!(f, "Alongside"),
+            SmoothingMode::Instead => write!(f, "Instead"),
+        }
+    }
+}
+
+impl SmoothingMode {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Alongside, Self::Instead];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            SmoothingMode::Off => tr("smoothing-mode-off"),
+            SmoothingMode::Alongside => tr("smoothing-mode-alongside"),
+            SmoothingMode::Instead => tr("smoothing-mode-instead"),
+        }
+    }
+}
+
+/// The trailing moving average of the y values over the window, in number of points.
+///
+/// The first points are averaged over the points available so far.
+pub fn moving_average(points: &[[f64; 2]], window: usize) -> Vec<[f64; 2]> {
+    let window = window.max(1);
+    let mut sum = 0.0;
+
+    points
+        .iter()
+        .enumerate()
+        .map(|(k, p)| {
+            sum += p[1];
+            if k >= window {
+                sum -= points[k - window][1];
+            }
+
+            [p[0], sum / (k + 1).min(window) as f64]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn moving_average() {
+        let points = [[0.0, 1.0], [1.0, 3.0], [2.0, 5.0], [3.0, 10.0]];
+
+        assert_eq!(
+            super::moving_average(&points, 2),
+            [[0.0, 1.0], [1.0, 2.0], [2.0, 4.0], [3.0, 7.5]]
+        );
+        assert_eq!(super::moving_average(&points, 1), points);
+    }
+}
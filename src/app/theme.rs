@@ -0,0 +1,123 @@
+use super::locale::tr;
+
+/// How much space is around and between the widgets
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum Density {
+    Compact,
+    #[default]
+    Normal,
+    Comfortable,
+}
+
+impl std::fmt::Display for Density {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Density::Compact => write!(f, "Compact"),
+            Density::Normal => write!(f, "Normal"),
+            Density::Comfortable => write!(f, "Comfortable"),
+        }
+    }
+}
+
+impl Density {
+    pub const ALL: [Self; 3] = [Self::Compact, Self::Normal, Self::Comfortable];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            Density::Compact => tr("density-compact"),
+            Density::Normal => tr("density-normal"),
+            Density::Comfortable => tr("density-comfortable"),
+        }
+    }
+
+    fn factor(self) -> f32 {
+        match self {
+            Density::Compact => 0.6,
+            Density::Normal => 1.0,
+            Density::Comfortable => 1.5,
+        }
+    }
+}
+
+/// Customizations layered on top of the egui dark or light style
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// Replace the accent color of the style
+    pub custom_accent: bool,
+    /// The accent color as sRGB
+    pub accent: [u8; 3],
+    /// The corner rounding of widgets. Windows are rounded more
+    pub rounding: f32,
+    pub density: Density,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            custom_accent: false,
+            accent: [0, 92, 128],
+            rounding: 2.0,
+            density: Density::default(),
+        }
+    }
+}
+
+impl Theme {
+    /// Applies the theme to the style. All values are set absolute, so it can be applied repeatedly.
+    pub fn apply(&self, style: &mut egui::Style) {
+        let default_spacing = egui::style::Spacing::default();
+        let factor = self.density.factor();
+        let spacing = &mut style.spacing;
+
+        spacing.item_spacing = default_spacing.item_spacing * factor;
+        spacing.button_padding = default_spacing.button_padding * factor;
+        spacing.window_margin = default_spacing.window_margin * factor;
+        spacing.menu_margin = default_spacing.menu_margin * factor;
+        spacing.indent = default_spacing.indent * factor;
+        spacing.interact_size.y = default_spacing.interact_size.y * factor.max(0.8);
+
+        // Start from the plain dark or light visuals, so that earlier customizations are undone
+        style.visuals = if style.visuals.dark_mode {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        };
+        let visuals = &mut style.visuals;
+        let rounding = egui::Rounding::same(self.rounding);
+
+        for widget_visuals in [
+            &mut visuals.widgets.noninteractive,
+            &mut visuals.widgets.inactive,
+            &mut visuals.widgets.hovered,
+            &mut visuals.widgets.active,
+            &mut visuals.widgets.open,
+        ] {
+            widget_visuals.rounding = rounding;
+        }
+        visuals.window_rounding = egui::Rounding::same(self.rounding * 3.0);
+        visuals.menu_rounding = egui::Rounding::same(self.rounding * 3.0);
+
+        if self.custom_accent {
+            let [r, g, b] = self.accent;
+            let accent = egui::Color32::from_rgb(r, g, b);
+
+            visuals.selection.bg_fill = accent;
+            visuals.hyperlink_color = accent;
+            visuals.widgets.hovered.bg_stroke.color = accent;
+            visuals.widgets.active.bg_fill = accent;
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::f32::consts::FRAC_PI_2;
+
+/// The distance of the camera from the center of the cube, for the perspective
+const CAMERA_DISTANCE: f32 = 5.0;
+
+/// The camera of the 3D trajectory page, orbiting around the center of the trajectory
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OrbitCamera {
+    /// The rotation around the vertical Z axis
+    pub yaw: f32,
+    /// The elevation above the X-Y plane
+    pub pitch: f32,
+    pub zoom: f32,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            yaw: 0.6,
+            pitch: 0.4,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl OrbitCamera {
+    /// Rotates the camera by the dragged distance in points
+    pub fn rotate(&mut self, delta: egui::Vec2) {
+        self.yaw -= delta.x * 0.01;
+        self.pitch = (self.pitch + delta.y * 0.01).clamp(-FRAC_PI_2, FRAC_PI_2);
+    }
+
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.zoom = (self.zoom * factor).clamp(0.1, 10.0);
+    }
+
+    /// Projects a point of the cube from -1 to 1 into the rect
+    pub fn project(&self, p: [f32; 3], rect: egui::Rect) -> egui::Pos2 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+
+        // Rotate around Z, then tilt around the horizontal screen axis
+        let x = p[0] * cos_yaw - p[1] * sin_yaw;
+        let depth = p[0] * sin_yaw + p[1] * cos_yaw;
+        let y = p[2] * cos_pitch - depth * sin_pitch;
+        let depth = p[2] * sin_pitch + depth * cos_pitch;
+
+        let perspective = CAMERA_DISTANCE / (CAMERA_DISTANCE + depth);
+        let scale = rect.width().min(rect.height()) * 0.3 * self.zoom * perspective;
+
+        rect.center() + egui::vec2(x * scale, -y * scale)
+    }
+}
+
+/// The range of the values of each axis
+#[derive(Debug, Clone, Copy)]
+pub struct Bounds3 {
+    pub min: [f64; 3],
+    pub max: [f64; 3],
+}
+
+impl Bounds3 {
+    pub fn from_points(points: &[[f64; 3]]) -> Option<Self> {
+        let first = *points.first()?;
+
+        Some(points.iter().fold(
+            Self {
+                min: first,
+                max: first,
+            },
+            |mut bounds, p| {
+                for (axis, &v) in p.iter().enumerate() {
+                    bounds.min[axis] = bounds.min[axis].min(v);
+                    bounds.max[axis] = bounds.max[axis].max(v);
+                }
+                bounds
+            },
+        ))
+    }
+
+    /// Maps the point into the cube from -1 to 1, each axis is scaled on its own,
+    /// because the channels usually have different units
+    pub fn normalize(&self, p: [f64; 3]) -> [f32; 3] {
+        let mut normalized = [0.0; 3];
+
+        for (axis, n) in normalized.iter_mut().enumerate() {
+            let range = self.max[axis] - self.min[axis];
+            if range > 0.0 {
+                *n = ((p[axis] - self.min[axis]) / range * 2.0 - 1.0) as f32;
+            }
+        }
+
+        normalized
+    }
+}
+
+/// The edges of the cube from -1 to 1
+pub fn cube_edges() -> impl Iterator<Item = ([f32; 3], [f32; 3])> {
+    let corners = (0..8).map(|i| {
+        [
+            if i & 1 == 0 { -1.0 } else { 1.0 },
+            if i & 2 == 0 { -1.0 } else { 1.0 },
+            if i & 4 == 0 { -1.0 } else { 1.0 },
+        ]
+    });
+
+    corners.flat_map(|a: [f32; 3]| {
+        // Each edge once, from the corner with the lower coordinate
+        (0..3).filter(move |&axis| a[axis] < 0.0).map(move |axis| {
+            let mut b = a;
+            b[axis] = 1.0;
+            (a, b)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trajectory_normalization() {
+        assert_eq!(cube_edges().count(), 12);
+
+        let points = [[0.0, 10.0, 5.0], [2.0, 20.0, 5.0], [1.0, 15.0, 5.0]];
+        let bounds = Bounds3::from_points(&points).unwrap();
+
+        assert_eq!(bounds.normalize(points[0]), [-1.0, -1.0, 0.0]);
+        assert_eq!(bounds.normalize(points[1]), [1.0, 1.0, 0.0]);
+        assert_eq!(bounds.normalize(points[2]), [0.0, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,157 @@
+use super::locale::tr;
+use super::Sample;
+use crate::fixedsizebuffer::FixedSizeBuffer;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TriggerMode {
+    /// Free running, the plot follows the newest samples
+    #[default]
+    Off,
+    /// Re-arms after each captured window
+    Normal,
+    /// Freezes after the first trigger until re-armed
+    Single,
+}
+
+impl std::fmt::Display for TriggerMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TriggerMode::Off => write!(f, "Off"),
+            TriggerMode::Normal => write!(f, "Normal"),
+            TriggerMode::Single => write!(f, "Single"),
+        }
+    }
+}
+
+impl TriggerMode {
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            TriggerMode::Off => tr("trigger-mode-off"),
+            TriggerMode::Normal => tr("trigger-mode-normal"),
+            TriggerMode::Single => tr("trigger-mode-single"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TriggerEdge {
+    #[default]
+    Rising,
+    Falling,
+    Both,
+}
+
+impl std::fmt::Display for TriggerEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TriggerEdge::Rising => write!(f, "Rising"),
+            TriggerEdge::Falling => write!(f, "Falling"),
+            TriggerEdge::Both => write!(f, "Both"),
+        }
+    }
+}
+
+impl TriggerEdge {
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            TriggerEdge::Rising => tr("trigger-edge-rising"),
+            TriggerEdge::Falling => tr("trigger-edge-falling"),
+            TriggerEdge::Both => tr("trigger-edge-both"),
+        }
+    }
+}
+
+/// An oscilloscope-style trigger, aligning the Time-Value plot around the time a channel crosses a level.
+#[derive(Debug, Clone)]
+pub struct Trigger {
+    pub mode: TriggerMode,
+    /// The index of the channel that is watched
+    pub samples: usize,
+    pub level: f64,
+    pub edge: TriggerEdge,
+    /// The part of the displayed window before the trigger point, in the range 0.0..=1.0
+    pub pre_trigger: f64,
+
+    /// The time of the last trigger event in nanoseconds
+    triggered_at: Option<i64>,
+    /// Triggers are ignored before this time, so that the window after the last trigger gets filled
+    hold_off_until: i64,
+}
+
+impl Default for Trigger {
+    fn default() -> Self {
+        Self {
+            mode: TriggerMode::default(),
+            samples: 0,
+            level: 0.0,
+            edge: TriggerEdge::default(),
+            pre_trigger: 0.2,
+            triggered_at: None,
+            hold_off_until: i64::MIN,
+        }
+    }
+}
+
+impl Trigger {
+    pub fn is_active(&self) -> bool {
+        self.mode != TriggerMode::Off
+    }
+
+    pub fn triggered_at(&self) -> Option<i64> {
+        self.triggered_at
+    }
+
+    /// Forgets the last trigger event and waits for the next one
+    pub fn rearm(&mut self) {
+        self.triggered_at = None;
+        self.hold_off_until = i64::MIN;
+    }
+
+    /// The displayed time window around the trigger point in nanoseconds, given the window length
+    pub fn window(&self, window_len: i64) -> Option<(i64, i64)> {
+        let t = self.triggered_at?;
+        let pre = (window_len as f64 * self.pre_trigger) as i64;
+
+        Some((t - pre, t - pre + window_len))
+    }
+
+    /// Checks the `n_new` newest samples of the watched channel for a trigger event
+    pub fn check(&mut self, samples: &FixedSizeBuffer<Sample>, n_new: usize, window_len: i64) {
+        if self.mode == TriggerMode::Off
+            || (self.mode == TriggerMode::Single && self.triggered_at.is_some())
+        {
+            return;
+        }
+
+        // Include the sample before the new ones, to detect crossings between them
+        let skip = samples.len().saturating_sub(n_new + 1);
+        let mut prev: Option<&Sample> = None;
+
+        for sample in samples.iter().skip(skip) {
+            if let Some(prev) = prev {
+                let rising = prev.value < self.level && sample.value >= self.level;
+                let falling = prev.value > self.level && sample.value <= self.level;
+
+                let crossed = match self.edge {
+                    TriggerEdge::Rising => rising,
+                    TriggerEdge::Falling => falling,
+                    TriggerEdge::Both => rising || falling,
+                };
+
+                if crossed && sample.time >= self.hold_off_until {
+                    self.triggered_at = Some(sample.time);
+                    self.hold_off_until =
+                        sample.time + (window_len as f64 * (1.0 - self.pre_trigger)) as i64;
+
+                    if self.mode == TriggerMode::Single {
+                        return;
+                    }
+                }
+            }
+
+            prev = Some(sample);
+        }
+    }
+}
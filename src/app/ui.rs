@@ -1,12 +1,86 @@
+use egui_dock::{DockArea, DockState};
+use instant::Duration;
+
 #[cfg(target_arch = "wasm32")]
 use super::WEB_SERIAL_API_SUPPORTED;
 
-use super::{PlotPage, SplotApp, TimeUnit};
-use crate::serialconnection::{DataBits, FlowControl, Parity, StopBits};
+use super::alarm::{AlarmCondition, AlarmRule};
+use super::ansi::{self, AnsiStyle};
+use super::automation::{AutomationRule, RuleAction, RuleHook};
+use super::binarylayout::{BinaryField, BinaryFieldType, Endianness};
+use super::can::CanSignal;
+#[cfg(not(target_arch = "wasm32"))]
+use super::capture;
+use super::commands::{InitCommand, LineEnding};
+use super::dashboard::{DashboardWidget, WidgetKind};
+use super::derived::{self, DerivedChannel, DerivedEdit, DerivedKind};
+use super::downsample::{self, Downsampling, RenderBudget};
+#[cfg(not(target_arch = "wasm32"))]
+use super::export::{self, ExportFormat};
+use super::gaps;
+use super::guideline::GuideLine;
+#[cfg(not(target_arch = "wasm32"))]
+use super::import;
+use super::ingeststats;
+use super::locale::{tr, tr_args, Language};
+#[cfg(not(target_arch = "wasm32"))]
+use super::preset::{AppearancePreset, ChannelAppearance};
+use super::reference::ReferenceTrace;
+use super::sessiondiff::DiffAlignment;
+use super::smoothing::{self, SmoothingMode};
+use super::theme::{Density, Theme};
+use super::trajectory::{self, OrbitCamera};
+use super::trigger::{TriggerEdge, TriggerMode};
+use super::ubx::UbxMessage;
+use super::valuefilter::FilterAction;
+use super::valueformat::{Notation, ValueFormat};
+use super::{
+    default_dock_state, interpolate_value_at, nanos_to_secs, secs_to_nanos, time_field_unit,
+    unique_color_in_list, AxisTransform, ChannelOrder, DataFormat, DockTab, MonitorLine,
+    MonitorTimestamp, PlotAxis, PlotPage, Sample, SplotApp, TextEvent, TimeUnit, XyPair, YBounds,
+    YBoundsMode,
+};
+use super::{expression, fft};
+use crate::fixedsizebuffer::FixedSizeBuffer;
+use crate::serialconnection::script::DeviceScript;
+use crate::serialconnection::{DataBits, FlowControl, Parity, StopBits, BAUDRATE_PRESETS};
+
+/// Splits a monitor line into the device time and the values of the channels, like the parser does.
+/// Markers are left out.
+fn monitor_table_row(line: &str, value_separator: char) -> (Option<&str>, Vec<&str>) {
+    let mut time = None;
+    let mut values = vec![];
+
+    for field in line.trim().split(value_separator) {
+        let (name, value) = match field.split_once('=') {
+            Some((name, value)) => (Some(name.trim()), value.trim()),
+            None => (None, field.trim()),
+        };
+
+        match name {
+            Some("marker") => {}
+            Some(name) if time_field_unit(name, TimeUnit::S).is_some() => time = Some(value),
+            _ => values.push(value),
+        }
+    }
+
+    (time, values)
+}
+
+/// The duration of the latest values shown by the sparklines, in seconds
+const SPARKLINE_SECS: f64 = 5.0;
+/// The minimum height of the stacked subplots, more channels scroll
+const MIN_SUBPLOT_HEIGHT: f32 = 80.0;
+/// The opacity of the area under the lines of filled channels
+const FILL_OPACITY: f32 = 0.3;
+/// The height of a lane of text events under the Time-Value plot
+const EVENT_LANE_HEIGHT: f32 = 22.0;
+/// The ratio of samples in view to the point budget of a line, above which the min / max envelope is drawn
+const ENVELOPE_MIN_RATIO: usize = 4;
 
 impl SplotApp {
     pub fn draw_ui(&mut self, ctx: &egui::Context) {
-        egui::Window::new("About")
+        egui::Window::new(tr("window-about"))
             .open(&mut self.show_about_window)
             .collapsible(false)
             .auto_sized()
@@ -21,19 +95,19 @@ impl SplotApp {
 
                     ui.label(egui::RichText::new("- Splot -").heading());
                     ui.add_space(12.0);
-                    ui.label("A multi-platform serial plotter and monitor");
+                    ui.label(tr("about-description"));
                     ui.separator();
-                    ui.label("Created by:");
+                    ui.label(tr("label-created-by"));
                     ui.label("Felix Zwettler");
-                    ui.hyperlink_to("Repository", "https://github.com/flxzt/splot");
+                    ui.hyperlink_to(tr("repository"), "https://github.com/flxzt/splot");
                     ui.separator();
-                    ui.label("Powered by:");
+                    ui.label(tr("label-powered-by"));
                     ui.hyperlink_to("egui", "https://github.com/emilk/egui/");
                     ui.hyperlink_to("rust", "https://www.rust-lang.org/");
                 });
             });
 
-        egui::Window::new("Usage")
+        egui::Window::new(tr("window-usage"))
             .open(&mut self.show_usage_window)
             .collapsible(false)
             .auto_sized()
@@ -41,53 +115,323 @@ impl SplotApp {
                 ui.set_width(500.0);
 
                 ui.vertical(|ui| {
-                    ui.label(
-"Splot parses data coming from a serial connection and looks for values separated by the specified separator and terminated by a newline character.
-Each value is inserted by its index, so it is important to keep the number of values per line constant."
-);
+                    ui.label(tr("usage-values"));
 
-                ui.add_space(12.0);
-                ui.label("Example:");
-                ui.code("UART_Transmit(\"%i, %i\\n\", var_1, var_2);");
+                    ui.add_space(12.0);
+                    ui.label(tr("label-example"));
+                    ui.code("UART_Transmit(\"%i, %i\\n\", var_1, var_2);");
 
-                ui.add_space(12.0);
-                ui.label(
-"Values can also have a name, which will appear in the values list. To specify a name, prefix the variable with \"<name>=\""
-);
+                    ui.add_space(12.0);
+                    ui.label(tr("usage-names"));
 
-                ui.add_space(12.0);
-                ui.label("Example:");
-                ui.code("UART_Transmit(\"dist=%i, temperature=%i\\n\", var_1, var_2);");
+                    ui.add_space(12.0);
+                    ui.label(tr("label-example"));
+                    ui.code("UART_Transmit(\"dist=%i, temperature=%i\\n\", var_1, var_2);");
 
-                ui.add_space(12.0);
-                ui.label(
-"A special named value is the one with \"time=\" or \"t=\".
-This indicates that this value should used as the time for plotting.
-It must be monotonically increasing, so probably should come from a timer.
-The time unit for the time values received by the device can be set in the application.
-If no such variable is specified, the application takes the time when receiving the data"
-);
+                    ui.add_space(12.0);
+                    ui.label(tr("usage-time"));
 
-                ui.add_space(12.0);
-                ui.label("Example:");
-                ui.code("UART_Transmit(\"time=%i, %i, %i\\n\", HAL_GetTick(), var_1, var_2);");
+                    ui.add_space(12.0);
+                    ui.label(tr("label-example"));
+                    ui.code("UART_Transmit(\"time=%i, %i, %i\\n\", HAL_GetTick(), var_1, var_2);");
                 });
             });
 
-        egui::Window::new("Help")
+        egui::Window::new(tr("window-help"))
             .open(&mut self.show_help_window)
             .collapsible(false)
             .auto_sized()
             .show(ctx, |ui| {
                 ui.set_width(500.0);
 
-                ui.vertical(|ui| {
-                    ui.label(
-                        "- Issue: When using the app on the web, the port does not show up.
-    Fix: Restart the browser. The device might need to be plugged in before the browser is launched.
-",
-                    )
+                ui.vertical(|ui| ui.label(tr("help-web-port")));
+            });
+
+        let mut show_binary_layout_window = self.show_binary_layout_window;
+        egui::Window::new(tr("window-binary-layout"))
+            .open(&mut show_binary_layout_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                if self.render_binary_layout(ui) {
+                    log::debug!("binary layout has changed. clearing samples");
+                    self.clear_samples(ctx);
+                    self.clear_parser();
+                }
+            });
+        self.show_binary_layout_window &= show_binary_layout_window;
+
+        let mut show_ubx_messages_window = self.show_ubx_messages_window;
+        egui::Window::new(tr("window-ubx-messages"))
+            .open(&mut show_ubx_messages_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                if self.render_ubx_messages(ui) {
+                    log::debug!("UBX messages have changed. clearing samples");
+                    self.clear_samples(ctx);
+                }
+            });
+        self.show_ubx_messages_window &= show_ubx_messages_window;
+
+        let mut show_can_signals_window = self.show_can_signals_window;
+        egui::Window::new(tr("window-can-signals"))
+            .open(&mut show_can_signals_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                if self.render_can_signals(ui) {
+                    log::debug!("CAN signals have changed. clearing samples");
+                    self.clear_samples(ctx);
+                }
+            });
+        self.show_can_signals_window &= show_can_signals_window;
+
+        let mut show_derived_channels_window = self.show_derived_channels_window;
+        egui::Window::new(tr("window-derived-channels"))
+            .open(&mut show_derived_channels_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                for edit in self.render_derived_channels(ui) {
+                    self.apply_derived_edit(edit);
+                }
+            });
+        self.show_derived_channels_window &= show_derived_channels_window;
+
+        let mut show_import_window = self.show_import_window;
+        egui::Window::new(tr("window-import-capture"))
+            .open(&mut show_import_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                self.render_import(ui, ctx);
+            });
+        self.show_import_window &= show_import_window;
+
+        let mut show_export_window = self.show_export_window;
+        egui::Window::new(tr("window-export-samples"))
+            .open(&mut show_export_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                self.render_export(ui);
+            });
+        self.show_export_window &= show_export_window;
+
+        let mut show_session_diff_window = self.show_session_diff_window;
+        egui::Window::new(tr("window-compare-captures"))
+            .open(&mut show_session_diff_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                self.render_session_diff(ui);
+            });
+        self.show_session_diff_window &= show_session_diff_window;
+
+        let mut show_acquisition_window = self.show_acquisition_window;
+        egui::Window::new(tr("window-acquisition"))
+            .open(&mut show_acquisition_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                self.render_acquisition(ui);
+            });
+        self.show_acquisition_window &= show_acquisition_window;
+
+        let mut show_device_script_window = self.show_device_script_window;
+        egui::Window::new(tr("window-device-script"))
+            .open(&mut show_device_script_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                self.render_device_script(ui, ctx);
+            });
+        self.show_device_script_window &= show_device_script_window;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let mut show_debug_bundle_window = self.show_debug_bundle_window;
+            egui::Window::new(tr("window-debug-bundle"))
+                .open(&mut show_debug_bundle_window)
+                .collapsible(false)
+                .auto_sized()
+                .show(ctx, |ui| {
+                    self.render_debug_bundle(ui);
+                });
+            self.show_debug_bundle_window &= show_debug_bundle_window;
+
+            let mut show_captures_window = self.show_captures_window;
+            egui::Window::new(tr("window-captures"))
+                .open(&mut show_captures_window)
+                .collapsible(false)
+                .auto_sized()
+                .show(ctx, |ui| {
+                    self.render_captures(ui, ctx);
+                });
+            self.show_captures_window &= show_captures_window;
+
+            let mut show_data_servers_window = self.show_data_servers_window;
+            egui::Window::new(tr("window-data-servers"))
+                .open(&mut show_data_servers_window)
+                .collapsible(false)
+                .auto_sized()
+                .show(ctx, |ui| {
+                    self.render_data_servers(ui);
+                });
+            self.show_data_servers_window &= show_data_servers_window;
+        }
+
+        let mut show_appearance_preset_window = self.show_appearance_preset_window;
+        egui::Window::new(tr("window-appearance-preset"))
+            .open(&mut show_appearance_preset_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                self.render_appearance_preset(ui);
+            });
+        self.show_appearance_preset_window &= show_appearance_preset_window;
+
+        egui::Window::new(tr("window-startup-actions"))
+            .open(&mut self.show_startup_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                ui.set_width(400.0);
+
+                ui.label(tr("startup-actions-help"));
+
+                ui.add_space(12.0);
+
+                egui::Grid::new("startup_actions_grid").show(ui, |ui| {
+                    ui.label(tr("label-port"));
+                    ui.text_edit_singleline(&mut self.startup_actions.port);
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.checkbox(&mut self.startup_actions.auto_connect, tr("connect"));
+                    ui.end_row();
+
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        ui.label("");
+                        ui.add_enabled(
+                            self.startup_actions.auto_connect,
+                            egui::Checkbox::new(
+                                &mut self.startup_actions.auto_record,
+                                tr("start-recording"),
+                            ),
+                        );
+                        ui.end_row();
+                    }
+                });
+            });
+
+        let mut show_init_commands_window = self.show_init_commands_window;
+        egui::Window::new(tr("window-init-commands"))
+            .open(&mut show_init_commands_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                self.render_init_commands(ui);
+            });
+        self.show_init_commands_window &= show_init_commands_window;
+
+        let mut show_automation_window = self.show_automation_window;
+        egui::Window::new(tr("window-automation"))
+            .open(&mut show_automation_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                self.render_automation(ui);
+            });
+        self.show_automation_window &= show_automation_window;
+
+        let mut show_alarms_window = self.show_alarms_window;
+        egui::Window::new(tr("window-alarms"))
+            .open(&mut show_alarms_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                self.render_alarms(ui);
+            });
+        self.show_alarms_window &= show_alarms_window;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let mut show_conditional_capture_window = self.show_conditional_capture_window;
+            egui::Window::new(tr("window-conditional-capture"))
+                .open(&mut show_conditional_capture_window)
+                .collapsible(false)
+                .auto_sized()
+                .show(ctx, |ui| {
+                    self.render_conditional_capture(ui);
+                });
+            self.show_conditional_capture_window &= show_conditional_capture_window;
+        }
+
+        let mut show_profiles_window = self.show_profiles_window;
+        egui::Window::new(tr("window-connection-profiles"))
+            .open(&mut show_profiles_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                self.render_profiles(ui);
+            });
+        self.show_profiles_window &= show_profiles_window;
+
+        egui::Window::new(tr("window-appearance"))
+            .open(&mut self.show_appearance_window)
+            .collapsible(false)
+            .auto_sized()
+            .show(ctx, |ui| {
+                egui::Grid::new("appearance_grid").show(ui, |ui| {
+                    ui.label(tr("label-accent-color"));
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.theme.custom_accent, "");
+                        ui.add_enabled_ui(self.theme.custom_accent, |ui| {
+                            ui.color_edit_button_srgb(&mut self.theme.accent);
+                        });
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("label-rounding"));
+                    ui.add(egui::Slider::new(&mut self.theme.rounding, 0.0..=12.0));
+                    ui.end_row();
+
+                    ui.label(tr("label-density"));
+                    egui::ComboBox::from_id_source("theme_density_combobox")
+                        .selected_text(self.theme.density.label())
+                        .show_ui(ui, |ui| {
+                            for density in Density::ALL {
+                                ui.selectable_value(
+                                    &mut self.theme.density,
+                                    density,
+                                    density.label(),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("label-language"));
+                    egui::ComboBox::from_id_source("language_combobox")
+                        .selected_text(self.language.to_string())
+                        .show_ui(ui, |ui| {
+                            for language in Language::ALL {
+                                ui.selectable_value(
+                                    &mut self.language,
+                                    language,
+                                    language.to_string(),
+                                );
+                            }
+                        });
+                    ui.end_row();
                 });
+
+                if ui.button(tr("reset")).clicked() {
+                    self.theme = Theme::default();
+                    self.dock_state = default_dock_state();
+                }
             });
 
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -96,43 +440,150 @@ If no such variable is specified, the application takes the time when receiving
 
                 ui.separator();
 
-                // Controls
-                self.render_connection_controls(ui, ctx);
+                self.render_page_bar(ui, ctx);
 
                 ui.add_space(5.0);
 
-                // Plots
-                ui.group(|ui| {
-                    ui.centered_and_justified(|ui| match self.plot_page {
-                        PlotPage::TimeValue => self.render_plot_tv(ui),
-                        PlotPage::XY => self.render_plot_xy(ui),
-                        PlotPage::SerialMonitor => self.render_serial_monitor(ui),
-                    });
-                });
+                // The pages and the connection controls, as tabs rearranged by dragging them
+                self.pages_rect = ui.available_rect_before_wrap();
+                let mut dock_state =
+                    std::mem::replace(&mut self.dock_state, DockState::new(vec![]));
+                DockArea::new(&mut dock_state)
+                    .id(egui::Id::new("pages_dock_area"))
+                    .style(egui_dock::Style::from_egui(ui.style().as_ref()))
+                    .show_inside(ui, &mut DockTabViewer { app: self, ctx });
+                self.dock_state = dock_state;
             });
         });
+
+        self.render_drop_overlay(ctx);
+    }
+
+    /// Dims the window while files are dragged over it
+    fn render_drop_overlay(&self, ctx: &egui::Context) {
+        if ctx.input(|i| i.raw.hovered_files.is_empty()) {
+            return;
+        }
+
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("drop_overlay"),
+        ));
+        let rect = ctx.screen_rect();
+        painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(192));
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            tr("drop-csv"),
+            egui::FontId::proportional(20.0),
+            egui::Color32::WHITE,
+        );
     }
 
     #[allow(unused)]
     fn render_top_bar(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
         ui.horizontal(|ui| {
             ui.menu_button("Splot", |ui| {
-                if ui.button("About").clicked() {
+                if ui.button(tr("window-startup-actions")).clicked() {
+                    ui.close_menu();
+                    self.show_startup_window = true;
+                }
+
+                if ui.button(tr("window-connection-profiles")).clicked() {
+                    ui.close_menu();
+                    self.show_profiles_window = true;
+                }
+
+                if ui.button(tr("window-init-commands")).clicked() {
+                    ui.close_menu();
+                    self.show_init_commands_window = true;
+                }
+
+                if ui.button(tr("window-automation")).clicked() {
+                    ui.close_menu();
+                    self.show_automation_window = true;
+                }
+
+                if ui.button(tr("window-alarms")).clicked() {
+                    ui.close_menu();
+                    self.show_alarms_window = true;
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.button(tr("window-conditional-capture")).clicked() {
+                    ui.close_menu();
+                    self.show_conditional_capture_window = true;
+                }
+
+                if ui.button(tr("window-appearance")).clicked() {
+                    ui.close_menu();
+                    self.show_appearance_window = true;
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.button(tr("window-data-servers")).clicked() {
+                    ui.close_menu();
+                    self.show_data_servers_window = true;
+                }
+
+                if ui.button(tr("window-acquisition")).clicked() {
+                    ui.close_menu();
+                    self.show_acquisition_window = true;
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.button(tr("menu-create-debug-bundle")).clicked() {
+                    ui.close_menu();
+                    self.show_debug_bundle_window = true;
+                }
+
+                if ui.button(tr("window-about")).clicked() {
                     ui.close_menu();
                     self.show_about_window = true;
                 }
 
                 #[cfg(not(target_arch = "wasm32"))] // no close() on web pages!
-                if ui.button("Quit").clicked() {
+                if ui.button(tr("menu-quit")).clicked() {
                     ctx.send_viewport_cmd(egui::ViewportCommand::Close)
                 }
             });
 
-            if ui.button("Usage").clicked() {
+            ui.menu_button(tr("menu-channels"), |ui| {
+                if ui.button(tr("window-derived-channels")).clicked() {
+                    ui.close_menu();
+                    self.show_derived_channels_window = true;
+                }
+                if ui.button(tr("window-import-capture")).clicked() {
+                    ui.close_menu();
+                    self.show_import_window = true;
+                }
+                if ui.button(tr("window-export-samples")).clicked() {
+                    ui.close_menu();
+                    // Start out with the visible channels
+                    self.export_channels.clear();
+                    self.show_export_window = true;
+                }
+                if ui.button(tr("window-compare-captures")).clicked() {
+                    ui.close_menu();
+                    self.show_session_diff_window = true;
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.button(tr("menu-browse-captures")).clicked() {
+                    ui.close_menu();
+                    self.refresh_captures();
+                    self.show_captures_window = true;
+                }
+                if ui.button(tr("window-appearance-preset")).clicked() {
+                    ui.close_menu();
+                    self.show_appearance_preset_window = true;
+                }
+            });
+
+            if ui.button(tr("window-usage")).clicked() {
                 self.show_usage_window = true;
             }
 
-            if ui.button("Help").clicked() {
+            if ui.button(tr("window-help")).clicked() {
                 self.show_help_window = true;
             }
 
@@ -142,18 +593,27 @@ If no such variable is specified, the application takes the time when receiving
                 #[cfg(feature = "demo")]
                 {
                     ui.add(egui::Label::new(
-                        egui::RichText::new("Demo Mode").color(egui::Color32::DARK_GREEN),
+                        egui::RichText::new(tr("demo-mode")).color(egui::Color32::DARK_GREEN),
                     ));
                 }
 
                 #[cfg(not(feature = "demo"))]
                 if ui
-                    .toggle_value(&mut self.dummy_connection, "Dummy connection")
+                    .toggle_value(&mut self.dummy_connection, tr("dummy-connection"))
                     .changed()
                 {
                     self.reset_connection(ctx);
                 }
-                ui.label(format!("Received Samples: {}", self.samples_received));
+                #[cfg(not(feature = "demo"))]
+                if self.dummy_connection && ui.button(tr("device-script")).clicked() {
+                    self.show_device_script_window = true;
+                }
+                ui.label(format!(
+                    "{} {}",
+                    tr("received-samples"),
+                    self.samples_received
+                ));
+                self.render_throughput(ui);
 
                 egui::warn_if_debug_build(ui);
 
@@ -167,11 +627,9 @@ If no such variable is specified, the application takes the time when receiving
 
                     if cond {
                         ui.label(
-                            egui::RichText::new(
-                                "⚠ Web Serial API not supported ⚠\n on this platform ",
-                            )
-                            .small()
-                            .color(egui::Color32::RED),
+                            egui::RichText::new(tr("web-serial-unsupported"))
+                                .small()
+                                .color(egui::Color32::RED),
                         );
                     }
                 }
@@ -182,139 +640,299 @@ If no such variable is specified, the application takes the time when receiving
     fn render_connection_controls(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
         ui.vertical_centered_justified(|ui| {
             ui.horizontal(|ui| {
-                ui.label("Port: ");
+                let locked = self.is_connected && !self.edit_connection;
 
-                if egui::ComboBox::new("available_ports_combobox", "")
-                    .selected_text(
-                        self.selected_port_index
-                            .and_then(|i| self.available_ports.get(i).map(|s| s.as_str()))
-                            .unwrap_or(""),
-                    )
-                    .show_ui(ui, |ui| {
-                        for (i, available_port) in self.available_ports.iter().enumerate() {
-                            ui.selectable_value(
-                                &mut self.selected_port_index,
-                                Some(i),
-                                available_port,
-                            );
-                        }
-                    })
-                    .response
-                    .clicked()
-                {
-                    self.available_ports(ctx);
-                }
-
-                if ui.button("⟲").clicked() {
-                    self.available_ports(ctx);
-                }
-
-                ui.label("Baudrate: ");
-                ui.add(egui::DragValue::new(&mut self.baudrate));
+                ui.add_enabled_ui(!locked, |ui| {
+                    if !self.connection_profiles.is_empty() {
+                        ui.label(tr("label-profile"));
 
-                ui.label("Data Bits:");
-                egui::ComboBox::from_id_source("data_bits_combobox")
-                    .selected_text(self.data_bits.to_string())
-                    .width(30.0)
-                    .show_ui(ui, |ui| {
-                        #[cfg(not(target_arch = "wasm32"))]
-                        ui.selectable_value(
-                            &mut self.data_bits,
-                            DataBits::Five,
-                            DataBits::Five.to_string(),
-                        );
-                        #[cfg(not(target_arch = "wasm32"))]
-                        ui.selectable_value(
-                            &mut self.data_bits,
-                            DataBits::Six,
-                            DataBits::Six.to_string(),
-                        );
-                        ui.selectable_value(
-                            &mut self.data_bits,
-                            DataBits::Seven,
-                            DataBits::Seven.to_string(),
-                        );
-                        ui.selectable_value(
-                            &mut self.data_bits,
-                            DataBits::Eight,
-                            DataBits::Eight.to_string(),
-                        );
-                    });
+                        let mut load = None;
+                        egui::ComboBox::from_id_source("connection_profile_combobox")
+                            .selected_text(self.active_profile.as_str())
+                            .show_ui(ui, |ui| {
+                                for (i, profile) in self.connection_profiles.iter().enumerate() {
+                                    if ui
+                                        .selectable_label(
+                                            profile.name == self.active_profile,
+                                            &profile.name,
+                                        )
+                                        .clicked()
+                                    {
+                                        load = Some(i);
+                                    }
+                                }
+                            });
+                        if let Some(i) = load {
+                            self.load_profile(i);
+                        }
+                    }
 
-                ui.label("Flow Control:");
-                egui::ComboBox::from_id_source("flow_control_combobox")
-                    .selected_text(self.flow_control.to_string())
-                    .width(30.0)
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(
-                            &mut self.flow_control,
-                            FlowControl::None,
-                            FlowControl::None.to_string(),
-                        );
-                        #[cfg(not(target_arch = "wasm32"))]
-                        ui.selectable_value(
-                            &mut self.flow_control,
-                            FlowControl::Software,
-                            FlowControl::Software.to_string(),
-                        );
-                        ui.selectable_value(
-                            &mut self.flow_control,
-                            FlowControl::Hardware,
-                            FlowControl::Hardware.to_string(),
-                        );
-                    });
+                    ui.label(tr("label-port"));
 
-                ui.label("Parity:");
-                egui::ComboBox::from_id_source("parity_combobox")
-                    .selected_text(self.parity.to_string())
-                    .width(30.0)
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(
-                            &mut self.parity,
-                            Parity::None,
-                            Parity::None.to_string(),
-                        );
-                        ui.selectable_value(&mut self.parity, Parity::Odd, Parity::Odd.to_string());
-                        ui.selectable_value(
-                            &mut self.parity,
-                            Parity::Even,
-                            Parity::Even.to_string(),
-                        );
-                    });
+                    if egui::ComboBox::new("available_ports_combobox", "")
+                        .selected_text(
+                            self.selected_port_index
+                                .and_then(|i| self.available_ports.get(i))
+                                .map(|p| p.to_string())
+                                .unwrap_or_default(),
+                        )
+                        .show_ui(ui, |ui| {
+                            for (i, available_port) in self.available_ports.iter().enumerate() {
+                                let response = ui.selectable_value(
+                                    &mut self.selected_port_index,
+                                    Some(i),
+                                    available_port.to_string(),
+                                );
 
-                ui.label("Stop Bits:");
-                egui::ComboBox::from_id_source("stop_bits_combobox")
-                    .selected_text(self.stop_bits.to_string())
-                    .width(30.0)
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(
-                            &mut self.stop_bits,
-                            StopBits::One,
-                            StopBits::One.to_string(),
-                        );
-                        ui.selectable_value(
-                            &mut self.stop_bits,
-                            StopBits::Two,
-                            StopBits::Two.to_string(),
-                        );
-                    });
+                                if let Some(usb) = &available_port.usb {
+                                    response.on_hover_text(tr_args(
+                                        "port-usb-info",
+                                        &[
+                                            (
+                                                "manufacturer",
+                                                &usb.manufacturer.as_deref().unwrap_or("-"),
+                                            ),
+                                            ("product", &usb.product.as_deref().unwrap_or("-")),
+                                            (
+                                                "serial",
+                                                &usb.serial_number.as_deref().unwrap_or("-"),
+                                            ),
+                                        ],
+                                    ));
+                                }
+                            }
+                        })
+                        .response
+                        .clicked()
+                    {
+                        self.available_ports(ctx);
+                    }
 
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    let button = egui::Button::new("Connect");
+                    if ui.button("⟲").clicked() {
+                        self.available_ports(ctx);
+                    }
 
-                    #[cfg(target_arch = "wasm32")]
-                    let button_resp = {
-                        #[cfg(not(feature = "demo"))]
-                        let cond = *WEB_SERIAL_API_SUPPORTED || self.dummy_connection;
+                    ui.label(tr("label-baudrate"));
+                    let custom = self.baudrate_custom || !BAUDRATE_PRESETS.contains(&self.baudrate);
+                    egui::ComboBox::from_id_source("baudrate_combobox")
+                        .selected_text(if custom {
+                            String::from(tr("custom"))
+                        } else {
+                            self.baudrate.to_string()
+                        })
+                        .width(80.0)
+                        .show_ui(ui, |ui| {
+                            for baudrate in BAUDRATE_PRESETS {
+                                if ui
+                                    .selectable_label(
+                                        !custom && self.baudrate == baudrate,
+                                        baudrate.to_string(),
+                                    )
+                                    .clicked()
+                                {
+                                    self.baudrate = baudrate;
+                                    self.baudrate_custom = false;
+                                }
+                            }
+                            if ui.selectable_label(custom, tr("custom")).clicked() {
+                                self.baudrate_custom = true;
+                            }
+                        });
+                    if custom {
+                        ui.add(egui::DragValue::new(&mut self.baudrate));
+                    }
+                    if let Err(e) = self.validate_baudrate() {
+                        ui.colored_label(ui.visuals().error_fg_color, "⚠")
+                            .on_hover_text(e.to_string());
+                    }
+
+                    ui.label(tr("label-data-bits"));
+                    egui::ComboBox::from_id_source("data_bits_combobox")
+                        .selected_text(self.data_bits.to_string())
+                        .width(30.0)
+                        .show_ui(ui, |ui| {
+                            #[cfg(not(target_arch = "wasm32"))]
+                            ui.selectable_value(
+                                &mut self.data_bits,
+                                DataBits::Five,
+                                DataBits::Five.to_string(),
+                            );
+                            #[cfg(not(target_arch = "wasm32"))]
+                            ui.selectable_value(
+                                &mut self.data_bits,
+                                DataBits::Six,
+                                DataBits::Six.to_string(),
+                            );
+                            ui.selectable_value(
+                                &mut self.data_bits,
+                                DataBits::Seven,
+                                DataBits::Seven.to_string(),
+                            );
+                            ui.selectable_value(
+                                &mut self.data_bits,
+                                DataBits::Eight,
+                                DataBits::Eight.to_string(),
+                            );
+                        });
+
+                    ui.label(tr("label-flow-control"));
+                    egui::ComboBox::from_id_source("flow_control_combobox")
+                        .selected_text(self.flow_control.to_string())
+                        .width(30.0)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.flow_control,
+                                FlowControl::None,
+                                FlowControl::None.to_string(),
+                            );
+                            #[cfg(not(target_arch = "wasm32"))]
+                            ui.selectable_value(
+                                &mut self.flow_control,
+                                FlowControl::Software,
+                                FlowControl::Software.to_string(),
+                            );
+                            ui.selectable_value(
+                                &mut self.flow_control,
+                                FlowControl::Hardware,
+                                FlowControl::Hardware.to_string(),
+                            );
+                        });
+
+                    ui.label(tr("label-parity"));
+                    egui::ComboBox::from_id_source("parity_combobox")
+                        .selected_text(self.parity.to_string())
+                        .width(30.0)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.parity,
+                                Parity::None,
+                                Parity::None.to_string(),
+                            );
+                            ui.selectable_value(
+                                &mut self.parity,
+                                Parity::Odd,
+                                Parity::Odd.to_string(),
+                            );
+                            ui.selectable_value(
+                                &mut self.parity,
+                                Parity::Even,
+                                Parity::Even.to_string(),
+                            );
+                        });
+
+                    ui.label(tr("label-stop-bits"));
+                    egui::ComboBox::from_id_source("stop_bits_combobox")
+                        .selected_text(self.stop_bits.to_string())
+                        .width(30.0)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.stop_bits,
+                                StopBits::One,
+                                StopBits::One.to_string(),
+                            );
+                            ui.selectable_value(
+                                &mut self.stop_bits,
+                                StopBits::Two,
+                                StopBits::Two.to_string(),
+                            );
+                        });
+
+                    ui.label(tr("label-timeout"));
+                    let mut timeout_ms = self.timeout.as_millis() as u64;
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut timeout_ms)
+                                .clamp_range(10..=60000)
+                                .suffix(" ms"),
+                        )
+                        .on_hover_text(tr("hover-timeout"))
+                        .changed()
+                    {
+                        self.timeout = Duration::from_millis(timeout_ms);
+                    }
+                });
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.checkbox(&mut self.auto_reconnect, tr("auto-reconnect"))
+                        .on_hover_text(tr("hover-auto-reconnect"));
+
+                    if let Some(lost) = &self.lost_connection {
+                        ui.label(
+                            egui::RichText::new(tr("status-lost-connection"))
+                                .color(egui::Color32::RED),
+                        )
+                        .on_hover_text(if self.auto_reconnect {
+                            tr_args(
+                                "hover-reconnecting",
+                                &[("port", &lost.port_name), ("attempt", &lost.attempts)],
+                            )
+                        } else {
+                            tr_args("hover-lost-connection", &[("port", &lost.port_name)])
+                        });
+                    } else if self.is_connected {
+                        ui.label(
+                            egui::RichText::new(tr_args(
+                                "status-connected",
+                                &[("port", &self.connected_port_name().unwrap_or_default())],
+                            ))
+                            .color(egui::Color32::DARK_GREEN),
+                        );
+                    } else if let Some(e) = &self.connection_error {
+                        ui.label(egui::RichText::new(tr("status-error")).color(egui::Color32::RED))
+                            .on_hover_text(e);
+                    } else {
+                        ui.weak(tr("status-disconnected"));
+                    }
+
+                    ui.separator();
+
+                    if (self.is_connected || self.lost_connection.is_some())
+                        && ui
+                            .button(tr("disconnect"))
+                            .on_hover_text(tr("hover-disconnect"))
+                            .clicked()
+                    {
+                        self.disconnect(ctx);
+                    }
+
+                    if locked {
+                        if ui
+                            .button(tr("edit-reconnect"))
+                            .on_hover_text(tr("hover-edit-reconnect"))
+                            .clicked()
+                        {
+                            self.edit_connection = true;
+                        }
+
+                        ui.separator();
+                        return;
+                    }
+
+                    if self.edit_connection && ui.button(tr("cancel")).clicked() {
+                        self.cancel_edit_connection();
+                    }
+
+                    let button = egui::Button::new(if self.edit_connection {
+                        tr("reconnect")
+                    } else {
+                        tr("connect")
+                    });
+
+                    let baudrate_valid = self.validate_baudrate();
+
+                    #[cfg(target_arch = "wasm32")]
+                    let button_resp = {
+                        #[cfg(not(feature = "demo"))]
+                        let cond = *WEB_SERIAL_API_SUPPORTED || self.dummy_connection;
 
                         #[cfg(feature = "demo")]
                         let cond = true;
 
-                        ui.add_enabled(cond, button)
+                        ui.add_enabled(cond && baudrate_valid.is_ok(), button)
                     };
 
                     #[cfg(not(target_arch = "wasm32"))]
-                    let button_resp = ui.add(button);
+                    let button_resp = ui.add_enabled(baudrate_valid.is_ok(), button);
 
                     if button_resp.clicked() {
                         self.try_connect(ctx);
@@ -323,33 +941,107 @@ If no such variable is specified, the application takes the time when receiving
                     ui.separator();
                 });
             });
+        });
+    }
 
-            ui.separator();
-
+    /// The tabs that can be opened in the dock area, and the controls of the samples
+    fn render_page_bar(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.vertical_centered_justified(|ui| {
             ui.horizontal(|ui| {
-                ui.label("Pages: ");
-                ui.selectable_value(
-                    &mut self.plot_page,
-                    PlotPage::TimeValue,
-                    PlotPage::TimeValue.to_string(),
-                );
-                ui.selectable_value(&mut self.plot_page, PlotPage::XY, PlotPage::XY.to_string());
-                ui.selectable_value(
-                    &mut self.plot_page,
-                    PlotPage::SerialMonitor,
-                    PlotPage::SerialMonitor.to_string(),
-                );
+                ui.label(tr("label-pages"));
+                for page in PlotPage::ALL {
+                    if ui
+                        .selectable_label(
+                            self.is_dock_tab_open(DockTab::Page(page)),
+                            page.to_string(),
+                        )
+                        .on_hover_text(tr("hover-page"))
+                        .clicked()
+                    {
+                        self.open_page(page);
+                    }
+                }
+
+                ui.separator();
+                let mut connection_visible = self.is_dock_tab_open(DockTab::Connection);
+                if ui
+                    .toggle_value(&mut connection_visible, tr("toggle-connection"))
+                    .on_hover_text(tr("hover-connection"))
+                    .changed()
+                {
+                    self.toggle_dock_tab(DockTab::Connection);
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.separator();
+                    let response = ui
+                        .button(tr("copy-image"))
+                        .on_hover_text(tr("hover-copy-image"));
+                    if response.clicked() {
+                        self.copy_pages_image(ctx);
+                    }
+                    match &self.copy_image_status {
+                        Some(Ok(msg)) => {
+                            ui.weak(msg);
+                        }
+                        Some(Err(e)) => {
+                            ui.colored_label(ui.visuals().error_fg_color, "⚠")
+                                .on_hover_text(e);
+                        }
+                        None => {}
+                    }
+                }
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if ui.button("Reset").clicked() {
+                    if ui.button(tr("reset")).clicked() {
                         self.reset_connection(ctx);
                     }
 
-                    if ui.button("Clear").clicked() {
+                    if ui.button(tr("clear")).clicked() {
+                        if self.archive_on_clear {
+                            self.archive_samples();
+                        }
                         self.clear_samples(ctx);
                     }
+                    ui.checkbox(&mut self.archive_on_clear, tr("archive"))
+                        .on_hover_text(tr("hover-archive"));
+
+                    ui.toggle_value(&mut self.pause, tr("pause"))
+                        .on_hover_text(tr("hover-pause"));
+
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        let mut recording = self.recorder.is_some();
+                        let record_response = ui
+                            .toggle_value(&mut recording, tr("record"))
+                            .on_hover_text(match &self.recorder {
+                                Some(recorder) => tr_args(
+                                    "hover-recording",
+                                    &[
+                                        ("file", &recorder.path().display()),
+                                        ("bytes", &recorder.bytes_written()),
+                                    ],
+                                ),
+                                None => tr_args("hover-record", &[("file", &self.record_path)]),
+                            });
+
+                        if record_response.changed() {
+                            if recording {
+                                self.start_recording();
+                            } else {
+                                self.stop_recording();
+                            }
+                        }
 
-                    ui.toggle_value(&mut self.pause, "Pause");
+                        record_response.context_menu(|ui| {
+                            ui.label(tr("label-record-file"));
+                            ui.add_enabled(
+                                self.recorder.is_none(),
+                                egui::TextEdit::singleline(&mut self.record_path),
+                            );
+                        });
+                    }
 
                     ui.separator();
 
@@ -388,17 +1080,117 @@ If no such variable is specified, the application takes the time when receiving
                         log::debug!("time unit has changed. clearing samples");
                         self.clear_samples(ctx);
                     }
-                    ui.label("Time Unit: ");
+                    ui.label(tr("label-time-unit"));
 
-                    egui::ComboBox::from_id_source("value_separator_combobox")
-                        .selected_text(self.value_separator.to_string())
-                        .width(30.0)
-                        .show_ui(ui, |ui| {
-                            ui.selectable_value(&mut self.value_separator, ',', ",");
-                            ui.selectable_value(&mut self.value_separator, ';', ";");
-                            ui.selectable_value(&mut self.value_separator, ':', ":");
+                    let unwrap_response = ui
+                        .toggle_value(&mut self.unwrap_device_time, tr("unwrap-time"))
+                        .on_hover_text(tr("hover-unwrap-time"));
+                    if unwrap_response.changed() {
+                        log::debug!("device time unwrapping has changed. clearing samples");
+                        self.clear_samples(ctx);
+                    }
+
+                    let sync_response = ui
+                        .toggle_value(&mut self.sync_device_time, tr("host-time"))
+                        .on_hover_text(match self.time_sync_estimate() {
+                            Some((offset, drift_ppm)) => tr_args(
+                                "hover-host-time-synced",
+                                &[
+                                    ("offset", &format!("{:.3}", nanos_to_secs(offset))),
+                                    ("drift", &format!("{drift_ppm:.1}")),
+                                ],
+                            ),
+                            None => String::from(tr("hover-host-time")),
                         });
-                    ui.label("Value Separator: ");
+                    if sync_response.changed() {
+                        log::debug!("device time sync has changed. clearing samples");
+                        self.clear_samples(ctx);
+                    }
+
+                    match self.data_format {
+                        DataFormat::Ascii => {
+                            egui::ComboBox::from_id_source("value_separator_combobox")
+                                .selected_text(self.value_separator.to_string())
+                                .width(30.0)
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut self.value_separator, ',', ",");
+                                    ui.selectable_value(&mut self.value_separator, ';', ";");
+                                    ui.selectable_value(&mut self.value_separator, ':', ":");
+                                });
+                            ui.label(tr("label-value-separator"));
+                        }
+                        DataFormat::Binary => {
+                            if ui.button(tr("layout")).clicked() {
+                                self.show_binary_layout_window = true;
+                            }
+                        }
+                        DataFormat::Ubx => {
+                            if ui.button(tr("messages")).clicked() {
+                                self.show_ubx_messages_window = true;
+                            }
+                        }
+                        DataFormat::Nmea => {}
+                        DataFormat::Can => {
+                            if ui.button(tr("signals")).clicked() {
+                                self.show_can_signals_window = true;
+                            }
+                        }
+                    }
+
+                    let data_format_response =
+                        egui::ComboBox::from_id_source("data_format_combobox")
+                            .selected_text(self.data_format.label())
+                            .width(60.0)
+                            .show_ui(ui, |ui| {
+                                let mut changed = false;
+
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.data_format,
+                                        DataFormat::Ascii,
+                                        DataFormat::Ascii.label(),
+                                    )
+                                    .changed();
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.data_format,
+                                        DataFormat::Binary,
+                                        DataFormat::Binary.label(),
+                                    )
+                                    .changed();
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.data_format,
+                                        DataFormat::Ubx,
+                                        DataFormat::Ubx.label(),
+                                    )
+                                    .changed();
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.data_format,
+                                        DataFormat::Nmea,
+                                        DataFormat::Nmea.label(),
+                                    )
+                                    .on_hover_text(tr("hover-nmea"))
+                                    .changed();
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut self.data_format,
+                                        DataFormat::Can,
+                                        DataFormat::Can.label(),
+                                    )
+                                    .on_hover_text(tr("hover-can"))
+                                    .changed();
+
+                                changed
+                            });
+
+                    if data_format_response.inner.unwrap_or(false) {
+                        log::debug!("data format has changed. clearing samples");
+                        self.clear_samples(ctx);
+                        self.clear_parser();
+                    }
+                    ui.label(tr("label-format"));
 
                     ui.separator();
                 });
@@ -406,242 +1198,4936 @@ If no such variable is specified, the application takes the time when receiving
         });
     }
 
-    fn render_plot_tv(&mut self, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            egui::ScrollArea::vertical()
-                .id_source("plot_scroll_area")
-                .show(ui, |ui| {
-                    ui.with_layout(
-                        egui::Layout::top_down(egui::Align::Min).with_cross_justify(true),
-                        |ui| {
-                            ui.set_width(270.0);
-
-                            ui.horizontal(|ui| {
-                                ui.label("Values newer:");
-                                ui.add(
-                                    egui::Slider::new(&mut self.plot_tv_newer, 0.1..=500.0)
-                                        .logarithmic(true)
-                                        .suffix(TimeUnit::S.to_string()),
-                                );
-                            });
+    /// Returns true when the layout has changed
+    fn render_binary_layout(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
 
-                            ui.add_space(5.0);
+        ui.set_width(400.0);
 
-                            for i in 0..self.samples_appearance.len() {
-                                ui.group(|ui| {
-                                    ui.with_layout(
-                                        egui::Layout::right_to_left(egui::Align::Min),
-                                        |ui| {
-                                            ui.horizontal(|ui| {
-                                                egui::color_picker::color_edit_button_rgba(
-                                                    ui,
-                                                    &mut self.samples_appearance[i].color,
-                                                    egui::widgets::color_picker::Alpha::Opaque,
-                                                );
-                                                ui.checkbox(
-                                                    &mut self.samples_appearance[i].visible,
-                                                    "",
-                                                );
-                                                ui.text_edit_singleline(
-                                                    &mut self.samples_appearance[i].name,
-                                                );
-                                            });
-                                        },
-                                    )
-                                });
+        ui.label(tr("binary-layout-help"));
 
-                                ui.end_row();
-                            }
-                        },
-                    );
-                });
+        ui.add_space(12.0);
 
-            ui.separator();
+        egui::Grid::new("binary_layout_grid").show(ui, |ui| {
+            ui.label(tr("label-magic-bytes"));
+            changed |= ui
+                .text_edit_singleline(&mut self.binary_layout.magic)
+                .changed();
+            ui.end_row();
 
-            egui_plot::Plot::new("plot_tv")
-                .label_formatter(move |name, value| {
-                    if !name.is_empty() {
-                        format!(
-                            "{}\nt: {} {}\nv: {}",
-                            name,
-                            round_to_decimals(value.x, 7),
-                            TimeUnit::S,
-                            round_to_decimals(value.y, 7),
-                        )
-                    } else {
-                        format!(
-                            "t: {} {}\nv: {}",
-                            round_to_decimals(value.x, 7),
-                            TimeUnit::S,
-                            round_to_decimals(value.y, 7),
-                        )
+            ui.label(tr("label-endianness"));
+            egui::ComboBox::from_id_source("endianness_combobox")
+                .selected_text(self.binary_layout.endianness.to_string())
+                .show_ui(ui, |ui| {
+                    for endianness in [Endianness::Little, Endianness::Big] {
+                        changed |= ui
+                            .selectable_value(
+                                &mut self.binary_layout.endianness,
+                                endianness,
+                                endianness.to_string(),
+                            )
+                            .changed();
                     }
-                })
-                .x_axis_formatter(move |mark, _c, _range| {
-                    format!("{} {}", round_to_decimals(mark.value, 5), TimeUnit::S)
-                })
-                .y_axis_formatter(move |mark, _c, _range| {
-                    round_to_decimals(mark.value, 7).to_string()
-                })
-                .allow_zoom(egui::Vec2b { x: false, y: true })
-                .allow_boxed_zoom(false)
-                .show(ui, |plot_ui| {
-                    for (i, samples) in self.samples_vec.iter().enumerate() {
-                        if !self.samples_appearance[i].visible {
-                            continue;
-                        }
+                });
+            ui.end_row();
+        });
 
-                        let Some(first) = self.samples_vec.first().and_then(|b| b.first()) else {
-                            continue;
-                        };
+        if let Err(e) = self.binary_layout.magic_bytes() {
+            ui.label(egui::RichText::new(e.to_string()).color(egui::Color32::RED));
+        }
 
-                        let Some(last) = self.samples_vec.first().and_then(|b| b.last()) else {
-                            continue;
-                        };
+        ui.separator();
 
-                        let last_plot_bounds = plot_ui.plot_bounds();
-                        let plot_bounds = egui_plot::PlotBounds::from_min_max(
-                            [last.time - self.plot_tv_newer, last_plot_bounds.min()[1]],
-                            [last.time, last_plot_bounds.max()[1]],
-                        );
-                        plot_ui.set_plot_bounds(plot_bounds);
-
-                        let plot_line = egui_plot::Line::new(
-                            samples
-                                .into_iter()
-                                .filter_map(|s| {
-                                    if last.time - s.time < self.plot_tv_newer {
-                                        Some([s.time, s.value])
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect::<egui_plot::PlotPoints>(),
-                        )
-                        .name(&self.samples_appearance[i].name)
-                        .color(self.samples_appearance[i].color);
+        let mut remove = None;
 
-                        let start_vline_val = first.time.max(last.time - self.plot_tv_newer);
+        egui::Grid::new("binary_layout_fields_grid").show(ui, |ui| {
+            for (i, field) in self.binary_layout.fields.iter_mut().enumerate() {
+                ui.label(format!("{i:02}"));
+                changed |= ui.text_edit_singleline(&mut field.name).changed();
 
-                        plot_ui.vline(
-                            egui_plot::VLine::new(start_vline_val)
-                                .style(egui_plot::LineStyle::Dashed { length: 2.0 })
-                                .color(egui::Color32::LIGHT_BLUE),
-                        );
+                egui::ComboBox::from_id_source(("binary_field_type_combobox", i))
+                    .selected_text(field.ty.to_string())
+                    .width(50.0)
+                    .show_ui(ui, |ui| {
+                        for ty in BinaryFieldType::ALL {
+                            changed |= ui
+                                .selectable_value(&mut field.ty, ty, ty.to_string())
+                                .changed();
+                        }
+                    });
 
-                        plot_ui.line(plot_line);
-                    }
+                if ui.button("🗑").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+
+        if let Some(i) = remove {
+            self.binary_layout.fields.remove(i);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button(tr("add-field")).clicked() {
+                self.binary_layout.fields.push(BinaryField {
+                    name: format!("field_{}", self.binary_layout.fields.len()),
+                    ty: BinaryFieldType::default(),
                 });
+                changed = true;
+            }
+
+            ui.label(tr_args(
+                "packet-size",
+                &[(
+                    "bytes",
+                    &(self
+                        .binary_layout
+                        .magic_bytes()
+                        .map(|m| m.len())
+                        .unwrap_or(0)
+                        + self.binary_layout.payload_size()),
+                )],
+            ));
         });
+
+        changed
     }
 
-    fn render_plot_xy(&mut self, ui: &mut egui::Ui) {
-        ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
-            egui::Grid::new("plot_xy_grid").show(ui, |ui| {
-                ui.set_width(270.0);
+    /// Returns true when the selected messages have changed
+    fn render_ubx_messages(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
 
-                ui.label("Values newer:");
-                ui.add(
-                    egui::Slider::new(&mut self.plot_xy_newer, 0.1..=500.0)
-                        .logarithmic(true)
-                        .suffix(TimeUnit::S.to_string()),
-                );
+        ui.label(tr("ubx-messages-help"));
+
+        ui.add_space(12.0);
+
+        egui::Grid::new("ubx_messages_grid").show(ui, |ui| {
+            for message in UbxMessage::ALL {
+                let mut selected = self.ubx_messages.contains(&message);
+                if ui.checkbox(&mut selected, message.to_string()).changed() {
+                    if selected {
+                        self.ubx_messages.push(message);
+                    } else {
+                        self.ubx_messages.retain(|&m| m != message);
+                    }
+                    changed = true;
+                }
+                ui.weak(message.fields().join(", "));
                 ui.end_row();
+            }
+        });
+
+        changed
+    }
+
+    /// Returns true when the signals have changed
+    fn render_can_signals(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+
+        ui.label(tr("can-signals-help"));
+
+        ui.add_space(12.0);
+
+        let mut remove = None;
 
-                ui.label("X-Axis");
-                egui::ComboBox::from_id_source("samples_x_combobox")
-                    .selected_text(
-                        self.samples_appearance
-                            .get(self.plot_xy_samples_x)
-                            .map(|s| s.name.as_str())
-                            .unwrap_or(""),
+        egui::Grid::new("can_signals_grid").show(ui, |ui| {
+            ui.label(tr("column-name"));
+            ui.label(tr("column-can-id"));
+            ui.label(tr("column-byte"));
+            ui.label(tr("column-type"));
+            ui.label(tr("column-endianness"));
+            ui.label(tr("column-scale"));
+            ui.label(tr("column-offset"));
+            ui.end_row();
+
+            for (i, signal) in self.can_signals.iter_mut().enumerate() {
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut signal.name).desired_width(100.0))
+                    .changed();
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut signal.id)
+                            .hexadecimal(3, false, true)
+                            .prefix("0x")
+                            .clamp_range(0..=0x1FFF_FFFF),
                     )
+                    .changed();
+                changed |= ui
+                    .add(egui::DragValue::new(&mut signal.offset).clamp_range(0..=7))
+                    .changed();
+
+                egui::ComboBox::from_id_source(("can_signal_type_combobox", i))
+                    .selected_text(signal.ty.to_string())
+                    .width(50.0)
                     .show_ui(ui, |ui| {
-                        for i in 0..self.samples_vec.len() {
-                            ui.selectable_value(
-                                &mut self.plot_xy_samples_x,
-                                i,
-                                &self.samples_appearance[i].name,
-                            );
+                        for ty in BinaryFieldType::ALL {
+                            changed |= ui
+                                .selectable_value(&mut signal.ty, ty, ty.to_string())
+                                .changed();
                         }
                     });
-                ui.end_row();
-
-                ui.label("Y-Axis");
-                egui::ComboBox::from_id_source("samples_y_combobox")
-                    .selected_text(
-                        self.samples_appearance
-                            .get(self.plot_xy_samples_y)
-                            .map(|s| s.name.as_str())
-                            .unwrap_or(""),
-                    )
+                egui::ComboBox::from_id_source(("can_signal_endianness_combobox", i))
+                    .selected_text(signal.endianness.to_string())
                     .show_ui(ui, |ui| {
-                        for i in 0..self.samples_vec.len() {
-                            ui.selectable_value(
-                                &mut self.plot_xy_samples_y,
-                                i,
-                                &self.samples_appearance[i].name,
-                            );
+                        for endianness in [Endianness::Little, Endianness::Big] {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut signal.endianness,
+                                    endianness,
+                                    endianness.to_string(),
+                                )
+                                .changed();
                         }
                     });
+
+                changed |= ui
+                    .add(egui::DragValue::new(&mut signal.scale).speed(0.01))
+                    .changed();
+                changed |= ui
+                    .add(egui::DragValue::new(&mut signal.value_offset).speed(0.1))
+                    .changed();
+
+                if signal.offset + signal.ty.size() > 8 {
+                    ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                        .on_hover_text(tr("hover-can-signal-overflow"));
+                }
+                if ui.button("🗑").clicked() {
+                    remove = Some(i);
+                }
                 ui.end_row();
-            });
+            }
+        });
 
-            ui.separator();
+        if let Some(i) = remove {
+            self.can_signals.remove(i);
+            changed = true;
+        }
 
-            egui_plot::Plot::new("xy plot")
-                .x_axis_formatter(move |mark, _c, _range| {
-                    round_to_decimals(mark.value, 7).to_string()
-                })
-                .y_axis_formatter(move |mark, _c, _range| {
-                    round_to_decimals(mark.value, 7).to_string()
-                })
-                .show(ui, |plot_ui| {
-                    if let (Some(samples_x), Some(samples_y)) = (
-                        self.samples_vec.get(self.plot_xy_samples_x),
-                        self.samples_vec.get(self.plot_xy_samples_y),
-                    ) {
-                        if let (Some(last_x), Some(last_y)) = (samples_x.last(), samples_y.last()) {
-                            let plot_line = egui_plot::Line::new(
-                                samples_x
-                                    .into_iter()
-                                    .zip(samples_y)
-                                    .filter_map(|(x, y)| {
-                                        if last_x.time - x.time < self.plot_xy_newer {
-                                            Some([x.value, y.value])
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                    .collect::<egui_plot::PlotPoints>(),
-                            )
-                            .color(egui::Color32::DARK_RED);
-                            let last_point =
-                                egui_plot::Points::new(vec![[last_x.value, last_y.value]])
-                                    .color(egui::Color32::RED)
-                                    .highlight(true);
+        if ui.button(tr("add-signal")).clicked() {
+            self.can_signals.push(CanSignal {
+                name: format!("signal_{}", self.can_signals.len()),
+                ..Default::default()
+            });
+            changed = true;
+        }
 
-                            plot_ui.line(plot_line);
-                            plot_ui.points(last_point);
-                        }
-                    }
-                });
-        });
+        changed
     }
 
-    fn render_serial_monitor(&mut self, ui: &mut egui::Ui) {
-        egui::ScrollArea::vertical()
-            .id_source("serial_monitor_scroll_area")
-            .stick_to_bottom(true)
-            .show(ui, |ui| {
-                let monitor_text: String = self
-                    .serial_monitor_lines
+    /// Returns the committed edits of the derived channels
+    fn render_derived_channels(&mut self, ui: &mut egui::Ui) -> Vec<DerivedEdit> {
+        let mut edits = vec![];
+
+        ui.set_width(500.0);
+
+        ui.label(tr("derived-channels-help"));
+        ui.label(tr_args(
+            "derived-operators",
+            &[(
+                "functions",
+                &expression::FUNCTIONS
                     .iter()
-                    .fold(String::new(), |acc, x| acc + x);
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+            )],
+        ));
 
-                ui.text_edit_multiline(&mut monitor_text.as_str());
-            });
-    }
+        ui.add_space(12.0);
+
+        let mut remove = None;
+
+        let channel_names = self.samples_appearance[..self.n_raw_samples]
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<&str>>();
+
+        egui::Grid::new("derived_channels_grid").show(ui, |ui| {
+            ui.label(tr("column-name"));
+            ui.label(tr("column-kind"));
+            ui.label(tr("column-expression"));
+            ui.label(tr("column-integrate"));
+            ui.end_row();
+
+            for (i, derived) in self.derived_channels.iter_mut().enumerate() {
+                if committed_text_edit(ui, ("derived_name", i), &mut derived.name, 100.0) {
+                    edits.push(DerivedEdit::Renamed(i));
+                }
+
+                let kind_before = derived.kind;
+                egui::ComboBox::from_id_source(("derived_kind_combobox", i))
+                    .selected_text(derived.kind.label())
+                    .show_ui(ui, |ui| {
+                        for kind in DerivedKind::ALL {
+                            ui.selectable_value(&mut derived.kind, kind, kind.label());
+                        }
+                    });
+                if derived.kind != kind_before {
+                    edits.push(DerivedEdit::Changed(i));
+                }
+
+                ui.vertical(|ui| {
+                    if derived.kind == DerivedKind::Expression {
+                        let width = ui.spacing().text_edit_width;
+                        if committed_text_edit(
+                            ui,
+                            ("derived_expression", i),
+                            &mut derived.expression,
+                            width,
+                        ) {
+                            edits.push(DerivedEdit::Changed(i));
+                        }
+                    } else if derived.kind.is_fft_peak() {
+                        let (channel, fft_size) = (derived.channels[0], derived.fft_size);
+
+                        ui.horizontal(|ui| {
+                            channel_combobox(
+                                ui,
+                                ("derived_fft_source", i),
+                                &mut derived.channels[0],
+                                &channel_names,
+                            );
+                            egui::ComboBox::from_id_source(("derived_fft_size_combobox", i))
+                                .selected_text(tr_args(
+                                    "n-samples",
+                                    &[("count", &derived.fft_size)],
+                                ))
+                                .show_ui(ui, |ui| {
+                                    for fft_size in fft::FFT_SIZES {
+                                        ui.selectable_value(
+                                            &mut derived.fft_size,
+                                            fft_size,
+                                            fft_size.to_string(),
+                                        );
+                                    }
+                                });
+                        });
+
+                        if (channel, fft_size) != (derived.channels[0], derived.fft_size) {
+                            edits.push(DerivedEdit::Changed(i));
+                        }
+                    } else if derived.kind.is_filter() {
+                        let before = (derived.channels[0], derived.filter_order);
+                        let mut cutoff_committed = false;
+
+                        ui.horizontal(|ui| {
+                            channel_combobox(
+                                ui,
+                                ("derived_filter_source", i),
+                                &mut derived.channels[0],
+                                &channel_names,
+                            );
+                            let response = ui
+                                .add(
+                                    egui::DragValue::new(&mut derived.cutoff)
+                                        .clamp_range(0.001..=1_000_000.0)
+                                        .speed(0.01)
+                                        .suffix(" Hz"),
+                                )
+                                .on_hover_text(tr("hover-cutoff"));
+                            // While dragging, the filter is only restarted once released
+                            cutoff_committed = response.drag_stopped()
+                                || (response.changed() && !response.dragged());
+                            ui.selectable_value(
+                                &mut derived.filter_order,
+                                1,
+                                tr("filter-first-order"),
+                            )
+                            .on_hover_text(tr("hover-first-order"));
+                            ui.selectable_value(
+                                &mut derived.filter_order,
+                                2,
+                                tr("filter-second-order"),
+                            )
+                            .on_hover_text(tr("hover-second-order"));
+                        });
+
+                        if cutoff_committed || before != (derived.channels[0], derived.filter_order)
+                        {
+                            edits.push(DerivedEdit::Changed(i));
+                        }
+                    } else {
+                        let mut channels = derived.channels;
+
+                        ui.horizontal(|ui| {
+                            channel_combobox(
+                                ui,
+                                ("derived_a", i),
+                                &mut channels[0],
+                                &channel_names,
+                            );
+                            ui.label(if derived.kind == DerivedKind::Ratio {
+                                "/"
+                            } else {
+                                "×"
+                            });
+                            channel_combobox(
+                                ui,
+                                ("derived_b", i),
+                                &mut channels[1],
+                                &channel_names,
+                            );
+                        });
+
+                        if channels != derived.channels {
+                            derived.channels = channels;
+                            edits.push(DerivedEdit::Changed(i));
+                        }
+                    }
+
+                    if let Some(e) = derived.error() {
+                        ui.label(egui::RichText::new(e).small().color(egui::Color32::RED));
+                    }
+                });
+
+                if ui.checkbox(&mut derived.integrate, "").changed() {
+                    edits.push(DerivedEdit::Changed(i));
+                }
+
+                if ui.button("🗑").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+
+        if let Some(i) = remove {
+            self.derived_channels.remove(i);
+            edits.push(DerivedEdit::Removed(i));
+        }
+
+        if ui.button(tr("add-derived-channel")).clicked() {
+            self.derived_channels.push(DerivedChannel::new(
+                format!("derived_{}", self.derived_channels.len()),
+                String::from("c0"),
+            ));
+            edits.push(DerivedEdit::Added);
+        }
+
+        edits
+    }
+
+    fn render_automation(&mut self, ui: &mut egui::Ui) {
+        ui.set_width(600.0);
+
+        ui.label(tr("automation-help"));
+
+        ui.add_space(12.0);
+
+        let mut remove = None;
+
+        egui::Grid::new("automation_grid").show(ui, |ui| {
+            ui.label("");
+            ui.label(tr("column-when"));
+            ui.label("");
+            ui.label(tr("column-action"));
+            ui.label("");
+            ui.end_row();
+
+            for (i, rule) in self.automation_rules.iter_mut().enumerate() {
+                ui.checkbox(&mut rule.enabled, "");
+
+                let hook_before = rule.hook;
+                egui::ComboBox::from_id_source(("automation_hook_combobox", i))
+                    .selected_text(rule.hook.label())
+                    .show_ui(ui, |ui| {
+                        for hook in RuleHook::ALL {
+                            ui.selectable_value(&mut rule.hook, hook, hook.label());
+                        }
+                    });
+                if rule.hook != hook_before {
+                    rule.invalidate();
+                }
+
+                ui.vertical(|ui| {
+                    if rule.hook == RuleHook::Timer {
+                        ui.add(
+                            egui::DragValue::new(&mut rule.interval_ms)
+                                .clamp_range(10..=3_600_000)
+                                .prefix(format!("{} ", tr("every")))
+                                .suffix(" ms"),
+                        );
+                    } else if ui.text_edit_singleline(&mut rule.pattern).changed() {
+                        rule.invalidate();
+                    }
+
+                    if let Some(e) = rule.error() {
+                        ui.label(egui::RichText::new(e).small().color(egui::Color32::RED));
+                    }
+                });
+
+                egui::ComboBox::from_id_source(("automation_action_combobox", i))
+                    .selected_text(rule.action.label())
+                    .show_ui(ui, |ui| {
+                        for action in RuleAction::ALL {
+                            ui.selectable_value(&mut rule.action, action, action.label());
+                        }
+                    });
+
+                ui.add_enabled(
+                    rule.action.has_argument(),
+                    egui::TextEdit::singleline(&mut rule.argument),
+                );
+
+                if ui.button("🗑").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+
+        if let Some(i) = remove {
+            self.automation_rules.remove(i);
+        }
+
+        if ui.button(tr("add-rule")).clicked() {
+            self.automation_rules.push(AutomationRule::default());
+        }
+
+        ui.checkbox(&mut self.notify_flash_taskbar, tr("flash-taskbar"));
+
+        ui.add_space(12.0);
+
+        egui::CollapsingHeader::new(tr("automation-script"))
+            .id_source("automation_script_collapsing_header")
+            .show(ui, |ui| {
+                let script = &mut self.automation_script;
+
+                ui.label(tr("automation-script-help"));
+
+                ui.horizontal(|ui| {
+                    if ui.checkbox(&mut script.enabled, tr("enabled")).changed() {
+                        script.invalidate();
+                    }
+                    ui.add(
+                        egui::DragValue::new(&mut script.interval_ms)
+                            .clamp_range(10..=3_600_000)
+                            .prefix(format!("on_timer() {} ", tr("every")))
+                            .suffix(" ms"),
+                    );
+                });
+
+                egui::ScrollArea::vertical()
+                    .id_source("automation_script_scroll_area")
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut script.source)
+                                .code_editor()
+                                .desired_rows(12)
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr("apply"))
+                        .on_hover_text(tr("hover-apply-automation-script"))
+                        .clicked()
+                    {
+                        script.invalidate();
+                    }
+
+                    if let Some(e) = script.error() {
+                        ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+                    }
+                });
+            });
+    }
+
+    fn render_alarms(&mut self, ui: &mut egui::Ui) {
+        ui.set_width(600.0);
+
+        ui.label(tr("alarms-help"));
+
+        ui.add_space(12.0);
+
+        let names = self
+            .samples_appearance
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<&str>>();
+        let mut remove = None;
+
+        egui::Grid::new("alarms_grid").show(ui, |ui| {
+            ui.label("");
+            ui.label(tr("column-channel"));
+            ui.label(tr("column-condition"));
+            ui.label(tr("column-low"));
+            ui.label(tr("column-high"));
+            ui.label(tr("column-for"));
+            ui.label(tr("column-notify"));
+            ui.end_row();
+
+            for (i, alarm) in self.alarms.iter_mut().enumerate() {
+                let mut changed = ui.checkbox(&mut alarm.enabled, "").changed();
+
+                ui.horizontal(|ui| {
+                    changed |= ui
+                        .add(egui::TextEdit::singleline(&mut alarm.channel).desired_width(100.0))
+                        .changed();
+                    if derived::resolve_channel(&alarm.channel, &names).is_none() {
+                        ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                            .on_hover_text(tr("hover-unknown-channel"));
+                    }
+                });
+
+                egui::ComboBox::from_id_source(("alarm_condition_combobox", i))
+                    .selected_text(alarm.condition.label())
+                    .show_ui(ui, |ui| {
+                        for condition in AlarmCondition::ALL {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut alarm.condition,
+                                    condition,
+                                    condition.label(),
+                                )
+                                .changed();
+                        }
+                    });
+
+                changed |= ui
+                    .add_enabled(
+                        alarm.condition.uses_low(),
+                        egui::DragValue::new(&mut alarm.low).speed(0.01),
+                    )
+                    .changed();
+                changed |= ui
+                    .add_enabled(
+                        alarm.condition.uses_high(),
+                        egui::DragValue::new(&mut alarm.high).speed(0.01),
+                    )
+                    .changed();
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut alarm.samples)
+                            .clamp_range(1..=1_000_000)
+                            .suffix(format!(" {}", tr("unit-samples"))),
+                    )
+                    .changed();
+                ui.checkbox(&mut alarm.notify, "");
+
+                if changed {
+                    alarm.reset();
+                }
+
+                if alarm.raised_on().is_some() {
+                    ui.colored_label(egui::Color32::RED, "🔔");
+                } else {
+                    ui.label("");
+                }
+                if ui.button("🗑").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+
+        if let Some(i) = remove {
+            self.alarms.remove(i);
+        }
+
+        if ui.button(tr("add-alarm")).clicked() {
+            self.alarms.push(AlarmRule::default());
+        }
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(tr_args(
+                "alarm-events",
+                &[("count", &self.alarm_events.len())],
+            ));
+            if ui.button(tr("clear")).clicked() {
+                self.alarm_events.clear();
+            }
+        });
+
+        egui::ScrollArea::vertical()
+            .id_source("alarm_events_scroll_area")
+            .max_height(200.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                egui::Grid::new("alarm_events_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for event in self.alarm_events.iter() {
+                            ui.monospace(format!(
+                                "{} {}",
+                                round_to_decimals(nanos_to_secs(event.time - self.time_zero), 3),
+                                TimeUnit::S
+                            ));
+                            ui.label(&event.channel);
+                            if event.raised {
+                                ui.colored_label(egui::Color32::RED, &event.message);
+                            } else {
+                                ui.label(&event.message);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_conditional_capture(&mut self, ui: &mut egui::Ui) {
+        ui.set_width(500.0);
+
+        ui.label(tr("conditional-capture-help"));
+
+        ui.add_space(12.0);
+
+        let (start_error, stop_error) = self.conditional_capture.errors();
+        let capture = &mut self.conditional_capture;
+        let editable = !capture.is_armed() && !capture.is_capturing();
+
+        egui::Grid::new("conditional_capture_grid").show(ui, |ui| {
+            ui.label(tr("label-start-condition"));
+            ui.horizontal(|ui| {
+                ui.add_enabled(
+                    editable,
+                    egui::TextEdit::singleline(&mut capture.start_condition),
+                );
+                if let Some(e) = start_error {
+                    ui.colored_label(ui.visuals().error_fg_color, "⚠")
+                        .on_hover_text(e);
+                }
+            });
+            ui.end_row();
+
+            ui.label(tr("label-stop-condition"));
+            ui.horizontal(|ui| {
+                ui.add_enabled(
+                    editable,
+                    egui::TextEdit::singleline(&mut capture.stop_condition)
+                        .hint_text(tr("hint-none")),
+                );
+                if let Some(e) = stop_error {
+                    ui.colored_label(ui.visuals().error_fg_color, "⚠")
+                        .on_hover_text(e);
+                }
+            });
+            ui.end_row();
+
+            ui.label(tr("label-duration"));
+            ui.add_enabled(
+                editable,
+                egui::DragValue::new(&mut capture.duration)
+                    .clamp_range(0.0..=f64::MAX)
+                    .speed(0.1)
+                    .suffix(" s"),
+            )
+            .on_hover_text(tr("hover-duration"));
+            ui.end_row();
+
+            ui.label(tr("label-pre-trigger"));
+            ui.add_enabled(
+                editable,
+                egui::DragValue::new(&mut capture.pre_trigger)
+                    .clamp_range(0.0..=f64::MAX)
+                    .speed(0.1)
+                    .suffix(" s"),
+            );
+            ui.end_row();
+
+            ui.label(tr("rearm"));
+            ui.add_enabled(editable, egui::Checkbox::new(&mut capture.rearm, ""))
+                .on_hover_text(tr("hover-rearm"));
+            ui.end_row();
+
+            ui.label(tr("label-capture-file"));
+            ui.add_enabled(
+                editable && !self.auto_session_names,
+                egui::TextEdit::singleline(&mut capture.path),
+            );
+            ui.end_row();
+        });
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            if capture.is_armed() || capture.is_capturing() {
+                if ui.button(tr("disarm")).clicked() {
+                    if let Err(e) = capture.disarm() {
+                        log::error!("failed to finish conditional capture, Err: {e:?}");
+                        self.conditional_capture_error = Some(e.to_string());
+                    }
+                }
+            } else if ui.button(tr("arm")).clicked() {
+                self.conditional_capture_error = None;
+                capture.arm();
+            }
+
+            if capture.is_capturing() {
+                ui.colored_label(egui::Color32::RED, tr("status-capturing"));
+            } else if capture.is_armed() {
+                ui.label(tr("status-armed"));
+            } else {
+                ui.label(tr("status-disarmed"));
+            }
+            ui.label(tr_args("n-captures", &[("count", &capture.n_captures())]));
+        });
+
+        if let Some(path) = capture.path() {
+            ui.label(tr_args("capture-file", &[("file", &path)]));
+        }
+        if let Some(e) = &self.conditional_capture_error {
+            ui.colored_label(ui.visuals().error_fg_color, e);
+        }
+    }
+
+    fn render_profiles(&mut self, ui: &mut egui::Ui) {
+        ui.set_width(400.0);
+
+        ui.label(tr("profiles-help"));
+
+        ui.add_space(12.0);
+
+        let locked = self.is_connected && !self.edit_connection;
+        let mut load = None;
+        let mut update = None;
+        let mut remove = None;
+
+        egui::Grid::new("profiles_grid").show(ui, |ui| {
+            for (i, profile) in self.connection_profiles.iter_mut().enumerate() {
+                ui.text_edit_singleline(&mut profile.name);
+                ui.label(format!("{} {} Bd", profile.port, profile.baudrate));
+
+                if ui
+                    .add_enabled(!locked, egui::Button::new(tr("load")))
+                    .clicked()
+                {
+                    load = Some(i);
+                }
+                if ui
+                    .button(tr("update"))
+                    .on_hover_text(tr("hover-update-profile"))
+                    .clicked()
+                {
+                    update = Some(i);
+                }
+                if ui.button("🗑").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+
+        if let Some(i) = load {
+            self.load_profile(i);
+        }
+        if let Some(i) = update {
+            let name = self.connection_profiles[i].name.clone();
+            self.connection_profiles[i] = self.current_profile(name.clone());
+            self.active_profile = name;
+        }
+        if let Some(i) = remove {
+            self.connection_profiles.remove(i);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(tr("label-name"));
+            ui.text_edit_singleline(&mut self.new_profile_name);
+
+            let name = self.new_profile_name.trim().to_string();
+            let exists = self.connection_profiles.iter().any(|p| p.name == name);
+
+            if ui
+                .add_enabled(
+                    !name.is_empty() && !exists,
+                    egui::Button::new(tr("save-current")),
+                )
+                .on_hover_text(tr("hover-save-profile"))
+                .clicked()
+            {
+                self.connection_profiles
+                    .push(self.current_profile(name.clone()));
+                self.active_profile = name;
+                self.new_profile_name.clear();
+            }
+        });
+    }
+
+    fn render_init_commands(&mut self, ui: &mut egui::Ui) {
+        ui.set_width(400.0);
+
+        ui.label(tr("init-commands-help"));
+
+        ui.add_space(12.0);
+
+        ui.horizontal(|ui| {
+            ui.label(tr("label-line-ending"));
+            egui::ComboBox::from_id_source("init_commands_line_ending_combobox")
+                .selected_text(self.init_commands_line_ending.label())
+                .show_ui(ui, |ui| {
+                    for line_ending in LineEnding::ALL {
+                        ui.selectable_value(
+                            &mut self.init_commands_line_ending,
+                            line_ending,
+                            line_ending.label(),
+                        );
+                    }
+                });
+        });
+
+        let mut remove = None;
+
+        egui::Grid::new("init_commands_grid").show(ui, |ui| {
+            ui.label(tr("column-delay"));
+            ui.label(tr("column-command"));
+            ui.end_row();
+
+            for (i, init_command) in self.init_commands.iter_mut().enumerate() {
+                ui.add(
+                    egui::DragValue::new(&mut init_command.delay_ms)
+                        .clamp_range(0..=60_000)
+                        .suffix(" ms"),
+                );
+                ui.text_edit_singleline(&mut init_command.command);
+
+                if ui.button("🗑").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+
+        if let Some(i) = remove {
+            self.init_commands.remove(i);
+        }
+
+        if ui.button(tr("add-command")).clicked() {
+            self.init_commands.push(InitCommand::default());
+        }
+    }
+
+    /// The controls to scroll back in time, and to spill the history to disk
+    fn render_history_controls(&mut self, ui: &mut egui::Ui) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            ui.checkbox(&mut self.history_spill, tr("history-spill"))
+                .on_hover_text(tr("hover-history-spill"));
+            if !self.history_spill {
+                self.history.take();
+            }
+        }
+
+        let first = self
+            .samples_vec
+            .iter()
+            .filter_map(|s| s.first().map(|s| s.time))
+            .min();
+        #[cfg(not(target_arch = "wasm32"))]
+        let first = match (first, self.history.as_ref().and_then(|h| h.first_time())) {
+            (Some(first), Some(spilled_first)) => Some(first.min(spilled_first)),
+            (first, spilled_first) => first.or(spilled_first),
+        };
+        let last = self
+            .samples_vec
+            .first()
+            .and_then(|s| s.last())
+            .map(|s| s.time);
+
+        let (Some(first), Some(last)) = (first, last) else {
+            return;
+        };
+        let available = nanos_to_secs(last - first);
+
+        ui.horizontal(|ui| {
+            ui.label(tr("label-scroll-back"));
+            let slider = ui.add(
+                egui::Slider::new(&mut self.plot_tv_scroll_back, 0.0..=available.max(0.0))
+                    .suffix(TimeUnit::S.to_string()),
+            );
+            if ui
+                .add_enabled(
+                    self.plot_tv_scroll_back > 0.0,
+                    egui::Button::new(tr("live")),
+                )
+                .clicked()
+            {
+                self.plot_tv_scroll_back = 0.0;
+                self.plot_tv_view_released = false;
+            }
+            // Places the browsed view of the paused plot at the scrolled position
+            if slider.changed() {
+                self.plot_tv_view_released = false;
+            }
+        });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(history) = &self.history {
+            ui.label(tr_args(
+                "history-spilled",
+                &[(
+                    "size",
+                    &format!("{:.1}", history.bytes() as f64 / 1_000_000.0),
+                )],
+            ));
+        }
+    }
+
+    fn render_session_diff(&mut self, ui: &mut egui::Ui) {
+        ui.set_width(600.0);
+
+        ui.label(tr("session-diff-help"));
+
+        ui.add_space(12.0);
+
+        egui::Grid::new("session_diff_grid").show(ui, |ui| {
+            ui.label(tr("label-first"));
+            ui.text_edit_singleline(&mut self.session_diff_paths[0]);
+            ui.end_row();
+
+            ui.label(tr("label-second"));
+            ui.text_edit_singleline(&mut self.session_diff_paths[1]);
+            ui.end_row();
+
+            ui.label(tr("label-align-by"));
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_source("session_diff_alignment_combobox")
+                    .selected_text(self.session_diff_alignment.label())
+                    .show_ui(ui, |ui| {
+                        for alignment in DiffAlignment::ALL {
+                            ui.selectable_value(
+                                &mut self.session_diff_alignment,
+                                alignment,
+                                alignment.label(),
+                            );
+                        }
+                    });
+
+                if self.session_diff_alignment == DiffAlignment::Trigger {
+                    ui.label(tr("label-channel"));
+                    ui.add(egui::DragValue::new(&mut self.session_diff_trigger_channel));
+                    ui.label(tr("label-rising-above"));
+                    ui.add(egui::DragValue::new(&mut self.session_diff_trigger_level).speed(0.1));
+                }
+            });
+            ui.end_row();
+        });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if ui.button(tr("compare")).clicked() {
+            self.session_diff = Some(self.compare_captures().map_err(|e| {
+                log::error!("comparing captures failed, Err: {e}");
+                tr_args("comparing-failed", &[("error", &e)])
+            }));
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        ui.label(tr("web-compare-unavailable"));
+
+        let diff = match &self.session_diff {
+            Some(Ok(diff)) => diff,
+            Some(Err(e)) => {
+                ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+                return;
+            }
+            None => return,
+        };
+
+        ui.add_space(12.0);
+
+        ui.label(tr_args(
+            "session-diff-offset",
+            &[("offset", &format!("{:.6}", diff.offset_secs))],
+        ));
+
+        egui::Grid::new("session_diff_stats_grid")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(tr("column-channel"));
+                ui.label(tr("column-mean"));
+                ui.label(tr("column-rms"));
+                ui.label(tr("column-max-diff"));
+                ui.end_row();
+
+                for channel in diff.channels.iter() {
+                    ui.label(&channel.name);
+                    ui.monospace(format!("{:.6}", channel.mean));
+                    ui.monospace(format!("{:.6}", channel.rms));
+                    ui.monospace(format!("{:.6}", channel.max_abs));
+                    ui.end_row();
+                }
+            });
+
+        egui_plot::Plot::new("session_diff_plot")
+            .height(250.0)
+            .legend(egui_plot::Legend::default())
+            .show(ui, |plot_ui| {
+                for (i, channel) in diff.channels.iter().enumerate() {
+                    let color = unique_color_in_list(i, diff.channels.len());
+
+                    plot_ui.line(
+                        egui_plot::Line::new(channel.points.clone())
+                            .name(&channel.name)
+                            .color(color),
+                    );
+                }
+            });
+    }
+
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    fn render_import(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.set_width(500.0);
+
+        ui.label(tr("import-help"));
+
+        ui.add_space(12.0);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        ui.horizontal(|ui| {
+            ui.label(tr("label-file"));
+            ui.text_edit_singleline(&mut self.import_path);
+
+            if ui.button(tr("import")).clicked() {
+                let res = std::fs::read_to_string(&self.import_path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|text| import::parse_csv_capture(&text));
+
+                self.finish_import(ctx, res);
+            }
+        });
+
+        #[cfg(target_arch = "wasm32")]
+        ui.label(tr("web-import-unavailable"));
+
+        match &self.import_status {
+            Some(Ok(status)) => {
+                ui.label(status);
+            }
+            Some(Err(e)) => {
+                ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+            }
+            None => {}
+        }
+    }
+
+    fn render_export(&mut self, ui: &mut egui::Ui) {
+        ui.set_width(400.0);
+
+        ui.label(tr("export-help"));
+
+        ui.add_space(12.0);
+
+        self.sync_export_channels();
+
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for (selected, appearance) in self
+                    .export_channels
+                    .iter_mut()
+                    .zip(self.samples_appearance.iter())
+                {
+                    ui.checkbox(selected, &appearance.name);
+                }
+            });
+
+        ui.horizontal(|ui| {
+            if ui.button(tr("select-all")).clicked() {
+                self.export_channels.iter_mut().for_each(|s| *s = true);
+            }
+            if ui.button(tr("none")).clicked() {
+                self.export_channels.iter_mut().for_each(|s| *s = false);
+            }
+            if ui.button(tr("select-visible")).clicked() {
+                for (selected, appearance) in self
+                    .export_channels
+                    .iter_mut()
+                    .zip(self.samples_appearance.iter())
+                {
+                    *selected = appearance.visible;
+                }
+            }
+        });
+
+        ui.separator();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        ui.horizontal(|ui| {
+            ui.label(tr("label-format"));
+            let format = self.export_format;
+            egui::ComboBox::from_id_source("export_format_combobox")
+                .selected_text(self.export_format.label())
+                .show_ui(ui, |ui| {
+                    for format in ExportFormat::ALL {
+                        ui.selectable_value(&mut self.export_format, format, format.label());
+                    }
+                });
+            if self.export_format != format {
+                self.export_path = std::path::Path::new(&self.export_path)
+                    .with_extension(self.export_format.extension())
+                    .to_string_lossy()
+                    .into_owned();
+            }
+        });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        ui.horizontal(|ui| {
+            ui.label(tr("label-file"));
+            ui.text_edit_singleline(&mut self.export_path);
+
+            if ui.button(tr("export")).clicked() {
+                self.export_status = Some(match self.export_samples() {
+                    Ok(n_channels) => Ok(tr_args(
+                        "exported-channels",
+                        &[("count", &n_channels), ("file", &self.export_path)],
+                    )),
+                    Err(e) => {
+                        log::error!("exporting samples failed, Err: {e}");
+                        Err(tr_args("export-failed", &[("error", &e)]))
+                    }
+                });
+            }
+        });
+
+        #[cfg(target_arch = "wasm32")]
+        ui.label(tr("web-export-unavailable"));
+
+        match &self.export_status {
+            Some(Ok(status)) => {
+                ui.label(status);
+            }
+            Some(Err(e)) => {
+                ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+            }
+            None => {}
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_data_servers(&mut self, ui: &mut egui::Ui) {
+        ui.set_width(400.0);
+
+        ui.label(tr("data-servers-help"));
+
+        ui.add_space(12.0);
+
+        ui.horizontal(|ui| {
+            ui.label(tr("label-allowed-origins"));
+            ui.add_enabled(
+                !self.http_api_enabled && !self.sample_broadcast_enabled,
+                egui::TextEdit::singleline(&mut self.data_server_origins)
+                    .hint_text("http://localhost:8000"),
+            )
+            .on_hover_text(tr("hover-allowed-origins"));
+        });
+
+        ui.add_space(12.0);
+        ui.strong("HTTP API");
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.http_api_enabled, tr("enabled"));
+            ui.label(tr("label-port"));
+            if ui
+                .add_enabled(
+                    !self.http_api_enabled,
+                    egui::DragValue::new(&mut self.http_api_port).clamp_range(1..=u16::MAX),
+                )
+                .changed()
+            {
+                self.http_api_error = None;
+            }
+        });
+
+        match (&self.http_api, &self.http_api_error) {
+            (Some(api), _) => {
+                ui.label(tr_args("http-api-serving", &[("addr", &api.addr())]));
+            }
+            (None, Some(e)) => {
+                ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+            }
+            (None, None) => {}
+        }
+
+        ui.separator();
+
+        egui::Grid::new("http_api_endpoints_grid").show(ui, |ui| {
+            ui.code("GET /channels");
+            ui.label(tr("http-api-channels"));
+            ui.end_row();
+
+            ui.code("GET /samples?since=<s>");
+            ui.label(tr("http-api-samples-since"));
+            ui.end_row();
+
+            ui.code("GET /samples?channel=<i>");
+            ui.label(tr("http-api-samples-channel"));
+            ui.end_row();
+        });
+
+        ui.separator();
+        ui.strong("WebSocket");
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.sample_broadcast_enabled, tr("enabled"));
+            ui.label(tr("label-port"));
+            if ui
+                .add_enabled(
+                    !self.sample_broadcast_enabled,
+                    egui::DragValue::new(&mut self.sample_broadcast_port).clamp_range(1..=u16::MAX),
+                )
+                .changed()
+            {
+                self.sample_broadcast_error = None;
+            }
+        });
+
+        match (&self.sample_broadcast, &self.sample_broadcast_error) {
+            (Some(broadcast), _) => {
+                ui.label(tr_args(
+                    "websocket-broadcasting",
+                    &[
+                        ("addr", &broadcast.addr()),
+                        ("clients", &broadcast.n_clients()),
+                    ],
+                ));
+            }
+            (None, Some(e)) => {
+                ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+            }
+            (None, None) => {}
+        }
+
+        ui.label(tr("websocket-message"));
+        ui.code(r#"{"channel":"temp","unit":"°C","time":1.5,"value":21.3}"#);
+    }
+
+    fn render_device_script(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.set_width(500.0);
+
+        ui.label(tr("device-script-help"));
+
+        ui.add_space(12.0);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        ui.horizontal(|ui| {
+            ui.label(tr("label-file"));
+            ui.text_edit_singleline(&mut self.device_script_path);
+
+            if ui.button(tr("load")).clicked() {
+                match std::fs::read_to_string(&self.device_script_path) {
+                    Ok(script) => self.device_script = script,
+                    Err(e) => log::error!("loading device script failed, Err: {e}"),
+                }
+            }
+        });
+
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.device_script)
+                        .code_editor()
+                        .desired_rows(12)
+                        .desired_width(f32::INFINITY),
+                );
+            });
+
+        let res = DeviceScript::parse(&self.device_script);
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(res.is_ok(), egui::Button::new(tr("apply")))
+                .on_hover_text(tr("hover-apply-script"))
+                .clicked()
+            {
+                self.reset_connection(ctx);
+            }
+
+            if let Err(e) = &res {
+                ui.label(egui::RichText::new(e.to_string()).color(egui::Color32::RED));
+            }
+        });
+    }
+
+    fn render_acquisition(&mut self, ui: &mut egui::Ui) {
+        ui.label(
+"How the reads from the connection perform. A read latency or poll interval much longer than
+the interval of the samples makes the plot choppy, partial reads are normal for fast devices."
+        );
+
+        ui.add_space(12.0);
+
+        let stats = &self.acquisition_stats;
+        let format_duration = |duration: Option<Duration>| {
+            duration.map_or(String::from("-"), |d| {
+                format!("{:.1} ms", d.as_secs_f64() * 1000.0)
+            })
+        };
+        let occupancy = |len: usize, size: usize| {
+            format!("{len} / {size} ({:.0} %)", len as f64 / size as f64 * 100.0)
+        };
+        let fullest_channel = self
+            .samples_vec
+            .iter()
+            .max_by_key(|samples| samples.len())
+            .map_or(String::from("-"), |samples| {
+                occupancy(samples.len(), samples.size())
+            });
+
+        egui::Grid::new("acquisition_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(tr("label-reads"));
+                ui.monospace(stats.reads.to_string());
+                ui.end_row();
+                ui.label(tr("label-empty-reads"));
+                ui.monospace(stats.empty_reads.to_string());
+                ui.end_row();
+                ui.label(tr("label-failed-reads"));
+                ui.monospace(stats.read_errors.to_string());
+                ui.end_row();
+                ui.label(tr("label-read-latency"));
+                ui.monospace(tr_args(
+                    "latest-and-max",
+                    &[
+                        ("latest", &format_duration(stats.last_read_latency)),
+                        ("max", &format_duration(Some(stats.max_read_latency))),
+                    ],
+                ));
+                ui.end_row();
+                ui.label(tr("label-poll-interval"));
+                ui.monospace(tr_args(
+                    "latest-and-max",
+                    &[
+                        ("latest", &format_duration(stats.last_poll_interval)),
+                        ("max", &format_duration(Some(stats.max_poll_interval))),
+                    ],
+                ));
+                ui.end_row();
+                ui.label(tr("label-parse-errors"))
+                    .on_hover_text(tr("hover-parse-errors"));
+                ui.monospace(stats.parse_errors.to_string());
+                ui.end_row();
+                ui.label(tr("label-partial-reads"))
+                    .on_hover_text(tr("hover-partial-reads"));
+                ui.monospace(stats.partial_reads.to_string());
+                ui.end_row();
+                ui.label(tr("label-unparsed-bytes"));
+                ui.monospace(self.n_unparsed_bytes().to_string());
+                ui.end_row();
+                ui.label(tr("label-fullest-channel-buffer"));
+                ui.monospace(fullest_channel);
+                ui.end_row();
+                ui.label(tr("label-monitor-buffer"));
+                ui.monospace(occupancy(
+                    self.serial_monitor_bytes.len(),
+                    self.serial_monitor_bytes.size(),
+                ));
+                ui.end_row();
+            });
+
+        if ui.button(tr("reset")).clicked() {
+            self.acquisition_stats.clear();
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_debug_bundle(&mut self, ui: &mut egui::Ui) {
+        ui.set_width(400.0);
+
+        ui.label(tr("debug-bundle-help"));
+
+        ui.add_space(12.0);
+
+        ui.horizontal(|ui| {
+            ui.label(tr("label-file"));
+            ui.text_edit_singleline(&mut self.debug_bundle_path);
+        });
+        ui.checkbox(&mut self.debug_bundle_redact_ports, tr("redact-port-names"));
+
+        if ui.button(tr("create")).clicked() {
+            self.debug_bundle_status = Some(match self.create_debug_bundle() {
+                Ok(()) => Ok(tr_args(
+                    "created-file",
+                    &[("file", &self.debug_bundle_path)],
+                )),
+                Err(e) => {
+                    log::error!("creating debug bundle failed, Err: {e}");
+                    Err(tr_args("debug-bundle-failed", &[("error", &e)]))
+                }
+            });
+        }
+
+        match &self.debug_bundle_status {
+            Some(Ok(status)) => {
+                ui.label(status);
+            }
+            Some(Err(e)) => {
+                ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+            }
+            None => {}
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_captures(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.set_width(500.0);
+
+        ui.label(tr("captures-help"));
+
+        ui.add_space(12.0);
+
+        egui::Grid::new("captures_settings_grid").show(ui, |ui| {
+            ui.label(tr("label-directory"));
+            if ui.text_edit_singleline(&mut self.capture_dir).lost_focus() {
+                self.refresh_captures();
+            }
+            ui.end_row();
+
+            ui.label(tr("label-session-name"));
+            ui.text_edit_singleline(&mut self.session_name_template)
+                .on_hover_text(tr_args(
+                    "hover-session-name",
+                    &[("placeholders", &capture::PLACEHOLDERS.join(", "))],
+                ));
+            ui.end_row();
+        });
+
+        ui.checkbox(&mut self.auto_session_names, tr("auto-session-names"));
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            if ui.button(tr("refresh")).clicked() {
+                self.refresh_captures();
+            }
+            ui.label(tr_args("n-files", &[("count", &self.captures.len())]));
+        });
+
+        let mut open = None;
+
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                egui::Grid::new("captures_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (i, capture) in self.captures.iter().enumerate() {
+                            ui.label(&capture.name);
+                            ui.label(format!("{:.1} kB", capture.size as f64 / 1000.0));
+                            ui.label(
+                                capture
+                                    .modified
+                                    .map(capture::format_date_time)
+                                    .unwrap_or_default(),
+                            );
+                            if ui
+                                .add_enabled(capture.can_open(), egui::Button::new(tr("open")))
+                                .clicked()
+                            {
+                                open = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if let Some(capture) = open.and_then(|i| self.captures.get(i).cloned()) {
+            self.captures_status = Some(match self.open_capture(ctx, &capture) {
+                Ok(status) => Ok(status),
+                Err(e) => {
+                    log::error!("opening capture failed, Err: {e}");
+                    Err(tr_args("opening-failed", &[("error", &e)]))
+                }
+            });
+        }
+
+        match &self.captures_status {
+            Some(Ok(status)) => {
+                ui.label(status);
+            }
+            Some(Err(e)) => {
+                ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+            }
+            None => {}
+        }
+    }
+
+    fn render_appearance_preset(&mut self, ui: &mut egui::Ui) {
+        ui.set_width(400.0);
+
+        ui.label(tr("appearance-preset-help"));
+
+        ui.add_space(12.0);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            ui.horizontal(|ui| {
+                ui.label(tr("label-file"));
+                ui.text_edit_singleline(&mut self.appearance_preset_path);
+            });
+
+            ui.horizontal(|ui| {
+                if ui.button(tr("export")).clicked() {
+                    let preset = AppearancePreset {
+                        channels: self
+                            .samples_appearance
+                            .iter()
+                            .map(ChannelAppearance::from)
+                            .collect(),
+                    };
+
+                    self.appearance_preset_status =
+                        Some(match preset.save(&self.appearance_preset_path) {
+                            Ok(()) => Ok(tr_args(
+                                "exported-appearance",
+                                &[("count", &preset.channels.len())],
+                            )),
+                            Err(e) => {
+                                log::error!("exporting appearance preset failed, Err: {e}");
+                                Err(tr_args("export-failed", &[("error", &e)]))
+                            }
+                        });
+                }
+
+                if ui.button(tr("import")).clicked() {
+                    self.appearance_preset_status =
+                        Some(match AppearancePreset::load(&self.appearance_preset_path) {
+                            Ok(preset) => {
+                                let status = tr_args(
+                                    "imported-appearance",
+                                    &[("count", &preset.channels.len())],
+                                );
+                                self.appearance_preset = preset;
+                                self.apply_appearance_preset();
+                                Ok(status)
+                            }
+                            Err(e) => {
+                                log::error!("importing appearance preset failed, Err: {e}");
+                                Err(tr_args("import-failed", &[("error", &e)]))
+                            }
+                        });
+                }
+
+                if ui
+                    .add_enabled(
+                        !self.appearance_preset.is_empty(),
+                        egui::Button::new(tr("clear")),
+                    )
+                    .on_hover_text(tr("hover-clear-preset"))
+                    .clicked()
+                {
+                    self.appearance_preset = AppearancePreset::default();
+                    self.appearance_preset_status = None;
+                }
+            });
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        ui.label(tr("web-preset-unavailable"));
+
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(tr_args(
+                "remembered-appearances",
+                &[("count", &self.channel_appearances.len())],
+            ))
+            .on_hover_text(tr("hover-remembered-appearances"));
+
+            if ui.button(tr("forget")).clicked() {
+                self.channel_appearances.clear();
+            }
+        });
+
+        match &self.appearance_preset_status {
+            Some(Ok(status)) => {
+                ui.label(status);
+            }
+            Some(Err(e)) => {
+                ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+            }
+            None => {}
+        }
+    }
+
+    fn render_page(&mut self, ui: &mut egui::Ui, page: PlotPage) {
+        match page {
+            PlotPage::TimeValue => self.render_plot_tv(ui),
+            PlotPage::XY => self.render_plot_xy(ui),
+            PlotPage::Trajectory => self.render_plot_3d(ui),
+            PlotPage::SerialMonitor => self.render_serial_monitor(ui),
+            PlotPage::RawTraffic => self.render_raw_traffic(ui),
+            PlotPage::Spectrogram => self.render_spectrogram(ui),
+            PlotPage::Dashboard => self.render_dashboard(ui),
+            PlotPage::Diagnostics => self.render_diagnostics(ui),
+            PlotPage::Archive => self.render_archive(ui),
+        }
+    }
+
+    fn render_page_controls(&mut self, ui: &mut egui::Ui, page: PlotPage) {
+        match page {
+            PlotPage::TimeValue => self.render_plot_tv_controls(ui),
+            PlotPage::XY => self.render_plot_xy_controls(ui),
+            PlotPage::Trajectory => self.render_plot_3d_controls(ui),
+            PlotPage::Spectrogram => self.render_spectrogram_controls(ui),
+            PlotPage::Dashboard => self.render_dashboard_controls(ui),
+            PlotPage::Diagnostics => self.render_diagnostics_controls(ui),
+            PlotPage::Archive => self.render_archive_controls(ui),
+            PlotPage::SerialMonitor | PlotPage::RawTraffic => {}
+        }
+    }
+
+    fn render_plot_tv(&mut self, ui: &mut egui::Ui) {
+        if !self.event_lanes.is_empty() {
+            egui::TopBottomPanel::bottom("plot_tv_event_lanes_panel")
+                .resizable(false)
+                .exact_height(EVENT_LANE_HEIGHT * self.event_lanes.len() as f32 + 8.0)
+                .frame(egui::Frame::none())
+                .show_inside(ui, |ui| {
+                    self.render_event_lanes(ui);
+                });
+        }
+
+        if self.plot_tv_stacked {
+            self.render_plot_tv_stacked(ui);
+            return;
+        }
+
+        let appearances = self.samples_appearance.clone();
+        let tracking_cursor = self.plot_tv_tracking_cursor;
+        let right_axis = self.plot_tv_right_axis;
+        let log_y = self.plot_tv_log_y;
+        let value_format = self.value_format;
+        let y_free = self.plot_tv_y_bounds.mode == YBoundsMode::Free;
+        // Paused, the whole buffer can be browsed
+        let free_view = self.pause;
+        let time_zero = self.time_zero;
+        let to_x = |time: i64| nanos_to_secs(time - time_zero);
+
+        // The units of the visible channels on each axis
+        let axis_label = |axis: PlotAxis| {
+            let mut units = Vec::<&str>::new();
+            for a in appearances
+                .iter()
+                .filter(|a| a.visible && a.axis == axis && !a.unit.is_empty())
+            {
+                if !units.contains(&a.unit.as_str()) {
+                    units.push(&a.unit);
+                }
+            }
+            units.join(", ")
+        };
+        let mut y_axes = vec![egui_plot::AxisHints::new_y()
+            .label(axis_label(PlotAxis::Left))
+            .formatter(move |mark, _c, _range| {
+                format_axis_value(from_axis(mark.value, log_y), log_y, value_format)
+            })];
+        if let Some(right_axis) = right_axis {
+            y_axes.push(
+                egui_plot::AxisHints::new_y()
+                    .label(axis_label(PlotAxis::Right))
+                    .placement(egui_plot::HPlacement::Right)
+                    .formatter(move |mark, _c, _range| {
+                        let value = from_axis(right_axis.invert(mark.value), log_y);
+                        format_axis_value(value, log_y, value_format)
+                    }),
+            );
+        }
+
+        let plot_id = ui.make_persistent_id("plot_tv");
+        let hidden_items = self.legend_hidden_items(ui.ctx(), plot_id);
+
+        let plot_response = egui_plot::Plot::new("plot_tv")
+            .id(plot_id)
+            .link_axis("plot_tv_axis", true, false)
+            .legend(egui_plot::Legend::default().hidden_items(hidden_items))
+            .label_formatter(move |name, value| {
+                if let Some(appearance) = appearances.iter().find(|a| a.legend_name() == name) {
+                    let y = match (appearance.axis, right_axis) {
+                        (PlotAxis::Right, Some(right_axis)) => right_axis.invert(value.y),
+                        _ => value.y,
+                    };
+                    let y = from_axis(y, log_y);
+
+                    format!(
+                        "{}\nt: {}\nv: {}",
+                        name,
+                        format_secs(value.x, value_format),
+                        appearance.format_value(y, value_format),
+                    )
+                } else if !name.is_empty() {
+                    format!(
+                        "{}\nt: {}\nv: {}",
+                        name,
+                        format_secs(value.x, value_format),
+                        value_format.format(from_axis(value.y, log_y)),
+                    )
+                } else {
+                    format!(
+                        "t: {}\nv: {}",
+                        format_secs(value.x, value_format),
+                        value_format.format(from_axis(value.y, log_y)),
+                    )
+                }
+            })
+            .x_axis_formatter(move |mark, _c, _range| {
+                format!("{} {}", round_to_decimals(mark.value, 5), TimeUnit::S)
+            })
+            .custom_y_axes(y_axes)
+            .allow_zoom(egui::Vec2b {
+                x: free_view,
+                y: y_free || free_view,
+            })
+            .allow_boxed_zoom(false)
+            .allow_drag((y_free || free_view) && !self.cursors.is_grabbing())
+            .show_x(!tracking_cursor)
+            .show_y(!tracking_cursor)
+            .show(ui, |plot_ui| {
+                if plot_ui.response().secondary_clicked() {
+                    self.plot_tv_menu_time = plot_ui
+                        .pointer_coordinate()
+                        .map(|p| secs_to_nanos(p.x) + time_zero);
+                }
+
+                let first = self.samples_vec.first().and_then(|b| b.first())?;
+                let last = self.samples_vec.first().and_then(|b| b.last())?;
+
+                let window_len = secs_to_nanos(self.plot_tv_newer);
+                let trigger_window = if self.trigger.is_active() {
+                    self.trigger.window(window_len)
+                } else {
+                    None
+                };
+                let scroll_end = last.time - secs_to_nanos(self.plot_tv_scroll_back);
+                let (start, end) = trigger_window.unwrap_or((scroll_end - window_len, scroll_end));
+
+                let value_range = |axis: PlotAxis| {
+                    self.samples_vec
+                        .iter()
+                        .zip(&self.samples_appearance)
+                        .filter(|(_, a)| a.visible && a.axis == axis)
+                        .flat_map(|(samples, _)| samples.iter())
+                        .filter(|s| s.time >= start && s.time <= end)
+                        .filter_map(|s| to_axis(s.value, log_y))
+                        .fold(None, |range: Option<(f64, f64)>, y| {
+                            Some(range.map_or((y, y), |(min, max)| (min.min(y), max.max(y))))
+                        })
+                };
+                let left_range = value_range(PlotAxis::Left);
+                let right_range = value_range(PlotAxis::Right);
+
+                let last_plot_bounds = plot_ui.plot_bounds();
+                let (y_min, y_max) = self
+                    .plot_tv_y_bounds
+                    .range(left_range, |v| to_axis(v, log_y))
+                    .unwrap_or((last_plot_bounds.min()[1], last_plot_bounds.max()[1]));
+                let follow_bounds =
+                    egui_plot::PlotBounds::from_min_max([to_x(start), y_min], [to_x(end), y_max]);
+
+                let view_released = free_view && self.plot_tv_view_released;
+                let plot_bounds = if view_released {
+                    last_plot_bounds
+                } else {
+                    // Snaps back to the followed view on resume
+                    plot_ui.set_plot_bounds(follow_bounds);
+                    self.plot_tv_view_released = free_view;
+                    follow_bounds
+                };
+                let (start, end) = if view_released {
+                    (
+                        secs_to_nanos(plot_bounds.min()[0]) + time_zero,
+                        secs_to_nanos(plot_bounds.max()[0]) + time_zero,
+                    )
+                } else {
+                    (start, end)
+                };
+
+                // Fit the channels of the right axis into the plot, kept while the view is browsed
+                if !view_released {
+                    self.plot_tv_right_axis = right_range.map(|(min, max)| {
+                        AxisTransform::fit(min, max, plot_bounds.min()[1], plot_bounds.max()[1])
+                    });
+                }
+                let transform = |i: usize, value: f64| {
+                    let y = to_axis(value, log_y)?;
+
+                    match (
+                        self.samples_appearance.get(i).map(|a| a.axis),
+                        self.plot_tv_right_axis,
+                    ) {
+                        (Some(PlotAxis::Right), Some(right_axis)) => Some(right_axis.apply(y)),
+                        _ => Some(y),
+                    }
+                };
+
+                if let Some(triggered_at) = trigger_window.and(self.trigger.triggered_at()) {
+                    plot_ui.vline(
+                        egui_plot::VLine::new(to_x(triggered_at))
+                            .style(egui_plot::LineStyle::Dashed { length: 4.0 })
+                            .color(egui::Color32::GOLD)
+                            .name(tr("trigger")),
+                    );
+                    if let Some(level) = transform(self.trigger.samples, self.trigger.level) {
+                        plot_ui.hline(
+                            egui_plot::HLine::new(level)
+                                .style(egui_plot::LineStyle::Dashed { length: 4.0 })
+                                .color(egui::Color32::GOLD),
+                        );
+                    }
+                } else {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let first_time = self
+                        .history
+                        .as_ref()
+                        .and_then(|h| h.first_time())
+                        .map_or(first.time, |t| t.min(first.time));
+                    #[cfg(target_arch = "wasm32")]
+                    let first_time = first.time;
+                    let start_vline_val = to_x(first_time.max(start));
+
+                    plot_ui.vline(
+                        egui_plot::VLine::new(start_vline_val)
+                            .style(egui_plot::LineStyle::Dashed { length: 2.0 })
+                            .color(egui::Color32::LIGHT_BLUE),
+                    );
+                }
+
+                // The lines share the render budget of the frame
+                let n_lines = self.samples_appearance.iter().filter(|a| a.visible).count()
+                    + self
+                        .references
+                        .iter()
+                        .filter(|r| r.visible)
+                        .map(|r| r.capture.names.len())
+                        .sum::<usize>();
+                let (downsampling, line_budget) = self.plot_tv_render_budget.line_budget(
+                    self.plot_tv_downsampling,
+                    self.plot_tv_point_budget,
+                    n_lines,
+                );
+                let (mut n_in_view, mut n_rendered) = (0, 0);
+
+                // Drawn first, so that the live data is in front
+                for reference in self.references.iter().filter(|r| r.visible) {
+                    for (k, name) in reference.capture.names.iter().enumerate() {
+                        // On the axis of the live channel with the same name
+                        let live = self.samples_appearance.iter().position(|a| &a.name == name);
+                        let points = reference
+                            .points(k, to_x(start), to_x(end))
+                            .into_iter()
+                            .filter_map(|[x, value]| {
+                                let y = match live {
+                                    Some(i) => transform(i, value)?,
+                                    None => to_axis(value, log_y)?,
+                                };
+                                Some([x, y])
+                            })
+                            .collect::<Vec<[f64; 2]>>();
+                        n_in_view += points.len();
+                        let points = downsampling.apply(points, line_budget);
+                        n_rendered += points.len();
+
+                        plot_ui.line(
+                            egui_plot::Line::new(points)
+                                .name(format!("{name} ({})", reference.label))
+                                .color(reference.line_color(k)),
+                        );
+                    }
+                }
+
+                for (i, samples) in self.samples_vec.iter().enumerate() {
+                    if !self.samples_appearance[i].visible {
+                        // Only the legend entry, so that it can be shown again from there
+                        plot_ui.line(
+                            egui_plot::Line::new(Vec::<[f64; 2]>::new())
+                                .name(self.samples_appearance[i].legend_name())
+                                .color(self.samples_appearance[i].color),
+                        );
+                        continue;
+                    }
+
+                    // Invalid values are kept as NaN, where the line is broken
+                    let y = |value: f64| {
+                        if value.is_finite() {
+                            transform(i, value)
+                        } else {
+                            Some(f64::NAN)
+                        }
+                    };
+
+                    let (in_view, n_samples) = self.samples_in_view(i, start, end, line_budget);
+                    // The samples that the envelope summarized count as decimated
+                    n_in_view += n_samples.saturating_sub(in_view.len());
+                    #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+                    let mut points = in_view
+                        .into_iter()
+                        .filter_map(|(time, value)| Some([to_x(time), y(value)?]))
+                        .collect::<Vec<[f64; 2]>>();
+
+                    // Older samples are read back from the spilled history
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if let (Some(history), true) = (self.history.as_mut(), i < self.n_raw_samples) {
+                        let memory_start = samples.first().map_or(end, |s| s.time);
+
+                        if start < memory_start {
+                            match history.read_range(
+                                i,
+                                start,
+                                end.min(memory_start - 1),
+                                line_budget,
+                            ) {
+                                Ok(spilled) => {
+                                    points.splice(
+                                        0..0,
+                                        spilled.iter().filter_map(|&(time, value)| {
+                                            Some([to_x(time), y(value)?])
+                                        }),
+                                    );
+                                }
+                                Err(e) => log::warn!("reading history failed, Err: {e}"),
+                            }
+                        }
+                    }
+
+                    let n_points = points.len();
+                    let gap_threshold =
+                        Some(self.plot_tv_gap_threshold).filter(|_| self.plot_tv_break_gaps);
+                    let mut segments = gaps::split_lines(points, gap_threshold);
+
+                    let appearance = &self.samples_appearance[i];
+                    let mut averaged = if appearance.smoothing == SmoothingMode::Off {
+                        vec![]
+                    } else {
+                        segments
+                            .iter()
+                            .map(|s| smoothing::moving_average(s, appearance.smoothing_window))
+                            .collect::<Vec<Vec<[f64; 2]>>>()
+                    };
+                    if appearance.smoothing == SmoothingMode::Instead {
+                        segments = std::mem::take(&mut averaged);
+                    }
+
+                    if let (Some(gap_threshold), true) = (gap_threshold, self.plot_tv_shade_gaps) {
+                        for (gap_start, gap_end) in gaps::gap_ranges(&segments, gap_threshold) {
+                            let (y_min, y_max) = (plot_bounds.min()[1], plot_bounds.max()[1]);
+
+                            plot_ui.polygon(
+                                egui_plot::Polygon::new(vec![
+                                    [gap_start, y_min],
+                                    [gap_end, y_min],
+                                    [gap_end, y_max],
+                                    [gap_start, y_max],
+                                ])
+                                .fill_color(self.samples_appearance[i].color.multiply(0.1))
+                                .stroke(egui::Stroke::NONE),
+                            );
+                        }
+                    }
+
+                    if self.samples_appearance[i].hold_last {
+                        let held = samples
+                            .partition_point(|s| s.time <= end)
+                            .checked_sub(1)
+                            .and_then(|k| samples.get(k));
+
+                        if let (Some(y), Some(points)) = (
+                            held.and_then(|s| transform(i, s.value)),
+                            segments.last_mut(),
+                        ) {
+                            if points.is_empty() {
+                                points.push([to_x(start), y]);
+                            }
+                            points.push([to_x(end), y]);
+                        }
+                    }
+
+                    // The raw trace is faded behind the average
+                    let color = if averaged.is_empty() {
+                        self.samples_appearance[i].color
+                    } else {
+                        self.samples_appearance[i].color.multiply(0.35)
+                    };
+                    let average_name = tr_args(
+                        "average-name",
+                        &[("name", &self.samples_appearance[i].name)],
+                    );
+
+                    // Filled under the average instead of the faded raw trace
+                    let fill = self.samples_appearance[i].fill;
+                    let fill_raw = fill && averaged.is_empty();
+                    let fill_baseline = transform(i, 0.0).unwrap_or(plot_bounds.min()[1]);
+
+                    let legend_name = self.samples_appearance[i].legend_name();
+                    let lines =
+                        segments
+                            .into_iter()
+                            .map(|s| (s, &legend_name, color, fill_raw))
+                            .chain(averaged.into_iter().map(|s| {
+                                (s, &average_name, self.samples_appearance[i].color, fill)
+                            }));
+                    for (points, name, color, fill) in lines {
+                        let budget = gaps::segment_budget(line_budget, points.len(), n_points);
+                        n_in_view += points.len();
+                        let points = downsampling.apply(points, budget);
+                        n_rendered += points.len();
+
+                        if fill {
+                            let fill_color =
+                                egui::Color32::from(color).gamma_multiply(FILL_OPACITY);
+                            for polygon in fill_polygons(&points, fill_baseline) {
+                                plot_ui.polygon(
+                                    egui_plot::Polygon::new(polygon)
+                                        .fill_color(fill_color)
+                                        .stroke(egui::Stroke::NONE),
+                                );
+                            }
+                        }
+
+                        // The segments share the name, so that they are one entry in the legend
+                        let plot_line = egui_plot::Line::new(points).name(name).color(color);
+
+                        plot_ui.line(plot_line);
+                    }
+                }
+                self.plot_tv_render_budget
+                    .finish_frame(n_in_view, n_rendered);
+
+                for guide_line in self.guide_lines.iter().filter(|g| g.visible) {
+                    let Some(y) = to_axis(guide_line.value, log_y) else {
+                        continue;
+                    };
+                    let y = match (guide_line.axis, right_axis) {
+                        (PlotAxis::Right, Some(right_axis)) => right_axis.apply(y),
+                        _ => y,
+                    };
+
+                    plot_ui.hline(
+                        egui_plot::HLine::new(y)
+                            .style(egui_plot::LineStyle::Dashed { length: 8.0 })
+                            .color(guide_line.color),
+                    );
+                    plot_ui.text(
+                        egui_plot::Text::new(
+                            egui_plot::PlotPoint::new(plot_bounds.min()[0], y),
+                            format!(
+                                "{} = {}",
+                                guide_line.label,
+                                value_format.format(guide_line.value)
+                            ),
+                        )
+                        .anchor(egui::Align2::LEFT_BOTTOM)
+                        .color(guide_line.color),
+                    );
+                }
+
+                for marker in self.markers.iter() {
+                    if marker.time < start || marker.time > end {
+                        continue;
+                    }
+                    let x = to_x(marker.time);
+
+                    plot_ui.vline(
+                        egui_plot::VLine::new(x)
+                            .style(egui_plot::LineStyle::Dotted { spacing: 4.0 })
+                            .color(egui::Color32::LIGHT_RED),
+                    );
+                    plot_ui.text(
+                        egui_plot::Text::new(
+                            egui_plot::PlotPoint::new(x, plot_bounds.max()[1]),
+                            &marker.label,
+                        )
+                        .anchor(egui::Align2::LEFT_TOP)
+                        .color(egui::Color32::LIGHT_RED),
+                    );
+                }
+
+                self.cursors.interact(plot_ui, start, end, time_zero);
+
+                if self.plot_crosshair {
+                    crosshair(plot_ui, |point| {
+                        let right_label = right_axis.map(|right_axis| {
+                            format_axis_value(
+                                from_axis(right_axis.invert(point.y), log_y),
+                                log_y,
+                                value_format,
+                            )
+                        });
+
+                        (
+                            format_secs(point.x, value_format),
+                            format_axis_value(from_axis(point.y, log_y), log_y, value_format),
+                            right_label,
+                        )
+                    });
+                }
+
+                let cursor_x = plot_ui
+                    .pointer_coordinate()
+                    .filter(|_| tracking_cursor && plot_ui.response().hovered())
+                    .map(|p| p.x)?;
+
+                plot_ui.vline(
+                    egui_plot::VLine::new(cursor_x)
+                        .color(plot_ui.ctx().style().visuals.weak_text_color()),
+                );
+
+                Some(cursor_x)
+            });
+
+        self.apply_legend_visibility(ui.ctx(), plot_id);
+
+        if let Some(cursor_x) = plot_response.inner {
+            self.render_tracking_cursor_tooltip(ui.ctx(), cursor_x);
+        }
+
+        plot_response.response.context_menu(|ui| {
+            if let Some(time) = self.plot_tv_menu_time {
+                if ui.button(tr("set-time-zero")).clicked() {
+                    self.time_zero = time;
+                    ui.close_menu();
+                }
+            }
+            if ui
+                .add_enabled(
+                    self.time_zero != 0,
+                    egui::Button::new(tr("reset-time-zero")),
+                )
+                .clicked()
+            {
+                self.time_zero = 0;
+                ui.close_menu();
+            }
+        });
+    }
+
+    fn render_plot_tv_controls(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical()
+            .id_source("plot_scroll_area")
+            .show(ui, |ui| {
+                ui.with_layout(
+                    egui::Layout::top_down(egui::Align::Min).with_cross_justify(true),
+                    |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("label-values-newer"));
+                            ui.add(
+                                egui::Slider::new(&mut self.plot_tv_newer, 0.1..=500.0)
+                                    .logarithmic(true)
+                                    .suffix(TimeUnit::S.to_string()),
+                            );
+                        });
+
+                        ui.checkbox(&mut self.plot_tv_tracking_cursor, tr("tracking-cursor"))
+                            .on_hover_text(tr("hover-tracking-cursor"));
+                        ui.checkbox(&mut self.plot_crosshair, tr("crosshair"))
+                            .on_hover_text(tr("hover-crosshair"));
+
+                        ui.horizontal(|ui| {
+                            ui.label("t=0:");
+                            if ui
+                                .button(tr("now"))
+                                .on_hover_text(tr("hover-time-zero"))
+                                .clicked()
+                            {
+                                if let Some(latest) = self
+                                    .samples_vec
+                                    .iter()
+                                    .filter_map(|s| s.last())
+                                    .map(|s| s.time)
+                                    .max()
+                                {
+                                    self.time_zero = latest;
+                                }
+                            }
+                            if ui
+                                .add_enabled(self.time_zero != 0, egui::Button::new(tr("reset")))
+                                .clicked()
+                            {
+                                self.time_zero = 0;
+                            }
+                            if self.time_zero != 0 {
+                                ui.weak(tr_args(
+                                    "time-zero-at",
+                                    &[(
+                                        "time",
+                                        &format!(
+                                            "{} {}",
+                                            round_to_decimals(nanos_to_secs(self.time_zero), 3),
+                                            TimeUnit::S
+                                        ),
+                                    )],
+                                ));
+                            }
+                        });
+                        ui.checkbox(&mut self.plot_tv_log_y, tr("log-y-axis"))
+                            .on_hover_text(tr("hover-log-y-axis"));
+
+                        ui.horizontal(|ui| {
+                            ui.label(tr("label-y-bounds"));
+                            y_bounds_controls(ui, "plot_tv_y_bounds", &mut self.plot_tv_y_bounds);
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(tr("label-downsampling"));
+                            egui::ComboBox::from_id_source("plot_tv_downsampling_combobox")
+                                .selected_text(self.plot_tv_downsampling.label())
+                                .show_ui(ui, |ui| {
+                                    for downsampling in Downsampling::ALL {
+                                        ui.selectable_value(
+                                            &mut self.plot_tv_downsampling,
+                                            downsampling,
+                                            downsampling.label(),
+                                        );
+                                    }
+                                });
+                            ui.add_enabled(
+                                self.plot_tv_downsampling != Downsampling::Off,
+                                egui::DragValue::new(&mut self.plot_tv_point_budget)
+                                    .clamp_range(100..=100_000)
+                                    .suffix(format!(" {}", tr("unit-points"))),
+                            )
+                            .on_hover_text(tr("hover-point-budget"));
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(tr("label-render-budget"));
+                            render_budget_controls(ui, &mut self.plot_tv_render_budget);
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(tr("label-values"));
+                            egui::ComboBox::from_id_source("value_notation_combobox")
+                                .selected_text(self.value_format.notation.label())
+                                .show_ui(ui, |ui| {
+                                    for notation in Notation::ALL {
+                                        ui.selectable_value(
+                                            &mut self.value_format.notation,
+                                            notation,
+                                            notation.label(),
+                                        );
+                                    }
+                                });
+                            ui.add(
+                                egui::DragValue::new(&mut self.value_format.decimals)
+                                    .clamp_range(0..=ValueFormat::MAX_DECIMALS)
+                                    .suffix(format!(" {}", tr("unit-decimals"))),
+                            )
+                            .on_hover_text(tr("hover-value-format"));
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.plot_tv_break_gaps, tr("break-gaps"))
+                                .on_hover_text(tr("hover-break-gaps"));
+                            ui.add_enabled(
+                                self.plot_tv_break_gaps,
+                                egui::DragValue::new(&mut self.plot_tv_gap_threshold)
+                                    .clamp_range(0.001..=3600.0)
+                                    .speed(0.01)
+                                    .suffix(format!(" {}", TimeUnit::S)),
+                            );
+                        });
+                        ui.add_enabled(
+                            self.plot_tv_break_gaps,
+                            egui::Checkbox::new(&mut self.plot_tv_shade_gaps, tr("shade-gaps")),
+                        );
+                        ui.checkbox(&mut self.plot_tv_stacked, tr("stacked-subplots"))
+                            .on_hover_text(tr("hover-stacked-subplots"));
+
+                        if ui
+                            .button(tr("snapshot"))
+                            .on_hover_text(tr("hover-snapshot"))
+                            .clicked()
+                        {
+                            self.take_snapshot();
+                        }
+
+                        self.render_history_controls(ui);
+
+                        ui.add_space(5.0);
+
+                        self.render_trigger_controls(ui);
+
+                        ui.add_space(5.0);
+
+                        self.render_cursor_controls(ui);
+
+                        ui.add_space(5.0);
+
+                        self.render_reference_controls(ui);
+
+                        ui.add_space(5.0);
+
+                        self.render_guide_line_controls(ui);
+
+                        ui.add_space(5.0);
+
+                        ui.horizontal(|ui| {
+                            ui.label(tr("label-sort-channels"));
+                            egui::ComboBox::from_id_source("channel_order_combobox")
+                                .selected_text(self.channel_order.label())
+                                .show_ui(ui, |ui| {
+                                    for order in ChannelOrder::ALL {
+                                        ui.selectable_value(
+                                            &mut self.channel_order,
+                                            order,
+                                            order.label(),
+                                        );
+                                    }
+                                });
+                        });
+
+                        for i in self.ordered_channels() {
+                            ui.group(|ui| {
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Min),
+                                    |ui| {
+                                        ui.horizontal(|ui| {
+                                            self.render_alarm_flash(ui, i);
+                                            egui::color_picker::color_edit_button_rgba(
+                                                ui,
+                                                &mut self.samples_appearance[i].color,
+                                                egui::widgets::color_picker::Alpha::Opaque,
+                                            );
+                                            ui.checkbox(
+                                                &mut self.samples_appearance[i].visible,
+                                                "",
+                                            );
+                                            ui.text_edit_singleline(
+                                                &mut self.samples_appearance[i].name,
+                                            );
+                                        });
+                                    },
+                                );
+
+                                ui.horizontal(|ui| {
+                                    ui.label(tr("label-unit"));
+                                    ui.add(
+                                        egui::TextEdit::singleline(
+                                            &mut self.samples_appearance[i].unit,
+                                        )
+                                        .desired_width(40.0),
+                                    );
+
+                                    let axis = &mut self.samples_appearance[i].axis;
+                                    ui.selectable_value(axis, PlotAxis::Left, "L")
+                                        .on_hover_text(tr("hover-left-axis"));
+                                    ui.selectable_value(axis, PlotAxis::Right, "R")
+                                        .on_hover_text(tr("hover-right-axis"));
+                                    ui.checkbox(
+                                        &mut self.samples_appearance[i].hold_last,
+                                        tr("hold"),
+                                    )
+                                    .on_hover_text(tr("hover-hold"));
+                                    ui.checkbox(&mut self.samples_appearance[i].fill, tr("fill"))
+                                        .on_hover_text(tr("hover-fill"));
+
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            if let Some(last) = self.samples_vec[i].last() {
+                                                ui.monospace(
+                                                    self.samples_appearance[i].format_value(
+                                                        last.value,
+                                                        self.value_format,
+                                                    ),
+                                                );
+                                            }
+                                            let invalid_values =
+                                                self.samples_appearance[i].invalid_values;
+                                            if invalid_values > 0 {
+                                                ui.colored_label(
+                                                    ui.visuals().warn_fg_color,
+                                                    format!("⚠ {invalid_values}"),
+                                                )
+                                                .on_hover_text(tr("hover-invalid-values"));
+                                            }
+                                            sparkline(
+                                                ui,
+                                                &self.samples_vec[i],
+                                                self.samples_appearance[i].color.into(),
+                                            );
+                                        },
+                                    );
+                                });
+
+                                self.render_smoothing(ui, i);
+                                if i < self.n_raw_samples {
+                                    self.render_value_filter(ui, i);
+                                }
+                            });
+
+                            ui.end_row();
+                        }
+                    },
+                );
+            });
+    }
+
+    /// The samples of the channel between the times as pairs of time and value, and the number of samples in between.
+    ///
+    /// Zoomed out over many more samples than the budget, the min / max envelope from the summary of the channel
+    /// is returned instead. Not when the lines are broken at gaps or smoothed, which need every sample.
+    fn samples_in_view(
+        &self,
+        i: usize,
+        start: i64,
+        end: i64,
+        budget: usize,
+    ) -> (Vec<(i64, f64)>, usize) {
+        let samples = &self.samples_vec[i];
+        let n_samples = samples
+            .partition_point(|s| s.time <= end)
+            .saturating_sub(samples.partition_point(|s| s.time < start));
+
+        if n_samples > ENVELOPE_MIN_RATIO * budget
+            && !self.plot_tv_break_gaps
+            && self.samples_appearance[i].smoothing == SmoothingMode::Off
+        {
+            if let Some(envelope) = self
+                .samples_summaries
+                .get(i)
+                .and_then(|summary| summary.envelope(samples, start, end, budget / 2))
+            {
+                return (envelope, n_samples);
+            }
+        }
+
+        let in_view = samples
+            .iter()
+            .filter(|s| s.time >= start && s.time <= end)
+            .map(|s| (s.time, s.value))
+            .collect::<Vec<_>>();
+        let n_samples = in_view.len();
+
+        (in_view, n_samples)
+    }
+
+    /// The hidden legend entries of the plot: the channels that are not visible, and the other entries that were hidden in the legend
+    fn legend_hidden_items(&self, ctx: &egui::Context, plot_id: egui::Id) -> Vec<String> {
+        let mut hidden = egui_plot::PlotMemory::load(ctx, plot_id)
+            .map(|mem| {
+                mem.hidden_items
+                    .into_iter()
+                    .filter(|name| {
+                        !self
+                            .samples_appearance
+                            .iter()
+                            .any(|a| &a.legend_name() == name)
+                    })
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default();
+        hidden.extend(
+            self.samples_appearance
+                .iter()
+                .filter(|a| !a.visible)
+                .map(|a| a.legend_name()),
+        );
+
+        hidden
+    }
+
+    /// Applies the clicks on the legend of the plot to the visibility of the channels.
+    ///
+    /// A click toggles the channel, a double click shows only it, or all channels again when it already is the only one.
+    fn apply_legend_visibility(&mut self, ctx: &egui::Context, plot_id: egui::Id) {
+        let Some(mem) = egui_plot::PlotMemory::load(ctx, plot_id) else {
+            return;
+        };
+
+        for appearance in self.samples_appearance.iter_mut() {
+            appearance.visible = !mem.hidden_items.contains(&appearance.legend_name());
+        }
+
+        let double_clicked = ctx.input(|i| {
+            i.pointer
+                .button_double_clicked(egui::PointerButton::Primary)
+        });
+        let Some(solo) = mem
+            .hovered_legend_item
+            .filter(|_| double_clicked)
+            .and_then(|name| {
+                self.samples_appearance
+                    .iter()
+                    .position(|a| a.legend_name() == name)
+            })
+        else {
+            return;
+        };
+
+        let is_solo = self
+            .samples_appearance
+            .iter()
+            .enumerate()
+            .all(|(i, a)| i == solo || !a.visible);
+        for (i, appearance) in self.samples_appearance.iter_mut().enumerate() {
+            appearance.visible = is_solo || i == solo;
+        }
+    }
+
+    /// The text events in a lane per field, on the linked time axis of the Time-Value plot.
+    ///
+    /// Each text is shown as a bar up to the next event of the field.
+    fn render_event_lanes(&self, ui: &mut egui::Ui) {
+        let n_lanes = self.event_lanes.len();
+        let lanes = self.event_lanes.clone();
+        let time_zero = self.time_zero;
+        let value_format = self.value_format;
+        let to_x = |time: i64| nanos_to_secs(time - time_zero);
+        let latest = self
+            .samples_vec
+            .iter()
+            .filter_map(|s| s.last())
+            .map(|s| s.time)
+            .chain(self.text_events.last().map(|e| e.time))
+            .max()
+            .unwrap_or_default();
+        let link = if self.plot_tv_stacked {
+            "plot_tv_stacked_axis"
+        } else {
+            "plot_tv_axis"
+        };
+        let text_color = ui.visuals().strong_text_color();
+
+        egui_plot::Plot::new("plot_tv_event_lanes")
+            .link_axis(link, true, false)
+            .show_axes([false, true])
+            .show_grid([true, false])
+            .y_axis_formatter(move |mark, _c, _range| {
+                // The lanes are at 0, -1, -2, ..
+                let lane = -mark.value;
+                if (lane - lane.round()).abs() > 1e-6 || lane < 0.0 {
+                    return String::new();
+                }
+                lanes
+                    .get(lane.round() as usize)
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .label_formatter(move |name, value| {
+                format!("{name}\nt: {}", format_secs(value.x, value_format))
+            })
+            .allow_zoom(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .allow_boxed_zoom(false)
+            .show(ui, |plot_ui| {
+                let bounds = plot_ui.plot_bounds();
+                let (x_min, x_max) = (bounds.min()[0], bounds.max()[0]);
+                plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max(
+                    [x_min, 0.5 - n_lanes as f64],
+                    [x_max, 0.5],
+                ));
+
+                for (k, lane) in self.event_lanes.iter().enumerate() {
+                    let y = -(k as f64);
+                    let events = self
+                        .text_events
+                        .iter()
+                        .filter(|e| &e.channel == lane)
+                        .collect::<Vec<&TextEvent>>();
+
+                    for (j, event) in events.iter().enumerate() {
+                        let start = to_x(event.time);
+                        let end = to_x(events.get(j + 1).map_or(latest, |e| e.time));
+                        if end < x_min || start > x_max {
+                            continue;
+                        }
+
+                        plot_ui.line(
+                            egui_plot::Line::new(vec![[start, y], [end, y]])
+                                .color(event_color(&event.text).gamma_multiply(0.6))
+                                .width(EVENT_LANE_HEIGHT * 0.6)
+                                .name(format!("{lane}: {}", event.text)),
+                        );
+                        plot_ui.text(
+                            egui_plot::Text::new(
+                                egui_plot::PlotPoint::new(start.max(x_min), y),
+                                format!(" {}", event.text),
+                            )
+                            .anchor(egui::Align2::LEFT_CENTER)
+                            .color(text_color),
+                        );
+                    }
+                }
+            });
+    }
+
+    /// Each visible channel in its own plot, stacked with linked time axes and cursors.
+    ///
+    /// The plots always follow the displayed window.
+    fn render_plot_tv_stacked(&mut self, ui: &mut egui::Ui) {
+        let channels = self
+            .ordered_channels()
+            .into_iter()
+            .filter(|&i| self.samples_appearance[i].visible)
+            .collect::<Vec<usize>>();
+        let Some(last) = self.samples_vec.first().and_then(|b| b.last()) else {
+            return;
+        };
+
+        let log_y = self.plot_tv_log_y;
+        let tracking_cursor = self.plot_tv_tracking_cursor;
+        let time_zero = self.time_zero;
+        let value_format = self.value_format;
+        let to_x = |time: i64| nanos_to_secs(time - time_zero);
+        // The hovered time of any of the subplots
+        let mut cursor_x = None;
+        // The subplots share the render budget of the frame
+        let (downsampling, line_budget) = self.plot_tv_render_budget.line_budget(
+            self.plot_tv_downsampling,
+            self.plot_tv_point_budget,
+            channels.len(),
+        );
+        let (mut n_in_view, mut n_rendered) = (0, 0);
+
+        let window_len = secs_to_nanos(self.plot_tv_newer);
+        let trigger_window = if self.trigger.is_active() {
+            self.trigger.window(window_len)
+        } else {
+            None
+        };
+        let scroll_end = last.time - secs_to_nanos(self.plot_tv_scroll_back);
+        let (start, end) = trigger_window.unwrap_or((scroll_end - window_len, scroll_end));
+        let gap_threshold = Some(self.plot_tv_gap_threshold).filter(|_| self.plot_tv_break_gaps);
+
+        let spacing = ui.spacing().item_spacing.y;
+        let height = ((ui.available_height() - spacing * channels.len() as f32)
+            / channels.len().max(1) as f32)
+            .max(MIN_SUBPLOT_HEIGHT);
+
+        egui::ScrollArea::vertical()
+            .id_source("plot_tv_stacked_scroll_area")
+            .show(ui, |ui| {
+                for (k, &i) in channels.iter().enumerate() {
+                    let appearance = &self.samples_appearance[i];
+                    let (in_view, n_samples) = self.samples_in_view(i, start, end, line_budget);
+                    n_in_view += n_samples.saturating_sub(in_view.len());
+                    let points = in_view
+                        .into_iter()
+                        .filter_map(|(time, value)| {
+                            // Invalid values are kept as NaN, where the line is broken
+                            let y = if value.is_finite() {
+                                to_axis(value, log_y)?
+                            } else {
+                                f64::NAN
+                            };
+                            Some([to_x(time), y])
+                        })
+                        .collect::<Vec<[f64; 2]>>();
+                    let (y_min, y_max) = points
+                        .iter()
+                        .map(|p| p[1])
+                        .filter(|y| y.is_finite())
+                        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), y| {
+                            (min.min(y), max.max(y))
+                        });
+                    let margin = ((y_max - y_min) * 0.05).max(1e-9);
+                    let n_points = points.len();
+
+                    let label_appearance = appearance.clone();
+                    let is_bottom = k + 1 == channels.len();
+
+                    egui_plot::Plot::new(("plot_tv_stacked", i))
+                        .height(height)
+                        .link_axis("plot_tv_stacked_axis", true, false)
+                        .link_cursor("plot_tv_stacked_cursor", true, false)
+                        .show_axes([is_bottom, true])
+                        .show_x(!tracking_cursor)
+                        .show_y(!tracking_cursor)
+                        .y_axis_label(if appearance.unit.is_empty() {
+                            appearance.name.clone()
+                        } else {
+                            format!("{} [{}]", appearance.name, appearance.unit)
+                        })
+                        .y_axis_width(6)
+                        .y_axis_formatter(move |mark, _c, _range| {
+                            format_axis_value(from_axis(mark.value, log_y), log_y, value_format)
+                        })
+                        .x_axis_formatter(move |mark, _c, _range| {
+                            format!("{} {}", round_to_decimals(mark.value, 5), TimeUnit::S)
+                        })
+                        .label_formatter(move |_name, value| {
+                            format!(
+                                "{}\nt: {}\nv: {}",
+                                label_appearance.name,
+                                format_secs(value.x, value_format),
+                                label_appearance
+                                    .format_value(from_axis(value.y, log_y), value_format),
+                            )
+                        })
+                        .allow_zoom(false)
+                        .allow_drag(false)
+                        .allow_scroll(false)
+                        .allow_boxed_zoom(false)
+                        .show(ui, |plot_ui| {
+                            if y_min <= y_max {
+                                plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max(
+                                    [to_x(start), y_min - margin],
+                                    [to_x(end), y_max + margin],
+                                ));
+                            }
+
+                            if let Some(triggered_at) =
+                                trigger_window.and(self.trigger.triggered_at())
+                            {
+                                plot_ui.vline(
+                                    egui_plot::VLine::new(to_x(triggered_at))
+                                        .style(egui_plot::LineStyle::Dashed { length: 4.0 })
+                                        .color(egui::Color32::GOLD),
+                                );
+                            }
+
+                            for marker in self
+                                .markers
+                                .iter()
+                                .filter(|m| m.time >= start && m.time <= end)
+                            {
+                                plot_ui.vline(
+                                    egui_plot::VLine::new(to_x(marker.time))
+                                        .style(egui_plot::LineStyle::Dotted { spacing: 4.0 })
+                                        .color(egui::Color32::LIGHT_RED)
+                                        .name(&marker.label),
+                                );
+                            }
+
+                            for points in gaps::split_lines(points, gap_threshold) {
+                                let budget =
+                                    gaps::segment_budget(line_budget, points.len(), n_points);
+                                n_in_view += points.len();
+                                let points = downsampling.apply(points, budget);
+                                n_rendered += points.len();
+
+                                if appearance.fill {
+                                    let baseline = to_axis(0.0, log_y).unwrap_or(y_min - margin);
+                                    let fill_color = egui::Color32::from(appearance.color)
+                                        .gamma_multiply(FILL_OPACITY);
+                                    for polygon in fill_polygons(&points, baseline) {
+                                        plot_ui.polygon(
+                                            egui_plot::Polygon::new(polygon)
+                                                .fill_color(fill_color)
+                                                .stroke(egui::Stroke::NONE),
+                                        );
+                                    }
+                                }
+
+                                plot_ui.line(
+                                    egui_plot::Line::new(points)
+                                        .name(&appearance.name)
+                                        .color(appearance.color),
+                                );
+                            }
+
+                            if tracking_cursor && plot_ui.response().hovered() {
+                                cursor_x = plot_ui.pointer_coordinate().map(|p| p.x);
+                            }
+                        });
+                }
+            });
+
+        self.plot_tv_render_budget
+            .finish_frame(n_in_view, n_rendered);
+
+        if let Some(cursor_x) = cursor_x {
+            self.render_tracking_cursor_tooltip(ui.ctx(), cursor_x);
+        }
+    }
+
+    /// Flashes a bell while an alarm is raised on the channel
+    fn render_alarm_flash(&self, ui: &mut egui::Ui, i: usize) {
+        let raised = self
+            .alarms
+            .iter()
+            .filter(|a| a.raised_on() == Some(i))
+            .map(|a| a.describe())
+            .collect::<Vec<String>>();
+        if raised.is_empty() {
+            return;
+        }
+
+        let on = (ui.input(|i| i.time) * 2.0) as i64 % 2 == 0;
+        let color = if on {
+            egui::Color32::RED
+        } else {
+            ui.visuals().weak_text_color()
+        };
+        ui.colored_label(color, "🔔")
+            .on_hover_text(tr_args("hover-alarm", &[("channels", &raised.join(", "))]));
+        ui.ctx()
+            .request_repaint_after(instant::Duration::from_millis(250));
+    }
+
+    fn render_smoothing(&mut self, ui: &mut egui::Ui, i: usize) {
+        let appearance = &mut self.samples_appearance[i];
+
+        egui::CollapsingHeader::new(tr("moving-average"))
+            .id_source(("smoothing_collapsing_header", i))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_source(("smoothing_mode_combobox", i))
+                        .selected_text(appearance.smoothing.label())
+                        .show_ui(ui, |ui| {
+                            for mode in SmoothingMode::ALL {
+                                ui.selectable_value(&mut appearance.smoothing, mode, mode.label());
+                            }
+                        });
+
+                    ui.add_enabled(
+                        appearance.smoothing != SmoothingMode::Off,
+                        egui::DragValue::new(&mut appearance.smoothing_window)
+                            .clamp_range(2..=10_000)
+                            .suffix(format!(" {}", tr("unit-samples"))),
+                    );
+                });
+            });
+    }
+
+    /// The received bytes per second, and the samples per second of each channel on hover
+    fn render_throughput(&self, ui: &mut egui::Ui) {
+        let rates = (0..self.n_raw_samples)
+            .map(|i| self.throughput.samples_per_sec(i))
+            .collect::<Vec<f64>>();
+
+        ui.label(format!(
+            "{:.1} {} | {:.0} {}",
+            self.throughput.bytes_per_sec() / 1000.0,
+            tr("unit-kilobytes-per-sec"),
+            rates.iter().sum::<f64>(),
+            tr("unit-samples-per-sec"),
+        ))
+        .on_hover_ui(|ui| {
+            egui::Grid::new("throughput_grid").show(ui, |ui| {
+                for (rate, appearance) in rates.iter().zip(&self.samples_appearance) {
+                    ui.colored_label(appearance.color, &appearance.name);
+                    ui.monospace(format!("{rate:.1} {}", tr("unit-samples-per-sec")));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn render_value_filter(&mut self, ui: &mut egui::Ui, i: usize) {
+        let filter = &mut self.samples_appearance[i].value_filter;
+
+        egui::CollapsingHeader::new(tr("plausible-range"))
+            .id_source(("value_filter_collapsing_header", i))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut filter.enabled, "");
+                    ui.add(egui::DragValue::new(&mut filter.min).speed(0.1));
+                    ui.label(tr("range-to"));
+                    ui.add(egui::DragValue::new(&mut filter.max).speed(0.1));
+                });
+
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_source(("value_filter_action_combobox", i))
+                        .selected_text(filter.action.label())
+                        .show_ui(ui, |ui| {
+                            for action in FilterAction::ALL {
+                                ui.selectable_value(&mut filter.action, action, action.label());
+                            }
+                        });
+
+                    ui.label(tr_args("out-of-range", &[("count", &filter.rejected())]));
+                });
+            });
+    }
+
+    /// Shows a tooltip with the values of all visible channels at the given time
+    fn render_tracking_cursor_tooltip(&self, ctx: &egui::Context, cursor_x: f64) {
+        let cursor_time = secs_to_nanos(cursor_x) + self.time_zero;
+
+        egui::show_tooltip_at_pointer(ctx, egui::Id::new("plot_tv_tracking_cursor"), |ui| {
+            ui.label(format!("t: {}", format_secs(cursor_x, self.value_format)));
+            ui.separator();
+
+            egui::Grid::new("plot_tv_tracking_cursor_grid").show(ui, |ui| {
+                for (samples, appearance) in self.samples_vec.iter().zip(&self.samples_appearance) {
+                    if !appearance.visible {
+                        continue;
+                    }
+
+                    ui.colored_label(appearance.color, &appearance.name);
+                    match interpolate_value_at(samples, cursor_time) {
+                        Some(value) => {
+                            ui.monospace(appearance.format_value(value, self.value_format))
+                        }
+                        None => ui.weak("-"),
+                    };
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn render_reference_controls(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("reference-traces"))
+            .id_source("references_collapsing_header")
+            .show(ui, |ui| {
+                ui.label(tr("reference-traces-help"));
+
+                #[cfg(not(target_arch = "wasm32"))]
+                ui.horizontal(|ui| {
+                    ui.label(tr("label-file"));
+                    ui.text_edit_singleline(&mut self.reference_path);
+
+                    if ui.button(tr("load")).clicked() {
+                        let res = std::fs::read_to_string(&self.reference_path)
+                            .map_err(anyhow::Error::from)
+                            .and_then(|text| import::parse_csv_capture(&text));
+
+                        match res {
+                            Ok(capture) => {
+                                let label = std::path::Path::new(&self.reference_path)
+                                    .file_stem()
+                                    .map_or_else(
+                                        || self.reference_path.clone(),
+                                        |s| s.to_string_lossy().into_owned(),
+                                    );
+                                self.references.push(ReferenceTrace::new(label, capture));
+                                self.reference_error = None;
+                            }
+                            Err(e) => {
+                                log::error!("loading reference failed, Err: {e}");
+                                self.reference_error =
+                                    Some(tr_args("loading-failed", &[("error", &e)]));
+                            }
+                        }
+                    }
+                });
+                ui.weak(tr("reference-traces-archive"));
+
+                if let Some(e) = &self.reference_error {
+                    ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+                }
+
+                let mut remove = None;
+
+                for (i, reference) in self.references.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut reference.visible, "");
+                        if reference.channel_colors.is_empty() {
+                            ui.color_edit_button_srgba(&mut reference.color);
+                        }
+                        ui.label(&reference.label);
+
+                        if ui.button("🗑").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::Slider::new(&mut reference.opacity, 0.05..=1.0)
+                                .text(tr("opacity")),
+                        );
+                        ui.add(
+                            egui::DragValue::new(&mut reference.offset)
+                                .speed(0.01)
+                                .prefix(format!("{} ", tr("label-offset")))
+                                .suffix(format!(" {}", TimeUnit::S)),
+                        )
+                        .on_hover_text(tr("hover-reference-offset"));
+                    });
+                }
+
+                if let Some(i) = remove {
+                    self.references.remove(i);
+                }
+            });
+    }
+
+    fn render_guide_line_controls(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("guide-lines"))
+            .id_source("guide_lines_collapsing_header")
+            .show(ui, |ui| {
+                ui.label(tr("guide-lines-help"));
+
+                let mut remove = None;
+
+                for (i, guide_line) in self.guide_lines.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut guide_line.visible, "");
+                        ui.color_edit_button_srgba(&mut guide_line.color);
+                        ui.add(
+                            egui::TextEdit::singleline(&mut guide_line.label).desired_width(80.0),
+                        );
+                        ui.label("=");
+                        ui.add(egui::DragValue::new(&mut guide_line.value).speed(0.01));
+
+                        if self.plot_tv_right_axis.is_some() || guide_line.axis != PlotAxis::Left {
+                            egui::ComboBox::from_id_source(("guide_line_axis_combobox", i))
+                                .width(60.0)
+                                .selected_text(guide_line.axis.label())
+                                .show_ui(ui, |ui| {
+                                    for axis in [PlotAxis::Left, PlotAxis::Right] {
+                                        ui.selectable_value(
+                                            &mut guide_line.axis,
+                                            axis,
+                                            axis.label(),
+                                        );
+                                    }
+                                });
+                        }
+
+                        if ui.button("🗑").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                }
+
+                if let Some(i) = remove {
+                    self.guide_lines.remove(i);
+                }
+
+                if ui.button(tr("add-guide-line")).clicked() {
+                    self.guide_lines.push(GuideLine::default());
+                }
+            });
+    }
+
+    fn render_cursor_controls(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("cursors"))
+            .id_source("cursors_collapsing_header")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.cursors.enabled, tr("show-cursors"));
+
+                    if self.cursors.enabled && ui.button(tr("place-in-view")).clicked() {
+                        self.cursors.reset();
+                    }
+                });
+
+                let Some([t1, t2]) = self.cursors.positions() else {
+                    return;
+                };
+                let dt = nanos_to_secs(t2 - t1);
+
+                egui::Grid::new("cursors_time_grid").show(ui, |ui| {
+                    ui.label("t1");
+                    ui.monospace(format_secs(
+                        nanos_to_secs(t1 - self.time_zero),
+                        self.value_format,
+                    ));
+                    ui.end_row();
+
+                    ui.label("t2");
+                    ui.monospace(format_secs(
+                        nanos_to_secs(t2 - self.time_zero),
+                        self.value_format,
+                    ));
+                    ui.end_row();
+
+                    ui.label("Δt");
+                    ui.monospace(format_secs(dt, self.value_format));
+                    ui.end_row();
+
+                    if dt != 0.0 {
+                        ui.label("1/Δt");
+                        ui.monospace(self.value_format.format_with_unit(1.0 / dt.abs(), "Hz"));
+                        ui.end_row();
+                    }
+                });
+
+                ui.separator();
+
+                egui::Grid::new("cursors_values_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("");
+                        ui.label("v1");
+                        ui.label("v2");
+                        ui.label("Δv");
+                        ui.end_row();
+
+                        for (samples, appearance) in
+                            self.samples_vec.iter().zip(&self.samples_appearance)
+                        {
+                            if !appearance.visible {
+                                continue;
+                            }
+
+                            let v1 = interpolate_value_at(samples, t1);
+                            let v2 = interpolate_value_at(samples, t2);
+
+                            ui.colored_label(appearance.color, &appearance.name);
+                            for value in [v1, v2, v1.zip(v2).map(|(v1, v2)| v2 - v1)] {
+                                match value {
+                                    Some(value) => ui.monospace(
+                                        appearance.format_value(value, self.value_format),
+                                    ),
+                                    None => ui.weak("-"),
+                                };
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn render_trigger_controls(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("trigger"))
+            .id_source("trigger_collapsing_header")
+            .show(ui, |ui| {
+                egui::Grid::new("trigger_grid").show(ui, |ui| {
+                    ui.label(tr("column-mode"));
+                    let mode_before = self.trigger.mode;
+                    egui::ComboBox::from_id_source("trigger_mode_combobox")
+                        .selected_text(self.trigger.mode.label())
+                        .show_ui(ui, |ui| {
+                            for mode in [TriggerMode::Off, TriggerMode::Normal, TriggerMode::Single]
+                            {
+                                ui.selectable_value(&mut self.trigger.mode, mode, mode.label());
+                            }
+                        });
+                    if self.trigger.mode != mode_before {
+                        self.trigger.rearm();
+                    }
+                    ui.end_row();
+
+                    ui.label(tr("column-channel"));
+                    egui::ComboBox::from_id_source("trigger_samples_combobox")
+                        .selected_text(
+                            self.samples_appearance
+                                .get(self.trigger.samples)
+                                .map(|s| s.name.as_str())
+                                .unwrap_or(""),
+                        )
+                        .show_ui(ui, |ui| {
+                            for i in 0..self.samples_vec.len() {
+                                if ui
+                                    .selectable_value(
+                                        &mut self.trigger.samples,
+                                        i,
+                                        &self.samples_appearance[i].name,
+                                    )
+                                    .changed()
+                                {
+                                    self.trigger.rearm();
+                                }
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("column-level"));
+                    ui.add(egui::DragValue::new(&mut self.trigger.level).speed(0.01));
+                    ui.end_row();
+
+                    ui.label(tr("column-edge"));
+                    egui::ComboBox::from_id_source("trigger_edge_combobox")
+                        .selected_text(self.trigger.edge.label())
+                        .show_ui(ui, |ui| {
+                            for edge in
+                                [TriggerEdge::Rising, TriggerEdge::Falling, TriggerEdge::Both]
+                            {
+                                ui.selectable_value(&mut self.trigger.edge, edge, edge.label());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("column-pre-trigger"));
+                    ui.add(
+                        egui::Slider::new(&mut self.trigger.pre_trigger, 0.0..=1.0)
+                            .custom_formatter(|v, _| format!("{:.0} %", v * 100.0))
+                            .custom_parser(|s| {
+                                s.trim_end_matches('%')
+                                    .trim()
+                                    .parse::<f64>()
+                                    .ok()
+                                    .map(|v| v / 100.0)
+                            }),
+                    );
+                    ui.end_row();
+                });
+
+                ui.horizontal(|ui| {
+                    let status = match (self.trigger.mode, self.trigger.triggered_at()) {
+                        (TriggerMode::Off, _) => tr("trigger-free-running"),
+                        (_, None) => tr("trigger-armed"),
+                        (TriggerMode::Single, Some(_)) => tr("trigger-stopped"),
+                        (_, Some(_)) => tr("trigger-triggered"),
+                    };
+                    ui.label(status);
+
+                    if self.trigger.is_active() && ui.button(tr("rearm")).clicked() {
+                        self.trigger.rearm();
+                    }
+                });
+            });
+    }
+
+    fn render_plot_xy(&mut self, ui: &mut egui::Ui) {
+        let (log_x, log_y) = (self.plot_xy_log_x, self.plot_xy_log_y);
+        let value_format = self.value_format;
+        let y_free = self.plot_xy_y_bounds.mode == YBoundsMode::Free;
+
+        egui_plot::Plot::new("xy plot")
+            .label_formatter(move |_name, value| {
+                format!(
+                    "x: {}\ny: {}",
+                    value_format.format(from_axis(value.x, log_x)),
+                    value_format.format(from_axis(value.y, log_y)),
+                )
+            })
+            .x_axis_formatter(move |mark, _c, _range| {
+                format_axis_value(from_axis(mark.value, log_x), log_x, value_format)
+            })
+            .y_axis_formatter(move |mark, _c, _range| {
+                format_axis_value(from_axis(mark.value, log_y), log_y, value_format)
+            })
+            .allow_zoom(egui::Vec2b { x: true, y: y_free })
+            .allow_drag(egui::Vec2b { x: true, y: y_free })
+            .show(ui, |plot_ui| {
+                // The points of the visible pairs within the displayed time, and their last point
+                let pairs = self
+                    .plot_xy_pairs
+                    .iter()
+                    .filter(|pair| pair.visible)
+                    .filter_map(|pair| {
+                        let samples_x = self.samples_vec.get(pair.x)?;
+                        let samples_y = self.samples_vec.get(pair.y)?;
+                        let (last_x, last_y) = (samples_x.last()?, samples_y.last()?);
+
+                        let points = samples_x
+                            .into_iter()
+                            .zip(samples_y)
+                            .filter(|(x, _)| {
+                                nanos_to_secs(last_x.time - x.time) < self.plot_xy_newer
+                            })
+                            .filter_map(|(x, y)| {
+                                Some([to_axis(x.value, log_x)?, to_axis(y.value, log_y)?])
+                            })
+                            .collect::<Vec<[f64; 2]>>();
+                        let last = to_axis(last_x.value, log_x)
+                            .zip(to_axis(last_y.value, log_y))
+                            .map(|(x, y)| [x, y]);
+
+                        Some((pair, points, last))
+                    })
+                    .collect::<Vec<_>>();
+
+                let y_range = pairs
+                    .iter()
+                    .flat_map(|(_, points, _)| points.iter().map(|p| p[1]))
+                    .fold(None, |range: Option<(f64, f64)>, y| {
+                        Some(range.map_or((y, y), |(min, max)| (min.min(y), max.max(y))))
+                    });
+
+                if let Some((y_min, y_max)) =
+                    self.plot_xy_y_bounds.range(y_range, |v| to_axis(v, log_y))
+                {
+                    let last_plot_bounds = plot_ui.plot_bounds();
+                    let auto_x = plot_ui.auto_bounds().x;
+
+                    plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max(
+                        [last_plot_bounds.min()[0], y_min],
+                        [last_plot_bounds.max()[0], y_max],
+                    ));
+                    // Keep fitting the X axis automatically until the user zooms or drags it
+                    plot_ui.set_auto_bounds(egui::Vec2b {
+                        x: auto_x,
+                        y: false,
+                    });
+                }
+
+                // The pairs share the render budget of the frame
+                let line_budget = self.plot_xy_render_budget.max_points / pairs.len().max(1);
+                let (mut n_in_view, mut n_rendered) = (0, 0);
+
+                for (pair, points, last) in pairs {
+                    n_in_view += points.len();
+                    let points = downsample::decimate_path(points, line_budget);
+                    n_rendered += points.len();
+
+                    if self.plot_xy_fade {
+                        for (segment, alpha) in fading_segments(&points) {
+                            plot_ui.line(
+                                egui_plot::Line::new(segment.to_vec())
+                                    .color(pair.color.gamma_multiply(alpha)),
+                            );
+                        }
+                    } else {
+                        plot_ui.line(egui_plot::Line::new(points).color(pair.color));
+                    }
+
+                    if let Some(last) = last {
+                        let last_point = egui_plot::Points::new(vec![last])
+                            .color(pair.color)
+                            .highlight(true);
+
+                        plot_ui.points(last_point);
+                    }
+                }
+                self.plot_xy_render_budget
+                    .finish_frame(n_in_view, n_rendered);
+
+                if self.plot_crosshair {
+                    crosshair(plot_ui, |point| {
+                        (
+                            format_axis_value(from_axis(point.x, log_x), log_x, value_format),
+                            format_axis_value(from_axis(point.y, log_y), log_y, value_format),
+                            None,
+                        )
+                    });
+                }
+            });
+    }
+
+    fn render_plot_xy_controls(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            egui::Grid::new("plot_xy_grid").show(ui, |ui| {
+                ui.label(tr("label-values-newer"));
+                ui.add(
+                    egui::Slider::new(&mut self.plot_xy_newer, 0.1..=500.0)
+                        .logarithmic(true)
+                        .suffix(TimeUnit::S.to_string()),
+                );
+                ui.end_row();
+
+                let names = self
+                    .samples_appearance
+                    .iter()
+                    .map(|a| a.name.as_str())
+                    .collect::<Vec<&str>>();
+
+                let mut remove = None;
+                let removable = self.plot_xy_pairs.len() > 1;
+
+                for (i, pair) in self.plot_xy_pairs.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut pair.visible, "");
+                        ui.color_edit_button_srgba(&mut pair.color);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("X");
+                        channel_drop_slot(ui, ("samples_x_combobox", i), &mut pair.x, &names);
+                        ui.label("Y");
+                        channel_drop_slot(ui, ("samples_y_combobox", i), &mut pair.y, &names);
+                        if removable && ui.button("🗑").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                    ui.end_row();
+                }
+
+                if let Some(i) = remove {
+                    self.plot_xy_pairs.remove(i);
+                }
+
+                ui.label("");
+                if ui.button(tr("add-pair")).clicked() {
+                    let pair = XyPair::new(self.plot_xy_pairs.len());
+                    self.plot_xy_pairs.push(pair);
+                }
+                ui.end_row();
+
+                ui.label(tr("column-logarithmic"));
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.plot_xy_log_x, "X");
+                    ui.checkbox(&mut self.plot_xy_log_y, "Y");
+                });
+                ui.end_row();
+
+                ui.label(tr("crosshair"));
+                ui.checkbox(&mut self.plot_crosshair, "")
+                    .on_hover_text(tr("hover-crosshair"));
+                ui.end_row();
+
+                ui.label(tr("fade-trail"));
+                ui.checkbox(&mut self.plot_xy_fade, "")
+                    .on_hover_text(tr("hover-fade-trail"));
+                ui.end_row();
+
+                ui.label(tr("column-y-bounds"));
+                ui.horizontal(|ui| {
+                    y_bounds_controls(ui, "plot_xy_y_bounds", &mut self.plot_xy_y_bounds);
+                });
+                ui.end_row();
+
+                ui.label(tr("column-render-budget"));
+                ui.horizontal(|ui| {
+                    render_budget_controls(ui, &mut self.plot_xy_render_budget);
+                });
+                ui.end_row();
+            });
+
+            ui.add_space(12.0);
+            ui.label(tr("label-channels"))
+                .on_hover_text(tr("hover-drag-channel"));
+
+            for (i, appearance) in self.samples_appearance.iter().enumerate() {
+                ui.dnd_drag_source(
+                    egui::Id::new(("xy_channel_drag", i)),
+                    ChannelDrag(i),
+                    |ui| {
+                        ui.label(
+                            egui::RichText::new(format!("☰ {}", appearance.name))
+                                .color(appearance.color),
+                        );
+                    },
+                );
+            }
+        });
+    }
+
+    fn render_plot_3d(&mut self, ui: &mut egui::Ui) {
+        let (response, painter) = ui.allocate_painter(ui.available_size(), egui::Sense::drag());
+        let rect = response.rect;
+        let camera = &mut self.plot_3d_camera;
+
+        camera.rotate(response.drag_delta());
+        if response.hovered() {
+            let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+            camera.zoom_by((scroll * 0.005).exp());
+        }
+        let camera = *camera;
+
+        let visuals = ui.visuals();
+        let grid_stroke = egui::Stroke::new(1.0, visuals.weak_text_color().gamma_multiply(0.5));
+        for (a, b) in trajectory::cube_edges() {
+            painter.line_segment(
+                [camera.project(a, rect), camera.project(b, rect)],
+                grid_stroke,
+            );
+        }
+
+        let [x, y, z] = self.plot_3d_samples;
+        let (Some(samples_x), Some(samples_y), Some(samples_z)) = (
+            self.samples_vec.get(x),
+            self.samples_vec.get(y),
+            self.samples_vec.get(z),
+        ) else {
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                tr("select-three-channels"),
+                egui::FontId::default(),
+                visuals.weak_text_color(),
+            );
+            return;
+        };
+
+        let Some(last_time) = samples_x.last().map(|s| s.time) else {
+            return;
+        };
+        let points = samples_x
+            .into_iter()
+            .zip(samples_y)
+            .zip(samples_z)
+            .filter(|((x, _), _)| nanos_to_secs(last_time - x.time) < self.plot_3d_newer)
+            .map(|((x, y), z)| [x.value, y.value, z.value])
+            .filter(|p| p.iter().all(|v| v.is_finite()))
+            .collect::<Vec<[f64; 3]>>();
+        let Some(bounds) = trajectory::Bounds3::from_points(&points) else {
+            return;
+        };
+
+        // The value ranges at the ends of the axes through the origin corner
+        for (axis, samples) in [x, y, z].into_iter().enumerate() {
+            let name = self
+                .samples_appearance
+                .get(samples)
+                .map(|a| a.name.as_str())
+                .unwrap_or_default();
+            let start = [-1.0; 3];
+            let mut end = [-1.0; 3];
+            end[axis] = 1.0;
+
+            painter.text(
+                camera.project(start, rect),
+                egui::Align2::RIGHT_TOP,
+                format!("{:.3}", bounds.min[axis]),
+                egui::FontId::monospace(10.0),
+                visuals.weak_text_color(),
+            );
+            painter.text(
+                camera.project(end, rect),
+                egui::Align2::LEFT_BOTTOM,
+                format!("{name}: {:.3}", bounds.max[axis]),
+                egui::FontId::monospace(10.0),
+                visuals.text_color(),
+            );
+        }
+
+        let projected = points
+            .iter()
+            .map(|&p| camera.project(bounds.normalize(p), rect))
+            .collect::<Vec<egui::Pos2>>();
+        let color = self
+            .samples_appearance
+            .get(z)
+            .map(|a| egui::Color32::from(a.color))
+            .unwrap_or(egui::Color32::DARK_RED);
+
+        if self.plot_xy_fade {
+            for (segment, alpha) in fading_segments(&projected) {
+                painter.add(egui::Shape::line(
+                    segment.to_vec(),
+                    egui::Stroke::new(1.5, color.gamma_multiply(alpha)),
+                ));
+            }
+        } else {
+            painter.add(egui::Shape::line(
+                projected.clone(),
+                egui::Stroke::new(1.5, color),
+            ));
+        }
+
+        if let Some(&last) = projected.last() {
+            painter.circle_filled(last, 4.0, color);
+        }
+    }
+
+    fn render_plot_3d_controls(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            egui::Grid::new("plot_3d_grid").show(ui, |ui| {
+                ui.label(tr("label-values-newer"));
+                ui.add(
+                    egui::Slider::new(&mut self.plot_3d_newer, 0.1..=500.0)
+                        .logarithmic(true)
+                        .suffix(TimeUnit::S.to_string()),
+                );
+                ui.end_row();
+
+                let names = self
+                    .samples_appearance
+                    .iter()
+                    .map(|a| a.name.as_str())
+                    .collect::<Vec<&str>>();
+
+                for (axis, selected) in ["X", "Y", "Z"].iter().zip(self.plot_3d_samples.iter_mut())
+                {
+                    ui.label(tr_args("axis-name", &[("axis", axis)]));
+                    channel_drop_slot(ui, ("plot_3d_combobox", axis), selected, &names);
+                    ui.end_row();
+                }
+
+                ui.label(tr("fade-trail"));
+                ui.checkbox(&mut self.plot_xy_fade, "")
+                    .on_hover_text(tr("hover-fade-trail"));
+                ui.end_row();
+            });
+
+            if ui.button(tr("reset-view")).clicked() {
+                self.plot_3d_camera = OrbitCamera::default();
+            }
+
+            ui.add_space(12.0);
+            ui.label(tr("label-channels"))
+                .on_hover_text(tr("hover-drag-channel"));
+
+            for (i, appearance) in self.samples_appearance.iter().enumerate() {
+                ui.dnd_drag_source(
+                    egui::Id::new(("plot_3d_channel_drag", i)),
+                    ChannelDrag(i),
+                    |ui| {
+                        ui.label(
+                            egui::RichText::new(format!("☰ {}", appearance.name))
+                                .color(appearance.color),
+                        );
+                    },
+                );
+            }
+        });
+    }
+
+    fn render_dashboard(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical()
+            .id_source("dashboard_scroll_area")
+            .show(ui, |ui| {
+                if self.dashboard_widgets.is_empty() {
+                    ui.weak(tr("dashboard-empty"));
+                }
+
+                ui.horizontal_wrapped(|ui| {
+                    for widget in self.dashboard_widgets.iter() {
+                        let channel = self
+                            .samples_appearance
+                            .iter()
+                            .position(|a| a.name == widget.channel);
+                        let value = channel
+                            .and_then(|i| self.samples_vec.get(i))
+                            .and_then(|samples| samples.last())
+                            .map(|s| s.value);
+                        let (color, unit) = channel
+                            .and_then(|i| self.samples_appearance.get(i))
+                            .map(|a| (egui::Color32::from(a.color), a.unit.as_str()))
+                            .unwrap_or((ui.visuals().text_color(), ""));
+
+                        let (rect, _) =
+                            ui.allocate_exact_size(egui::vec2(220.0, 160.0), egui::Sense::hover());
+                        paint_dashboard_widget(ui, rect, widget, value, unit, color);
+                    }
+                });
+            });
+    }
+
+    fn render_dashboard_controls(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical()
+            .id_source("dashboard_controls_scroll_area")
+            .show(ui, |ui| {
+                let mut remove = None;
+
+                for (i, widget) in self.dashboard_widgets.iter_mut().enumerate() {
+                    egui::Grid::new(("dashboard_widget_grid", i)).show(ui, |ui| {
+                        ui.label(tr("column-channel"));
+                        egui::ComboBox::from_id_source(("dashboard_channel", i))
+                            .selected_text(widget.channel.as_str())
+                            .show_ui(ui, |ui| {
+                                for appearance in self.samples_appearance.iter() {
+                                    ui.selectable_value(
+                                        &mut widget.channel,
+                                        appearance.name.clone(),
+                                        &appearance.name,
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr("column-kind"));
+                        egui::ComboBox::from_id_source(("dashboard_kind", i))
+                            .selected_text(widget.kind.label())
+                            .show_ui(ui, |ui| {
+                                for kind in WidgetKind::ALL {
+                                    ui.selectable_value(&mut widget.kind, kind, kind.label());
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr("column-range"));
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut widget.min).speed(0.1));
+                            ui.label("..");
+                            ui.add(egui::DragValue::new(&mut widget.max).speed(0.1));
+                        });
+                        ui.end_row();
+
+                        ui.label(tr("column-low-limit"));
+                        limit_edit(ui, &mut widget.low_limit, widget.min);
+                        ui.end_row();
+
+                        ui.label(tr("column-high-limit"));
+                        limit_edit(ui, &mut widget.high_limit, widget.max);
+                        ui.end_row();
+                    });
+
+                    if ui.button(tr("remove")).clicked() {
+                        remove = Some(i);
+                    }
+                    ui.separator();
+                }
+
+                if let Some(i) = remove {
+                    self.dashboard_widgets.remove(i);
+                }
+
+                if ui.button(tr("add-widget")).clicked() {
+                    self.dashboard_widgets.push(DashboardWidget {
+                        channel: self
+                            .samples_appearance
+                            .first()
+                            .map(|a| a.name.clone())
+                            .unwrap_or_default(),
+                        ..Default::default()
+                    });
+                }
+            });
+    }
+
+    fn render_spectrogram(&mut self, ui: &mut egui::Ui) {
+        let Some(samples) = self.samples_vec.get(self.spectrogram_samples) else {
+            ui.label(tr("no-samples"));
+            return;
+        };
+
+        let fft_size = self.spectrogram_fft_size;
+        if samples.len() < fft_size {
+            ui.label(tr_args(
+                "spectrogram-not-enough-samples",
+                &[("needed", &fft_size), ("received", &samples.len())],
+            ));
+            return;
+        }
+
+        // Limit the number of frames to keep the computation cheap
+        const MAX_FRAMES: usize = 512;
+        let hop = fft_size / 2;
+        let n_frames = ((samples.len() - fft_size) / hop + 1).min(MAX_FRAMES);
+        let used = (n_frames - 1) * hop + fft_size;
+
+        let (times, values): (Vec<f64>, Vec<f64>) = samples
+            .iter()
+            .skip(samples.len() - used)
+            .map(|s| (nanos_to_secs(s.time - self.time_zero), s.value))
+            .unzip();
+
+        let Some(sample_rate) = fft::sample_rate(&times) else {
+            ui.label(tr("unknown-sample-rate"));
+            return;
+        };
+
+        let spectra = (0..n_frames)
+            .map(|f| {
+                fft::amplitude_spectrum(&values[f * hop..f * hop + fft_size])
+                    .into_iter()
+                    .map(|a| 20.0 * (a + 1e-12).log10())
+                    .collect::<Vec<f64>>()
+            })
+            .collect::<Vec<Vec<f64>>>();
+
+        let max_db = spectra
+            .iter()
+            .flatten()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let min_db = max_db - self.spectrogram_dynamic_range;
+        let n_bins = fft_size / 2;
+
+        let mut image = egui::ColorImage::new([n_frames, n_bins], egui::Color32::BLACK);
+        for (x, spectrum) in spectra.iter().enumerate() {
+            for (bin, db) in spectrum.iter().enumerate() {
+                // Highest frequency on top
+                let y = n_bins - 1 - bin;
+                image[(x, y)] = heat_color(((db - min_db) / (max_db - min_db)) as f32);
+            }
+        }
+
+        let texture = match self.spectrogram_texture.as_mut() {
+            Some(texture) => {
+                texture.set(image, egui::TextureOptions::NEAREST);
+                texture
+            }
+            None => self.spectrogram_texture.insert(ui.ctx().load_texture(
+                "spectrogram",
+                image,
+                egui::TextureOptions::NEAREST,
+            )),
+        };
+
+        let t_start = times[0];
+        let t_end = times[times.len() - 1];
+        let f_max = sample_rate / 2.0;
+
+        egui_plot::Plot::new("plot_spectrogram")
+            .label_formatter(move |_name, value| {
+                format!(
+                    "t: {} {}\nf: {} Hz",
+                    round_to_decimals(value.x, 5),
+                    TimeUnit::S,
+                    round_to_decimals(value.y, 2),
+                )
+            })
+            .x_axis_formatter(move |mark, _c, _range| {
+                format!("{} {}", round_to_decimals(mark.value, 5), TimeUnit::S)
+            })
+            .y_axis_formatter(move |mark, _c, _range| {
+                format!("{} Hz", round_to_decimals(mark.value, 2))
+            })
+            .allow_boxed_zoom(false)
+            .show(ui, |plot_ui| {
+                plot_ui.image(egui_plot::PlotImage::new(
+                    texture.id(),
+                    egui_plot::PlotPoint::new((t_start + t_end) / 2.0, f_max / 2.0),
+                    egui::vec2((t_end - t_start) as f32, f_max as f32),
+                ));
+            });
+    }
+
+    fn render_spectrogram_controls(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("spectrogram_grid").show(ui, |ui| {
+            ui.label(tr("column-channel"));
+            egui::ComboBox::from_id_source("spectrogram_samples_combobox")
+                .selected_text(
+                    self.samples_appearance
+                        .get(self.spectrogram_samples)
+                        .map(|s| s.name.as_str())
+                        .unwrap_or(""),
+                )
+                .show_ui(ui, |ui| {
+                    for i in 0..self.samples_vec.len() {
+                        ui.selectable_value(
+                            &mut self.spectrogram_samples,
+                            i,
+                            &self.samples_appearance[i].name,
+                        );
+                    }
+                });
+            ui.end_row();
+
+            ui.label(tr("column-fft-size"));
+            egui::ComboBox::from_id_source("spectrogram_fft_size_combobox")
+                .selected_text(self.spectrogram_fft_size.to_string())
+                .show_ui(ui, |ui| {
+                    for fft_size in fft::FFT_SIZES {
+                        ui.selectable_value(
+                            &mut self.spectrogram_fft_size,
+                            fft_size,
+                            fft_size.to_string(),
+                        );
+                    }
+                });
+            ui.end_row();
+
+            ui.label(tr("column-dynamic-range"));
+            ui.add(
+                egui::Slider::new(&mut self.spectrogram_dynamic_range, 10.0..=160.0).suffix(" dB"),
+            );
+            ui.end_row();
+        });
+    }
+
+    fn render_diagnostics(&mut self, ui: &mut egui::Ui) {
+        egui_plot::Plot::new("plot_diagnostics")
+            .label_formatter(|name, value| {
+                format!(
+                    "{}\nt: {} {}\n{}",
+                    name,
+                    round_to_decimals(value.x, 3),
+                    TimeUnit::S,
+                    round_to_decimals(value.y, 1),
+                )
+            })
+            .x_axis_formatter(|mark, _c, _range| {
+                format!("{} {}", round_to_decimals(mark.value, 3), TimeUnit::S)
+            })
+            .legend(egui_plot::Legend::default())
+            .show(ui, |plot_ui| {
+                for (i, metric) in ingeststats::METRICS.iter().enumerate() {
+                    plot_ui.line(
+                        egui_plot::Line::new(self.ingest_stats.rates(i))
+                            .name(metric)
+                            .color(unique_color_in_list(i, ingeststats::METRICS.len())),
+                    );
+                }
+            });
+    }
+
+    fn render_diagnostics_controls(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("diagnostics_grid").show(ui, |ui| {
+            for (i, metric) in ingeststats::METRICS.iter().enumerate() {
+                ui.label(*metric);
+                ui.label(
+                    self.ingest_stats
+                        .latest(i)
+                        .map(|rate| round_to_decimals(rate, 1).to_string())
+                        .unwrap_or_else(|| String::from("-")),
+                );
+                ui.end_row();
+            }
+
+            ui.label(tr("column-total-bytes"));
+            ui.label(self.bytes_received.to_string());
+            ui.end_row();
+
+            ui.label(tr("column-total-samples"));
+            ui.label(self.samples_received.to_string());
+            ui.end_row();
+        });
+    }
+
+    fn render_archive(&mut self, ui: &mut egui::Ui) {
+        let Some(archived) = self.archived.get(self.archive_selected) else {
+            return;
+        };
+        let (downsampling, budget) = (self.plot_tv_downsampling, self.plot_tv_point_budget);
+
+        egui_plot::Plot::new("plot_archive")
+            .x_axis_formatter(|mark, _c, _range| {
+                format!("{} {}", round_to_decimals(mark.value, 5), TimeUnit::S)
+            })
+            .legend(egui_plot::Legend::default())
+            .show(ui, |plot_ui| {
+                let n_channels = archived.capture.names.len();
+
+                for (i, (name, samples)) in archived
+                    .capture
+                    .names
+                    .iter()
+                    .zip(archived.capture.samples_vec.iter())
+                    .enumerate()
+                {
+                    let points = samples
+                        .iter()
+                        .map(|s| [nanos_to_secs(s.time - archived.time_zero), s.value])
+                        .collect::<Vec<[f64; 2]>>();
+
+                    plot_ui.line(
+                        egui_plot::Line::new(downsampling.apply(points, budget))
+                            .name(name)
+                            .color(unique_color_in_list(i, n_channels)),
+                    );
+                }
+            });
+    }
+
+    fn render_archive_controls(&mut self, ui: &mut egui::Ui) {
+        ui.vertical(|ui| {
+            if self.archived.is_empty() {
+                ui.label(tr("archive-empty"));
+                return;
+            }
+
+            let mut remove = None;
+
+            egui::Grid::new("archive_grid").show(ui, |ui| {
+                for (i, archived) in self.archived.iter().enumerate() {
+                    ui.selectable_value(&mut self.archive_selected, i, &archived.label);
+                    ui.label(tr_args(
+                        "archived-capture",
+                        &[
+                            ("count", &archived.capture.n_samples()),
+                            (
+                                "duration",
+                                &format!(
+                                    "{} {}",
+                                    round_to_decimals(archived.duration_secs(), 1),
+                                    TimeUnit::S
+                                ),
+                            ),
+                        ],
+                    ));
+                    if ui.button("🗑").clicked() {
+                        remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+
+            if let Some(i) = remove {
+                self.archived.remove(i);
+                self.archive_selected = self
+                    .archive_selected
+                    .min(self.archived.len().saturating_sub(1));
+                self.archive_status = None;
+            }
+
+            if ui
+                .button(tr("use-as-reference"))
+                .on_hover_text(tr("hover-use-as-reference"))
+                .clicked()
+            {
+                if let Some(archived) = self.archived.get(self.archive_selected) {
+                    self.references
+                        .push(ReferenceTrace::from_archived(archived));
+                }
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                ui.add_space(12.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("label-file"));
+                    ui.text_edit_singleline(&mut self.export_path);
+                });
+
+                if ui
+                    .button(tr("export"))
+                    .on_hover_text(tr("hover-export-archived"))
+                    .clicked()
+                {
+                    match self.session_path("csv") {
+                        Ok(Some(path)) => self.export_path = path,
+                        Ok(None) => {}
+                        Err(e) => {
+                            log::error!("naming the export file failed, Err: {e}")
+                        }
+                    }
+                    self.archive_status =
+                        self.archived.get(self.archive_selected).map(|archived| {
+                            let channels = archived
+                                .capture
+                                .names
+                                .iter()
+                                .zip(archived.capture.units.iter())
+                                .zip(archived.capture.samples_vec.iter())
+                                .map(|((name, unit), samples)| export::ExportChannel {
+                                    name,
+                                    unit,
+                                    samples: samples.iter(),
+                                })
+                                .collect();
+
+                            match std::fs::write(
+                                &self.export_path,
+                                export::write_csv_capture(channels, archived.time_zero),
+                            ) {
+                                Ok(()) => Ok(tr_args(
+                                    "exported-capture",
+                                    &[("capture", &archived.label), ("file", &self.export_path)],
+                                )),
+                                Err(e) => {
+                                    log::error!("exporting archived capture failed, Err: {e}");
+                                    Err(tr_args("export-failed", &[("error", &e)]))
+                                }
+                            }
+                        });
+                }
+
+                match &self.archive_status {
+                    Some(Ok(status)) => {
+                        ui.label(status);
+                    }
+                    Some(Err(e)) => {
+                        ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+                    }
+                    None => {}
+                }
+            }
+        });
+    }
+
+    fn render_serial_monitor(&mut self, ui: &mut egui::Ui) {
+        egui::TopBottomPanel::top("serial_monitor_view_panel")
+            .show_separator_line(false)
+            .show_inside(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("label-timestamp"));
+                    ui.add_enabled_ui(!self.serial_monitor_hex, |ui| {
+                        egui::ComboBox::from_id_source("serial_monitor_timestamp_combobox")
+                            .selected_text(self.serial_monitor_timestamp.label())
+                            .show_ui(ui, |ui| {
+                                for timestamp in MonitorTimestamp::ALL {
+                                    ui.selectable_value(
+                                        &mut self.serial_monitor_timestamp,
+                                        timestamp,
+                                        timestamp.label(),
+                                    );
+                                }
+                            });
+                    });
+                    ui.checkbox(&mut self.serial_monitor_hex, "Hex")
+                        .on_hover_text(tr("hover-hex"));
+                    ui.add_enabled(
+                        !self.serial_monitor_hex,
+                        egui::Checkbox::new(&mut self.serial_monitor_ansi, tr("ansi-colors")),
+                    )
+                    .on_hover_text(tr("hover-ansi-colors"));
+                    ui.add_enabled(
+                        !self.serial_monitor_hex,
+                        egui::Checkbox::new(&mut self.serial_monitor_table, tr("table")),
+                    )
+                    .on_hover_text(tr("hover-table"));
+                });
+            });
+
+        egui::TopBottomPanel::bottom("serial_monitor_send_panel")
+            .show_separator_line(false)
+            .show_inside(ui, |ui| {
+                ui.add_enabled_ui(self.is_connected, |ui| {
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source("send_line_ending_combobox")
+                            .selected_text(self.send_line_ending.label())
+                            .width(60.0)
+                            .show_ui(ui, |ui| {
+                                for line_ending in LineEnding::ALL {
+                                    ui.selectable_value(
+                                        &mut self.send_line_ending,
+                                        line_ending,
+                                        line_ending.label(),
+                                    );
+                                }
+                            });
+                        ui.checkbox(&mut self.send_local_echo, tr("echo"));
+
+                        let send_clicked = ui.button(tr("send")).clicked();
+                        let text_response = ui.add(
+                            egui::TextEdit::singleline(&mut self.send_text)
+                                .font(egui::TextStyle::Monospace)
+                                .desired_width(f32::INFINITY)
+                                .hint_text(tr("hint-send")),
+                        );
+                        let enter_pressed = text_response.lost_focus()
+                            && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                        if send_clicked || enter_pressed {
+                            self.send_text(ui.ctx());
+                            text_response.request_focus();
+                        }
+                    });
+                });
+            });
+
+        if self.serial_monitor_hex {
+            self.render_hex_dump(ui, "serial_monitor_hex_scroll_area", false);
+            return;
+        }
+        if self.serial_monitor_table {
+            self.render_monitor_table(ui);
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .id_source("serial_monitor_scroll_area")
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                let timestamp = |line: &MonitorLine| match self.serial_monitor_timestamp {
+                    MonitorTimestamp::None => String::new(),
+                    MonitorTimestamp::Relative => format!("[{:>10.3}] ", nanos_to_secs(line.time)),
+                    MonitorTimestamp::WallClock => {
+                        format!("[{}] ", format_wall_clock(line.wall_time))
+                    }
+                };
+
+                if self.serial_monitor_ansi {
+                    let default_format = egui::TextFormat::simple(
+                        egui::TextStyle::Monospace.resolve(ui.style()),
+                        ui.visuals().text_color(),
+                    );
+                    let mut job = egui::text::LayoutJob::default();
+                    job.wrap.max_width = ui.available_width();
+                    let mut style = AnsiStyle::default();
+
+                    for line in self.serial_monitor_lines.iter() {
+                        job.append(&timestamp(line), 0.0, default_format.clone());
+                        ansi::append_ansi(&mut job, &line.text, &default_format, &mut style);
+                    }
+
+                    ui.label(job);
+                } else {
+                    let timestamp_mode = self.serial_monitor_timestamp;
+
+                    // Concatenating all lines every frame is expensive, so it is cached until they change
+                    if !matches!(&self.serial_monitor_text, Some((t, _)) if *t == timestamp_mode) {
+                        let monitor_text = self.serial_monitor_lines.iter().fold(
+                            String::new(),
+                            |mut acc, line| {
+                                acc.push_str(&timestamp(line));
+                                acc.push_str(&line.text);
+                                acc
+                            },
+                        );
+                        self.serial_monitor_text = Some((timestamp_mode, monitor_text));
+                    }
+
+                    if let Some((_, monitor_text)) = &self.serial_monitor_text {
+                        ui.text_edit_multiline(&mut monitor_text.as_str());
+                    }
+                }
+            });
+    }
+
+    /// Shows the lines as table, with a column for each channel named by the channel names
+    fn render_monitor_table(&self, ui: &mut egui::Ui) {
+        let rows = self
+            .serial_monitor_lines
+            .iter()
+            .filter(|line| !line.text.trim().is_empty())
+            .map(|line| (line, monitor_table_row(&line.text, self.value_separator)))
+            .collect::<Vec<_>>();
+
+        let show_timestamp = self.serial_monitor_timestamp != MonitorTimestamp::None;
+        let show_device_time = rows.iter().any(|(_, (time, _))| time.is_some());
+        let n_columns = rows
+            .iter()
+            .map(|(_, (_, values))| values.len())
+            .max()
+            .unwrap_or(0);
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+
+        let mut table = egui_extras::TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .stick_to_bottom(true)
+            .auto_shrink(false);
+        if show_timestamp {
+            table = table.column(egui_extras::Column::auto());
+        }
+        if show_device_time {
+            table = table.column(egui_extras::Column::auto());
+        }
+        table = table.columns(egui_extras::Column::auto().at_least(60.0), n_columns);
+
+        table
+            .header(row_height + 4.0, |mut header| {
+                if show_timestamp {
+                    header.col(|ui| {
+                        ui.strong(tr("column-received"));
+                    });
+                }
+                if show_device_time {
+                    header.col(|ui| {
+                        ui.strong(tr("column-time"));
+                    });
+                }
+                for i in 0..n_columns {
+                    header.col(|ui| {
+                        match self.samples_appearance[..self.n_raw_samples].get(i) {
+                            Some(appearance) => ui.strong(&appearance.name),
+                            None => ui.strong(tr_args("column-index", &[("index", &i)])),
+                        };
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(row_height, rows.len(), |mut row| {
+                    let (line, (time, values)) = &rows[row.index()];
+
+                    if show_timestamp {
+                        row.col(|ui| {
+                            ui.monospace(match self.serial_monitor_timestamp {
+                                MonitorTimestamp::WallClock => format_wall_clock(line.wall_time),
+                                _ => format!("{:.3}", nanos_to_secs(line.time)),
+                            });
+                        });
+                    }
+                    if show_device_time {
+                        row.col(|ui| {
+                            ui.monospace(time.unwrap_or_default());
+                        });
+                    }
+                    for i in 0..n_columns {
+                        row.col(|ui| {
+                            ui.monospace(values.get(i).copied().unwrap_or_default());
+                        });
+                    }
+                });
+            });
+    }
+
+    fn render_raw_traffic(&mut self, ui: &mut egui::Ui) {
+        let bytes = &self.serial_monitor_bytes;
+        let count = |byte: u8| bytes.iter().filter(|&&b| b == byte).count();
+        let crlf = bytes
+            .iter()
+            .zip(bytes.iter().skip(1))
+            .filter(|&(&a, &b)| a == b'\r' && b == b'\n')
+            .count();
+        let other_control = bytes
+            .iter()
+            .filter(|&&b| b.is_ascii_control() && b != b'\r' && b != b'\n')
+            .count();
+        let non_ascii = bytes.iter().filter(|b| !b.is_ascii()).count();
+
+        egui::TopBottomPanel::top("raw_traffic_panel")
+            .show_separator_line(false)
+            .show_inside(ui, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr_args(
+                        "received-bytes",
+                        &[("count", &self.bytes_received)],
+                    ));
+                    ui.label(tr_args("shown-bytes", &[("count", &bytes.len())]))
+                        .on_hover_text(tr("hover-shown-bytes"));
+                    ui.separator();
+                    ui.label(format!(
+                        "CR LF: {crlf}, LF: {}, CR: {}",
+                        count(b'\n') - crlf,
+                        count(b'\r') - crlf
+                    ))
+                    .on_hover_text(tr("hover-line-terminators"));
+                    ui.label(tr_args("other-control-bytes", &[("count", &other_control)]));
+                    ui.label(tr_args("non-ascii-bytes", &[("count", &non_ascii)]));
+                    ui.separator();
+                    ui.checkbox(
+                        &mut self.raw_traffic_highlight,
+                        tr("highlight-line-endings"),
+                    );
+                });
+            });
+
+        self.render_hex_dump(ui, "raw_traffic_scroll_area", self.raw_traffic_highlight);
+    }
+
+    /// Shows the raw received bytes as rows of offset, hex values and ASCII characters
+    fn render_hex_dump(&self, ui: &mut egui::Ui, id_source: &str, highlight_terminators: bool) {
+        const BYTES_PER_ROW: usize = 16;
+
+        let bytes = &self.serial_monitor_bytes;
+        // The offset of the first byte in the buffer
+        let first_offset = self.bytes_received - bytes.len() as u64;
+        let n_rows = (bytes.len() + BYTES_PER_ROW - 1) / BYTES_PER_ROW;
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+
+        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+        let text_color = ui.visuals().text_color();
+        let terminator_color = ui.visuals().warn_fg_color;
+        let color_of = |b: u8| {
+            if highlight_terminators && (b == b'\r' || b == b'\n') {
+                terminator_color
+            } else {
+                text_color
+            }
+        };
+
+        egui::ScrollArea::vertical()
+            .id_source(id_source)
+            .stick_to_bottom(true)
+            .auto_shrink(false)
+            .show_rows(ui, row_height, n_rows, |ui, row_range| {
+                for row in row_range {
+                    let row_bytes = (row * BYTES_PER_ROW..((row + 1) * BYTES_PER_ROW))
+                        .filter_map(|i| bytes.get(i).copied())
+                        .collect::<Vec<u8>>();
+
+                    let mut job = egui::text::LayoutJob::default();
+                    let mut append = |text: &str, color: egui::Color32| {
+                        job.append(text, 0.0, egui::TextFormat::simple(font_id.clone(), color));
+                    };
+
+                    append(
+                        &format!("{:08x} ", first_offset + (row * BYTES_PER_ROW) as u64),
+                        text_color,
+                    );
+                    for k in 0..BYTES_PER_ROW {
+                        match row_bytes.get(k) {
+                            Some(&b) => append(&format!(" {b:02x}"), color_of(b)),
+                            None => append("   ", text_color),
+                        }
+                    }
+                    append("  |", text_color);
+                    for &b in row_bytes.iter() {
+                        let c = if b.is_ascii_graphic() || b == b' ' {
+                            b as char
+                        } else {
+                            '.'
+                        };
+                        append(&c.to_string(), color_of(b));
+                    }
+                    append("|", text_color);
+
+                    ui.label(job);
+                }
+            });
+    }
+}
+
+/// Formats the time of day in UTC as `hh:mm:ss.mmm`
+fn format_wall_clock(since_epoch: Duration) -> String {
+    let secs_of_day = since_epoch.as_secs() % 86400;
+
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        secs_of_day / 3600,
+        (secs_of_day / 60) % 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Splits the trail into segments from the oldest to the newest, with the opacity increasing with their recency.
+/// Neighboring segments share a point, so that they are connected.
+fn fading_segments<T>(points: &[T]) -> impl Iterator<Item = (&[T], f32)> {
+    const N_SEGMENTS: usize = 16;
+    /// The opacity of the oldest segment
+    const MIN_ALPHA: f32 = 0.05;
+
+    let segment_len = (points.len() / N_SEGMENTS).max(1);
+    let n_segments = (points.len().saturating_sub(1) + segment_len - 1) / segment_len;
+
+    (0..n_segments).map(move |i| {
+        let start = i * segment_len;
+        let end = (start + segment_len + 1).min(points.len());
+        let t = (i + 1) as f32 / n_segments as f32;
+
+        (&points[start..end], MIN_ALPHA + (1.0 - MIN_ALPHA) * t)
+    })
+}
+
+/// Shows the pages and the connection controls in the tabs of the dock area
+struct DockTabViewer<'a> {
+    app: &'a mut SplotApp,
+    ctx: &'a egui::Context,
+}
+
+impl egui_dock::TabViewer for DockTabViewer<'_> {
+    type Tab = DockTab;
+
+    fn title(&mut self, tab: &mut DockTab) -> egui::WidgetText {
+        tab.to_string().into()
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, tab: &mut DockTab) {
+        match *tab {
+            DockTab::Connection => self.app.render_connection_controls(ui, self.ctx),
+            DockTab::Page(page) => {
+                ui.centered_and_justified(|ui| self.app.render_page(ui, page));
+            }
+            DockTab::Controls(page) => {
+                ui.with_layout(
+                    egui::Layout::top_down(egui::Align::Min).with_cross_justify(true),
+                    |ui| self.app.render_page_controls(ui, page),
+                );
+            }
+        }
+    }
+
+    // The title changes with the language
+    fn id(&mut self, tab: &mut DockTab) -> egui::Id {
+        egui::Id::new(("dock_tab", *tab))
+    }
+
+    // The pages are cropped to the dock area when copied as image
+    fn allowed_in_windows(&self, _tab: &mut DockTab) -> bool {
+        false
+    }
+
+    // The pages fill their tab and scroll themselves
+    fn scroll_bars(&self, tab: &DockTab) -> [bool; 2] {
+        match tab {
+            DockTab::Connection => [true, false],
+            DockTab::Page(_) => [false, false],
+            DockTab::Controls(_) => [false, true],
+        }
+    }
+}
+
+/// Edits an optional limit, a checkbox enables it starting at the default value
+fn limit_edit(ui: &mut egui::Ui, limit: &mut Option<f64>, default: f64) {
+    ui.horizontal(|ui| {
+        let mut enabled = limit.is_some();
+        if ui.checkbox(&mut enabled, "").changed() {
+            *limit = enabled.then_some(default);
+        }
+        if let Some(limit) = limit {
+            ui.add(egui::DragValue::new(limit).speed(0.1));
+        }
+    });
+}
+
+/// Paints a tile of the dashboard. Values outside of the limits of the widget are drawn in the error color.
+fn paint_dashboard_widget(
+    ui: &egui::Ui,
+    rect: egui::Rect,
+    widget: &DashboardWidget,
+    value: Option<f64>,
+    unit: &str,
+    color: egui::Color32,
+) {
+    let visuals = ui.visuals();
+    let painter = ui.painter_at(rect);
+
+    painter.rect_filled(rect.shrink(2.0), 4.0, visuals.faint_bg_color);
+    painter.text(
+        rect.left_top() + egui::vec2(8.0, 6.0),
+        egui::Align2::LEFT_TOP,
+        &widget.channel,
+        egui::FontId::proportional(14.0),
+        visuals.text_color(),
+    );
+
+    let Some(value) = value else {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            tr("no-data"),
+            egui::FontId::default(),
+            visuals.weak_text_color(),
+        );
+        return;
+    };
+
+    let color = if widget.is_out_of_limits(value) {
+        visuals.error_fg_color
+    } else {
+        color
+    };
+    let text = format!("{} {unit}", round_to_decimals(value, 3));
+    let fraction = widget.fraction(value);
+    let track_stroke = egui::Stroke::new(10.0, visuals.extreme_bg_color);
+    let range_font = egui::FontId::monospace(10.0);
+
+    match widget.kind {
+        WidgetKind::Numeric => {
+            painter.text(
+                rect.center() + egui::vec2(0.0, 8.0),
+                egui::Align2::CENTER_CENTER,
+                text,
+                egui::FontId::proportional(36.0),
+                color,
+            );
+        }
+        WidgetKind::Bar => {
+            painter.text(
+                rect.center() - egui::vec2(0.0, 12.0),
+                egui::Align2::CENTER_CENTER,
+                text,
+                egui::FontId::proportional(22.0),
+                color,
+            );
+
+            let bar = egui::Rect::from_min_size(
+                egui::pos2(rect.left() + 12.0, rect.center().y + 12.0),
+                egui::vec2(rect.width() - 24.0, 16.0),
+            );
+            painter.rect_filled(bar, 2.0, visuals.extreme_bg_color);
+            painter.rect_filled(
+                egui::Rect::from_min_size(
+                    bar.min,
+                    egui::vec2(bar.width() * fraction, bar.height()),
+                ),
+                2.0,
+                color,
+            );
+            painter.text(
+                bar.left_bottom() + egui::vec2(0.0, 4.0),
+                egui::Align2::LEFT_TOP,
+                round_to_decimals(widget.min, 3).to_string(),
+                range_font.clone(),
+                visuals.weak_text_color(),
+            );
+            painter.text(
+                bar.right_bottom() + egui::vec2(0.0, 4.0),
+                egui::Align2::RIGHT_TOP,
+                round_to_decimals(widget.max, 3).to_string(),
+                range_font,
+                visuals.weak_text_color(),
+            );
+        }
+        WidgetKind::Gauge => {
+            // A 240° arc, opening downwards
+            const START: f32 = std::f32::consts::PI * 7.0 / 6.0;
+            const SWEEP: f32 = std::f32::consts::PI * 4.0 / 3.0;
+
+            let center = egui::pos2(rect.center().x, rect.bottom() - 50.0);
+            let radius = 60.0;
+            let arc = |from: f32, to: f32| {
+                (0..=32)
+                    .map(|i| {
+                        let angle = START - SWEEP * (from + (to - from) * i as f32 / 32.0);
+                        center + radius * egui::vec2(angle.cos(), -angle.sin())
+                    })
+                    .collect::<Vec<egui::Pos2>>()
+            };
+
+            painter.add(egui::Shape::line(arc(0.0, 1.0), track_stroke));
+            painter.add(egui::Shape::line(
+                arc(0.0, fraction),
+                egui::Stroke::new(10.0, color),
+            ));
+            painter.text(
+                center,
+                egui::Align2::CENTER_CENTER,
+                text,
+                egui::FontId::proportional(18.0),
+                color,
+            );
+
+            let [min_end, max_end] = [START, START - SWEEP]
+                .map(|angle| center + (radius + 4.0) * egui::vec2(angle.cos(), -angle.sin()));
+            painter.text(
+                min_end,
+                egui::Align2::CENTER_TOP,
+                round_to_decimals(widget.min, 3).to_string(),
+                range_font.clone(),
+                visuals.weak_text_color(),
+            );
+            painter.text(
+                max_end,
+                egui::Align2::CENTER_TOP,
+                round_to_decimals(widget.max, 3).to_string(),
+                range_font,
+                visuals.weak_text_color(),
+            );
+        }
+    }
+}
+
+/// Maps a value in the range 0.0..=1.0 to a color from dark blue over red to yellow.
+fn heat_color(t: f32) -> egui::Color32 {
+    const STOPS: [[f32; 3]; 5] = [
+        [0.0, 0.0, 0.1],
+        [0.2, 0.0, 0.5],
+        [0.8, 0.1, 0.3],
+        [1.0, 0.6, 0.0],
+        [1.0, 1.0, 0.6],
+    ];
+
+    let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f32;
+    let i = (t as usize).min(STOPS.len() - 2);
+    let frac = t - i as f32;
+
+    let [r, g, b] = [0, 1, 2].map(|c| STOPS[i][c] + (STOPS[i + 1][c] - STOPS[i][c]) * frac);
+    egui::Rgba::from_rgb(r, g, b).into()
+}
+
+/// Draws a tiny line of the latest values of a channel, scaled to their range
+fn sparkline(ui: &mut egui::Ui, samples: &FixedSizeBuffer<Sample>, color: egui::Color32) {
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(60.0, ui.spacing().interact_size.y),
+        egui::Sense::hover(),
+    );
+
+    let Some(last) = samples.last() else {
+        return;
+    };
+    let window = secs_to_nanos(SPARKLINE_SECS);
+    let start_time = last.time - window;
+    let start = samples.partition_point(|s| s.time < start_time);
+
+    // There is no point in drawing more than about one value per pixel
+    let step = ((samples.len() - start) / rect.width() as usize).max(1);
+    let values = (start..samples.len())
+        .step_by(step)
+        .filter_map(|i| samples.get(i))
+        .filter(|s| s.value.is_finite())
+        .collect::<Vec<&Sample>>();
+    if values.len() < 2 {
+        return;
+    }
+
+    let (min, max) = values.iter().fold((f64::MAX, f64::MIN), |(min, max), s| {
+        (min.min(s.value), max.max(s.value))
+    });
+    let points = values
+        .iter()
+        .map(|s| {
+            let x = (s.time - start_time) as f64 / window as f64;
+            let y = if max > min {
+                (s.value - min) / (max - min)
+            } else {
+                0.5
+            };
+
+            egui::pos2(
+                rect.left() + x as f32 * rect.width(),
+                rect.bottom() - y as f32 * rect.height(),
+            )
+        })
+        .collect::<Vec<egui::Pos2>>();
+
+    ui.painter()
+        .add(egui::Shape::line(points, egui::Stroke::new(1.0, color)));
+}
+
+/// Maps a value onto a plot axis, which is either linear or logarithmic.
+/// Values that are not positive can't be displayed on a logarithmic axis.
+/// A color for the text of an event, the same text always gets the same color
+fn event_color(text: &str) -> egui::Color32 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    let hue = (hasher.finish() % 360) as f32 / 360.0;
+
+    egui::ecolor::Hsva::new(hue, 0.7, 0.8, 1.0).into()
+}
+
+/// Convex polygons that fill the area between the line and the baseline, split where the line crosses it.
+/// Non-finite points break the area like the line.
+fn fill_polygons(points: &[[f64; 2]], baseline: f64) -> Vec<Vec<[f64; 2]>> {
+    let mut polygons = vec![];
+
+    for w in points.windows(2) {
+        let ([x0, y0], [x1, y1]) = (w[0], w[1]);
+        if !(x0.is_finite() && y0.is_finite() && x1.is_finite() && y1.is_finite()) {
+            continue;
+        }
+
+        let (a0, a1) = (y0 - baseline, y1 - baseline);
+        if a0 * a1 < 0.0 {
+            let xc = x0 + (x1 - x0) * a0 / (a0 - a1);
+            polygons.push(vec![[x0, baseline], [x0, y0], [xc, baseline]]);
+            polygons.push(vec![[xc, baseline], [x1, y1], [x1, baseline]]);
+        } else {
+            polygons.push(vec![[x0, baseline], [x0, y0], [x1, y1], [x1, baseline]]);
+        }
+    }
+
+    polygons
+}
+
+fn to_axis(value: f64, log: bool) -> Option<f64> {
+    if !value.is_finite() {
+        None
+    } else if !log {
+        Some(value)
+    } else if value > 0.0 {
+        Some(value.log10())
+    } else {
+        None
+    }
+}
+
+/// The inverse of `to_axis()`
+fn from_axis(axis_value: f64, log: bool) -> f64 {
+    if log {
+        10.0_f64.powf(axis_value)
+    } else {
+        axis_value
+    }
+}
+
+/// Formats a value for the axis labels. Values on logarithmic axes span many decades
+fn format_axis_value(value: f64, log: bool, format: ValueFormat) -> String {
+    if log && format.notation == Notation::Plain && !(1e-3..1e5).contains(&value.abs()) {
+        format!("{value:.1e}")
+    } else {
+        format.format(value)
+    }
+}
+
+/// Formats a time in seconds for the labels and readouts
+fn format_secs(secs: f64, format: ValueFormat) -> String {
+    format.format_with_unit(secs, &TimeUnit::S.to_string())
+}
+
+/// Draws lines through the pointer, with its coordinates pinned at the bottom and the left edge of the plot
+/// like the readouts of a scope. The labels are the X, the Y and optionally the right Y axis coordinate.
+fn crosshair(
+    plot_ui: &mut egui_plot::PlotUi,
+    labels: impl FnOnce(egui_plot::PlotPoint) -> (String, String, Option<String>),
+) {
+    let Some(point) = plot_ui.pointer_coordinate() else {
+        return;
+    };
+    let bounds = plot_ui.plot_bounds();
+    let visuals = plot_ui.ctx().style().visuals.clone();
+    let (x_label, y_label, right_label) = labels(point);
+
+    let readout = |text: String| {
+        egui::RichText::new(text)
+            .small()
+            .color(visuals.strong_text_color())
+            .background_color(visuals.extreme_bg_color)
+    };
+
+    plot_ui.vline(egui_plot::VLine::new(point.x).color(visuals.weak_text_color()));
+    plot_ui.hline(egui_plot::HLine::new(point.y).color(visuals.weak_text_color()));
+
+    plot_ui.text(
+        egui_plot::Text::new(
+            egui_plot::PlotPoint::new(point.x, bounds.min()[1]),
+            readout(x_label),
+        )
+        .anchor(egui::Align2::CENTER_BOTTOM),
+    );
+    plot_ui.text(
+        egui_plot::Text::new(
+            egui_plot::PlotPoint::new(bounds.min()[0], point.y),
+            readout(y_label),
+        )
+        .anchor(egui::Align2::LEFT_CENTER),
+    );
+    if let Some(right_label) = right_label {
+        plot_ui.text(
+            egui_plot::Text::new(
+                egui_plot::PlotPoint::new(bounds.max()[0], point.y),
+                readout(right_label),
+            )
+            .anchor(egui::Align2::RIGHT_CENTER),
+        );
+    }
+}
+
+/// The controls for the render budget of a plot, with the decimation of the last frame
+fn render_budget_controls(ui: &mut egui::Ui, render_budget: &mut RenderBudget) {
+    ui.add(
+        egui::DragValue::new(&mut render_budget.max_points)
+            .clamp_range(RenderBudget::MIN_POINTS..=RenderBudget::MAX_POINTS)
+            .speed(100.0)
+            .suffix(format!(" {}", tr("unit-points"))),
+    )
+    .on_hover_text(tr("hover-render-budget"));
+
+    let decimation = render_budget.decimation();
+    if decimation > 1.0 {
+        ui.weak(tr_args(
+            "decimated",
+            &[("factor", &format!("{:.1}", decimation))],
+        ));
+    }
+}
+
+/// The controls for the Y bounds of a plot
+fn y_bounds_controls(ui: &mut egui::Ui, id_source: &str, y_bounds: &mut YBounds) {
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(y_bounds.mode.label())
+        .show_ui(ui, |ui| {
+            for mode in YBoundsMode::ALL {
+                ui.selectable_value(&mut y_bounds.mode, mode, mode.label());
+            }
+        });
+
+    match y_bounds.mode {
+        YBoundsMode::Free | YBoundsMode::Autoscale => {}
+        YBoundsMode::AutoscaleMargin => {
+            let mut margin_percent = y_bounds.margin * 100.0;
+            if ui
+                .add(
+                    egui::DragValue::new(&mut margin_percent)
+                        .clamp_range(0.0..=100.0)
+                        .suffix(" %"),
+                )
+                .changed()
+            {
+                y_bounds.margin = margin_percent / 100.0;
+            }
+        }
+        YBoundsMode::Fixed => {
+            ui.add(egui::DragValue::new(&mut y_bounds.min).speed(0.1))
+                .on_hover_text(tr("minimum"));
+            ui.add(egui::DragValue::new(&mut y_bounds.max).speed(0.1))
+                .on_hover_text(tr("maximum"));
+        }
+    }
+}
+
+/// A channel that is dragged from a channel list
+struct ChannelDrag(usize);
+
+/// A channel combobox, which also accepts channels that are dropped onto it
+fn channel_drop_slot(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    selected: &mut usize,
+    channel_names: &[&str],
+) {
+    let (_, dropped) = ui
+        .dnd_drop_zone::<ChannelDrag, ()>(egui::Frame::default().inner_margin(2.0), |ui| {
+            channel_combobox(ui, id_source, selected, channel_names)
+        });
+
+    if let Some(dropped) = dropped {
+        *selected = dropped.0;
+    }
+}
+
+/// A single line text edit that only writes the text back once it loses focus, e.g. on enter.
+/// Returns true when the text was changed.
+fn committed_text_edit(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    text: &mut String,
+    width: f32,
+) -> bool {
+    let id = ui.make_persistent_id(id_source);
+    let mut draft = ui
+        .data(|d| d.get_temp::<String>(id))
+        .unwrap_or_else(|| text.clone());
+
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut draft)
+            .id(id)
+            .desired_width(width),
+    );
+
+    if response.has_focus() {
+        ui.data_mut(|d| d.insert_temp(id, draft));
+        false
+    } else {
+        ui.data_mut(|d| d.remove::<String>(id));
+        if response.lost_focus() && draft != *text {
+            *text = draft;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn channel_combobox(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    selected: &mut usize,
+    channel_names: &[&str],
+) {
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(
+            channel_names
+                .get(*selected)
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| format!("c{selected}")),
+        )
+        .show_ui(ui, |ui| {
+            for (i, name) in channel_names.iter().enumerate() {
+                ui.selectable_value(selected, i, *name);
+            }
+        });
 }
 
 /// Round a value to the given number of decimal places.
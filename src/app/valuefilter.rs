@@ -0,0 +1,143 @@
+use super::locale::tr;
+use super::Sample;
+
+/// What happens with samples outside of the plausible range
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum FilterAction {
+    /// The samples are dropped
+    #[default]
+    Discard,
+    /// The values are clamped into the range
+    Clamp,
+    /// The samples are kept unchanged, and only counted in the diagnostics
+    Flag,
+}
+
+impl std::fmt::Display for FilterAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterAction::Discard => write!(f, "Discard"),
+            FilterAction::Clamp => write!(f, "Clamp"),
+            FilterAction::Flag => write!(f, "Flag"),
+        }
+    }
+}
+
+impl FilterAction {
+    pub const ALL: [Self; 3] = [Self::Discard, Self::Clamp, Self::Flag];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            FilterAction::Discard => tr("filter-action-discard"),
+            FilterAction::Clamp => tr("filter-action-clamp"),
+            FilterAction::Flag => tr("filter-action-flag"),
+        }
+    }
+}
+
+/// Rejects implausible values of a channel at ingest,
+/// so that a single corrupted line doesn't distort the plot scale and statistics.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ValueFilter {
+    pub enabled: bool,
+    pub min: f64,
+    pub max: f64,
+    pub action: FilterAction,
+    /// The number of samples outside of the range
+    #[serde(skip)]
+    rejected: u64,
+}
+
+impl Default for ValueFilter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min: -1000.0,
+            max: 1000.0,
+            action: FilterAction::default(),
+            rejected: 0,
+        }
+    }
+}
+
+impl ValueFilter {
+    pub fn rejected(&self) -> u64 {
+        self.rejected
+    }
+
+    /// Discards, clamps or flags the samples outside of the range
+    pub fn apply(&mut self, samples: &mut Vec<Sample>) {
+        if !self.enabled {
+            return;
+        }
+
+        let (min, max) = (self.min.min(self.max), self.min.max(self.max));
+        let n_before = samples.len() as u64;
+
+        match self.action {
+            FilterAction::Discard => {
+                samples.retain(|s| s.value >= min && s.value <= max);
+                self.rejected += n_before - samples.len() as u64;
+            }
+            FilterAction::Clamp => {
+                for sample in samples.iter_mut() {
+                    if sample.value < min || sample.value > max {
+                        sample.value = sample.value.clamp(min, max);
+                        self.rejected += 1;
+                    }
+                }
+            }
+            FilterAction::Flag => {
+                self.rejected += samples
+                    .iter()
+                    .filter(|s| s.value < min || s.value > max)
+                    .count() as u64;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_filter_flag_keeps_samples() {
+        let mut filter = ValueFilter {
+            enabled: true,
+            min: 0.0,
+            max: 10.0,
+            action: FilterAction::Flag,
+            ..Default::default()
+        };
+        let mut samples = [5.0, 20.0, -1.0]
+            .into_iter()
+            .map(|value| Sample {
+                time: 0,
+                value,
+                name: None,
+            })
+            .collect::<Vec<_>>();
+
+        filter.apply(&mut samples);
+
+        assert_eq!(
+            samples.iter().map(|s| s.value).collect::<Vec<_>>(),
+            [5.0, 20.0, -1.0]
+        );
+        assert_eq!(filter.rejected(), 2);
+    }
+}
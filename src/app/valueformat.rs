@@ -0,0 +1,148 @@
+use super::locale::tr;
+use super::ui::round_to_decimals;
+
+/// The SI prefixes from 1e-24 to 1e24, in steps of three decades
+const SI_PREFIXES: [&str; 17] = [
+    "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+];
+
+/// How values are written in the plot labels, axes and readouts
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum Notation {
+    /// Like `0.0012`
+    #[default]
+    Plain,
+    /// Like `1.2e-3`
+    Scientific,
+    /// Like `1.2 m`, the prefix is put in front of the unit
+    SiPrefix,
+}
+
+impl std::fmt::Display for Notation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Notation::Plain => write!(f, "Plain"),
+            Notation::Scientific => write!(f, "Scientific"),
+            Notation::SiPrefix => write!(f, "SI prefix"),
+        }
+    }
+}
+
+impl Notation {
+    pub const ALL: [Self; 3] = [Self::Plain, Self::Scientific, Self::SiPrefix];
+
+    /// The label in the current language, shown in the combo boxes
+    pub fn label(self) -> &'static str {
+        match self {
+            Notation::Plain => tr("notation-plain"),
+            Notation::Scientific => tr("notation-scientific"),
+            Notation::SiPrefix => tr("notation-si-prefix"),
+        }
+    }
+}
+
+/// The notation and the maximum number of decimals of displayed values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ValueFormat {
+    pub notation: Notation,
+    /// Trailing zeros are dropped, except in the scientific notation
+    pub decimals: usize,
+}
+
+impl Default for ValueFormat {
+    fn default() -> Self {
+        Self {
+            notation: Notation::default(),
+            decimals: 7,
+        }
+    }
+}
+
+impl ValueFormat {
+    pub const MAX_DECIMALS: usize = 12;
+
+    pub fn format(&self, value: f64) -> String {
+        self.format_with_unit(value, "")
+    }
+
+    /// Formats the value followed by the unit, if it isn't empty
+    pub fn format_with_unit(&self, value: f64, unit: &str) -> String {
+        let decimals = self.decimals.min(Self::MAX_DECIMALS);
+
+        let (number, prefix) = match self.notation {
+            _ if !value.is_finite() => (value.to_string(), ""),
+            Notation::Plain => (round_to_decimals(value, decimals).to_string(), ""),
+            Notation::Scientific => (format!("{value:.decimals$e}"), ""),
+            Notation::SiPrefix => {
+                let (scaled, prefix) = si_scaled(value, decimals);
+                (round_to_decimals(scaled, decimals).to_string(), prefix)
+            }
+        };
+
+        match (prefix, unit) {
+            ("", "") => number,
+            _ => format!("{number} {prefix}{unit}"),
+        }
+    }
+}
+
+/// The value scaled to the SI prefix it is displayed with
+fn si_scaled(value: f64, decimals: usize) -> (f64, &'static str) {
+    if value == 0.0 {
+        return (value, "");
+    }
+
+    let mut exponent = (value.abs().log10() / 3.0).floor() as i32;
+    // Rounding can carry the value into the next prefix, like 999.9999 to 1000
+    if round_to_decimals(value.abs() / 10f64.powi(exponent * 3), decimals) >= 1000.0 {
+        exponent += 1;
+    }
+    let exponent = exponent.clamp(-8, 8);
+
+    (
+        value / 10f64.powi(exponent * 3),
+        SI_PREFIXES[(exponent + 8) as usize],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_formatting() {
+        let plain = ValueFormat::default();
+        assert_eq!(plain.format(0.00123456789), "0.0012346");
+        assert_eq!(plain.format_with_unit(3.3, "V"), "3.3 V");
+
+        let scientific = ValueFormat {
+            notation: Notation::Scientific,
+            decimals: 2,
+        };
+        assert_eq!(scientific.format(0.00123456789), "1.23e-3");
+
+        let si = ValueFormat {
+            notation: Notation::SiPrefix,
+            decimals: 3,
+        };
+        assert_eq!(si.format_with_unit(0.0033, "V"), "3.3 mV");
+        assert_eq!(si.format_with_unit(-1500.0, "Hz"), "-1.5 kHz");
+        assert_eq!(si.format_with_unit(999.9999, "s"), "1 ks");
+        assert_eq!(si.format_with_unit(42.0, "s"), "42 s");
+        assert_eq!(si.format(2e-6), "2 µ");
+        assert_eq!(si.format(0.0), "0");
+        assert_eq!(si.format(f64::NAN), "NaN");
+    }
+}
@@ -0,0 +1,399 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+
+use base64::Engine;
+use instant::Duration;
+use sha1::Digest;
+
+use super::httpapi;
+
+/// Appended to the key of the client for the accept header of the handshake
+const HANDSHAKE_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// The number of messages queued for a client, a client that falls further behind is disconnected
+const CLIENT_QUEUE_LEN: usize = 16384;
+/// The time a client waits for frames of the client, before it writes the queued messages
+const CLIENT_POLL: Duration = Duration::from_millis(20);
+/// The maximum payload of a frame of a client, larger frames close the connection
+const MAX_CLIENT_PAYLOAD: usize = 65536;
+
+pub const OPCODE_TEXT: u8 = 0x1;
+pub const OPCODE_CLOSE: u8 = 0x8;
+pub const OPCODE_PING: u8 = 0x9;
+pub const OPCODE_PONG: u8 = 0xA;
+/// The status code of a close frame when the server stops or disconnects a client
+const CLOSE_GOING_AWAY: u16 = 1001;
+
+/// A WebSocket server on localhost that sends every message to all connected clients.
+///
+/// Each client is served by its own thread, that answers pings and close frames.
+/// Clients that can't keep up or don't respond are disconnected, connections of other websites are rejected
+/// unless their origin is allowed. The server is stopped when it is dropped.
+#[derive(Debug)]
+pub struct Broadcast {
+    addr: SocketAddr,
+    clients: Arc<Mutex<Vec<Client>>>,
+    stop: Arc<AtomicBool>,
+}
+
+/// The queue of the messages of a connected client
+#[derive(Debug)]
+struct Client {
+    messages: mpsc::SyncSender<Arc<[u8]>>,
+    /// Cleared by the thread of the client when it ends
+    connected: Arc<AtomicBool>,
+}
+
+impl Broadcast {
+    /// Binds to the port on localhost.
+    ///
+    /// The allowed origins are websites like `http://localhost:8000` that may connect.
+    pub fn start(port: u16, allowed_origins: Vec<String>) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .map_err(|e| anyhow::anyhow!("failed to bind to port {port}, Err: {e}"))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+
+        let clients = Arc::new(Mutex::new(Vec::<Client>::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let accept_clients = Arc::clone(&clients);
+        let accept_stop = Arc::clone(&stop);
+        std::thread::spawn(move || {
+            while !accept_stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let clients = Arc::clone(&accept_clients);
+                        let allowed_origins = allowed_origins.clone();
+                        std::thread::spawn(move || {
+                            let stream = match handshake(stream, addr.port(), &allowed_origins) {
+                                Ok(stream) => stream,
+                                Err(e) => {
+                                    log::debug!("WebSocket handshake failed, Err: {e}");
+                                    return;
+                                }
+                            };
+
+                            let (messages, receiver) = mpsc::sync_channel(CLIENT_QUEUE_LEN);
+                            let connected = Arc::new(AtomicBool::new(true));
+                            if let Ok(mut clients) = clients.lock() {
+                                clients.push(Client {
+                                    messages,
+                                    connected: Arc::clone(&connected),
+                                });
+                            }
+
+                            if let Err(e) = serve_client(stream, &receiver) {
+                                log::debug!("WebSocket client disconnected, Err: {e}");
+                            }
+                            connected.store(false, Ordering::Relaxed);
+                        });
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        std::thread::sleep(Duration::from_millis(50));
+                    }
+                    Err(e) => {
+                        log::error!("accepting WebSocket connection failed, Err: {e}");
+                        std::thread::sleep(Duration::from_millis(500));
+                    }
+                }
+            }
+            log::debug!("stopped WebSocket broadcast on {addr}");
+        });
+
+        log::debug!("started WebSocket broadcast on {addr}");
+        Ok(Self {
+            addr,
+            clients,
+            stop,
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn n_clients(&self) -> usize {
+        self.clients
+            .lock()
+            .map(|c| {
+                c.iter()
+                    .filter(|c| c.connected.load(Ordering::Relaxed))
+                    .count()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Queues the message for all clients. Clients whose queue is full are disconnected.
+    pub fn send(&self, message: String) {
+        let frame: Arc<[u8]> = text_frame(&message).into();
+
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.retain(
+                |client| match client.messages.try_send(Arc::clone(&frame)) {
+                    Ok(()) => true,
+                    Err(mpsc::TrySendError::Full(_)) => {
+                        log::debug!("disconnecting WebSocket client that can't keep up");
+                        false
+                    }
+                    Err(mpsc::TrySendError::Disconnected(_)) => false,
+                },
+            );
+        }
+    }
+}
+
+impl Drop for Broadcast {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // The threads of the clients end when their queue is dropped
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.clear();
+        }
+    }
+}
+
+/// Answers the upgrade request of the client
+fn handshake(
+    mut stream: TcpStream,
+    port: u16,
+    allowed_origins: &[String],
+) -> anyhow::Result<TcpStream> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+
+    let head = httpapi::read_request_head(&mut stream)?
+        .ok_or_else(|| anyhow::anyhow!("request is too large"))?;
+
+    if let Err(e) = httpapi::check_origin(&head, port, allowed_origins) {
+        stream.write_all(b"HTTP/1.1 403 Forbidden\r\nConnection: close\r\n\r\n")?;
+        return Err(anyhow::anyhow!("{e}"));
+    }
+    let Some(key) = httpapi::header(&head, "sec-websocket-key") else {
+        stream.write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n")?;
+        return Err(anyhow::anyhow!("not a WebSocket upgrade request"));
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+        Sec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(key)
+    )?;
+    stream.flush()?;
+
+    Ok(stream)
+}
+
+/// Writes the queued messages to the client and answers its pings and close frames,
+/// until the client closes the connection or its queue is dropped.
+///
+/// Messages of the client are ignored.
+fn serve_client(mut stream: TcpStream, messages: &mpsc::Receiver<Arc<[u8]>>) -> anyhow::Result<()> {
+    stream.set_read_timeout(Some(CLIENT_POLL))?;
+    let mut received = vec![];
+    let mut buf = [0; 1024];
+
+    loop {
+        loop {
+            match messages.try_recv() {
+                Ok(frame) => stream.write_all(&frame)?,
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    stream.write_all(&frame(OPCODE_CLOSE, &CLOSE_GOING_AWAY.to_be_bytes()))?;
+                    return Ok(());
+                }
+            }
+        }
+
+        match stream.read(&mut buf) {
+            Ok(0) => return Err(anyhow::anyhow!("connection closed by the client")),
+            Ok(n) => received.extend_from_slice(&buf[..n]),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                continue
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        while let Some((opcode, payload, len)) = read_client_frame(&received)? {
+            received.drain(..len);
+
+            match opcode {
+                OPCODE_CLOSE => {
+                    // Echo the status code of the client
+                    stream.write_all(&frame(OPCODE_CLOSE, &payload[..payload.len().min(2)]))?;
+                    return Ok(());
+                }
+                OPCODE_PING => stream.write_all(&frame(OPCODE_PONG, &payload))?,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Reads a masked frame of a client from the start of the received bytes.
+///
+/// Returns the opcode, the unmasked payload and the length of the frame, None when the frame is incomplete.
+pub fn read_client_frame(received: &[u8]) -> anyhow::Result<Option<(u8, Vec<u8>, usize)>> {
+    let (Some(&first), Some(&second)) = (received.first(), received.get(1)) else {
+        return Ok(None);
+    };
+    if second & 0x80 == 0 {
+        return Err(anyhow::anyhow!("frames of clients must be masked"));
+    }
+
+    let (len, header_len) = match second & 0x7F {
+        126 => match received.get(2..4) {
+            Some(len) => (u16::from_be_bytes([len[0], len[1]]) as u64, 4),
+            None => return Ok(None),
+        },
+        127 => match received.get(2..10) {
+            Some(len) => (u64::from_be_bytes(len.try_into()?), 10),
+            None => return Ok(None),
+        },
+        len => (len as u64, 2),
+    };
+    if len > MAX_CLIENT_PAYLOAD as u64 {
+        return Err(anyhow::anyhow!("frame of {len} bytes is too large"));
+    }
+
+    let payload_start = header_len + 4;
+    let frame_len = payload_start + len as usize;
+    let Some(frame) = received.get(..frame_len) else {
+        return Ok(None);
+    };
+    let mask = &frame[header_len..payload_start];
+    let payload = frame[payload_start..]
+        .iter()
+        .enumerate()
+        .map(|(i, b)| b ^ mask[i % 4])
+        .collect();
+
+    Ok(Some((first & 0x0F, payload, frame_len)))
+}
+
+/// The accept header value for the key of the client
+pub fn accept_key(key: &str) -> String {
+    let hash = sha1::Sha1::digest(format!("{key}{HANDSHAKE_GUID}").as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(hash)
+}
+
+/// An unmasked text frame, as sent by servers
+pub fn text_frame(message: &str) -> Vec<u8> {
+    frame(OPCODE_TEXT, message.as_bytes())
+}
+
+/// An unmasked final frame with the opcode, as sent by servers
+pub fn frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend((len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend((len as u64).to_be_bytes());
+        }
+    }
+    frame.extend(payload);
+
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn websocket_handshake_and_frames() {
+        // The example of RFC 6455
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+
+        assert_eq!(text_frame("hi"), [0x81, 2, b'h', b'i']);
+        let frame = text_frame(&"a".repeat(300));
+        assert_eq!(frame[..4], [0x81, 126, 1, 44]);
+        assert_eq!(frame.len(), 4 + 300);
+
+        // A masked ping of a client, the example of RFC 6455
+        let ping = [
+            0x89, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+        ];
+        assert_eq!(read_client_frame(&ping[..6]).unwrap(), None);
+        assert_eq!(
+            read_client_frame(&ping).unwrap(),
+            Some((OPCODE_PING, b"Hello".to_vec(), ping.len()))
+        );
+        // Unmasked frames are only sent by servers
+        assert!(read_client_frame(&text_frame("hi")).is_err());
+    }
+
+    #[test]
+    fn websocket_broadcast_clients() {
+        let broadcast = Broadcast::start(0, vec![]).unwrap();
+        let addr = broadcast.addr();
+        let connect = |origin: &str| {
+            let mut stream = std::net::TcpStream::connect(addr).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            write!(
+                stream,
+                "GET / HTTP/1.1\r\nHost: {addr}\r\n{origin}Upgrade: websocket\r\n\
+                Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n"
+            )
+            .unwrap();
+            // The response head, byte by byte so that no frame is consumed
+            let mut response = vec![];
+            while !response.ends_with(b"\r\n\r\n") {
+                let mut byte = [0];
+                stream.read_exact(&mut byte).unwrap();
+                response.push(byte[0]);
+            }
+            (stream, String::from_utf8(response).unwrap())
+        };
+
+        let (_, response) = connect("Origin: https://evil.example\r\n");
+        assert!(response.starts_with("HTTP/1.1 403 Forbidden"));
+
+        let (mut stream, response) = connect("");
+        assert!(response.starts_with("HTTP/1.1 101 Switching Protocols"));
+        while broadcast.n_clients() == 0 {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        // An empty masked ping is answered with a pong
+        stream.write_all(&[0x89, 0x80, 1, 2, 3, 4]).unwrap();
+        let mut pong = [0; 2];
+        stream.read_exact(&mut pong).unwrap();
+        assert_eq!(pong, [0x80 | OPCODE_PONG, 0]);
+
+        broadcast.send(String::from("hi"));
+        let mut message = [0; 4];
+        stream.read_exact(&mut message).unwrap();
+        assert_eq!(message, [0x81, 2, b'h', b'i']);
+
+        // The close frame of the client is echoed, and the client is removed
+        stream
+            .write_all(&[0x88, 0x82, 0, 0, 0, 0, 0x03, 0xE8])
+            .unwrap();
+        let mut close = [0; 4];
+        stream.read_exact(&mut close).unwrap();
+        assert_eq!(close, [0x88, 2, 0x03, 0xE8]);
+        while broadcast.n_clients() > 0 {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
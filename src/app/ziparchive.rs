@@ -0,0 +1,78 @@
+/// Writes the files into a zip archive, stored without compression
+pub fn write_stored(files: &[(String, Vec<u8>)]) -> Vec<u8> {
+    // DOS date of 1980-01-01, the earliest representable
+    const DATE: u16 = (1 << 5) | 1;
+
+    let mut out = vec![];
+    let mut central_dir = vec![];
+
+    for (name, data) in files.iter() {
+        let offset = out.len() as u32;
+        let crc = crc32(data);
+        let size = data.len() as u32;
+
+        // Local file header
+        out.extend(0x04034b50_u32.to_le_bytes());
+        out.extend(20_u16.to_le_bytes()); // version needed
+        out.extend(0_u16.to_le_bytes()); // flags
+        out.extend(0_u16.to_le_bytes()); // stored
+        out.extend(0_u16.to_le_bytes()); // time
+        out.extend(DATE.to_le_bytes());
+        out.extend(crc.to_le_bytes());
+        out.extend(size.to_le_bytes()); // compressed size
+        out.extend(size.to_le_bytes());
+        out.extend((name.len() as u16).to_le_bytes());
+        out.extend(0_u16.to_le_bytes()); // extra field length
+        out.extend(name.as_bytes());
+        out.extend(data);
+
+        // Central directory header
+        central_dir.extend(0x02014b50_u32.to_le_bytes());
+        central_dir.extend(20_u16.to_le_bytes()); // version made by
+        central_dir.extend(20_u16.to_le_bytes()); // version needed
+        central_dir.extend(0_u16.to_le_bytes()); // flags
+        central_dir.extend(0_u16.to_le_bytes()); // stored
+        central_dir.extend(0_u16.to_le_bytes()); // time
+        central_dir.extend(DATE.to_le_bytes());
+        central_dir.extend(crc.to_le_bytes());
+        central_dir.extend(size.to_le_bytes()); // compressed size
+        central_dir.extend(size.to_le_bytes());
+        central_dir.extend((name.len() as u16).to_le_bytes());
+        central_dir.extend([0; 12]); // extra, comment, disk, attributes
+        central_dir.extend(offset.to_le_bytes());
+        central_dir.extend(name.as_bytes());
+    }
+
+    let central_dir_offset = out.len() as u32;
+    let n_files = files.len() as u16;
+    out.extend(&central_dir);
+
+    // End of central directory record
+    out.extend(0x06054b50_u32.to_le_bytes());
+    out.extend([0; 4]); // disk numbers
+    out.extend(n_files.to_le_bytes());
+    out.extend(n_files.to_le_bytes());
+    out.extend((central_dir.len() as u32).to_le_bytes());
+    out.extend(central_dir_offset.to_le_bytes());
+    out.extend(0_u16.to_le_bytes()); // comment length
+
+    out
+}
+
+/// The CRC-32 checksum used by zip archives
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
@@ -0,0 +1,11 @@
+//! The Web Worker of the web build that parses the received data, built by trunk next to the app.
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    splot::run_parser_worker();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    eprintln!("the parser worker is only used by the web build");
+}
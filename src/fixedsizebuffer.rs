@@ -1,10 +1,17 @@
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
 
 /// A buffer with fixed size. When pushing exceeds its size, the oldest / first item is removed.
 #[derive(Debug, Clone)]
 pub struct FixedSizeBuffer<T> {
     inner: VecDeque<T>,
     size: usize,
+    /// Identifies the buffer, for data that is derived from its items
+    id: u64,
+    /// The number of items that were ever added
+    n_added: u64,
 }
 
 impl<T> FixedSizeBuffer<T> {
@@ -12,6 +19,8 @@ impl<T> FixedSizeBuffer<T> {
         Self {
             inner: VecDeque::new(),
             size,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            n_added: 0,
         }
     }
 
@@ -23,10 +32,22 @@ impl<T> FixedSizeBuffer<T> {
         };
 
         self.inner.push_back(item);
+        self.n_added += 1;
 
         removed
     }
 
+    /// Identifies the buffer, for data that is derived from its items
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// The number of items that were ever added. Items are only removed from the front,
+    /// so the first item is the one that was added as `n_added() - len()`th
+    pub fn n_added(&self) -> u64 {
+        self.n_added
+    }
+
     pub fn remove(&mut self) -> Option<T> {
         self.inner.pop_front()
     }
@@ -55,6 +76,15 @@ impl<T> FixedSizeBuffer<T> {
         self.inner.is_empty()
     }
 
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.inner.get(index)
+    }
+
+    /// The index of the first item for which the predicate is false, given the items are partitioned by it
+    pub fn partition_point(&self, pred: impl FnMut(&T) -> bool) -> usize {
+        self.inner.partition_point(pred)
+    }
+
     pub fn iter(&self) -> FixedSizeBufferIter<'_, T> {
         self.into_iter()
     }
@@ -1,6 +1,31 @@
+#[cfg(feature = "gui")]
 mod app;
 mod fixedsizebuffer;
+#[cfg(not(target_arch = "wasm32"))]
+mod logbuffer;
+mod parser;
 mod serialconnection;
 
 // Re-Exports
+#[cfg(feature = "gui")]
 pub use app::SplotApp;
+pub use fixedsizebuffer::FixedSizeBuffer;
+#[cfg(not(target_arch = "wasm32"))]
+pub use logbuffer::{init_logger, log_lines};
+pub use parser::binarylayout::{BinaryField, BinaryFieldType, BinaryLayout, Endianness};
+pub use parser::can::CanSignal;
+pub use parser::nmea::NmeaSentence;
+pub use parser::ubx::UbxMessage;
+#[cfg(target_arch = "wasm32")]
+pub use parser::worker::run as run_parser_worker;
+pub use parser::{
+    interpolate_value_at, nanos_to_secs, parse_value, parse_value_with_unit, secs_to_nanos, Marker,
+    ParseFormat, ParseResult, Parser, Sample, TextEvent, TimeUnit,
+};
+pub use serialconnection::mock::MockSerialConnection;
+pub use serialconnection::script::DeviceScript;
+pub use serialconnection::{
+    new_serial_connection, new_serial_connection_dummy, new_serial_connection_dummy_script,
+    validate_baudrate, DataBits, FlowControl, Parity, PortInfo, SerialConnection, StopBits,
+    UsbPortInfo, BAUDRATE_PRESETS,
+};
@@ -0,0 +1,76 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+/// The number of log lines kept for debug bundles
+const LOG_BUF_LINES: usize = 1024;
+
+static LOG_BUFFER: Lazy<Mutex<VecDeque<String>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(LOG_BUF_LINES)));
+
+/// Forwards the log records to the inner logger and keeps the latest ones in a ring buffer,
+/// so that they can be included in debug bundles.
+struct RingLogger {
+    inner: Box<dyn log::Log>,
+}
+
+impl log::Log for RingLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.inner.enabled(record.metadata()) {
+            self.inner.log(record);
+        }
+
+        if record.level() > log::Level::Debug || !record.target().starts_with("splot") {
+            return;
+        }
+
+        if let Ok(mut buf) = LOG_BUFFER.lock() {
+            if buf.len() >= LOG_BUF_LINES {
+                buf.pop_front();
+            }
+            buf.push_back(format!(
+                "{:<5} {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            ));
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Initializes the logger, which is configured through the `RUST_LOG` environment variable.
+///
+/// Debug messages of the app are always kept in the log buffer, regardless of the configuration.
+pub fn init_logger() {
+    let mut builder = pretty_env_logger::formatted_builder();
+    if let Ok(filters) = std::env::var("RUST_LOG") {
+        builder.parse_filters(&filters);
+    }
+    let inner = builder.build();
+    let max_level = inner.filter().max(log::LevelFilter::Debug);
+
+    if log::set_boxed_logger(Box::new(RingLogger {
+        inner: Box::new(inner),
+    }))
+    .is_ok()
+    {
+        log::set_max_level(max_level);
+    }
+}
+
+/// The latest log lines, oldest first
+pub fn log_lines() -> Vec<String> {
+    LOG_BUFFER
+        .lock()
+        .map(|buf| buf.iter().cloned().collect())
+        .unwrap_or_default()
+}
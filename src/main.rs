@@ -3,9 +3,9 @@
 // When compiling natively:
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
-    pretty_env_logger::init();
+    splot::init_logger();
 
-    log::debug!("pretty_env_logger initialized.");
+    log::debug!("logger initialized.");
 
     let native_options = eframe::NativeOptions::default();
 
@@ -0,0 +1,202 @@
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
+
+impl std::fmt::Display for Endianness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Endianness::Little => write!(f, "Little Endian"),
+            Endianness::Big => write!(f, "Big Endian"),
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum BinaryFieldType {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    #[default]
+    F32,
+    F64,
+}
+
+impl std::fmt::Display for BinaryFieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryFieldType::U8 => write!(f, "u8"),
+            BinaryFieldType::I8 => write!(f, "i8"),
+            BinaryFieldType::U16 => write!(f, "u16"),
+            BinaryFieldType::I16 => write!(f, "i16"),
+            BinaryFieldType::U32 => write!(f, "u32"),
+            BinaryFieldType::I32 => write!(f, "i32"),
+            BinaryFieldType::U64 => write!(f, "u64"),
+            BinaryFieldType::I64 => write!(f, "i64"),
+            BinaryFieldType::F32 => write!(f, "f32"),
+            BinaryFieldType::F64 => write!(f, "f64"),
+        }
+    }
+}
+
+impl BinaryFieldType {
+    pub const ALL: [Self; 10] = [
+        Self::U8,
+        Self::I8,
+        Self::U16,
+        Self::I16,
+        Self::U32,
+        Self::I32,
+        Self::U64,
+        Self::I64,
+        Self::F32,
+        Self::F64,
+    ];
+
+    /// The size of the field in bytes
+    pub fn size(self) -> usize {
+        match self {
+            BinaryFieldType::U8 | BinaryFieldType::I8 => 1,
+            BinaryFieldType::U16 | BinaryFieldType::I16 => 2,
+            BinaryFieldType::U32 | BinaryFieldType::I32 | BinaryFieldType::F32 => 4,
+            BinaryFieldType::U64 | BinaryFieldType::I64 | BinaryFieldType::F64 => 8,
+        }
+    }
+
+    /// Decodes the value. `bytes` must have the length returned by `size()`.
+    pub(crate) fn decode(self, bytes: &[u8], endianness: Endianness) -> f64 {
+        macro_rules! from_bytes {
+            ($t:ty) => {{
+                let arr = bytes.try_into().unwrap();
+                match endianness {
+                    Endianness::Little => <$t>::from_le_bytes(arr) as f64,
+                    Endianness::Big => <$t>::from_be_bytes(arr) as f64,
+                }
+            }};
+        }
+
+        match self {
+            BinaryFieldType::U8 => from_bytes!(u8),
+            BinaryFieldType::I8 => from_bytes!(i8),
+            BinaryFieldType::U16 => from_bytes!(u16),
+            BinaryFieldType::I16 => from_bytes!(i16),
+            BinaryFieldType::U32 => from_bytes!(u32),
+            BinaryFieldType::I32 => from_bytes!(i32),
+            BinaryFieldType::U64 => from_bytes!(u64),
+            BinaryFieldType::I64 => from_bytes!(i64),
+            BinaryFieldType::F32 => from_bytes!(f32),
+            BinaryFieldType::F64 => from_bytes!(f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BinaryField {
+    /// The name of the field. A field named "time" or "t" is used as the time of the packet.
+    pub name: String,
+    pub ty: BinaryFieldType,
+}
+
+/// Describes a repeating packet of fixed size:
+/// the magic bytes followed by the fields, packed without padding.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct BinaryLayout {
+    /// The magic bytes marking the start of a packet, as hex string. e.g. "AA 55"
+    pub magic: String,
+    pub endianness: Endianness,
+    pub fields: Vec<BinaryField>,
+}
+
+impl Default for BinaryLayout {
+    fn default() -> Self {
+        Self {
+            magic: String::from("AA 55"),
+            endianness: Endianness::default(),
+            fields: vec![BinaryField {
+                name: String::from("value"),
+                ty: BinaryFieldType::default(),
+            }],
+        }
+    }
+}
+
+impl BinaryLayout {
+    /// Parses the magic hex string into bytes. Whitespace is ignored.
+    pub fn magic_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        let hex = self
+            .magic
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+
+        if hex.len() % 2 != 0 {
+            return Err(anyhow::anyhow!(
+                "magic bytes `{}` have an odd number of hex digits",
+                self.magic
+            ));
+        }
+
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| {
+                    anyhow::anyhow!("magic bytes `{}` are not valid hex, Err: {e}", self.magic)
+                })
+            })
+            .collect()
+    }
+
+    /// The size of the packet payload (without the magic bytes) in bytes
+    pub fn payload_size(&self) -> usize {
+        self.fields.iter().map(|f| f.ty.size()).sum()
+    }
+
+    /// Decodes the payload into the values of all fields, in field order.
+    /// `payload` must have the length returned by `payload_size()`.
+    pub fn decode_payload<'a>(
+        &'a self,
+        payload: &'a [u8],
+    ) -> impl Iterator<Item = (&'a BinaryField, f64)> + 'a {
+        let mut offset = 0;
+
+        self.fields.iter().map(move |field| {
+            let size = field.ty.size();
+            let value = field
+                .ty
+                .decode(&payload[offset..offset + size], self.endianness);
+            offset += size;
+
+            (field, value)
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use super::binarylayout::{BinaryFieldType, Endianness};
+
+/// The size of a classic CAN frame in the layout of the SocketCAN `struct can_frame`:
+/// the id with the flags, the data length, padding and 8 data bytes.
+pub const FRAME_SIZE: usize = 16;
+
+const EFF_FLAG: u32 = 0x8000_0000;
+const RTR_FLAG: u32 = 0x4000_0000;
+const ERR_FLAG: u32 = 0x2000_0000;
+const EFF_MASK: u32 = 0x1FFF_FFFF;
+const SFF_MASK: u32 = 0x0000_07FF;
+
+/// A value in the data of the frames with the id, mapped to a channel without the need of a DBC file
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CanSignal {
+    /// The name of the channel
+    pub name: String,
+    /// The standard or extended CAN id
+    pub id: u32,
+    /// The offset of the value in the data bytes
+    pub offset: usize,
+    pub ty: BinaryFieldType,
+    pub endianness: Endianness,
+    /// The value is `raw * scale + value_offset`
+    pub scale: f64,
+    pub value_offset: f64,
+}
+
+impl Default for CanSignal {
+    fn default() -> Self {
+        Self {
+            name: String::from("signal"),
+            id: 0x100,
+            offset: 0,
+            ty: BinaryFieldType::U16,
+            endianness: Endianness::default(),
+            scale: 1.0,
+            value_offset: 0.0,
+        }
+    }
+}
+
+impl CanSignal {
+    /// Decodes the scaled value from the frame data. None when the data is too short.
+    pub fn decode(&self, data: &[u8]) -> Option<f64> {
+        let bytes = data.get(self.offset..self.offset + self.ty.size())?;
+        Some(self.ty.decode(bytes, self.endianness) * self.scale + self.value_offset)
+    }
+}
+
+/// The id and the data of a frame. None for remote and error frames, which carry no signals.
+pub fn decode_frame(frame: &[u8]) -> Option<(u32, &[u8])> {
+    let id = u32::from_ne_bytes(frame.get(0..4)?.try_into().ok()?);
+    if id & (RTR_FLAG | ERR_FLAG) != 0 {
+        return None;
+    }
+    let id = if id & EFF_FLAG != 0 {
+        id & EFF_MASK
+    } else {
+        id & SFF_MASK
+    };
+    let len = usize::from(*frame.get(4)?).min(8);
+
+    Some((id, frame.get(8..8 + len)?))
+}
+
+/// Encodes a data frame, ids that do not fit into 11 bits are sent as extended ids
+#[cfg(test)]
+pub fn encode_frame(id: u32, data: &[u8]) -> [u8; FRAME_SIZE] {
+    let mut frame = [0; FRAME_SIZE];
+    let len = data.len().min(8);
+    let id = if id > SFF_MASK {
+        (id & EFF_MASK) | EFF_FLAG
+    } else {
+        id
+    };
+
+    frame[0..4].copy_from_slice(&id.to_ne_bytes());
+    frame[4] = len as u8;
+    frame[8..8 + len].copy_from_slice(&data[..len]);
+    frame
+}
@@ -0,0 +1,832 @@
+//! Parsing of the received data into samples, independent of the user interface.
+
+pub mod binarylayout;
+pub mod can;
+pub mod nmea;
+pub mod timesync;
+pub mod ubx;
+#[cfg_attr(not(target_arch = "wasm32"), allow(unused))]
+pub mod worker;
+
+#[cfg(test)]
+mod tests;
+
+use std::io::{BufRead, Cursor};
+
+use self::binarylayout::BinaryLayout;
+use self::can::CanSignal;
+use self::nmea::NmeaSentence;
+use self::timesync::{TimeSync, TimeUnwrap};
+use self::ubx::UbxMessage;
+use crate::fixedsizebuffer::FixedSizeBuffer;
+
+/// A received value of a channel
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Sample {
+    /// The time in nanoseconds.
+    ///
+    /// An integer representation avoids precision loss for long captures with fine grained device timestamps.
+    /// It is signed, because imported captures can have negative (pre-trigger) times.
+    pub time: i64,
+    pub value: f64,
+    /// The name of the channel, only set for the first sample of a channel
+    pub name: Option<String>,
+}
+
+impl Sample {
+    /// The time in seconds, for display
+    pub fn time_secs(&self) -> f64 {
+        nanos_to_secs(self.time)
+    }
+}
+
+/// A discrete event sent by the device as `marker=<label>`, e.g. `marker=button_pressed`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Marker {
+    /// The time in nanoseconds
+    pub time: i64,
+    pub label: String,
+}
+
+/// A named field with a text instead of a number, e.g. the state of a state machine in `state=IDLE`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TextEvent {
+    /// The time in nanoseconds
+    pub time: i64,
+    /// The name of the field
+    pub channel: String,
+    pub text: String,
+}
+
+/// The value of the samples at the given time, linearly interpolated between the neighbouring samples.
+///
+/// Returns `None` if the time is outside of the range of the samples.
+pub fn interpolate_value_at(samples: &FixedSizeBuffer<Sample>, time: i64) -> Option<f64> {
+    let i = samples.partition_point(|s| s.time < time);
+    let next = samples.get(i)?;
+
+    if next.time == time {
+        return Some(next.value);
+    }
+
+    let prev = samples.get(i.checked_sub(1)?)?;
+    let t = (time - prev.time) as f64 / (next.time - prev.time) as f64;
+
+    Some(prev.value + (next.value - prev.value) * t)
+}
+
+pub fn nanos_to_secs(nanos: i64) -> f64 {
+    nanos as f64 / 1e9
+}
+
+pub fn secs_to_nanos(secs: f64) -> i64 {
+    (secs * 1e9).round() as i64
+}
+
+/// The result of parsing.
+///
+/// It is meant to be reused between parses, to avoid allocating new buffers for every read.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ParseResult {
+    pub full_lines: Vec<String>,
+    /// Outer vec is one for each position, inner vec is the "history"
+    pub samples_vec: Vec<Vec<Sample>>,
+    pub n_new_samples: u64,
+    pub markers: Vec<Marker>,
+    pub text_events: Vec<TextEvent>,
+    /// The units that followed the values of each position, e.g. `V` in `3.30V`
+    pub units: Vec<Option<String>>,
+    /// Lines without any value, or garbage between binary packets
+    pub n_errors: u64,
+}
+
+impl ParseResult {
+    /// Clears the result while keeping the allocated buffers
+    pub fn clear(&mut self) {
+        self.full_lines.clear();
+        for samples in self.samples_vec.iter_mut() {
+            samples.clear();
+        }
+        self.n_new_samples = 0;
+        self.markers.clear();
+        self.text_events.clear();
+        self.units.clear();
+        self.n_errors = 0;
+    }
+}
+
+/// The format of the received data, with the settings needed to parse it
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum ParseFormat {
+    Ascii {
+        time_unit: TimeUnit,
+        value_separator: char,
+    },
+    Binary {
+        layout: BinaryLayout,
+        time_unit: TimeUnit,
+    },
+    Ubx {
+        messages: Vec<UbxMessage>,
+    },
+    Nmea,
+    Can {
+        signals: Vec<CanSignal>,
+    },
+}
+
+/// reads full lines into `lines` and counts the number of read bytes
+fn read_full_lines(input_buf: &[u8], lines: &mut Vec<String>) -> std::io::Result<usize> {
+    let mut read_bytes = 0;
+
+    let mut line = String::new();
+    let mut input_cursor = Cursor::new(input_buf);
+    loop {
+        let b = match input_cursor.read_line(&mut line) {
+            // Skip full lines that are not valid UTF-8 (or some other error)
+            Err(_e) => {
+                let pos = input_cursor.position() as usize;
+                if !input_buf[..pos].ends_with(b"\n") {
+                    break;
+                }
+
+                read_bytes = pos;
+                continue;
+            }
+            // if 0, the last line terminates with EOF, so is not a full line
+            Ok(0) => break,
+            Ok(b) => b,
+        };
+
+        // detect unfinished lines
+        if !line.ends_with('\n') {
+            break;
+        }
+
+        lines.push(std::mem::take(&mut line));
+        read_bytes += b;
+    }
+
+    Ok(read_bytes)
+}
+
+/// Parses the received data into samples.
+///
+/// The parse functions take the receive time `now` in nanoseconds since the start of the connection,
+/// which is the time of the samples without a time field.
+#[derive(Debug, Clone, Default)]
+pub struct Parser {
+    /// The received bytes that were not parsed yet
+    pub(crate) buf: Vec<u8>,
+    /// The UBX messages in the order they were first received, their channels follow each other in this order
+    ubx_order: Vec<UbxMessage>,
+    /// The names of the NMEA quantities in the order they were first received, each one is a channel
+    nmea_channels: Vec<&'static str>,
+    /// The indices of the CAN signals in the order they were first received
+    can_order: Vec<usize>,
+    /// Unwraps the times of time fields that roll over, when enabled
+    time_unwrap: Option<TimeUnwrap>,
+    /// Maps the times of time fields onto the host clock, when enabled
+    time_sync: Option<TimeSync>,
+}
+
+impl Parser {
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.ubx_order.clear();
+        self.nmea_channels.clear();
+        self.can_order.clear();
+        if let Some(time_unwrap) = self.time_unwrap.as_mut() {
+            time_unwrap.reset();
+        }
+        if let Some(time_sync) = self.time_sync.as_mut() {
+            time_sync.reset();
+        }
+    }
+
+    /// Unwraps the times of time fields when they roll over or jump backwards, instead of using them as they are
+    pub fn set_time_unwrap(&mut self, enabled: bool) {
+        if enabled != self.time_unwrap.is_some() {
+            self.time_unwrap = enabled.then(TimeUnwrap::default);
+        }
+    }
+
+    /// Maps the times of time fields onto the host clock, instead of using them as they are
+    pub fn set_time_sync(&mut self, enabled: bool) {
+        if enabled != self.time_sync.is_some() {
+            self.time_sync = enabled.then(TimeSync::default);
+        }
+    }
+
+    pub fn time_sync(&self) -> Option<&TimeSync> {
+        self.time_sync.as_ref()
+    }
+
+    /// Lays out the channels again, when the samples were cleared
+    pub fn reset_channels(&mut self) {
+        self.ubx_order.clear();
+        self.nmea_channels.clear();
+        self.can_order.clear();
+    }
+
+    pub fn parse_from_serial_data(
+        &mut self,
+        serial_data: &[u8],
+        time_unit: TimeUnit,
+        value_separator: char,
+        now: i64,
+        result: &mut ParseResult,
+    ) -> anyhow::Result<()> {
+        self.buf.extend(serial_data);
+        result.clear();
+
+        let ParseResult {
+            full_lines,
+            samples_vec,
+            n_new_samples: added_samples,
+            markers,
+            text_events,
+            units,
+            n_errors,
+        } = result;
+
+        let mut time = now;
+
+        // Read out full lines
+        let bytes_read = read_full_lines(&self.buf, full_lines)?;
+
+        // Drain the buffer by the bytes length of the read full lines
+        self.buf.drain(..bytes_read);
+
+        // parse them
+        for line in full_lines.iter() {
+            let line = line.trim();
+
+            // Don't add empy lines
+            if line.is_empty() {
+                continue;
+            }
+
+            // Fast path for the common case of a single unnamed value per line
+            if !line.contains(value_separator) && !line.contains('=') {
+                if let Some((value, value_unit)) = parse_value_with_unit(line) {
+                    set_unit(units, 0, value_unit);
+                    push_sample(samples_vec, 0, time, value, None);
+                    *added_samples += 1;
+                } else {
+                    *n_errors += 1;
+                }
+                continue;
+            }
+
+            // The index of the value, not counting the time
+            let mut i = 0;
+            let mut has_fields = false;
+
+            for value_str in line.split(value_separator) {
+                let (name, value_str) = match value_str.split_once('=') {
+                    Some((name, value_str)) => (Some(name.trim()), value_str),
+                    None => (None, value_str),
+                };
+
+                if name == Some("marker") {
+                    markers.push(Marker {
+                        time,
+                        label: value_str.trim().to_string(),
+                    });
+                    has_fields = true;
+                    continue;
+                }
+
+                let value_str_first = value_str.split('=').next().unwrap_or_default();
+                let parsed = match parse_number_with_unit(value_str_first) {
+                    // Named text values like `mode=A1` are events, even if they contain digits
+                    None if name.is_some() && starts_with_letter(value_str_first) => None,
+                    None => parse_digits(value_str_first),
+                    parsed => parsed,
+                };
+                let Some((value, value_unit)) = parsed else {
+                    // Named fields without a number are events, like state transitions
+                    let text = value_str.trim();
+                    if let (Some(name), false) = (name, text.is_empty()) {
+                        text_events.push(TextEvent {
+                            time,
+                            channel: name.to_string(),
+                            text: text.to_string(),
+                        });
+                        has_fields = true;
+                    }
+                    continue;
+                };
+
+                has_fields = true;
+
+                if let Some(unit) = name.and_then(|name| time_field_unit(name, time_unit)) {
+                    // An invalid time keeps the previous one
+                    if value.is_finite() {
+                        time = unit.convert_to_nanos(value);
+                        if let Some(time_unwrap) = self.time_unwrap.as_mut() {
+                            time = time_unwrap.unwrap(time, unit);
+                        }
+                        if let Some(time_sync) = self.time_sync.as_mut() {
+                            time = time_sync.map(time, now);
+                        }
+                    }
+                    continue;
+                }
+
+                set_unit(units, i, value_unit);
+                push_sample(samples_vec, i, time, value, name);
+                *added_samples += 1;
+                i += 1;
+            }
+
+            if !has_fields {
+                *n_errors += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn parse_binary_from_serial_data(
+        &mut self,
+        serial_data: &[u8],
+        layout: &BinaryLayout,
+        time_unit: TimeUnit,
+        now: i64,
+        result: &mut ParseResult,
+    ) -> anyhow::Result<()> {
+        self.buf.extend(serial_data);
+        result.clear();
+
+        let magic = layout.magic_bytes()?;
+        let payload_size = layout.payload_size();
+
+        if magic.is_empty() && payload_size == 0 {
+            self.buf.clear();
+            return Err(anyhow::anyhow!("binary layout is empty"));
+        }
+
+        let mut pos = 0;
+
+        loop {
+            // Resynchronize on the magic bytes, skipping garbage in between
+            let Some(start) = find_subslice(&self.buf[pos..], &magic).map(|i| pos + i) else {
+                // Keep a possibly incomplete magic sequence at the end
+                pos = pos.max(self.buf.len().saturating_sub(magic.len().saturating_sub(1)));
+                break;
+            };
+
+            if start > pos {
+                result.n_errors += 1;
+            }
+
+            let payload_start = start + magic.len();
+            let payload_end = payload_start + payload_size;
+            if payload_end > self.buf.len() {
+                pos = start;
+                break;
+            }
+
+            let mut time = now;
+            let mut i = 0;
+
+            for (field, value) in layout.decode_payload(&self.buf[payload_start..payload_end]) {
+                if let Some(unit) = time_field_unit(&field.name, time_unit) {
+                    time = unit.convert_to_nanos(value);
+                    if let Some(time_unwrap) = self.time_unwrap.as_mut() {
+                        time = time_unwrap.unwrap(time, unit);
+                    }
+                    if let Some(time_sync) = self.time_sync.as_mut() {
+                        time = time_sync.map(time, now);
+                    }
+                    continue;
+                }
+
+                push_sample(&mut result.samples_vec, i, time, value, Some(&field.name));
+                result.n_new_samples += 1;
+                i += 1;
+            }
+
+            pos = payload_end;
+        }
+
+        self.buf.drain(..pos);
+
+        Ok(())
+    }
+
+    /// Decodes the selected UBX messages, the fields of each message become channels
+    pub fn parse_ubx_from_serial_data(
+        &mut self,
+        serial_data: &[u8],
+        messages: &[UbxMessage],
+        now: i64,
+        result: &mut ParseResult,
+    ) -> anyhow::Result<()> {
+        self.buf.extend(serial_data);
+        result.clear();
+
+        let mut pos = 0;
+
+        loop {
+            // Resynchronize on the sync chars, skipping garbage in between
+            let Some(start) = find_subslice(&self.buf[pos..], &ubx::SYNC).map(|i| pos + i) else {
+                // Keep a possibly incomplete sync char at the end
+                pos = pos.max(self.buf.len().saturating_sub(1));
+                break;
+            };
+
+            if start > pos {
+                result.n_errors += 1;
+            }
+
+            let Some(header) = self.buf.get(start..start + ubx::HEADER_SIZE) else {
+                pos = start;
+                break;
+            };
+            let (class, id) = (header[2], header[3]);
+            let payload_size = u16::from_le_bytes([header[4], header[5]]) as usize;
+
+            // Corrupted frames are skipped, and counted as garbage when resynchronizing
+            if payload_size > ubx::MAX_PAYLOAD_SIZE {
+                pos = start + 1;
+                continue;
+            }
+
+            let payload_start = start + ubx::HEADER_SIZE;
+            let payload_end = payload_start + payload_size;
+            let Some(ck) = self.buf.get(payload_end..payload_end + 2) else {
+                pos = start;
+                break;
+            };
+
+            if ck != ubx::checksum(&self.buf[start + 2..payload_end]) {
+                pos = start + 1;
+                continue;
+            }
+            pos = payload_end + 2;
+
+            let Some(message) =
+                UbxMessage::from_class_id(class, id).filter(|m| messages.contains(m))
+            else {
+                continue;
+            };
+            let Some(values) = message.decode(&self.buf[payload_start..payload_end]) else {
+                result.n_errors += 1;
+                continue;
+            };
+
+            if !self.ubx_order.contains(&message) {
+                self.ubx_order.push(message);
+            }
+            let offset = self
+                .ubx_order
+                .iter()
+                .take_while(|&&m| m != message)
+                .map(|m| m.fields().len())
+                .sum::<usize>();
+
+            for (i, (name, value)) in message.fields().iter().zip(values).enumerate() {
+                push_sample(&mut result.samples_vec, offset + i, now, value, Some(name));
+                result.n_new_samples += 1;
+            }
+        }
+
+        self.buf.drain(..pos);
+
+        Ok(())
+    }
+
+    /// Decodes the GGA, RMC and VTG sentences of NMEA 0183, other sentences are ignored.
+    ///
+    /// Sentences with an invalid checksum are counted as errors.
+    pub fn parse_nmea_from_serial_data(
+        &mut self,
+        serial_data: &[u8],
+        now: i64,
+        result: &mut ParseResult,
+    ) -> anyhow::Result<()> {
+        self.buf.extend(serial_data);
+        result.clear();
+
+        let bytes_read = read_full_lines(&self.buf, &mut result.full_lines)?;
+        self.buf.drain(..bytes_read);
+
+        for line in result.full_lines.iter() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let Some((address, fields)) = nmea::split_sentence(line) else {
+                result.n_errors += 1;
+                continue;
+            };
+            let Some(sentence) = NmeaSentence::from_address(address) else {
+                continue;
+            };
+            let Some(values) = sentence.decode(&fields) else {
+                result.n_errors += 1;
+                continue;
+            };
+
+            // A quantity has one channel, shared by all sentences that contain it
+            for (name, value) in sentence.fields().iter().zip(values) {
+                let i = match self.nmea_channels.iter().position(|n| n == name) {
+                    Some(i) => i,
+                    None => {
+                        self.nmea_channels.push(name);
+                        self.nmea_channels.len() - 1
+                    }
+                };
+                push_sample(&mut result.samples_vec, i, now, value, Some(name));
+                result.n_new_samples += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the signals from CAN frames in the layout of SocketCAN, each signal becomes a channel.
+    ///
+    /// Frames that are too short for a signal with their id are counted as errors.
+    pub fn parse_can_from_serial_data(
+        &mut self,
+        serial_data: &[u8],
+        signals: &[CanSignal],
+        now: i64,
+        result: &mut ParseResult,
+    ) -> anyhow::Result<()> {
+        self.buf.extend(serial_data);
+        result.clear();
+
+        let frames_len = self.buf.len() / can::FRAME_SIZE * can::FRAME_SIZE;
+
+        for frame in self.buf[..frames_len].chunks_exact(can::FRAME_SIZE) {
+            let Some((id, data)) = can::decode_frame(frame) else {
+                continue;
+            };
+
+            for (signal_index, signal) in signals.iter().enumerate() {
+                if signal.id != id {
+                    continue;
+                }
+                let Some(value) = signal.decode(data) else {
+                    result.n_errors += 1;
+                    continue;
+                };
+
+                if !self.can_order.contains(&signal_index) {
+                    self.can_order.push(signal_index);
+                }
+                let i = self
+                    .can_order
+                    .iter()
+                    .position(|&s| s == signal_index)
+                    .unwrap_or_default();
+
+                push_sample(&mut result.samples_vec, i, now, value, Some(&signal.name));
+                result.n_new_samples += 1;
+            }
+        }
+
+        self.buf.drain(..frames_len);
+
+        Ok(())
+    }
+
+    /// Parses the data in the given format
+    pub fn parse(
+        &mut self,
+        serial_data: &[u8],
+        format: &ParseFormat,
+        now: i64,
+        result: &mut ParseResult,
+    ) -> anyhow::Result<()> {
+        match format {
+            ParseFormat::Ascii {
+                time_unit,
+                value_separator,
+            } => {
+                self.parse_from_serial_data(serial_data, *time_unit, *value_separator, now, result)
+            }
+            ParseFormat::Binary { layout, time_unit } => {
+                self.parse_binary_from_serial_data(serial_data, layout, *time_unit, now, result)
+            }
+            ParseFormat::Ubx { messages } => {
+                self.parse_ubx_from_serial_data(serial_data, messages, now, result)
+            }
+            ParseFormat::Nmea => self.parse_nmea_from_serial_data(serial_data, now, result),
+            ParseFormat::Can { signals } => {
+                self.parse_can_from_serial_data(serial_data, signals, now, result)
+            }
+        }
+    }
+}
+
+/// Returns the time unit if the field holds the time.
+///
+/// `time` and `t` are in the default unit, a unit suffix like in `t_ms` or `time_us` overrides it.
+/// This allows sources with different time units to be mixed.
+pub fn time_field_unit(name: &str, default_unit: TimeUnit) -> Option<TimeUnit> {
+    let (base, unit) = match name.rsplit_once('_') {
+        Some((base, suffix)) => (base, TimeUnit::from_suffix(suffix)?),
+        None => (name, default_unit),
+    };
+
+    matches!(base, "time" | "t").then_some(unit)
+}
+
+/// Parses a value, stripping away everything that is not part of a number.
+///
+/// `nan`, `inf` and the `ovf` of the Arduino print functions are kept as NaN and infinite values,
+/// as well as numbers that overflow.
+pub fn parse_value(value_str: &str) -> Option<f64> {
+    parse_value_with_unit(value_str).map(|(value, _)| value)
+}
+
+/// Parses a value like `parse_value`, and returns the unit when the number is followed by one,
+/// e.g. `C` for `23.5C` or `mV` for `3.3e2 mV`.
+pub fn parse_value_with_unit(value_str: &str) -> Option<(f64, Option<&str>)> {
+    parse_number_with_unit(value_str).or_else(|| parse_digits(value_str))
+}
+
+/// Parses a value that is a number, optionally followed by a unit, without picking the digits out of other text
+fn parse_number_with_unit(value_str: &str) -> Option<(f64, Option<&str>)> {
+    let value_str = value_str.trim();
+
+    if value_str.eq_ignore_ascii_case("ovf") {
+        return Some((f64::INFINITY, None));
+    }
+    if let Ok(value) = value_str.parse::<f64>() {
+        return Some((value, None));
+    }
+
+    let (number, unit) = value_str.split_at(number_prefix_len(value_str));
+    let unit = unit.trim();
+    if !number.is_empty()
+        && !unit.is_empty()
+        && unit.chars().all(|c| c.is_alphabetic() || "°%/".contains(c))
+    {
+        if let Ok(value) = number.parse::<f64>() {
+            return Some((value, Some(unit)));
+        }
+    }
+
+    None
+}
+
+/// Parses the digits, signs and decimal points of the value, ignoring everything else
+fn parse_digits(value_str: &str) -> Option<(f64, Option<&str>)> {
+    value_str
+        .chars()
+        .filter(|&c| c.is_ascii_digit() || c == '-' || c == '.')
+        .collect::<String>()
+        .parse()
+        .ok()
+        .map(|value| (value, None))
+}
+
+fn starts_with_letter(text: &str) -> bool {
+    text.trim_start()
+        .chars()
+        .next()
+        .map_or(false, char::is_alphabetic)
+}
+
+/// The length of the number at the start of the text, with sign, decimals and exponent
+fn number_prefix_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut len = usize::from(matches!(bytes.first(), Some(b'-' | b'+')));
+
+    let digits_start = len;
+    while bytes
+        .get(len)
+        .map_or(false, |b| b.is_ascii_digit() || *b == b'.')
+    {
+        len += 1;
+    }
+    if len == digits_start {
+        return 0;
+    }
+
+    // Only an exponent with digits, so that a unit starting with `e` is kept
+    if matches!(bytes.get(len), Some(b'e' | b'E')) {
+        let mut exponent_len = len + 1;
+        if matches!(bytes.get(exponent_len), Some(b'-' | b'+')) {
+            exponent_len += 1;
+        }
+        if bytes.get(exponent_len).map_or(false, u8::is_ascii_digit) {
+            while bytes.get(exponent_len).map_or(false, u8::is_ascii_digit) {
+                exponent_len += 1;
+            }
+            len = exponent_len;
+        }
+    }
+
+    len
+}
+
+/// Remembers the unit of the value at index `i`, the first one of a parse is kept
+fn set_unit(units: &mut Vec<Option<String>>, i: usize, unit: Option<&str>) {
+    let Some(unit) = unit else {
+        return;
+    };
+    if units.len() <= i {
+        units.resize(i + 1, None);
+    }
+    if units[i].is_none() {
+        units[i] = Some(unit.to_string());
+    }
+}
+
+/// Pushes a sample for the value at index `i`.
+/// The name is only stored with the first sample of each index, to avoid allocating for every sample.
+fn push_sample(
+    samples_vec: &mut Vec<Vec<Sample>>,
+    i: usize,
+    time: i64,
+    value: f64,
+    name: Option<&str>,
+) {
+    if let Some(samples) = samples_vec.get_mut(i) {
+        let name = if samples.is_empty() {
+            name.map(|s| s.to_string())
+        } else {
+            None
+        };
+
+        samples.push(Sample { time, value, name });
+    } else {
+        samples_vec.push(vec![Sample {
+            time,
+            value,
+            name: name.map(|s| s.to_string()),
+        }]);
+    }
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum TimeUnit {
+    Us,
+    Ms,
+    S,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for TimeUnit {
+    fn default() -> Self {
+        Self::S
+    }
+}
+
+impl std::fmt::Display for TimeUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeUnit::Us => write!(f, "us"),
+            TimeUnit::Ms => write!(f, "ms"),
+            TimeUnit::S => write!(f, "s"),
+        }
+    }
+}
+
+impl TimeUnit {
+    /// Parses a unit suffix like `ms`
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "us" | "µs" => Some(TimeUnit::Us),
+            "ms" => Some(TimeUnit::Ms),
+            "s" => Some(TimeUnit::S),
+            _ => None,
+        }
+    }
+
+    #[allow(unused)]
+    pub fn convert_from_secs(self, secs: f64) -> f64 {
+        match self {
+            TimeUnit::Us => secs * 1_000_000.0,
+            TimeUnit::Ms => secs * 1000.0,
+            TimeUnit::S => secs,
+        }
+    }
+
+    pub fn convert_to_nanos(self, val: f64) -> i64 {
+        match self {
+            TimeUnit::Us => (val * 1000.0).round() as i64,
+            TimeUnit::Ms => (val * 1_000_000.0).round() as i64,
+            TimeUnit::S => secs_to_nanos(val),
+        }
+    }
+}
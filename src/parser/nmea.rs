@@ -0,0 +1,117 @@
+/// The NMEA 0183 sentences of GNSS modules that are decoded into channels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NmeaSentence {
+    /// Fix data
+    Gga,
+    /// Recommended minimum data
+    Rmc,
+    /// Course and speed over ground
+    Vtg,
+}
+
+impl std::fmt::Display for NmeaSentence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NmeaSentence::Gga => write!(f, "GGA"),
+            NmeaSentence::Rmc => write!(f, "RMC"),
+            NmeaSentence::Vtg => write!(f, "VTG"),
+        }
+    }
+}
+
+impl NmeaSentence {
+    pub const ALL: [NmeaSentence; 3] = [NmeaSentence::Gga, NmeaSentence::Rmc, NmeaSentence::Vtg];
+
+    /// The sentence of the address field, e.g. `GPGGA` or `GNRMC`, with any talker id
+    pub fn from_address(address: &str) -> Option<Self> {
+        let formatter = address.get(2..)?;
+        Self::ALL.into_iter().find(|s| s.to_string() == formatter)
+    }
+
+    /// The names of the quantities in the order of the decoded values, each one has a channel shared by all sentences
+    pub fn fields(self) -> &'static [&'static str] {
+        match self {
+            NmeaSentence::Gga => &[
+                "lat",
+                "lon",
+                "fix_quality",
+                "satellites",
+                "hdop",
+                "altitude",
+            ],
+            NmeaSentence::Rmc => &["lat", "lon", "speed", "course", "valid"],
+            NmeaSentence::Vtg => &["course", "speed"],
+        }
+    }
+
+    /// Decodes the comma separated fields after the address into the values of the channels,
+    /// scaled to degrees, meters and m/s.
+    ///
+    /// Empty fields, e.g. the position without a fix, are NaN. Returns None when fields are missing or malformed.
+    pub fn decode(self, fields: &[&str]) -> Option<Vec<f64>> {
+        let field = |i: usize| -> Option<f64> {
+            let field = *fields.get(i)?;
+            if field.is_empty() {
+                Some(f64::NAN)
+            } else {
+                field.parse().ok()
+            }
+        };
+        let coordinate = |i: usize| -> Option<f64> {
+            let degrees = to_degrees(field(i)?);
+            match *fields.get(i + 1)? {
+                "S" | "W" => Some(-degrees),
+                _ => Some(degrees),
+            }
+        };
+
+        let values = match self {
+            NmeaSentence::Gga => vec![
+                coordinate(1)?,
+                coordinate(3)?,
+                field(5)?,
+                field(6)?,
+                field(7)?,
+                field(8)?,
+            ],
+            NmeaSentence::Rmc => vec![
+                coordinate(2)?,
+                coordinate(4)?,
+                field(6)? * KNOTS_TO_METERS_PER_SEC,
+                field(7)?,
+                f64::from(*fields.get(1)? == "A"),
+            ],
+            NmeaSentence::Vtg => vec![field(0)?, field(6)? / 3.6],
+        };
+
+        Some(values)
+    }
+}
+
+const KNOTS_TO_METERS_PER_SEC: f64 = 1852.0 / 3600.0;
+
+/// Converts the `ddmm.mmmm` format of the coordinates to degrees
+fn to_degrees(value: f64) -> f64 {
+    let degrees = (value / 100.0).trunc();
+    degrees + (value - degrees * 100.0) / 60.0
+}
+
+/// The XOR of the bytes between `$` and `*`
+pub fn checksum(body: &str) -> u8 {
+    body.bytes().fold(0, |ck, b| ck ^ b)
+}
+
+/// Splits a sentence like `$GPVTG,...*2F` into the address and the fields, validating the checksum.
+///
+/// Returns None for lines that are not a sentence or that have an invalid checksum.
+pub fn split_sentence(line: &str) -> Option<(&str, Vec<&str>)> {
+    let (body, ck) = line.strip_prefix('$')?.split_once('*')?;
+    if u8::from_str_radix(ck.get(..2)?, 16).ok()? != checksum(body) {
+        return None;
+    }
+
+    let mut fields = body.split(',');
+    let address = fields.next()?;
+
+    Some((address, fields.collect()))
+}
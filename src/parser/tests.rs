@@ -0,0 +1,597 @@
+//! Golden tests for the parser.
+//!
+//! The parse results are rendered into a compact text form, one line per channel,
+//! and compared against the expected output.
+
+use futures::executor::block_on;
+use instant::{Duration, Instant};
+
+use super::binarylayout::{BinaryField, BinaryFieldType, BinaryLayout, Endianness};
+use super::can::{self, CanSignal};
+use super::nmea;
+use super::ubx::{self, UbxMessage};
+use super::{nanos_to_secs, ParseResult, Parser, TimeUnit};
+use crate::serialconnection::mock::MockSerialConnection;
+use crate::serialconnection::{DataBits, FlowControl, Parity, SerialConnection, StopBits};
+
+/// Renders the channels as `<index> <name>: <values>`
+fn render_values(result: &ParseResult) -> String {
+    render(result, |s| s.value.to_string())
+}
+
+/// Renders the channels as `<index> <name>: <time>=<value>`, with the time in seconds
+fn render_times(result: &ParseResult) -> String {
+    render(result, |s| format!("{}={}", nanos_to_secs(s.time), s.value))
+}
+
+fn render(result: &ParseResult, sample_fmt: impl Fn(&super::Sample) -> String) -> String {
+    result
+        .samples_vec
+        .iter()
+        .enumerate()
+        .filter(|(_, samples)| !samples.is_empty())
+        .map(|(i, samples)| {
+            let name = samples
+                .first()
+                .and_then(|s| s.name.as_deref())
+                .unwrap_or("-");
+            let values = samples.iter().map(&sample_fmt).collect::<Vec<_>>();
+
+            format!("{i} {name}: {}", values.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_ascii(chunks: &[&str], value_separator: char) -> ParseResult {
+    let mut parser = Parser::default();
+    let mut result = ParseResult::default();
+    let mut all = ParseResult::default();
+
+    for chunk in chunks {
+        parser
+            .parse_from_serial_data(
+                chunk.as_bytes(),
+                TimeUnit::S,
+                value_separator,
+                0,
+                &mut result,
+            )
+            .unwrap();
+
+        append(&mut all, &mut result);
+    }
+
+    all
+}
+
+/// Appends the result of one parse to the accumulated results
+fn append(all: &mut ParseResult, result: &mut ParseResult) {
+    all.full_lines.append(&mut result.full_lines);
+    all.n_new_samples += result.n_new_samples;
+    all.markers.append(&mut result.markers);
+    all.text_events.append(&mut result.text_events);
+    if all.units.len() < result.units.len() {
+        all.units.resize(result.units.len(), None);
+    }
+    for (all_unit, unit) in all.units.iter_mut().zip(result.units.drain(..)) {
+        if all_unit.is_none() {
+            *all_unit = unit;
+        }
+    }
+    all.n_errors += result.n_errors;
+
+    for (i, samples) in result.samples_vec.iter_mut().enumerate() {
+        if all.samples_vec.len() <= i {
+            all.samples_vec.push(vec![]);
+        }
+        all.samples_vec[i].append(samples);
+    }
+}
+
+#[test]
+fn single_values() {
+    let result = parse_ascii(&["1\n2.5\n-3\n"], ',');
+
+    assert_eq!(render_values(&result), "0 -: 1 2.5 -3");
+    assert_eq!(result.n_new_samples, 3);
+}
+
+#[test]
+fn separators() {
+    for (input, sep) in [
+        ("1,2,3\n", ','),
+        ("1;2;3\n", ';'),
+        ("1 2 3\n", ' '),
+        ("1\t2\t3\n", '\t'),
+    ] {
+        let result = parse_ascii(&[input], sep);
+
+        assert_eq!(
+            render_values(&result),
+            "0 -: 1\n1 -: 2\n2 -: 3",
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn separator_with_whitespace() {
+    let result = parse_ascii(&["1, 2 ,  3\r\n"], ',');
+
+    assert_eq!(render_values(&result), "0 -: 1\n1 -: 2\n2 -: 3");
+}
+
+#[test]
+fn names() {
+    let result = parse_ascii(&["a=1, b=2\n", "a=3, b=4\n"], ',');
+
+    assert_eq!(render_values(&result), "0 a: 1 3\n1 b: 2 4");
+}
+
+#[test]
+fn mixed_names() {
+    let result = parse_ascii(&["temp=21.5, 7\n"], ',');
+
+    assert_eq!(render_values(&result), "0 temp: 21.5\n1 -: 7");
+}
+
+#[test]
+fn time_field() {
+    let result = parse_ascii(&["time=0.5, a=1\na=2, t=1.5, b=3\n"], ',');
+
+    // The time applies to the values following it, until the next time field
+    assert_eq!(render_times(&result), "0 a: 0.5=1 0.5=2\n1 b: 1.5=3");
+}
+
+#[test]
+fn time_field_does_not_shift_channels() {
+    let with_time = parse_ascii(&["t=1, 10, 20\n"], ',');
+    let without_time = parse_ascii(&["10, 20\n"], ',');
+
+    assert_eq!(render_values(&with_time), render_values(&without_time));
+    assert_eq!(render_times(&with_time), "0 -: 1=10\n1 -: 1=20");
+}
+
+#[test]
+fn time_units() {
+    for (unit, expected) in [
+        (TimeUnit::S, "0 -: 2=1"),
+        (TimeUnit::Ms, "0 -: 0.002=1"),
+        (TimeUnit::Us, "0 -: 0.000002=1"),
+    ] {
+        let mut parser = Parser::default();
+        let mut result = ParseResult::default();
+        parser
+            .parse_from_serial_data(b"time=2, 1\n", unit, ',', 0, &mut result)
+            .unwrap();
+
+        assert_eq!(render_times(&result), expected, "{unit}");
+    }
+}
+
+#[test]
+fn time_unit_suffix_overrides_default() {
+    let result = parse_ascii(
+        &["t_ms=2000, 1\ntime_us=3000000, 2\nt=4, 3\nt_min=5\n"],
+        ',',
+    );
+
+    // An unknown suffix is a regular named value
+    assert_eq!(render_times(&result), "0 -: 2=1 3=2 4=3 4=5");
+}
+
+#[test]
+fn time_rollover_is_unwrapped() {
+    let mut parser = Parser::default();
+    parser.set_time_unwrap(true);
+    let mut result = ParseResult::default();
+
+    // A u32 millisecond tick rolls over, then the device restarts
+    parser
+        .parse_from_serial_data(
+            b"t_ms=4294967290, 1\nt_ms=5, 2\nt_ms=100000, 3\nt_ms=1000, 4\n",
+            TimeUnit::S,
+            ',',
+            0,
+            &mut result,
+        )
+        .unwrap();
+
+    assert_eq!(
+        render_times(&result),
+        "0 -: 4294967.29=1 4294967.301=2 4295067.296=3 4295067.296=4"
+    );
+}
+
+#[test]
+fn partial_lines() {
+    let mut parser = Parser::default();
+    let mut result = ParseResult::default();
+
+    parser
+        .parse_from_serial_data(b"1,2", TimeUnit::S, ',', 0, &mut result)
+        .unwrap();
+    assert_eq!(render_values(&result), "");
+    assert!(result.full_lines.is_empty());
+
+    parser
+        .parse_from_serial_data(b"3\n4", TimeUnit::S, ',', 0, &mut result)
+        .unwrap();
+    assert_eq!(render_values(&result), "0 -: 1\n1 -: 23");
+    assert_eq!(result.full_lines, vec!["1,23\n".to_string()]);
+
+    parser
+        .parse_from_serial_data(b"\n", TimeUnit::S, ',', 0, &mut result)
+        .unwrap();
+    assert_eq!(render_values(&result), "0 -: 4");
+}
+
+#[test]
+fn empty_lines() {
+    let result = parse_ascii(&["\n\r\n  \n1\n"], ',');
+
+    assert_eq!(render_values(&result), "0 -: 1");
+    assert_eq!(result.full_lines.len(), 4);
+}
+
+#[test]
+fn malformed_values() {
+    let result = parse_ascii(&["1,,x,3\n", "abc\n", "nan,inf\n", "12abc, -\n"], ',');
+
+    assert_eq!(render_values(&result), "0 -: 1 NaN 12\n1 -: 3 inf");
+}
+
+#[test]
+fn invalid_values() {
+    let result = parse_ascii(&["-inf,ovf,1e400,NaN\n"], ',');
+    assert_eq!(
+        render_values(&result),
+        "0 -: -inf\n1 -: inf\n2 -: inf\n3 -: NaN"
+    );
+
+    // An invalid time keeps the previous one
+    let result = parse_ascii(&["t=2,a=1\nt=nan,a=2\n"], ',');
+    assert_eq!(render_times(&result), "0 a: 2=1 2=2");
+}
+
+#[test]
+fn markers() {
+    let result = parse_ascii(
+        &["t=1, a=1\nt=2, marker=button pressed , a=2\nmarker=x\n"],
+        ',',
+    );
+
+    assert_eq!(render_times(&result), "0 a: 1=1 2=2");
+    let markers = result
+        .markers
+        .iter()
+        .map(|m| format!("{}={}", nanos_to_secs(m.time), m.label))
+        .collect::<Vec<_>>();
+    assert_eq!(markers, ["2=button pressed", "2=x"]);
+}
+
+#[test]
+fn text_events() {
+    let result = parse_ascii(&["t=1, state=IDLE, a=1\nt=2, state= RUN \nmode=\n"], ',');
+
+    assert_eq!(render_times(&result), "0 a: 1=1");
+    let events = result
+        .text_events
+        .iter()
+        .map(|e| format!("{}={}:{}", nanos_to_secs(e.time), e.channel, e.text))
+        .collect::<Vec<_>>();
+    assert_eq!(events, ["1=state:IDLE", "2=state:RUN"]);
+    assert_eq!(result.n_errors, 1);
+}
+
+#[test]
+fn text_events_with_digits() {
+    let result = parse_ascii(&["state=RUN2, mode=A1, a=12V\nb=x5\n"], ',');
+
+    assert_eq!(render_values(&result), "0 a: 12");
+    let events = result
+        .text_events
+        .iter()
+        .map(|e| format!("{}:{}", e.channel, e.text))
+        .collect::<Vec<_>>();
+    assert_eq!(events, ["state:RUN2", "mode:A1", "b:x5"]);
+    assert_eq!(result.n_errors, 0);
+}
+
+#[test]
+fn unit_suffixes() {
+    let result = parse_ascii(
+        &["temp=23.5C, v=3.30 V, f=1.2e3Hz, n=5\ntemp=24°C, v=-1e-3V, f=7, n=6\n"],
+        ',',
+    );
+
+    assert_eq!(
+        render_values(&result),
+        "0 temp: 23.5 24\n1 v: 3.3 -0.001\n2 f: 1200 7\n3 n: 5 6"
+    );
+    assert_eq!(
+        result.units,
+        [
+            Some(String::from("C")),
+            Some(String::from("V")),
+            Some(String::from("Hz"))
+        ]
+    );
+    assert_eq!(result.n_errors, 0);
+}
+
+#[test]
+fn invalid_utf8() {
+    let mut parser = Parser::default();
+    let mut result = ParseResult::default();
+
+    parser
+        .parse_from_serial_data(b"\xff\xfe\n1\n", TimeUnit::S, ',', 0, &mut result)
+        .unwrap();
+    assert_eq!(render_values(&result), "0 -: 1");
+
+    // The invalid line must be consumed, so that nothing is parsed twice
+    parser
+        .parse_from_serial_data(b"2\n", TimeUnit::S, ',', 0, &mut result)
+        .unwrap();
+    assert_eq!(render_values(&result), "0 -: 2");
+}
+
+#[test]
+fn result_reuse() {
+    let mut parser = Parser::default();
+    let mut result = ParseResult::default();
+
+    parser
+        .parse_from_serial_data(b"a=1, b=2\n", TimeUnit::S, ',', 0, &mut result)
+        .unwrap();
+    parser
+        .parse_from_serial_data(b"5\n", TimeUnit::S, ',', 0, &mut result)
+        .unwrap();
+
+    assert_eq!(render_values(&result), "0 -: 5");
+    assert_eq!(result.n_new_samples, 1);
+}
+
+#[test]
+fn binary_packets() {
+    let layout = BinaryLayout {
+        magic: "AA55".to_string(),
+        endianness: Endianness::Little,
+        fields: vec![
+            BinaryField {
+                name: "t".to_string(),
+                ty: BinaryFieldType::U16,
+            },
+            BinaryField {
+                name: "x".to_string(),
+                ty: BinaryFieldType::I16,
+            },
+        ],
+    };
+
+    let mut data = vec![0x00, 0xAA]; // garbage
+    data.extend([0xAA, 0x55, 0x02, 0x00, 0xFF, 0xFF]);
+    data.extend([0xAA, 0x55, 0x03, 0x00]); // incomplete
+
+    let mut parser = Parser::default();
+    let mut result = ParseResult::default();
+    parser
+        .parse_binary_from_serial_data(&data, &layout, TimeUnit::S, 0, &mut result)
+        .unwrap();
+    assert_eq!(render_times(&result), "0 x: 2=-1");
+
+    parser
+        .parse_binary_from_serial_data(&[0x05, 0x00], &layout, TimeUnit::S, 0, &mut result)
+        .unwrap();
+    assert_eq!(render_times(&result), "0 x: 3=5");
+}
+
+#[test]
+fn nmea_sentences() {
+    let sentence = |body: &str| format!("${body}*{:02X}\r\n", nmea::checksum(body));
+
+    let mut data = sentence("GPGGA,123519,4807.500,N,01131.000,W,1,08,0.9,545.4,M,46.9,M,,");
+    data += &sentence("GPGSV,3,1,11,03,03,111,00"); // not decoded
+    data += "$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*00\r\n"; // invalid checksum
+    data += &sentence("GNVTG,054.7,T,034.4,M,005.5,N,036.0,K,A");
+    data += &sentence("GPRMC,123519,V,,,,,,,230394,,");
+
+    let mut parser = Parser::default();
+    let mut result = ParseResult::default();
+    parser
+        .parse_nmea_from_serial_data(data.as_bytes(), 0, &mut result)
+        .unwrap();
+
+    assert_eq!(
+        render_values(&result),
+        "0 lat: 48.125 NaN\n1 lon: -11.516666666666667 NaN\n2 fix_quality: 1\n3 satellites: 8\n\
+        4 hdop: 0.9\n5 altitude: 545.4\n6 course: 54.7 NaN\n7 speed: 10 NaN\n8 valid: 0"
+    );
+    assert_eq!(result.n_errors, 1);
+}
+
+#[test]
+fn can_frames() {
+    let signals = [
+        CanSignal {
+            name: String::from("rpm"),
+            id: 0x18FF_0001,
+            offset: 2,
+            ty: BinaryFieldType::U16,
+            endianness: Endianness::Big,
+            scale: 0.5,
+            value_offset: 0.0,
+        },
+        CanSignal {
+            name: String::from("temp"),
+            id: 0x120,
+            offset: 0,
+            ty: BinaryFieldType::I8,
+            endianness: Endianness::Little,
+            scale: 1.0,
+            value_offset: -40.0,
+        },
+    ];
+
+    let mut data = vec![];
+    data.extend(can::encode_frame(0x120, &[100]));
+    data.extend(can::encode_frame(0x7FF, &[1, 2, 3])); // not mapped
+    data.extend(can::encode_frame(0x18FF_0001, &[0, 0, 0x0F, 0xA0]));
+    data.extend(can::encode_frame(0x18FF_0001, &[0, 0, 1])); // too short
+    let partial = can::encode_frame(0x120, &[0]);
+    data.extend(&partial[..10]);
+
+    let mut parser = Parser::default();
+    let mut result = ParseResult::default();
+    parser
+        .parse_can_from_serial_data(&data, &signals, 0, &mut result)
+        .unwrap();
+
+    assert_eq!(render_values(&result), "0 temp: 60\n1 rpm: 2000");
+    assert_eq!(result.n_errors, 1);
+
+    parser
+        .parse_can_from_serial_data(&partial[10..], &signals, 0, &mut result)
+        .unwrap();
+    assert_eq!(render_values(&result), "0 temp: -40");
+}
+
+#[test]
+fn ubx_frames() {
+    let mut posllh = vec![0; 28];
+    posllh[4..8].copy_from_slice(&134_000_000_i32.to_le_bytes()); // lon
+    posllh[8..12].copy_from_slice(&(-485_000_000_i32).to_le_bytes()); // lat
+    let mut status = vec![0; 16];
+    status[4] = 3;
+    status[8..12].copy_from_slice(&2500_u32.to_le_bytes());
+
+    let mut data = vec![0xB5, 0x00]; // garbage
+    data.extend(ubx::frame(0x01, 0x03, &status));
+    let mut corrupted = ubx::frame(0x01, 0x02, &posllh);
+    corrupted[10] ^= 0xFF;
+    data.extend(corrupted);
+    data.extend(ubx::frame(0x01, 0x35, &[0; 8])); // not selected
+    let posllh_frame = ubx::frame(0x01, 0x02, &posllh);
+    data.extend(&posllh_frame[..20]); // incomplete
+
+    let messages = [UbxMessage::NavPosllh, UbxMessage::NavStatus];
+    let mut parser = Parser::default();
+    let mut result = ParseResult::default();
+    parser
+        .parse_ubx_from_serial_data(&data, &messages, 0, &mut result)
+        .unwrap();
+    assert_eq!(render_values(&result), "0 gps_fix: 3\n1 ttff: 2.5");
+    assert_eq!(result.n_errors, 2);
+
+    // The channels of the next message follow the ones received first
+    parser
+        .parse_ubx_from_serial_data(&posllh_frame[20..], &messages, 0, &mut result)
+        .unwrap();
+    assert_eq!(
+        render_values(&result),
+        "2 lat: -48.5\n3 lon: 13.4\n4 height: 0\n5 h_msl: 0\n6 h_acc: 0\n7 v_acc: 0"
+    );
+}
+
+#[test]
+fn mock_connection_drives_parser() {
+    let mut connection = MockSerialConnection::new()
+        .with_chunk(Duration::ZERO, "t=0, a=1\nt=1, ")
+        .with_chunk(Duration::ZERO, "a=2\n")
+        .with_chunk(Duration::from_secs(3600), "t=2, a=3\n");
+
+    block_on(async {
+        connection
+            .try_connect(
+                0,
+                115200,
+                Duration::from_millis(10),
+                DataBits::default(),
+                FlowControl::default(),
+                Parity::default(),
+                StopBits::default(),
+            )
+            .await
+            .unwrap();
+
+        let mut parser = Parser::default();
+        let mut result = ParseResult::default();
+        let mut all = ParseResult::default();
+
+        // Read in chunks of the read buffer size, until the delayed chunk is pending
+        while let Ok(data) = connection.read(4).await {
+            if data.is_empty() {
+                break;
+            }
+            parser
+                .parse_from_serial_data(&data, TimeUnit::S, ',', 0, &mut result)
+                .unwrap();
+            append(&mut all, &mut result);
+        }
+
+        assert_eq!(render_times(&all), "0 a: 0=1 1=2");
+        assert!(!connection.is_finished());
+    });
+}
+
+#[test]
+fn parse_errors_are_counted() {
+    let result = parse_ascii(&["1,2\nnope\n\n3\nmarker=a\n", "--\n"], ',');
+
+    // Empty lines are not errors, markers are valid lines
+    assert_eq!(result.n_errors, 2);
+}
+
+fn single_channel_lines(n_lines: usize) -> String {
+    (0..n_lines)
+        .map(|i| format!("{}\n", i as f64 * 0.25))
+        .collect()
+}
+
+/// Parses the data in chunks of the size of a serial read.
+/// Returns the number of samples and the last value of the first channel.
+fn parse_chunked(data: &str) -> (u64, Option<f64>) {
+    let mut parser = Parser::default();
+    let mut result = ParseResult::default();
+    let mut n_samples = 0;
+    let mut last_value = None;
+
+    for chunk in data.as_bytes().chunks(4096) {
+        parser
+            .parse_from_serial_data(chunk, TimeUnit::Ms, ',', 0, &mut result)
+            .unwrap();
+        n_samples += result.n_new_samples;
+        if let Some(sample) = result.samples_vec.first().and_then(|s| s.last()) {
+            last_value = Some(sample.value);
+        }
+    }
+
+    (n_samples, last_value)
+}
+
+#[test]
+fn single_channel_chunked() {
+    // Lines are split across the chunks
+    let data = single_channel_lines(20_000);
+    assert_eq!(parse_chunked(&data), (20_000, Some(4999.75)));
+}
+
+/// Run with `cargo test --release -- --ignored single_channel_throughput`
+#[test]
+#[ignore = "timing dependent, only meaningful in release builds"]
+fn single_channel_throughput() {
+    const N_LINES: usize = 200_000;
+
+    let data = single_channel_lines(N_LINES);
+    let start = Instant::now();
+    let (n_samples, _) = parse_chunked(&data);
+    let lines_per_sec = N_LINES as f64 / start.elapsed().as_secs_f64();
+
+    assert_eq!(n_samples, N_LINES as u64);
+    assert!(
+        lines_per_sec >= 100_000.0,
+        "parsed only {lines_per_sec:.0} lines/s"
+    );
+}
@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+
+use super::TimeUnit;
+
+/// The widths of the device counters that roll over, in bits
+const COUNTER_BITS: [i32; 2] = [16, 32];
+/// The minimum host time between the time pairs the estimation is based on, in nanoseconds
+const PAIR_INTERVAL: i64 = 100_000_000;
+/// The number of time pairs the estimation is based on, the oldest ones are dropped
+const MAX_PAIRS: usize = 300;
+/// The span of device time the pairs need to cover before the drift is estimated, in nanoseconds.
+/// Over shorter spans the latency jitter dominates.
+const MIN_DRIFT_SPAN: i64 = 10_000_000_000;
+
+/// Maps device timestamps onto the host clock, so that samples with `time=` fields line up with the host time
+/// the data was received at, e.g. in the serial monitor.
+///
+/// The offset and the drift of the device clock are estimated from pairs of device time and host receive time.
+/// The transmission only ever delays the data, so the offset follows the lower envelope of the differences
+/// and latency jitter shifts it only by the shortest observed latency.
+#[derive(Debug, Clone, Default)]
+pub struct TimeSync {
+    /// Pairs of device and host time
+    pairs: VecDeque<(i64, i64)>,
+    /// The host time of the device time zero, in nanoseconds
+    offset: i64,
+    /// How much faster the host clock runs than the device clock, as ratio
+    drift: f64,
+    last_device: Option<i64>,
+    last_mapped: Option<i64>,
+}
+
+impl TimeSync {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The host time of the device time zero, in nanoseconds
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// How much faster the host clock runs than the device clock, in parts per million
+    pub fn drift_ppm(&self) -> f64 {
+        self.drift * 1e6
+    }
+
+    /// Maps the device time onto the host clock, `host` is the time the data was received at.
+    ///
+    /// The mapped times never decrease, so that the samples stay sorted by time.
+    pub fn map(&mut self, device: i64, host: i64) -> i64 {
+        // The device restarted or its clock wrapped around
+        if self.last_device.map_or(false, |last| device < last) {
+            let last_mapped = self.last_mapped;
+            self.reset();
+            self.last_mapped = last_mapped;
+        }
+        self.last_device = Some(device);
+
+        if self
+            .pairs
+            .back()
+            .map_or(true, |&(_, last_host)| host - last_host >= PAIR_INTERVAL)
+        {
+            self.pairs.push_back((device, host));
+            if self.pairs.len() > MAX_PAIRS {
+                self.pairs.pop_front();
+            }
+            self.estimate();
+        }
+
+        let mapped = self.scale(device) + self.offset;
+        let mapped = self.last_mapped.map_or(mapped, |last| mapped.max(last));
+        self.last_mapped = Some(mapped);
+
+        mapped
+    }
+
+    fn scale(&self, device: i64) -> i64 {
+        device + (device as f64 * self.drift) as i64
+    }
+
+    fn estimate(&mut self) {
+        let (Some(&(first_device, first_host)), Some(&(last_device, _))) =
+            (self.pairs.front(), self.pairs.back())
+        else {
+            return;
+        };
+
+        // Least squares slope of the host over the device time, relative to the first pair for precision
+        self.drift = if last_device - first_device >= MIN_DRIFT_SPAN {
+            let n = self.pairs.len() as f64;
+            let points = self
+                .pairs
+                .iter()
+                .map(|&(d, h)| ((d - first_device) as f64, (h - first_host) as f64));
+            let (mean_x, mean_y) = points
+                .clone()
+                .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x / n, sy + y / n));
+            let (sxy, sxx) = points.fold((0.0, 0.0), |(sxy, sxx), (x, y)| {
+                (
+                    sxy + (x - mean_x) * (y - mean_y),
+                    sxx + (x - mean_x).powi(2),
+                )
+            });
+
+            if sxx > 0.0 {
+                sxy / sxx - 1.0
+            } else {
+                0.0
+            }
+        } else {
+            0.0
+        };
+
+        self.offset = self
+            .pairs
+            .iter()
+            .map(|&(d, h)| h - self.scale(d))
+            .min()
+            .unwrap_or_default();
+    }
+}
+
+/// Unwraps device timestamps that roll over, like a u32 millisecond tick every ~49 days,
+/// and continues the time after other backward jumps, e.g. when the device restarted.
+#[derive(Debug, Clone, Default)]
+pub struct TimeUnwrap {
+    /// The last received time, in nanoseconds
+    last: Option<i64>,
+    /// Added to the received times, in nanoseconds
+    offset: i64,
+}
+
+impl TimeUnwrap {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Unwraps the received time in nanoseconds, `unit` is the unit of the device counter
+    pub fn unwrap(&mut self, time: i64, unit: TimeUnit) -> i64 {
+        if let Some(last) = self.last.filter(|&last| time < last) {
+            // The narrowest counter that the last time fits into and that continues close after it when wrapped
+            let period = COUNTER_BITS
+                .iter()
+                .map(|&bits| unit.convert_to_nanos(2f64.powi(bits)))
+                .find(|&period| {
+                    let wrapped = time + period;
+                    last < period && wrapped >= last && wrapped - last < period / 2
+                });
+
+            self.offset += match period {
+                Some(period) => {
+                    log::debug!("device time rolled over after {last} ns");
+                    period
+                }
+                None => {
+                    log::debug!("device time jumped back from {last} to {time} ns");
+                    last - time
+                }
+            };
+        }
+        self.last = Some(time);
+
+        time + self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeSync;
+
+    #[test]
+    fn device_time_sync() {
+        // The device clock runs 100 ppm slow and started 5 s after the host, the latency jitters up to 2 ms
+        let host_time = |device: i64| device + device / 10_000 + 5_000_000_000;
+        let mut time_sync = TimeSync::default();
+        let mut last = 0;
+        for i in 0..600 {
+            let device = i * 100_000_000;
+            let latency = (i * 7919 % 2000) * 1000;
+            last = time_sync.map(device, host_time(device) + latency);
+        }
+
+        assert!(
+            (time_sync.drift_ppm() - 100.0).abs() < 20.0,
+            "{}",
+            time_sync.drift_ppm()
+        );
+        assert!(
+            (last - host_time(59_900_000_000)).abs() < 1_000_000,
+            "{last}"
+        );
+
+        // A restarted device keeps the times increasing
+        assert!(time_sync.map(0, host_time(60_000_000_000)) >= last);
+    }
+}
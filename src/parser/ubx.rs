@@ -0,0 +1,187 @@
+/// The sync chars every UBX frame starts with
+pub const SYNC: [u8; 2] = [0xB5, 0x62];
+/// Sync chars, class, id and the payload length
+pub const HEADER_SIZE: usize = 6;
+/// Longer frames are treated as corrupted, the decoded messages are much shorter
+pub const MAX_PAYLOAD_SIZE: usize = 1024;
+
+/// The UBX messages of u-blox GNSS modules that are decoded into channels
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[allow(clippy::enum_variant_names)]
+pub enum UbxMessage {
+    /// Navigation position velocity time solution
+    #[default]
+    NavPvt,
+    /// Geodetic position
+    NavPosllh,
+    /// Velocity in NED frame
+    NavVelned,
+    /// Receiver navigation status
+    NavStatus,
+    /// Dilution of precision
+    NavDop,
+}
+
+impl std::fmt::Display for UbxMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UbxMessage::NavPvt => write!(f, "NAV-PVT"),
+            UbxMessage::NavPosllh => write!(f, "NAV-POSLLH"),
+            UbxMessage::NavVelned => write!(f, "NAV-VELNED"),
+            UbxMessage::NavStatus => write!(f, "NAV-STATUS"),
+            UbxMessage::NavDop => write!(f, "NAV-DOP"),
+        }
+    }
+}
+
+impl UbxMessage {
+    pub const ALL: [UbxMessage; 5] = [
+        UbxMessage::NavPvt,
+        UbxMessage::NavPosllh,
+        UbxMessage::NavVelned,
+        UbxMessage::NavStatus,
+        UbxMessage::NavDop,
+    ];
+
+    /// The message class and id
+    pub fn class_id(self) -> (u8, u8) {
+        match self {
+            UbxMessage::NavPvt => (0x01, 0x07),
+            UbxMessage::NavPosllh => (0x01, 0x02),
+            UbxMessage::NavVelned => (0x01, 0x12),
+            UbxMessage::NavStatus => (0x01, 0x03),
+            UbxMessage::NavDop => (0x01, 0x04),
+        }
+    }
+
+    pub fn from_class_id(class: u8, id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.class_id() == (class, id))
+    }
+
+    /// The names of the channels, in the order of the decoded values
+    pub fn fields(self) -> &'static [&'static str] {
+        match self {
+            UbxMessage::NavPvt => &[
+                "fix_type", "num_sv", "lat", "lon", "height", "h_msl", "h_acc", "v_acc", "vel_n",
+                "vel_e", "vel_d", "g_speed", "head_mot", "p_dop",
+            ],
+            UbxMessage::NavPosllh => &["lat", "lon", "height", "h_msl", "h_acc", "v_acc"],
+            UbxMessage::NavVelned => &[
+                "vel_n", "vel_e", "vel_d", "speed", "g_speed", "heading", "s_acc",
+            ],
+            UbxMessage::NavStatus => &["gps_fix", "ttff"],
+            UbxMessage::NavDop => &[
+                "g_dop", "p_dop", "t_dop", "v_dop", "h_dop", "n_dop", "e_dop",
+            ],
+        }
+    }
+
+    /// Decodes the payload into the values of the fields, scaled to degrees, meters, m/s and seconds.
+    ///
+    /// Returns None when the payload is too short.
+    pub fn decode(self, payload: &[u8]) -> Option<Vec<f64>> {
+        let p = Payload(payload);
+
+        let values = match self {
+            UbxMessage::NavPvt => vec![
+                p.u8(20)?,
+                p.u8(23)?,
+                p.i32(28)? / 1e7,
+                p.i32(24)? / 1e7,
+                p.i32(32)? / 1e3,
+                p.i32(36)? / 1e3,
+                p.u32(40)? / 1e3,
+                p.u32(44)? / 1e3,
+                p.i32(48)? / 1e3,
+                p.i32(52)? / 1e3,
+                p.i32(56)? / 1e3,
+                p.i32(60)? / 1e3,
+                p.i32(64)? / 1e5,
+                p.u16(76)? / 100.0,
+            ],
+            UbxMessage::NavPosllh => vec![
+                p.i32(8)? / 1e7,
+                p.i32(4)? / 1e7,
+                p.i32(12)? / 1e3,
+                p.i32(16)? / 1e3,
+                p.u32(20)? / 1e3,
+                p.u32(24)? / 1e3,
+            ],
+            UbxMessage::NavVelned => vec![
+                p.i32(4)? / 100.0,
+                p.i32(8)? / 100.0,
+                p.i32(12)? / 100.0,
+                p.u32(16)? / 100.0,
+                p.u32(20)? / 100.0,
+                p.i32(24)? / 1e5,
+                p.u32(28)? / 100.0,
+            ],
+            UbxMessage::NavStatus => vec![p.u8(4)?, p.u32(8)? / 1e3],
+            UbxMessage::NavDop => (0..7)
+                .map(|i| p.u16(4 + i * 2).map(|v| v / 100.0))
+                .collect::<Option<Vec<f64>>>()?,
+        };
+
+        Some(values)
+    }
+}
+
+/// The 8-Bit Fletcher checksum over class, id, length and payload
+pub fn checksum(data: &[u8]) -> [u8; 2] {
+    let mut ck_a: u8 = 0;
+    let mut ck_b: u8 = 0;
+
+    for &b in data {
+        ck_a = ck_a.wrapping_add(b);
+        ck_b = ck_b.wrapping_add(ck_a);
+    }
+
+    [ck_a, ck_b]
+}
+
+/// Frames the payload with sync chars, header and checksum
+#[cfg(test)]
+pub fn frame(class: u8, id: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = SYNC.to_vec();
+    frame.extend([class, id]);
+    frame.extend((payload.len() as u16).to_le_bytes());
+    frame.extend(payload);
+    frame.extend(checksum(&frame[2..]));
+    frame
+}
+
+/// Little endian fields of a payload, at byte offsets
+struct Payload<'a>(&'a [u8]);
+
+impl Payload<'_> {
+    fn bytes<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+        self.0.get(offset..offset + N)?.try_into().ok()
+    }
+
+    fn u8(&self, offset: usize) -> Option<f64> {
+        self.0.get(offset).map(|&b| b as f64)
+    }
+
+    fn u16(&self, offset: usize) -> Option<f64> {
+        self.bytes(offset).map(|b| u16::from_le_bytes(b) as f64)
+    }
+
+    fn u32(&self, offset: usize) -> Option<f64> {
+        self.bytes(offset).map(|b| u32::from_le_bytes(b) as f64)
+    }
+
+    fn i32(&self, offset: usize) -> Option<f64> {
+        self.bytes(offset).map(|b| i32::from_le_bytes(b) as f64)
+    }
+}
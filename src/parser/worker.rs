@@ -0,0 +1,204 @@
+//! Parsing in a Web Worker, so that the web build parses the received data off the main thread.
+//!
+//! The user interface posts the read chunks as [`WorkerRequest`]s and merges the [`WorkerResponse`]s.
+//! Both are encoded with bincode, so that a message is transferred as a single buffer.
+
+use super::{ParseFormat, ParseResult, Parser};
+
+/// A message from the user interface to the worker
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum WorkerRequest {
+    /// Parses a read chunk
+    Parse {
+        /// Echoed by the response, so that results of chunks read before the parser was cleared are dropped
+        generation: u64,
+        data: Vec<u8>,
+        format: ParseFormat,
+        time_unwrap: bool,
+        time_sync: bool,
+        /// The receive time in nanoseconds since the start of the connection
+        now: i64,
+    },
+    /// Clears the parser, like [`Parser::clear`]
+    Clear,
+    /// Lays out the channels again, like [`Parser::reset_channels`]
+    ResetChannels,
+}
+
+/// The result of parsing a chunk, sent back to the user interface
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WorkerResponse {
+    pub generation: u64,
+    pub result: ParseResult,
+    /// The parse failed and the parser was cleared
+    pub error: Option<String>,
+    /// The size of the parsed chunk
+    pub n_bytes: usize,
+    /// The received bytes that were not parsed yet
+    pub n_pending_bytes: usize,
+    /// The offset in nanoseconds and the drift in ppm of the device time sync, when enabled
+    pub time_sync: Option<(i64, f64)>,
+}
+
+/// The parser state of the worker
+#[derive(Debug, Default)]
+pub struct Worker {
+    parser: Parser,
+}
+
+impl Worker {
+    /// Handles a request, parse requests are answered with a response
+    pub fn handle(&mut self, request: WorkerRequest) -> Option<WorkerResponse> {
+        match request {
+            WorkerRequest::Parse {
+                generation,
+                data,
+                format,
+                time_unwrap,
+                time_sync,
+                now,
+            } => {
+                self.parser.set_time_unwrap(time_unwrap);
+                self.parser.set_time_sync(time_sync);
+
+                let mut result = ParseResult::default();
+                let error = self
+                    .parser
+                    .parse(&data, &format, now, &mut result)
+                    .err()
+                    .map(|e| e.to_string());
+                let n_pending_bytes = self.parser.buf.len();
+                if error.is_some() {
+                    self.parser.clear();
+                }
+
+                Some(WorkerResponse {
+                    generation,
+                    result,
+                    error,
+                    n_bytes: data.len(),
+                    n_pending_bytes,
+                    time_sync: self
+                        .parser
+                        .time_sync()
+                        .map(|time_sync| (time_sync.offset(), time_sync.drift_ppm())),
+                })
+            }
+            WorkerRequest::Clear => {
+                self.parser.clear();
+                None
+            }
+            WorkerRequest::ResetChannels => {
+                self.parser.reset_channels();
+                None
+            }
+        }
+    }
+}
+
+pub fn encode<T: serde::Serialize>(message: &T) -> anyhow::Result<Vec<u8>> {
+    Ok(bincode::serialize(message)?)
+}
+
+pub fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> anyhow::Result<T> {
+    Ok(bincode::deserialize(bytes)?)
+}
+
+/// The entry point of the worker, answers the requests posted to it until it is terminated
+#[cfg(target_arch = "wasm32")]
+pub fn run() {
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen::JsCast;
+
+    console_error_panic_hook::set_once();
+    console_log::init_with_level(log::Level::Debug).ok();
+
+    let scope = js_sys::global().unchecked_into::<web_sys::DedicatedWorkerGlobalScope>();
+    let mut worker = Worker::default();
+
+    let onmessage = {
+        let scope = scope.clone();
+
+        Closure::<dyn FnMut(_)>::new(move |event: web_sys::MessageEvent| {
+            let bytes = js_sys::Uint8Array::new(&event.data()).to_vec();
+            let request = match decode::<WorkerRequest>(&bytes) {
+                Ok(request) => request,
+                Err(e) => {
+                    log::error!("failed to decode parser worker request, Err: {e}");
+                    return;
+                }
+            };
+            let Some(response) = worker.handle(request) else {
+                return;
+            };
+
+            match encode(&response) {
+                Ok(bytes) => {
+                    let array = js_sys::Uint8Array::from(bytes.as_slice());
+                    let transfer = js_sys::Array::of1(&array.buffer());
+                    if let Err(e) = scope.post_message_with_transfer(&array, &transfer) {
+                        log::error!("failed to post parser worker response, Err: {e:?}");
+                    }
+                }
+                Err(e) => log::error!("failed to encode parser worker response, Err: {e}"),
+            }
+        })
+    };
+    scope.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    // Lives as long as the worker
+    onmessage.forget();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TimeUnit;
+
+    fn parse_request(generation: u64, data: &str) -> WorkerRequest {
+        WorkerRequest::Parse {
+            generation,
+            data: data.as_bytes().to_vec(),
+            format: ParseFormat::Ascii {
+                time_unit: TimeUnit::S,
+                value_separator: ',',
+            },
+            time_unwrap: false,
+            time_sync: false,
+            now: 5,
+        }
+    }
+
+    /// Passes the request and the response through their encoding, like the messages of the worker
+    fn roundtrip(worker: &mut Worker, request: WorkerRequest) -> Option<WorkerResponse> {
+        let request = decode(&encode(&request).unwrap()).unwrap();
+        let response = worker.handle(request)?;
+
+        Some(decode(&encode(&response).unwrap()).unwrap())
+    }
+
+    #[test]
+    fn worker_parses_chunks() {
+        let mut worker = Worker::default();
+
+        let response = roundtrip(&mut worker, parse_request(1, "a=1, b=2\nmarker=x\na=")).unwrap();
+        assert_eq!(response.generation, 1);
+        assert_eq!(response.n_bytes, 20);
+        assert_eq!(response.n_pending_bytes, 2);
+        assert_eq!(response.result.n_new_samples, 2);
+        assert_eq!(response.result.markers[0].label, "x");
+        let sample = &response.result.samples_vec[1][0];
+        assert_eq!((sample.time, sample.value), (5, 2.0));
+        assert_eq!(sample.name.as_deref(), Some("b"));
+
+        // The pending line is completed by the next chunk, unless the parser was cleared
+        let response = roundtrip(&mut worker, parse_request(1, "3\n")).unwrap();
+        assert_eq!(response.result.samples_vec[0][0].value, 3.0);
+
+        assert!(roundtrip(&mut worker, parse_request(1, "a=")).is_some());
+        assert!(roundtrip(&mut worker, WorkerRequest::Clear).is_none());
+        let response = roundtrip(&mut worker, parse_request(2, "4\n")).unwrap();
+        assert_eq!(response.generation, 2);
+        assert_eq!(response.result.samples_vec[0][0].value, 4.0);
+        assert_eq!(response.n_pending_bytes, 0);
+    }
+}
@@ -1,22 +1,48 @@
 use async_trait::async_trait;
 use instant::{Duration, Instant};
 
-use super::{DataBits, FlowControl, Parity, SerialConnection, StopBits};
+use super::script::{DeviceScript, ScriptStep};
+use super::{DataBits, FlowControl, Parity, PortInfo, SerialConnection, StopBits};
 
 #[derive(Debug)]
 pub struct SerialConnectionDummy {
     connected: bool,
     start_time: Instant,
     last_read: Instant,
+    /// If hostile input is injected into the emitted lines
+    chaos: bool,
+    rng: XorShift,
+    /// The rest of a line that was cut off, emitted with the next read
+    pending: Vec<u8>,
+    /// The script of the scripted device, if one is loaded
+    script: Option<DeviceScript>,
+    /// If the scripted device is connected
+    scripted: bool,
+    /// The next step of the script and the time it is due
+    script_pos: usize,
+    script_due: Instant,
 }
 
 /// The port name for the dummy device.
 pub const DUMMY_PORT_STR: &str = "dummy";
+/// The port name for the dummy device that injects random bytes, partial lines, invalid UTF-8 and bursts,
+/// to verify that the parser and the UI stay robust under hostile input.
+pub const DUMMY_CHAOS_PORT_STR: &str = "dummy (chaos)";
+/// The port name for the dummy device that plays the loaded device script
+pub const DUMMY_SCRIPT_PORT_STR: &str = "dummy (script)";
 
 #[async_trait(?Send)]
 impl SerialConnection for SerialConnectionDummy {
-    async fn available_ports(&mut self) -> Vec<String> {
-        vec![DUMMY_PORT_STR.to_string()]
+    async fn available_ports(&mut self) -> Vec<PortInfo> {
+        let mut ports = vec![
+            PortInfo::new(DUMMY_PORT_STR),
+            PortInfo::new(DUMMY_CHAOS_PORT_STR),
+        ];
+        if self.script.is_some() {
+            ports.push(PortInfo::new(DUMMY_SCRIPT_PORT_STR));
+        }
+
+        ports
     }
 
     async fn try_connect(
@@ -29,12 +55,19 @@ impl SerialConnection for SerialConnectionDummy {
         _parity: Parity,
         _stop_bits: StopBits,
     ) -> anyhow::Result<()> {
-        if port_index == 0 {
+        if port_index <= 1 || (port_index == 2 && self.script.is_some()) {
             let now = Instant::now();
 
             self.connected = true;
             self.start_time = now;
             self.last_read = now;
+            self.chaos = port_index == 1;
+            self.scripted = port_index == 2;
+            self.script_pos = 0;
+            self.script_due = now;
+            // Seeded the same on every connect, so that a run can be reproduced
+            self.rng = XorShift::default();
+            self.pending.clear();
 
             Ok(())
         } else {
@@ -63,6 +96,11 @@ impl SerialConnection for SerialConnectionDummy {
         }
 
         let now = Instant::now();
+
+        if self.scripted {
+            return Ok(self.play_script(now));
+        }
+
         let elapsed_since_start = now.duration_since(self.start_time).as_secs_f64();
 
         // Only emit values at this frequency
@@ -88,8 +126,29 @@ impl SerialConnection for SerialConnectionDummy {
 
         self.last_read = now;
 
+        if self.chaos {
+            return Ok(self.inject_chaos(read_buf));
+        }
+
         Ok(read_buf)
     }
+
+    async fn write(&mut self, data: &[u8]) -> anyhow::Result<()> {
+        if !self.connected {
+            return Err(anyhow::anyhow!(
+                "failed to write dummy serial port, not connected."
+            ));
+        }
+
+        log::debug!("dummy device received: {:?}", String::from_utf8_lossy(data));
+
+        if let Some(script) = self.script.as_ref().filter(|_| self.scripted) {
+            // Answered with the next read
+            self.pending.extend(script.respond(data));
+        }
+
+        Ok(())
+    }
 }
 
 impl SerialConnectionDummy {
@@ -101,6 +160,104 @@ impl SerialConnectionDummy {
             connected: false,
             start_time: now,
             last_read: now,
+            chaos: false,
+            rng: XorShift::default(),
+            pending: vec![],
+            script: None,
+            scripted: false,
+            script_pos: 0,
+            script_due: now,
+        }
+    }
+
+    /// The dummy device with the additional port that plays the script
+    pub fn with_script(script: DeviceScript) -> Self {
+        Self {
+            script: Some(script),
+            ..Self::new()
+        }
+    }
+
+    /// The responses to writes and the output of the steps that are due.
+    ///
+    /// Waits are counted from when the previous wait ended, so that the timing does not drift with the read interval.
+    fn play_script(&mut self, now: Instant) -> Vec<u8> {
+        let mut buf = std::mem::take(&mut self.pending);
+        let Some(script) = self.script.as_ref() else {
+            return buf;
+        };
+
+        while self.script_due <= now {
+            match script.steps.get(self.script_pos) {
+                Some(ScriptStep::Wait(duration)) => self.script_due += *duration,
+                Some(ScriptStep::Send(data)) => buf.extend_from_slice(data),
+                None if script.repeat => {
+                    self.script_pos = 0;
+                    continue;
+                }
+                None => break,
+            }
+            self.script_pos += 1;
+        }
+
+        buf
+    }
+
+    /// Randomly corrupts the line, cuts it off or repeats it in a burst
+    fn inject_chaos(&mut self, line: Vec<u8>) -> Vec<u8> {
+        let mut buf = std::mem::take(&mut self.pending);
+
+        match self.rng.next() % 10 {
+            // Random bytes, including line breaks
+            0 => {
+                let n = self.rng.next() % 64;
+                buf.extend((0..n).map(|_| self.rng.next() as u8));
+                buf.extend(line);
+            }
+            // A partial line, the rest follows with the next read
+            1 => {
+                let split = (self.rng.next() as usize) % line.len();
+                buf.extend_from_slice(&line[..split]);
+                self.pending.extend_from_slice(&line[split..]);
+            }
+            // Invalid UTF-8 in the middle of the line
+            2 => {
+                let split = (self.rng.next() as usize) % line.len();
+                buf.extend_from_slice(&line[..split]);
+                buf.extend_from_slice(&[0xff, 0xfe, 0xc3]);
+                buf.extend_from_slice(&line[split..]);
+            }
+            // A burst of the same line
+            3 => {
+                let n = 10 + self.rng.next() % 500;
+                for _ in 0..n {
+                    buf.extend_from_slice(&line);
+                }
+            }
+            _ => buf.extend(line),
         }
+
+        buf
+    }
+}
+
+/// A small pseudo random number generator, good enough for generating garbage
+#[derive(Debug, Clone, Copy)]
+struct XorShift(u64);
+
+impl Default for XorShift {
+    fn default() -> Self {
+        Self(0x853c_49e6_748f_ea9b)
+    }
+}
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
     }
 }
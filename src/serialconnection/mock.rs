@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use instant::{Duration, Instant};
+use std::collections::VecDeque;
+
+use super::{DataBits, FlowControl, Parity, PortInfo, SerialConnection, StopBits};
+
+/// The port name for the mock device.
+pub const MOCK_PORT_STR: &str = "mock";
+
+/// A serial connection that replays a script of byte sequences, for testing.
+///
+/// Each chunk of the script is emitted once its delay since the previous chunk has elapsed.
+/// Chunks bigger than the read buffer size are split up over multiple reads.
+#[derive(Debug)]
+pub struct MockSerialConnection {
+    script: VecDeque<(Duration, Vec<u8>)>,
+    connected: bool,
+    last_emit: Instant,
+    fail_connect: bool,
+    written: Vec<u8>,
+}
+
+impl Default for MockSerialConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockSerialConnection {
+    pub fn new() -> Self {
+        Self {
+            script: VecDeque::new(),
+            connected: false,
+            last_emit: Instant::now(),
+            fail_connect: false,
+            written: vec![],
+        }
+    }
+
+    /// Appends a chunk to the script, emitted `delay` after the previous one
+    pub fn with_chunk(mut self, delay: Duration, data: impl Into<Vec<u8>>) -> Self {
+        self.push_chunk(delay, data);
+        self
+    }
+
+    /// Makes connecting fail, to test error handling
+    pub fn with_failing_connect(mut self) -> Self {
+        self.fail_connect = true;
+        self
+    }
+
+    /// Appends a chunk to the script, emitted `delay` after the previous one
+    pub fn push_chunk(&mut self, delay: Duration, data: impl Into<Vec<u8>>) {
+        self.script.push_back((delay, data.into()));
+    }
+
+    /// All data that was written to the connection
+    pub fn written(&self) -> &[u8] {
+        &self.written
+    }
+
+    /// Whether all chunks of the script have been read
+    pub fn is_finished(&self) -> bool {
+        self.script.is_empty()
+    }
+}
+
+#[async_trait(?Send)]
+impl SerialConnection for MockSerialConnection {
+    async fn available_ports(&mut self) -> Vec<PortInfo> {
+        vec![PortInfo::new(MOCK_PORT_STR)]
+    }
+
+    async fn try_connect(
+        &mut self,
+        port_index: usize,
+        _baudrate: u32,
+        _timeout: Duration,
+        _data_bits: DataBits,
+        _flow_control: FlowControl,
+        _parity: Parity,
+        _stop_bits: StopBits,
+    ) -> anyhow::Result<()> {
+        if port_index != 0 || self.fail_connect {
+            self.connected = false;
+
+            return Err(anyhow::anyhow!(
+                "failed to connect to mock device with port index `{port_index}`"
+            ));
+        }
+
+        self.connected = true;
+        self.last_emit = Instant::now();
+
+        Ok(())
+    }
+
+    fn is_connected(&mut self) -> bool {
+        self.connected
+    }
+
+    async fn close(&mut self) -> anyhow::Result<()> {
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn read(&mut self, read_buf_size: usize) -> anyhow::Result<Vec<u8>> {
+        if !self.connected {
+            return Err(anyhow::anyhow!(
+                "failed to read mock serial port, not connected."
+            ));
+        }
+
+        let now = Instant::now();
+
+        let Some((delay, data)) = self.script.front_mut() else {
+            return Ok(vec![]);
+        };
+
+        if now.duration_since(self.last_emit) < *delay {
+            return Ok(vec![]);
+        }
+
+        if data.len() > read_buf_size {
+            // The rest of the chunk follows without delay
+            *delay = Duration::ZERO;
+            self.last_emit = now;
+
+            return Ok(data.drain(..read_buf_size).collect());
+        }
+
+        self.last_emit = now;
+
+        Ok(self
+            .script
+            .pop_front()
+            .map(|(_, data)| data)
+            .unwrap_or_default())
+    }
+
+    async fn write(&mut self, data: &[u8]) -> anyhow::Result<()> {
+        if !self.connected {
+            return Err(anyhow::anyhow!(
+                "failed to write mock serial port, not connected."
+            ));
+        }
+
+        self.written.extend_from_slice(data);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    #[test]
+    fn mock_connection_errors() {
+        let mut connection = MockSerialConnection::new().with_failing_connect();
+
+        block_on(async {
+            assert!(connection.read(16).await.is_err());
+            assert!(connection
+                .try_connect(
+                    0,
+                    115200,
+                    Duration::from_millis(10),
+                    DataBits::default(),
+                    FlowControl::default(),
+                    Parity::default(),
+                    StopBits::default(),
+                )
+                .await
+                .is_err());
+            assert!(!connection.is_connected());
+        });
+    }
+}
@@ -2,8 +2,12 @@ use async_trait::async_trait;
 use instant::Duration;
 
 pub mod dummy;
+pub mod mock;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native;
+pub mod script;
+#[cfg(target_os = "linux")]
+pub mod socketcan;
 #[cfg(target_arch = "wasm32")]
 pub mod web;
 
@@ -17,6 +21,7 @@ pub enum DataBits {
     Eight,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for DataBits {
     fn default() -> Self {
         Self::Eight
@@ -46,6 +51,7 @@ pub enum FlowControl {
     Hardware,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for FlowControl {
     fn default() -> Self {
         Self::None
@@ -71,6 +77,7 @@ pub enum Parity {
     Even,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for Parity {
     fn default() -> Self {
         Self::None
@@ -95,6 +102,7 @@ pub enum StopBits {
     Two,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for StopBits {
     fn default() -> Self {
         Self::One
@@ -110,6 +118,81 @@ impl std::fmt::Display for StopBits {
     }
 }
 
+/// A port that can be connected to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortInfo {
+    /// The name or path of the port, e.g. `/dev/ttyUSB0` or `COM3`
+    pub name: String,
+    /// The details of the device, if it is a USB port
+    pub usb: Option<UsbPortInfo>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsbPortInfo {
+    pub vid: u16,
+    pub pid: u16,
+    pub serial_number: Option<String>,
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+}
+
+impl PortInfo {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            usb: None,
+        }
+    }
+
+    /// The USB vendor and product id, if known
+    pub fn usb_id(&self) -> Option<(u16, u16)> {
+        self.usb.as_ref().map(|usb| (usb.vid, usb.pid))
+    }
+}
+
+/// The name, followed by the product and USB id, so that identical adapters can be told apart
+impl std::fmt::Display for PortInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if let Some(usb) = &self.usb {
+            if let Some(product) = &usb.product {
+                write!(f, " - {product}")?;
+            }
+            write!(f, " ({:04x}:{:04x})", usb.vid, usb.pid)?;
+            if let Some(serial_number) = &usb.serial_number {
+                write!(f, " SN {serial_number}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Common baud rates, offered as presets
+pub const BAUDRATE_PRESETS: [u32; 17] = [
+    300, 1200, 2400, 4800, 9600, 19200, 38400, 57600, 115200, 230400, 460800, 500000, 921600,
+    1_000_000, 1_500_000, 2_000_000, 3_000_000,
+];
+
+/// Checks if the baud rate is supported by the backend that is used to connect
+pub fn validate_baudrate(baudrate: u32, dummy: bool) -> anyhow::Result<()> {
+    if dummy {
+        // The dummy connection ignores the baud rate
+        return Ok(());
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        web::validate_baudrate(baudrate)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        native::validate_baudrate(baudrate)
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 pub fn new_serial_connection() -> Box<dyn SerialConnection> {
     Box::new(web::SerialConnectionWeb::new())
@@ -124,11 +207,18 @@ pub fn new_serial_connection_dummy() -> Box<dyn SerialConnection> {
     Box::new(dummy::SerialConnectionDummy::new())
 }
 
+/// The dummy connection with an additional port that plays the device script
+pub fn new_serial_connection_dummy_script(
+    script: script::DeviceScript,
+) -> Box<dyn SerialConnection> {
+    Box::new(dummy::SerialConnectionDummy::with_script(script))
+}
+
 #[async_trait(?Send)]
 pub trait SerialConnection {
-    async fn available_ports(&mut self) -> Vec<String>;
+    async fn available_ports(&mut self) -> Vec<PortInfo>;
 
-    /// The port index must with the item index of the vector returned by `available_ports()`.
+    /// The port index must match the item index of the vector returned by `available_ports()`.
     #[allow(clippy::too_many_arguments)]
     async fn try_connect(
         &mut self,
@@ -143,7 +233,10 @@ pub trait SerialConnection {
 
     fn is_connected(&mut self) -> bool;
 
+    #[allow(unused)]
     async fn close(&mut self) -> anyhow::Result<()>;
 
     async fn read(&mut self, read_buf_size: usize) -> anyhow::Result<Vec<u8>>;
+
+    async fn write(&mut self, data: &[u8]) -> anyhow::Result<()>;
 }
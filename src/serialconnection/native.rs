@@ -1,7 +1,14 @@
 use async_trait::async_trait;
 use instant::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 
-use super::{DataBits, FlowControl, Parity, SerialConnection, StopBits};
+use super::{DataBits, FlowControl, Parity, PortInfo, SerialConnection, StopBits, UsbPortInfo};
+
+/// The size of the chunks read by the reader thread
+const READ_CHUNK_SIZE: usize = 4096;
+/// The reader thread checks if it should stop at least this often
+const READ_POLL: Duration = Duration::from_millis(50);
 
 impl From<DataBits> for serialport::DataBits {
     fn from(v: DataBits) -> Self {
@@ -43,20 +50,115 @@ impl From<StopBits> for serialport::StopBits {
     }
 }
 
+impl From<serialport::SerialPortInfo> for PortInfo {
+    fn from(info: serialport::SerialPortInfo) -> Self {
+        let usb = match info.port_type {
+            serialport::SerialPortType::UsbPort(usb) => Some(UsbPortInfo {
+                vid: usb.vid,
+                pid: usb.pid,
+                serial_number: usb.serial_number,
+                manufacturer: usb.manufacturer,
+                product: usb.product,
+            }),
+            _ => None,
+        };
+
+        Self {
+            name: info.port_name,
+            usb,
+        }
+    }
+}
+
 pub struct SerialConnectionNative {
+    /// Used for writing, reading happens in the reader thread
     port: Option<Box<dyn serialport::SerialPort>>,
+    reader: Option<ReaderThread>,
     available_ports: Vec<serialport::SerialPortInfo>,
+    /// The CAN interfaces, listed after the serial ports
+    available_can_interfaces: Vec<String>,
+    /// The CAN interface that is connected, it is only read
+    can_interface: Option<String>,
+}
+
+/// Reads the port in a dedicated thread, so that no data is lost at high baud rates while the UI is busy
+struct ReaderThread {
+    receiver: mpsc::Receiver<std::io::Result<Vec<u8>>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl ReaderThread {
+    fn spawn(mut port: impl std::io::Read + Send + 'static) -> anyhow::Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+
+        let handle = std::thread::Builder::new()
+            .name(String::from("serial reader"))
+            .spawn(move || {
+                let mut buf = vec![0; READ_CHUNK_SIZE];
+
+                while !thread_stop.load(Ordering::Relaxed) {
+                    let res = match port.read(&mut buf) {
+                        Ok(0) => continue,
+                        Ok(n) => Ok(buf[..n].to_vec()),
+                        Err(e)
+                            if matches!(
+                                e.kind(),
+                                std::io::ErrorKind::TimedOut
+                                    | std::io::ErrorKind::WouldBlock
+                                    | std::io::ErrorKind::Interrupted
+                            ) =>
+                        {
+                            continue
+                        }
+                        Err(e) => Err(e),
+                    };
+                    let failed = res.is_err();
+
+                    // Stop when the connection was dropped or the port failed
+                    if sender.send(res).is_err() || failed {
+                        break;
+                    }
+                }
+
+                log::debug!("serial reader thread stopped");
+            })?;
+
+        Ok(Self {
+            receiver,
+            stop,
+            handle: Some(handle),
+        })
+    }
+}
+
+impl Drop for ReaderThread {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
 }
 
 #[async_trait(?Send)]
 impl SerialConnection for SerialConnectionNative {
-    async fn available_ports(&mut self) -> Vec<String> {
-        if let Ok(ports) = serialport::available_ports() {
-            self.available_ports = ports.clone();
-            ports.into_iter().map(|i| i.port_name).collect()
-        } else {
-            vec![]
+    async fn available_ports(&mut self) -> Vec<PortInfo> {
+        self.available_ports = serialport::available_ports().unwrap_or_default();
+        #[cfg(target_os = "linux")]
+        {
+            self.available_can_interfaces = super::socketcan::available_interfaces();
         }
+
+        self.available_ports
+            .iter()
+            .cloned()
+            .map(PortInfo::from)
+            .chain(self.available_can_interfaces.iter().map(PortInfo::new))
+            .collect()
     }
 
     async fn try_connect(
@@ -69,10 +171,30 @@ impl SerialConnection for SerialConnectionNative {
         parity: Parity,
         stop_bits: StopBits,
     ) -> anyhow::Result<()> {
-        if let Some(port_info) = self.available_ports.get(port_index) {
+        if let Some(interface) = port_index
+            .checked_sub(self.available_ports.len())
+            .and_then(|i| self.available_can_interfaces.get(i))
+        {
+            log::debug!("try_connect() to CAN interface '{interface}'");
+
+            self.reader.take();
+            self.port.take();
+            self.can_interface.take();
+
+            #[cfg(target_os = "linux")]
+            {
+                let socket = super::socketcan::CanSocket::open(interface, timeout.min(READ_POLL))?;
+                self.reader = Some(ReaderThread::spawn(socket)?);
+                self.can_interface = Some(interface.clone());
+
+                log::debug!("successfully connected to CAN interface: {interface}");
+            }
+        } else if let Some(port_info) = self.available_ports.get(port_index) {
             log::debug!("try_connect() to port '{}'", &port_info.port_name);
 
             // First drop the existing connection so that the port is not busy anymore
+            self.reader.take();
+            self.can_interface.take();
             if let Some(port) = self.port.take() {
                 port.clear(serialport::ClearBuffer::All)?;
                 drop(port);
@@ -90,45 +212,91 @@ impl SerialConnection for SerialConnectionNative {
 
             port.clear(serialport::ClearBuffer::All)?;
 
+            let mut reader_port = port.try_clone()?;
+            reader_port.set_timeout(timeout.min(READ_POLL))?;
+            self.reader = Some(ReaderThread::spawn(reader_port)?);
+
             self.port.replace(port);
         }
         Ok(())
     }
 
     fn is_connected(&mut self) -> bool {
-        self.port.is_some()
+        self.port.is_some() || self.can_interface.is_some()
     }
 
     async fn close(&mut self) -> anyhow::Result<()> {
+        self.reader.take();
         self.port.take();
+        self.can_interface.take();
         Ok(())
     }
 
     async fn read(&mut self, read_buf_size: usize) -> anyhow::Result<Vec<u8>> {
-        if let Some(port) = self.port.as_mut() {
-            let mut read_buf = vec![0; read_buf_size];
+        let Some(reader) = self.reader.as_ref() else {
+            return Err(anyhow::anyhow!(
+                "failed to read serial port, Not connected."
+            ));
+        };
 
-            if port.bytes_to_read()? < read_buf_size as u32 {
-                return Ok(vec![]);
+        // Drain the chunks the reader thread received since the last read
+        let mut data = vec![];
+        while data.len() < read_buf_size {
+            match reader.receiver.try_recv() {
+                Ok(Ok(chunk)) => data.extend(chunk),
+                Ok(Err(e)) => {
+                    self.reader.take();
+                    return Err(anyhow::anyhow!("failed to read serial port, Err: {e}"));
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.reader.take();
+                    return Err(anyhow::anyhow!(
+                        "failed to read serial port, the reader thread stopped."
+                    ));
+                }
             }
-            let bytes_read = port.read(&mut read_buf)?;
-            read_buf.resize(bytes_read, 0);
+        }
 
-            Ok(read_buf)
+        Ok(data)
+    }
+
+    async fn write(&mut self, data: &[u8]) -> anyhow::Result<()> {
+        if let Some(port) = self.port.as_mut() {
+            port.write_all(data)?;
+            port.flush()?;
+
+            Ok(())
+        } else if self.can_interface.is_some() {
+            Err(anyhow::anyhow!(
+                "failed to write, sending to a CAN interface is not supported."
+            ))
         } else {
             Err(anyhow::anyhow!(
-                "failed to read serial port, Not connected."
+                "failed to write serial port, Not connected."
             ))
         }
     }
 }
 
+/// A baud rate of zero hangs up the line on POSIX systems, all other rates are passed on to the driver
+pub fn validate_baudrate(baudrate: u32) -> anyhow::Result<()> {
+    if baudrate == 0 {
+        return Err(anyhow::anyhow!("Baud rate: must be greater than zero"));
+    }
+
+    Ok(())
+}
+
 impl SerialConnectionNative {
     #[allow(unused)]
     pub fn new() -> Self {
         Self {
             port: None,
+            reader: None,
             available_ports: vec![],
+            available_can_interfaces: vec![],
+            can_interface: None,
         }
     }
 }
@@ -0,0 +1,180 @@
+use instant::Duration;
+
+/// A step of the timed output of a device script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptStep {
+    Wait(Duration),
+    Send(Vec<u8>),
+}
+
+/// A fake device for the dummy connection, so that parser settings and protocol features can be tested
+/// without hardware.
+///
+/// The script is line based, empty lines and lines starting with `#` are ignored:
+///
+/// - `wait <ms>` waits before the next step
+/// - `send <text>` sends the text as is
+/// - `line <text>` sends the text followed by a line feed
+/// - `on <request> => <response>` sends the response whenever a write contains the request
+/// - `repeat` starts over when the end of the script is reached
+///
+/// Texts understand the escapes `\n`, `\r`, `\t`, `\\` and `\xNN`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceScript {
+    pub steps: Vec<ScriptStep>,
+    /// The canned responses to writes, as pairs of request and response
+    pub responses: Vec<(Vec<u8>, Vec<u8>)>,
+    pub repeat: bool,
+}
+
+impl DeviceScript {
+    pub fn parse(source: &str) -> anyhow::Result<Self> {
+        let mut script = Self::default();
+
+        for (i, line) in source.lines().enumerate() {
+            let line = line.trim_start();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (command, arg) = line.split_once(' ').unwrap_or((line, ""));
+            let err = |msg: &str| anyhow::anyhow!("line {}: {msg}", i + 1);
+
+            match command {
+                "wait" => {
+                    let ms = arg
+                        .trim()
+                        .parse::<u64>()
+                        .map_err(|_| err("expected the time to wait in ms"))?;
+                    script
+                        .steps
+                        .push(ScriptStep::Wait(Duration::from_millis(ms)));
+                }
+                "send" => script
+                    .steps
+                    .push(ScriptStep::Send(unescape(arg).map_err(err)?)),
+                "line" => {
+                    let mut text = unescape(arg).map_err(err)?;
+                    text.push(b'\n');
+                    script.steps.push(ScriptStep::Send(text));
+                }
+                "on" => {
+                    let (request, response) = arg
+                        .split_once(" => ")
+                        .ok_or_else(|| err("expected `on <request> => <response>`"))?;
+                    let request = unescape(request).map_err(err)?;
+                    if request.is_empty() {
+                        return Err(err("the request must not be empty"));
+                    }
+                    script
+                        .responses
+                        .push((request, unescape(response).map_err(err)?));
+                }
+                "repeat" => script.repeat = true,
+                _ => return Err(err(&format!("unknown command `{command}`"))),
+            }
+        }
+
+        // Otherwise repeating would never yield
+        if script.repeat
+            && !script
+                .steps
+                .iter()
+                .any(|s| matches!(s, ScriptStep::Wait(d) if !d.is_zero()))
+        {
+            return Err(anyhow::anyhow!("a repeating script needs to wait"));
+        }
+
+        Ok(script)
+    }
+
+    /// The responses to the written data
+    pub fn respond(&self, data: &[u8]) -> Vec<u8> {
+        self.responses
+            .iter()
+            .filter(|(request, _)| data.windows(request.len()).any(|w| w == request))
+            .flat_map(|(_, response)| response.iter().copied())
+            .collect()
+    }
+}
+
+fn unescape(text: &str) -> Result<Vec<u8>, &'static str> {
+    let mut bytes = vec![];
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => bytes.push(b'\n'),
+            Some('r') => bytes.push(b'\r'),
+            Some('t') => bytes.push(b'\t'),
+            Some('\\') => bytes.push(b'\\'),
+            Some('x') => {
+                let hex = chars.by_ref().take(2).collect::<String>();
+                let byte = u8::from_str_radix(&hex, 16).map_err(|_| "invalid `\\x` escape")?;
+                bytes.push(byte);
+            }
+            _ => return Err("invalid escape"),
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialconnection::{
+        new_serial_connection_dummy_script, DataBits, FlowControl, Parity, StopBits,
+    };
+    use futures::executor::block_on;
+
+    #[test]
+    fn scripted_device() {
+        assert_eq!(
+            DeviceScript::parse("line a\nwait x")
+                .unwrap_err()
+                .to_string(),
+            "line 2: expected the time to wait in ms"
+        );
+        assert!(DeviceScript::parse("line a\nrepeat").is_err());
+
+        let script = DeviceScript::parse(
+            "# comment
+    on PING => PONG\\r\\n
+    send \\x01a=1
+    line ,b=2
+    wait 100000
+    line never",
+        )
+        .unwrap();
+        let mut connection = new_serial_connection_dummy_script(script);
+
+        block_on(async {
+            assert_eq!(connection.available_ports().await.len(), 3);
+            connection
+                .try_connect(
+                    2,
+                    115200,
+                    Duration::from_millis(10),
+                    DataBits::default(),
+                    FlowControl::default(),
+                    Parity::default(),
+                    StopBits::default(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(connection.read(64).await.unwrap(), b"\x01a=1,b=2\n");
+            assert_eq!(connection.read(64).await.unwrap(), b"");
+
+            connection.write(b"xPINGx").await.unwrap();
+            connection.write(b"PONG").await.unwrap();
+            assert_eq!(connection.read(64).await.unwrap(), b"PONG\r\n");
+        });
+    }
+}
@@ -0,0 +1,115 @@
+use instant::Duration;
+use std::ffi::CString;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+/// The type of CAN interfaces in `/sys/class/net/<interface>/type`
+const ARPHRD_CAN: &str = "280";
+
+/// A raw SocketCAN socket bound to one interface, e.g. `can0` or `vcan0`.
+///
+/// Every read returns whole frames in the layout of `struct can_frame`, see `parser::can`.
+pub struct CanSocket {
+    fd: OwnedFd,
+}
+
+impl CanSocket {
+    /// Opens the socket, reads time out after the timeout so that the reader thread can stop
+    pub fn open(interface: &str, timeout: Duration) -> anyhow::Result<Self> {
+        let name = CString::new(interface)?;
+        let ifindex = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if ifindex == 0 {
+            return Err(anyhow::anyhow!(
+                "failed to find CAN interface '{interface}', Err: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+
+        let fd = unsafe { libc::socket(libc::PF_CAN, libc::SOCK_RAW, libc::CAN_RAW) };
+        if fd < 0 {
+            return Err(anyhow::anyhow!(
+                "failed to open CAN socket, Err: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        // Closes the socket when binding fails
+        let socket = Self {
+            fd: unsafe { OwnedFd::from_raw_fd(fd) },
+        };
+
+        let mut addr: libc::sockaddr_can = unsafe { std::mem::zeroed() };
+        addr.can_family = libc::AF_CAN as libc::sa_family_t;
+        addr.can_ifindex = ifindex as libc::c_int;
+        let res = unsafe {
+            libc::bind(
+                fd,
+                &addr as *const libc::sockaddr_can as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_can>() as libc::socklen_t,
+            )
+        };
+        if res < 0 {
+            return Err(anyhow::anyhow!(
+                "failed to bind CAN socket to '{interface}', Err: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+
+        let timeval = libc::timeval {
+            tv_sec: timeout.as_secs() as libc::time_t,
+            tv_usec: timeout.subsec_micros() as libc::suseconds_t,
+        };
+        let res = unsafe {
+            libc::setsockopt(
+                fd,
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &timeval as *const libc::timeval as *const libc::c_void,
+                std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+        if res < 0 {
+            return Err(anyhow::anyhow!(
+                "failed to set the CAN socket timeout, Err: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+
+        Ok(socket)
+    }
+}
+
+impl std::io::Read for CanSocket {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = unsafe {
+            libc::read(
+                self.fd.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+            )
+        };
+
+        if n < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(n as usize)
+        }
+    }
+}
+
+/// The names of the CAN network interfaces
+pub fn available_interfaces() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/net") else {
+        return vec![];
+    };
+
+    let mut interfaces = entries
+        .flatten()
+        .filter(|entry| {
+            std::fs::read_to_string(entry.path().join("type"))
+                .map_or(false, |ty| ty.trim() == ARPHRD_CAN)
+        })
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect::<Vec<String>>();
+    interfaces.sort();
+
+    interfaces
+}
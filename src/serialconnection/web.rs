@@ -1,7 +1,7 @@
 use async_trait::async_trait;
-use instant::Duration;
+use instant::{Duration, Instant};
 
-use super::{DataBits, FlowControl, Parity, SerialConnection, StopBits};
+use super::{DataBits, FlowControl, Parity, PortInfo, SerialConnection, StopBits, UsbPortInfo};
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
 
@@ -9,6 +9,8 @@ use wasm_bindgen_futures::JsFuture;
 const REQUEST_PORT_STR: &str = "Request port";
 /// Indicate that the Web Serial API is not supported
 const WEB_SERIAL_UNSUPPORTED_STR: &str = "Web Serial API is unsupported by this platform.";
+/// The time in milliseconds a read waits for data, before it returns without any and keeps the read pending
+const READ_POLL_MS: i32 = 50;
 
 impl From<Parity> for web_sys::ParityType {
     fn from(v: Parity) -> Self {
@@ -38,11 +40,32 @@ pub struct SerialConnectionWeb {
     /// the port, and if it is opened
     requested_ports: Vec<web_sys::SerialPort>,
     active_port: Option<usize>,
+    /// Reads and writes that take longer are aborted
+    timeout: Duration,
+    /// The read that didn't complete yet, with its reader and when it was started
+    pending_read: Option<PendingRead>,
+}
+
+#[derive(Debug)]
+struct PendingRead {
+    reader: web_sys::ReadableStreamDefaultReader,
+    promise: js_sys::Promise,
+    started: Instant,
+}
+
+impl PendingRead {
+    /// Cancels the read and releases the reader, the next read gets a new stream of the port
+    async fn cancel(self) {
+        if let Err(e) = JsFuture::from(self.reader.cancel()).await {
+            log::warn!("cancelling pending read failed, Err: {e:?}");
+        }
+        self.reader.release_lock();
+    }
 }
 
 #[async_trait(?Send)]
 impl SerialConnection for SerialConnectionWeb {
-    async fn available_ports(&mut self) -> Vec<String> {
+    async fn available_ports(&mut self) -> Vec<PortInfo> {
         if !check_serial_api_supported() {
             log::warn!("{WEB_SERIAL_UNSUPPORTED_STR}");
             return vec![];
@@ -50,7 +73,7 @@ impl SerialConnection for SerialConnectionWeb {
 
         let serial_itf = web_sys::window().unwrap().navigator().serial();
 
-        let mut available_ports_ret = vec![REQUEST_PORT_STR.to_string()];
+        let mut available_ports_ret = vec![PortInfo::new(REQUEST_PORT_STR)];
         self.requested_ports.clear();
 
         if let Ok(ports) = JsFuture::from(serial_itf.get_ports()).await {
@@ -63,6 +86,8 @@ impl SerialConnection for SerialConnectionWeb {
                         let info = port.get_info();
                         log::debug!("got port info: {info:?}");
 
+                        let mut port_info = PortInfo::new(format!("port `{i}`"));
+
                         // Try to get PID and VID, but sometimes this is undefined (At least on chrome, linux)
                         if let (Ok(vid), Ok(pid)) = (
                             js_sys::Reflect::get(
@@ -74,12 +99,17 @@ impl SerialConnection for SerialConnectionWeb {
                                 &wasm_bindgen::JsValue::from("usbProductId"),
                             ),
                         ) {
-                            if !vid.is_undefined() && !pid.is_undefined() {
+                            if let (Some(vid), Some(pid)) = (vid.as_f64(), pid.as_f64()) {
                                 log::debug!("got port info - device pid: {:?} vid: {:?}", vid, pid);
+                                port_info.usb = Some(UsbPortInfo {
+                                    vid: vid as u16,
+                                    pid: pid as u16,
+                                    ..Default::default()
+                                });
                             }
                         }
 
-                        available_ports_ret.push(format!("port `{i}`"));
+                        available_ports_ret.push(port_info);
                         self.requested_ports.push(port);
                     }
                 }
@@ -93,7 +123,7 @@ impl SerialConnection for SerialConnectionWeb {
         &mut self,
         port_index: usize,
         baudrate: u32,
-        _timeout: Duration,
+        timeout: Duration,
         data_bits: DataBits,
         flow_control: FlowControl,
         parity: Parity,
@@ -133,6 +163,7 @@ impl SerialConnection for SerialConnectionWeb {
             };
 
             self.active_port = Some(port_index - 1);
+            self.timeout = timeout;
         } else {
             return Err(anyhow::anyhow!("no port available for index: {port_index}"));
         }
@@ -170,30 +201,102 @@ impl SerialConnection for SerialConnectionWeb {
                 return Ok(vec![]);
             }
 
-            let reader = readable
-                .get_reader()
-                .dyn_into::<web_sys::ReadableStreamDefaultReader>()
-                .map_err(|e| {
-                    anyhow::anyhow!(
-                        "failed to cast reader into ReadableStreamDefaultHandler, Err {e:?}"
-                    )
-                })?;
-            let read_data = JsFuture::from(reader.read())
-                .await
-                .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+            let pending = match self.pending_read.take() {
+                Some(pending) => pending,
+                None => {
+                    let reader = readable
+                        .get_reader()
+                        .dyn_into::<web_sys::ReadableStreamDefaultReader>()
+                        .map_err(|e| {
+                            anyhow::anyhow!(
+                                "failed to cast reader into ReadableStreamDefaultHandler, Err {e:?}"
+                            )
+                        })?;
+                    let promise = reader.read();
+                    PendingRead {
+                        reader,
+                        promise,
+                        started: Instant::now(),
+                    }
+                }
+            };
+            // Don't wait long for data, so that the connection is not locked for writing.
+            // The read stays pending until it completes or the timeout is exceeded.
+            let read_res = JsFuture::from(js_sys::Promise::race(&js_sys::Array::of2(
+                &pending.promise,
+                &timeout_promise(READ_POLL_MS.min(self.timeout_ms())),
+            )))
+            .await;
+            let reader = pending.reader.clone();
+
+            let read_data = match read_res {
+                Ok(read_data) if read_data.is_undefined() => {
+                    if pending.started.elapsed() < self.timeout {
+                        self.pending_read = Some(pending);
+                    } else {
+                        log::debug!("read timed out after {} ms", self.timeout_ms());
+                        pending.cancel().await;
+                    }
+                    return Ok(vec![]);
+                }
+                Ok(read_data) => read_data,
+                Err(e) => {
+                    // Always release the lock, so that the next read can recover
+                    reader.release_lock();
+                    return Err(anyhow::anyhow!("{e:?}"));
+                }
+            };
 
             let data = js_sys::Reflect::get(&read_data, &JsValue::from("value"))
                 .and_then(|jsv| jsv.dyn_into::<js_sys::Uint8Array>())
-                .map_err(|e| anyhow::anyhow!("{e:?}"))?
-                .to_vec();
+                .map_err(|e| anyhow::anyhow!("{e:?}"));
 
             reader.release_lock();
 
-            return Ok(data);
+            return Ok(data?.to_vec());
         }
 
         Ok(vec![])
     }
+
+    async fn write(&mut self, data: &[u8]) -> anyhow::Result<()> {
+        if !check_serial_api_supported() {
+            return Err(anyhow::anyhow!(
+                "serial connection write() aborted, web serial API not supported."
+            ));
+        }
+
+        let Some(port) = self.active_port.and_then(|a| self.requested_ports.get(a)) else {
+            return Err(anyhow::anyhow!(
+                "failed to write serial port, Not connected."
+            ));
+        };
+
+        let writable = port.writable();
+        if writable.is_null() {
+            return Err(anyhow::anyhow!("can't write to port. writable is null."));
+        }
+
+        let writer = writable
+            .get_writer()
+            .map_err(|e| anyhow::anyhow!("failed to get writer, Err {e:?}"))?;
+        let res = JsFuture::from(js_sys::Promise::race(&js_sys::Array::of2(
+            &writer.write_with_chunk(&js_sys::Uint8Array::from(data)),
+            &timeout_reject_promise(self.timeout_ms()),
+        )))
+        .await;
+        // Releasing the lock rejects a pending write, so a hung write doesn't block the port
+        writer.release_lock();
+
+        match res {
+            Ok(_) => Ok(()),
+            Err(e) if e.is_undefined() => Err(anyhow::anyhow!(
+                "write timed out after {} ms",
+                self.timeout_ms()
+            )),
+            Err(e) => Err(anyhow::anyhow!("{e:?}")),
+        }
+    }
 }
 
 impl SerialConnectionWeb {
@@ -202,10 +305,20 @@ impl SerialConnectionWeb {
         Self {
             requested_ports: vec![],
             active_port: None,
+            timeout: Duration::from_millis(5000),
+            pending_read: None,
         }
     }
 
+    fn timeout_ms(&self) -> i32 {
+        self.timeout.as_millis().min(i32::MAX as u128) as i32
+    }
+
     async fn close_all_ports(&mut self) -> anyhow::Result<()> {
+        // A locked readable prevents closing the port
+        if let Some(pending) = self.pending_read.take() {
+            pending.cancel().await;
+        }
         for (i, port) in self.requested_ports.iter().enumerate() {
             if let Err(_e) = JsFuture::from(port.close()).await {
                 log::warn!("close_all_ports(), port {i} was already closed.");
@@ -216,12 +329,46 @@ impl SerialConnectionWeb {
     }
 }
 
+/// A promise that resolves to `undefined` after the timeout
+fn timeout_promise(timeout_ms: i32) -> js_sys::Promise {
+    js_sys::Promise::new(&mut |resolve, _reject| {
+        let _ = web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, timeout_ms);
+    })
+}
+
+/// A promise that is rejected with `undefined` after the timeout
+fn timeout_reject_promise(timeout_ms: i32) -> js_sys::Promise {
+    js_sys::Promise::new(&mut |_resolve, reject| {
+        let _ = web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&reject, timeout_ms);
+    })
+}
+
 fn check_serial_api_supported() -> bool {
     let serial_itf = web_sys::window().unwrap().navigator().serial();
 
     !serial_itf.is_undefined()
 }
 
+/// The Web Serial API rejects a baud rate of zero, and browsers only accept rates that fit into a signed 32 bit integer
+pub fn validate_baudrate(baudrate: u32) -> anyhow::Result<()> {
+    if baudrate == 0 {
+        return Err(anyhow::anyhow!(
+            "Baud rate: must be greater than zero in Web Serial API"
+        ));
+    }
+    if baudrate > i32::MAX as u32 {
+        return Err(anyhow::anyhow!(
+            "Baud rate: {baudrate} not supported in Web Serial API"
+        ));
+    }
+
+    Ok(())
+}
+
 fn create_web_serial_options(
     baudrate: u32,
     data_bits: DataBits,